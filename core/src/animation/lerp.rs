@@ -145,11 +145,54 @@ impl Lerp for Brush {
   fn lerp(&self, to: &Self, factor: f32) -> Self {
     match (self, to) {
       (Brush::Color(from), Brush::Color(to)) => from.lerp(to, factor).into(),
+      (Brush::SweepGradient(from), Brush::SweepGradient(to)) => from.lerp(to, factor).into(),
+      (Brush::Pattern(from), Brush::Pattern(to)) => from.lerp(to, factor).into(),
       _ => to.clone(),
     }
   }
 }
 
+impl Lerp for GradientStop {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    GradientStop::new(self.color.lerp(&to.color, factor), self.offset.lerp(&to.offset, factor))
+  }
+}
+
+impl Lerp for SweepGradient {
+  /// Interpolates the center, the angles and the stops, the stops only
+  /// interpolate between gradients with the same number of stops.
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    let stops = if self.stops.len() == to.stops.len() {
+      self
+        .stops
+        .iter()
+        .zip(&to.stops)
+        .map(|(from, to)| from.lerp(to, factor))
+        .collect()
+    } else {
+      to.stops.clone()
+    };
+    SweepGradient {
+      center: self.center.lerp(&to.center, factor),
+      start_angle: self.start_angle.lerp(&to.start_angle, factor),
+      end_angle: self.end_angle.lerp(&to.end_angle, factor),
+      stops,
+      spread_method: to.spread_method,
+    }
+  }
+}
+
+impl Lerp for ImagePattern {
+  /// Interpolates the transform of patterns with the same image, so a pattern
+  /// can scroll or scale smoothly.
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    if self.image != to.image {
+      return to.clone();
+    }
+    ImagePattern { transform: self.transform.lerp(&to.transform, factor), ..to.clone() }
+  }
+}
+
 impl Lerp for Measure {
   fn lerp(&self, to: &Self, factor: f32) -> Self {
     match (self, to) {
@@ -160,6 +203,47 @@ impl Lerp for Measure {
   }
 }
 
+impl Lerp for StrokeOptions {
+  /// Interpolates the width, the miter limit and the dash, the dash offset
+  /// animates smoothly, so a dashed stroke can "march" along its path. The
+  /// dash pattern only interpolates between patterns with the same length.
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    let dash = match (&self.dash, &to.dash) {
+      (Some((from_pattern, from_offset)), Some((to_pattern, to_offset))) => {
+        let pattern = if from_pattern.len() == to_pattern.len() {
+          from_pattern
+            .iter()
+            .zip(to_pattern)
+            .map(|(from, to)| from.lerp(to, factor))
+            .collect()
+        } else {
+          to_pattern.clone()
+        };
+        Some((pattern, from_offset.lerp(to_offset, factor)))
+      }
+      _ => to.dash.clone(),
+    };
+
+    StrokeOptions {
+      width: self.width.lerp(&to.width, factor),
+      miter_limit: self.miter_limit.lerp(&to.miter_limit, factor),
+      dash,
+      ..to.clone()
+    }
+  }
+}
+
+impl Lerp for PaintingStyle {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    match (self, to) {
+      (PaintingStyle::Stroke(from), PaintingStyle::Stroke(to)) => {
+        PaintingStyle::Stroke(from.lerp(to, factor))
+      }
+      _ => to.clone(),
+    }
+  }
+}
+
 impl Lerp for Transform {
   fn lerp(&self, to: &Self, factor: f32) -> Self {
     let m11 = self.m11.lerp(&to.m11, factor);
@@ -177,6 +261,15 @@ impl Lerp for Transform {
 mod tests {
   use super::*;
 
+  #[test]
+  fn lerp_stroke_dash_offset() {
+    let from = StrokeOptions { dash: Some(([4., 2.][..].into(), 0.)), ..Default::default() };
+    let to = StrokeOptions { dash: Some(([4., 2.][..].into(), 6.)), ..Default::default() };
+    let (pattern, offset) = from.lerp(&to, 0.5).dash.unwrap();
+    assert_eq!(pattern.as_slice(), &[4., 2.]);
+    assert_eq!(offset, 3.);
+  }
+
   #[test]
   fn lerp_f32() {
     let eq = |f1: f32, f2: f32| (f1 - f2).abs() < f32::EPSILON;
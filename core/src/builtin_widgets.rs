@@ -19,9 +19,11 @@ pub use reuse_scope::*;
 pub use text_align::*;
 mod text_decoration;
 pub use text_decoration::*;
+mod text_highlight;
+pub use text_highlight::*;
 pub mod image;
 
-pub use image::{DecodedFrame, FrameIterator, Image, LoopCount};
+pub use self::image::{DecodedFrame, FrameIterator, Image, ImageError, LoopCount};
 
 pub mod backdrop_filter;
 pub use backdrop_filter::*;
@@ -32,6 +34,10 @@ use smallvec::SmallVec;
 pub use theme::*;
 mod cursor;
 pub use cursor::*;
+mod a11y;
+pub use a11y::*;
+mod shortcuts;
+pub use shortcuts::*;
 pub use winit::window::CursorIcon;
 mod margin;
 pub use margin::*;
@@ -53,6 +59,8 @@ mod unconstrained_box;
 pub use unconstrained_box::*;
 mod opacity;
 pub use opacity::*;
+mod blend_mode;
+pub use blend_mode::*;
 
 mod layout_box;
 pub use layout_box::*;
@@ -110,6 +118,8 @@ mod background;
 pub use background::*;
 pub mod location;
 pub use location::*;
+mod child_window;
+pub use child_window::*;
 
 use crate::prelude::*;
 
@@ -162,21 +172,26 @@ pub struct FatObj<T> {
   layout_box: Option<Stateful<LayoutBox>>,
   mix_builtin: Option<MixBuiltin>,
   cursor: Option<Stateful<Cursor>>,
+  a11y: Option<Stateful<A11yWidget>>,
   margin: Option<Stateful<Margin>>,
   transform: Option<Stateful<TransformWidget>>,
   opacity: Option<Stateful<Opacity>>,
+  blend_mode: Option<Stateful<BlendModeWidget>>,
   visibility: Option<Stateful<Visibility>>,
 
   anchor: Option<Stateful<Anchor>>,
   painting_style: Option<Stateful<PaintingStyleWidget>>,
   text_align: Option<Stateful<TextAlignWidget>>,
   text_decoration: Option<Stateful<TextDecorationWidget>>,
+  text_highlights: Option<Stateful<TextHighlightsWidget>>,
   text_style: Option<Stateful<TextStyleWidget>>,
   tooltip: Option<Tooltip>,
   disabled: Option<Stateful<Disabled>>,
   clip_boundary: Option<Stateful<ClipBoundary>>,
   providers: Option<SmallVec<[Provider; 1]>>,
   reuse: Option<Reuse>,
+  #[cfg(feature = "persist")]
+  persist: Option<CowArc<str>>,
 }
 
 /// Create a function widget that uses an empty `FatObj` as the host object.
@@ -215,6 +230,7 @@ impl<T> FatObj<T> {
       padding: self.padding,
       layout_box: self.layout_box,
       cursor: self.cursor,
+      a11y: self.a11y,
       margin: self.margin,
       scrollable: self.scrollable,
       constrained_box: self.constrained_box,
@@ -226,13 +242,17 @@ impl<T> FatObj<T> {
       text_style: self.text_style,
       text_align: self.text_align,
       text_decoration: self.text_decoration,
+      text_highlights: self.text_highlights,
       visibility: self.visibility,
       opacity: self.opacity,
+      blend_mode: self.blend_mode,
       tooltip: self.tooltip,
       clip_boundary: self.clip_boundary,
       disabled: self.disabled,
       providers: self.providers,
       reuse: self.reuse,
+      #[cfg(feature = "persist")]
+      persist: self.persist,
     }
   }
 
@@ -246,6 +266,10 @@ impl<T> FatObj<T> {
 
   /// Return true if the FatObj not contains any builtin widgets.
   pub fn is_empty(&self) -> bool {
+    #[cfg(feature = "persist")]
+    if self.persist.is_some() {
+      return false;
+    }
     self.track_id.is_none()
       && self.mix_builtin.is_none()
       && self.fitted_box.is_none()
@@ -258,6 +282,7 @@ impl<T> FatObj<T> {
       && self.padding.is_none()
       && self.layout_box.is_none()
       && self.cursor.is_none()
+      && self.a11y.is_none()
       && self.margin.is_none()
       && self.scrollable.is_none()
       && self.constrained_box.is_none()
@@ -268,8 +293,10 @@ impl<T> FatObj<T> {
       && self.painting_style.is_none()
       && self.text_style.is_none()
       && self.text_decoration.is_none()
+      && self.text_highlights.is_none()
       && self.visibility.is_none()
       && self.opacity.is_none()
+      && self.blend_mode.is_none()
       && self.tooltip.is_none()
       && self.disabled.is_none()
       && self.clip_boundary.is_none()
@@ -668,6 +695,14 @@ impl<T> FatObj<T> {
     self
   }
 
+  /// Initializes the highlights painted behind the text of this widget. They
+  /// only apply to a `Text` or `RichText` host, not to its descendants.
+  pub fn with_text_highlights<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<TextHighlights>, K>,
+  ) -> &mut Self {
+    init_sub_widget!(self, text_highlights, text_highlights, v)
+  }
+
   /// Initializes the text style of this widget.
   pub fn with_text_style<K: ?Sized>(
     &mut self, v: impl RInto<PipeValue<TextStyle>, K>,
@@ -698,6 +733,26 @@ impl<T> FatObj<T> {
     init_text_style!(self, letter_space, v)
   }
 
+  /// Initializes the word space of this widget.
+  pub fn with_word_spacing<K: ?Sized>(&mut self, v: impl RInto<PipeValue<f32>, K>) -> &mut Self {
+    init_text_style!(self, word_space, v)
+  }
+
+  /// Initializes the OpenType features of this widget, e.g. `tnum` for tabular
+  /// numbers.
+  pub fn with_font_features<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<Box<[FontFeature]>>, K>,
+  ) -> &mut Self {
+    init_text_style!(self, font_features, v)
+  }
+
+  /// Initializes the variable font axis values of this widget.
+  pub fn with_font_variations<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<Box<[FontVariation]>>, K>,
+  ) -> &mut Self {
+    init_text_style!(self, font_variations, v)
+  }
+
   /// Initializes the text line height of this widget.
   pub fn with_text_line_height<K: ?Sized>(
     &mut self, v: impl RInto<PipeValue<LineHeight>, K>,
@@ -761,6 +816,12 @@ impl<T> FatObj<T> {
     init_sub_widget!(self, cursor, cursor, v)
   }
 
+  /// Initializes the semantics of the widget exposed to the assistive
+  /// technologies.
+  pub fn with_a11y<K: ?Sized>(&mut self, v: impl RInto<PipeValue<A11y>, K>) -> &mut Self {
+    init_sub_widget!(self, a11y, a11y, v)
+  }
+
   /// Initializes the space around the widget.
   pub fn with_margin<K: ?Sized>(&mut self, v: impl RInto<PipeValue<EdgeInsets>, K>) -> &mut Self {
     init_sub_widget!(self, margin, margin, v)
@@ -929,6 +990,13 @@ impl<T> FatObj<T> {
     init_sub_widget!(self, opacity, opacity, v)
   }
 
+  /// Initializes how the widget blends with the content behind it.
+  pub fn with_blend_mode<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<BlendMode>, K>,
+  ) -> &mut Self {
+    init_sub_widget!(self, blend_mode, blend_mode, v)
+  }
+
   /// Initializes the tooltip of the widget.
   pub fn with_tooltip<K: ?Sized>(&mut self, v: impl RInto<Tooltip, K>) -> &mut Self {
     self.tooltip = Some(v.r_into());
@@ -961,6 +1029,17 @@ impl<T> FatObj<T> {
     self
   }
 
+  /// Keeps the scroll position of the widget across launches with `key`, by
+  /// the [`Persist`] of the widget.
+  ///
+  /// It works with the `scrollable` field only, a warning is logged if the
+  /// widget isn't scrollable. Use [`Persist::register`] to keep other states.
+  #[cfg(feature = "persist")]
+  pub fn with_persist(&mut self, key: impl Into<CowArc<str>>) -> &mut Self {
+    self.persist = Some(key.into());
+    self
+  }
+
   /// Sets an explicit debug name for this widget.
   ///
   /// This is only effective when the `debug` feature is enabled.
@@ -1176,6 +1255,13 @@ impl<T> FatObj<T> {
     part_writer!(&mut text_decoration.text_decoration)
   }
 
+  /// Returns a state writer for modifying the text highlights, changing them
+  /// only repaints the text.
+  pub fn text_highlights(&mut self) -> impl StateWriter<Value = TextHighlights> + use<T> {
+    let text_highlights = sub_widget!(self, text_highlights);
+    part_writer!(&mut text_highlights.text_highlights)
+  }
+
   /// Returns a state writer for modifying the complete text style.
   /// Provides comprehensive control over font properties, colors, and text
   /// rendering.
@@ -1204,6 +1290,25 @@ impl<T> FatObj<T> {
     part_writer!(&mut style.text_style.letter_space)
   }
 
+  /// Returns a state writer for modifying the extra space added to word
+  /// separators.
+  pub fn word_space(&mut self) -> impl StateWriter<Value = f32> + use<T> {
+    let style = self.text_style_widget();
+    part_writer!(&mut style.text_style.word_space)
+  }
+
+  /// Returns a state writer for modifying the OpenType feature settings.
+  pub fn font_features(&mut self) -> impl StateWriter<Value = Box<[FontFeature]>> + use<T> {
+    let style = self.text_style_widget();
+    part_writer!(&mut style.text_style.font_features)
+  }
+
+  /// Returns a state writer for modifying the variable font axis values.
+  pub fn font_variations(&mut self) -> impl StateWriter<Value = Box<[FontVariation]>> + use<T> {
+    let style = self.text_style_widget();
+    part_writer!(&mut style.text_style.font_variations)
+  }
+
   /// Returns a state writer for modifying line height (leading).
   /// Supports relative and absolute semantics. Passing a bare `f32`
   /// keeps the existing absolute-length behavior.
@@ -1242,6 +1347,13 @@ impl<T> FatObj<T> {
     part_writer!(&mut cursor.cursor)
   }
 
+  /// Returns a state writer for modifying the semantics of the widget.
+  /// Describes the widget to the screen readers (role, label, value, etc.).
+  pub fn a11y(&mut self) -> impl StateWriter<Value = A11y> + use<T> {
+    let a11y = sub_widget!(self, a11y);
+    part_writer!(&mut a11y.a11y)
+  }
+
   /// Returns a state writer for enabling/disabling scroll behavior.
   /// Controls whether the widget responds to scroll gestures and shows scroll
   /// indicators.
@@ -1309,6 +1421,13 @@ impl<T> FatObj<T> {
     part_writer!(&mut opacity.opacity)
   }
 
+  /// Returns a state writer for modifying the blend mode.
+  /// Controls how the widget composites with the content behind it.
+  pub fn blend_mode(&mut self) -> impl StateWriter<Value = BlendMode> + use<T> {
+    let blend_mode = sub_widget!(self, blend_mode);
+    part_writer!(&mut blend_mode.blend_mode)
+  }
+
   /// Returns the widget's unique tracking identifier.
   /// Used for performance monitoring and debugging purposes.
   pub fn track_id(&mut self) -> TrackId { sub_widget!(self, track_id).read().track_id() }
@@ -1501,6 +1620,19 @@ impl<'a> FatObj<Widget<'a>> {
         )*
       };
     }
+    #[cfg(feature = "persist")]
+    if let Some(key) = self.persist.take() {
+      match self.scrollable.as_ref().map(|s| s.clone_writer()) {
+        Some(scrollable) => {
+          crate::persist::persist_scroll_pos(key, &scrollable, self.mix_builtin_widget())
+        }
+        None => tracing::warn!(
+          "`persist: \"{key}\"` keeps the scroll position only, it does nothing without \
+           `scrollable`."
+        ),
+      }
+    }
+
     let mut host = self.host;
     consume_providers_widget!(host, + [
       painting_style: PaintingStyleWidget,
@@ -1512,6 +1644,7 @@ impl<'a> FatObj<Widget<'a>> {
     compose_builtin_widgets!(
       host
         + [
+          text_highlights,
           track_id,
           backdrop,
           padding,
@@ -1525,7 +1658,8 @@ impl<'a> FatObj<Widget<'a>> {
           radius,
           scrollable,
           layout_box,
-          tooltip
+          tooltip,
+          a11y
         ]
     );
     if let Some(providers) = self.providers {
@@ -1543,6 +1677,7 @@ impl<'a> FatObj<Widget<'a>> {
           mix_builtin,
           transform,
           opacity,
+          blend_mode,
           visibility,
           disabled,
           anchor,
@@ -0,0 +1,348 @@
+pub use accesskit::{Action, ActionData, Live, Role, Toggled};
+use smallvec::SmallVec;
+
+use crate::prelude::*;
+
+/// The semantics of a widget exposed to the assistive technologies, such as
+/// the screen readers.
+///
+/// Only the widgets with semantics appear in the accessibility tree, the
+/// semantic widgets of the descendants become its children.
+#[derive(Debug, Clone, PartialEq)]
+pub struct A11y {
+  pub role: Role,
+  /// The name of the widget read by the screen readers.
+  pub label: Option<CowArc<str>>,
+  pub description: Option<CowArc<str>>,
+  /// The text value, such as the content of an input.
+  pub value: Option<CowArc<str>>,
+  /// The numeric value, such as the value of a slider.
+  pub range: Option<A11yRange>,
+  pub toggled: Option<Toggled>,
+  pub selected: Option<bool>,
+  pub expanded: Option<bool>,
+  pub disabled: bool,
+  /// How the changes of the widget are announced.
+  pub live: Option<Live>,
+  /// The actions the widget supports besides focusing.
+  pub actions: SmallVec<[Action; 2]>,
+}
+
+/// The numeric value of a widget and its bounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct A11yRange {
+  pub value: f64,
+  pub min: f64,
+  pub max: f64,
+  pub step: Option<f64>,
+}
+
+/// The action request from the assistive technologies, it bubbles from the
+/// target widget as a custom event.
+///
+/// The `Focus` and `Click` actions are handled by the framework, `Click` is
+/// simulated by a tap at the center of the target widget. Listen to the other
+/// actions by `on_custom::<A11yActionEvent>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct A11yActionEvent {
+  pub action: Action,
+  pub data: Option<ActionData>,
+}
+
+/// A wrapper that exposes the semantics of its child to the assistive
+/// technologies.
+///
+/// This is a built-in `FatObj` field. Setting the `a11y` field attaches an
+/// `A11yWidget` wrapper that describes the child by [`A11y`].
+///
+/// # Example
+///
+/// Label an icon button for the screen readers.
+///
+/// ```rust
+/// use ribir::prelude::*;
+///
+/// container! {
+///   size: Size::new(24., 24.),
+///   a11y: A11y::new(Role::Button).with_label("Close"),
+///   on_tap: |_| println!("close"),
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct A11yWidget {
+  pub a11y: A11y,
+}
+
+impl A11y {
+  pub fn new(role: Role) -> Self { Self { role, ..Default::default() } }
+
+  pub fn with_label(mut self, label: impl Into<CowArc<str>>) -> Self {
+    self.label = Some(label.into());
+    self
+  }
+
+  pub fn with_description(mut self, description: impl Into<CowArc<str>>) -> Self {
+    self.description = Some(description.into());
+    self
+  }
+
+  pub fn with_value(mut self, value: impl Into<CowArc<str>>) -> Self {
+    self.value = Some(value.into());
+    self
+  }
+
+  pub fn with_range(mut self, range: A11yRange) -> Self {
+    self.range = Some(range);
+    self
+  }
+
+  pub fn with_toggled(mut self, toggled: Toggled) -> Self {
+    self.toggled = Some(toggled);
+    self
+  }
+
+  pub fn with_selected(mut self, selected: bool) -> Self {
+    self.selected = Some(selected);
+    self
+  }
+
+  pub fn with_expanded(mut self, expanded: bool) -> Self {
+    self.expanded = Some(expanded);
+    self
+  }
+
+  pub fn with_disabled(mut self, disabled: bool) -> Self {
+    self.disabled = disabled;
+    self
+  }
+
+  pub fn with_live(mut self, live: Live) -> Self {
+    self.live = Some(live);
+    self
+  }
+
+  pub fn with_action(mut self, action: Action) -> Self {
+    if !self.actions.contains(&action) {
+      self.actions.push(action);
+    }
+    self
+  }
+
+  /// Fills the unset properties of this semantics by `other`, used when more
+  /// than one semantics is attached to a widget, such as a user label on a
+  /// button.
+  pub fn merge(&mut self, other: &A11y) {
+    if self.role == Role::Unknown {
+      self.role = other.role;
+    }
+    fn fill<T: Clone>(this: &mut Option<T>, other: &Option<T>) {
+      if this.is_none() {
+        this.clone_from(other);
+      }
+    }
+    fill(&mut self.label, &other.label);
+    fill(&mut self.description, &other.description);
+    fill(&mut self.value, &other.value);
+    fill(&mut self.range, &other.range);
+    fill(&mut self.toggled, &other.toggled);
+    fill(&mut self.selected, &other.selected);
+    fill(&mut self.expanded, &other.expanded);
+    fill(&mut self.live, &other.live);
+    self.disabled |= other.disabled;
+    for a in other.actions.iter() {
+      if !self.actions.contains(a) {
+        self.actions.push(*a);
+      }
+    }
+  }
+
+  pub(crate) fn to_node(&self) -> accesskit::Node {
+    let mut node = accesskit::Node::new(self.role);
+    if let Some(label) = &self.label {
+      node.set_label(label.to_string());
+    }
+    if let Some(description) = &self.description {
+      node.set_description(description.to_string());
+    }
+    if let Some(value) = &self.value {
+      node.set_value(value.to_string());
+    }
+    if let Some(A11yRange { value, min, max, step }) = self.range {
+      node.set_numeric_value(value);
+      node.set_min_numeric_value(min);
+      node.set_max_numeric_value(max);
+      if let Some(step) = step {
+        node.set_numeric_value_step(step);
+      }
+    }
+    if let Some(toggled) = self.toggled {
+      node.set_toggled(toggled);
+    }
+    if let Some(selected) = self.selected {
+      node.set_selected(selected);
+    }
+    if let Some(expanded) = self.expanded {
+      node.set_expanded(expanded);
+    }
+    if self.disabled {
+      node.set_disabled();
+    }
+    if let Some(live) = self.live {
+      node.set_live(live);
+    }
+    for a in self.actions.iter() {
+      node.add_action(*a);
+    }
+    node
+  }
+}
+
+impl Default for A11y {
+  fn default() -> Self {
+    Self {
+      role: Role::Unknown,
+      label: None,
+      description: None,
+      value: None,
+      range: None,
+      toggled: None,
+      selected: None,
+      expanded: None,
+      disabled: false,
+      live: None,
+      actions: SmallVec::new(),
+    }
+  }
+}
+
+impl From<Role> for A11y {
+  fn from(role: Role) -> Self { A11y::new(role) }
+}
+
+impl Declare for A11yWidget {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl<'c> ComposeChild<'c> for A11yWidget {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    child
+      .dirty_on(this.raw_modifies(), DirtyPhase::Paint)
+      .attach_data(Box::new(this.clone_writer()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn semantic_tree() {
+    reset_test_env!();
+
+    let (label, w_label) = split_value("Ok");
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @MockMulti {
+        a11y: A11y::new(Role::Group).with_label("dialog"),
+        @MockBox {
+          size: Size::new(50., 20.),
+          a11y: pipe!(A11y::new(Role::Button).with_label(*$read(label))),
+        }
+        @MockBox {
+          size: Size::new(50., 20.),
+          @MockBox {
+            size: Size::new(20., 20.),
+            a11y: A11y::new(Role::CheckBox).with_toggled(Toggled::True),
+          }
+        }
+      }
+    });
+    wnd.enable_a11y();
+    wnd.draw_frame();
+
+    let tree = wnd.a11y_tree();
+    let root = tree.root().unwrap();
+    let group = tree.children(root)[0];
+    assert_eq!(tree.node(group).unwrap().role(), Role::Group);
+    // The widget without semantics is skipped.
+    let children = tree.children(group).to_vec();
+    assert_eq!(children.len(), 2);
+    let button = tree.node(children[0]).unwrap();
+    assert_eq!(button.label(), Some("Ok"));
+    let bounds = button.bounds().unwrap();
+    assert_eq!((bounds.x1, bounds.y1), (50., 20.));
+    let check = tree.node(children[1]).unwrap();
+    assert_eq!(check.toggled(), Some(Toggled::True));
+    drop(tree);
+
+    // Only the changed widget is updated.
+    *w_label.write() = "Cancel";
+    wnd.draw_frame();
+    let tree = wnd.a11y_tree();
+    assert_eq!(tree.last_update_len(), 1);
+    assert_eq!(tree.node(children[0]).unwrap().label(), Some("Cancel"));
+  }
+
+  #[test]
+  fn text_label_in_labelled_node() {
+    reset_test_env!();
+
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(50., 20.),
+          a11y: A11y::new(Role::Button).with_label("Save the file"),
+          @Text { text: "Save" }
+        }
+        @MockBox {
+          size: Size::new(50., 20.),
+          a11y: A11y::new(Role::Link),
+          @Text { text: "Open" }
+        }
+      }
+    });
+    wnd.enable_a11y();
+    wnd.draw_frame();
+
+    let tree = wnd.a11y_tree();
+    // The label of the button is announced without its text.
+    let button = tree.find_by_role(Role::Button).unwrap();
+    assert_eq!(tree.node(button).unwrap().label(), Some("Save the file"));
+    assert!(tree.children(button).is_empty());
+    // The unlabelled link is named by its text.
+    let link = tree.find_by_role(Role::Link).unwrap();
+    let text = tree.children(link)[0];
+    assert_eq!(tree.node(text).unwrap().label(), Some("Open"));
+  }
+
+  #[test]
+  fn action_click() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(0);
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @MockStack {
+        @MockBox {
+          size: Size::new(50., 20.),
+          a11y: A11y::new(Role::Button).with_action(Action::Click),
+          on_tap: move |_| *$write(w_taps) += 1,
+        }
+        // The button is covered, but the click still reaches it.
+        @MockBox { size: Size::new(50., 20.) }
+      }
+    });
+    wnd.enable_a11y();
+    wnd.draw_frame();
+
+    let button = wnd
+      .a11y_tree()
+      .find_by_role(Role::Button)
+      .unwrap();
+    wnd.a11y_action(button, Action::Click);
+    wnd.draw_frame();
+    assert_eq!(*taps.read(), 1);
+  }
+}
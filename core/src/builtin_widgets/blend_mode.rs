@@ -0,0 +1,59 @@
+use crate::{prelude::*, wrap_render::*};
+
+/// A wrapper that blends its child with the content behind it.
+///
+/// This is a built-in `FatObj` field. Setting the `blend_mode` field attaches
+/// a `BlendModeWidget` wrapper, the child is painted as a single layer which
+/// composites with the backdrop by the [`BlendMode`].
+///
+/// # Example
+///
+/// Tint an image card by multiplying a color over it.
+///
+/// ```rust
+/// use ribir::prelude::*;
+///
+/// stack! {
+///   @container {
+///     size: Size::new(100., 100.),
+///     background: Color::YELLOW,
+///   }
+///   @container {
+///     size: Size::new(100., 100.),
+///     background: Color::from_rgb(0, 128, 255),
+///     blend_mode: BlendMode::Multiply,
+///   }
+/// };
+/// ```
+#[derive(Clone, Default)]
+pub struct BlendModeWidget {
+  pub blend_mode: BlendMode,
+}
+
+impl Declare for BlendModeWidget {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl_compose_child_for_wrap_render!(BlendModeWidget);
+
+impl WrapRender for BlendModeWidget {
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    if self.blend_mode != BlendMode::Normal {
+      ctx.painter().set_blend_mode(self.blend_mode);
+    }
+    host.paint(ctx)
+  }
+
+  #[inline]
+  fn wrapper_dirty_phase(&self) -> DirtyPhase { DirtyPhase::Paint }
+
+  #[cfg(feature = "debug")]
+  fn debug_type(&self) -> Option<&'static str> { Some("blendMode") }
+
+  #[cfg(feature = "debug")]
+  fn debug_properties(&self) -> Option<serde_json::Value> {
+    Some(serde_json::json!({ "mode": format!("{:?}", self.blend_mode) }))
+  }
+}
@@ -1,5 +1,6 @@
-use std::ops::Range;
+use std::{borrow::Cow, ops::Range};
 
+use smallvec::{SmallVec, smallvec};
 use wrap_render::WrapRender;
 
 use super::*;
@@ -39,6 +40,20 @@ pub struct Border {
 pub struct BorderSide {
   pub color: Brush,
   pub width: f32,
+  pub style: BorderStyle,
+}
+
+/// The line style of a border side.
+///
+/// A dashed border only follows the corner radius when all its sides are the
+/// same, otherwise every dashed side is drawn as a straight line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle {
+  #[default]
+  Solid,
+  /// Dashes of three times the border width, separated by gaps of twice the
+  /// border width.
+  Dashed,
 }
 
 impl Declare for BorderWidget {
@@ -71,7 +86,30 @@ impl Border {
 
 impl BorderSide {
   #[inline]
-  pub fn new(width: f32, color: Brush) -> Self { Self { width, color } }
+  pub fn new(width: f32, color: Brush) -> Self { Self { width, color, style: BorderStyle::Solid } }
+
+  #[inline]
+  pub fn dashed(width: f32, color: Brush) -> Self {
+    Self { width, color, style: BorderStyle::Dashed }
+  }
+
+  #[inline]
+  pub fn with_style(mut self, style: BorderStyle) -> Self {
+    self.style = style;
+    self
+  }
+
+  /// The dash of the stroke that draws this side, `None` for a solid side.
+  fn dash(&self) -> Option<(SmallVec<[f32; 4]>, f32)> {
+    match self.style {
+      BorderStyle::Solid => None,
+      BorderStyle::Dashed => Some((smallvec![self.width * 3., self.width * 2.], 0.)),
+    }
+  }
+}
+
+impl Lerp for BorderStyle {
+  fn lerp(&self, to: &Self, factor: f32) -> Self { if factor == 0. { *self } else { *to } }
 }
 
 impl_compose_child_for_wrap_render!(BorderWidget);
@@ -98,8 +136,12 @@ impl WrapRender for BorderWidget {
       // Connecting adjacent borders implies that the styles of the neighboring
       // borders should match. If one of the adjacent borders is absent, the corner
       // radius will align with the existing border.
-      let border = &self.border;
-      let uniform_painted = border.try_paint_uniform_stroke(size, &radius, &mut painter);
+      let uniform_painted = self
+        .border
+        .try_paint_uniform_stroke(size, &radius, &mut painter);
+      // The dashed sides are stroked one by one, only the solid sides connect.
+      let solid = self.border.solid_sides();
+      let border = &*solid;
       let first = (!uniform_painted)
         .then(|| {
           border
@@ -107,6 +149,9 @@ impl WrapRender for BorderWidget {
             .map(|side| border.expand_continuous(side))
         })
         .flatten();
+      if !uniform_painted {
+        self.border.paint_dashed_sides(size, &mut painter);
+      }
 
       if let Some(rg) = first {
         let old_brush = painter.fill_brush().clone();
@@ -198,6 +243,9 @@ impl Border {
       && self.top.color == self.right.color
       && self.top.color == self.bottom.color
       && self.top.color == self.left.color
+      && self.top.style == self.right.style
+      && self.top.style == self.bottom.style
+      && self.top.style == self.left.style
       && size.width > w
       && size.height > w)
     {
@@ -219,12 +267,67 @@ impl Border {
         .set_style(PathStyle::Stroke)
         .set_stroke_brush(self.top.color.clone())
         .set_line_width(w)
+        .set_line_dash(self.top.dash())
         .rect_round(&stroke_rect, &stroke_radius, true)
         .stroke();
     }
     true
   }
 
+  /// Returns the border with its dashed sides removed.
+  fn solid_sides(&self) -> Cow<'_, Border> {
+    let sides = [&self.left, &self.right, &self.top, &self.bottom];
+    if sides
+      .iter()
+      .all(|side| side.style == BorderStyle::Solid)
+    {
+      return Cow::Borrowed(self);
+    }
+
+    let solid = |side: &BorderSide| match side.style {
+      BorderStyle::Solid => side.clone(),
+      BorderStyle::Dashed => BorderSide::default(),
+    };
+    Cow::Owned(Border {
+      left: solid(&self.left),
+      right: solid(&self.right),
+      top: solid(&self.top),
+      bottom: solid(&self.bottom),
+    })
+  }
+
+  /// Strokes every dashed side along its center line.
+  fn paint_dashed_sides(&self, size: Size, painter: &mut Painter) {
+    for pos in [SidePos::Top, SidePos::Right, SidePos::Bottom, SidePos::Left] {
+      let side = self.get_side(pos);
+      if side.style == BorderStyle::Solid || side.width <= 0. {
+        continue;
+      }
+
+      let half = side.width * 0.5;
+      let (from, to) = match pos {
+        SidePos::Top => (Point::new(0., half), Point::new(size.width, half)),
+        SidePos::Right => {
+          (Point::new(size.width - half, 0.), Point::new(size.width - half, size.height))
+        }
+        SidePos::Bottom => {
+          (Point::new(size.width, size.height - half), Point::new(0., size.height - half))
+        }
+        SidePos::Left => (Point::new(half, size.height), Point::new(half, 0.)),
+      };
+      let mut painter = painter.save_guard();
+      painter
+        .set_style(PathStyle::Stroke)
+        .set_stroke_brush(side.color.clone())
+        .set_line_width(side.width)
+        .set_line_dash(side.dash())
+        .begin_path(from)
+        .line_to(to)
+        .end_path(false)
+        .stroke();
+    }
+  }
+
   fn paint_continuous_borders(
     &self, size: Size, rg: &Range<SidePos>, radius: &Radius, painter: &mut Painter,
   ) {
@@ -590,6 +693,48 @@ mod tests {
     LayoutCase::new(&[0]).with_rect(ribir_types::rect(0., 0., 100., 100.))
   );
 
+  #[test]
+  fn dashed_border() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 50.),
+          border: Border::all(BorderSide::dashed(2., Color::RED.into())),
+        }
+      },
+      Size::new(200., 100.),
+    );
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().unwrap();
+
+    let mut dashes = frame
+      .commands
+      .iter()
+      .filter_map(|cmd| match cmd {
+        PaintCommand::Path(PathCommand {
+          path: PaintPath::Own(path),
+          action: PaintPathAction::Paint { painting_style: PaintingStyle::Stroke(options), .. },
+          ..
+        }) => {
+          let (pattern, offset) = options.dash.as_ref()?;
+          assert_eq!(pattern.as_slice(), &[6., 4.]);
+          let count = path
+            .dashed(pattern, *offset)
+            .segments()
+            .filter(|s| matches!(s, PathSegment::MoveTo(_)))
+            .count();
+          Some(count)
+        }
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    dashes.sort();
+    // Each side is stroked alone, a dash with a gap is 10 pixels.
+    assert_eq!(dashes, [5, 5, 10, 10]);
+  }
+
   fn border_100_50_box(
     top: f32, right: f32, bottom: f32, left: f32, radius: Option<Radius>,
   ) -> Widget<'static> {
@@ -0,0 +1,306 @@
+use std::cell::Cell;
+
+use ribir_algo::Rc;
+
+use crate::{
+  prelude::*,
+  window::{WindowAttributes, WindowId},
+};
+
+/// A widget that opens its child in a new window owned by the window it is
+/// declared in.
+///
+/// The window opens after the widget is mounted and closes when the widget is
+/// disposed, so its lifetime follows the declaring widget. The widget itself
+/// takes no space in the owner window.
+///
+/// Use the [`WindowKind`] to open a popup for menus and tooltips that may
+/// extend past the bounds of the owner, or a modal window that blocks the
+/// input of the owner until it closes.
+///
+/// The child is built in the new window, so the providers of the declaring
+/// widget are not visible to it.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _ = fn_widget! {
+///   @Window {
+///     kind: WindowKind::Popup,
+///     window_size: Size::new(120., 80.),
+///     offset: Point::new(0., 24.),
+///     @ { text! { text: "A menu beyond the window edge" } }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct ChildWindow {
+  #[declare(default)]
+  pub title: String,
+  #[declare(default)]
+  pub kind: WindowKind,
+  /// The size of the window client area, the shell decides it if not set.
+  #[declare(default)]
+  pub window_size: Option<Size>,
+  /// The position of the window relative to the origin of the declaring
+  /// widget.
+  #[declare(default)]
+  pub offset: Point,
+}
+
+/// `@Window { ... }` declares a [`ChildWindow`].
+impl Declare for Window {
+  type Builder = <ChildWindow as Declare>::Builder;
+
+  fn declarer() -> Self::Builder { ChildWindow::declarer() }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ChildWindowState {
+  Pending,
+  Opened(WindowId),
+  Closed,
+}
+
+impl ChildWindow {
+  fn attributes(&self, owner: &Window, host: WidgetId) -> WindowAttributes {
+    let mut attrs = WindowAttributes::default();
+    attrs
+      .with_title(self.title.clone())
+      .with_owner(owner.id())
+      .with_kind(self.kind);
+    if let Some(size) = self.window_size {
+      attrs.with_size(size);
+    }
+    attrs.position(self.screen_position(owner, host))
+  }
+
+  fn screen_position(&self, owner: &Window, host: WidgetId) -> Point {
+    // The offset is in the client area of the owner, not its decorations.
+    owner.client_position() + owner.map_to_global(self.offset, host).to_vector()
+  }
+}
+
+impl ComposeChild<'static> for ChildWindow {
+  /// The child should be a `GenWidget`, because it's built in another window.
+  type Child = GenWidget;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    let owner = BuildCtx::get().window();
+    let state = Rc::new(Cell::new(ChildWindowState::Pending));
+    let mut host = FatObj::new(Void::default());
+    let track_id = host.track_id();
+
+    let sync = {
+      let (this, state, owner, track_id) =
+        (this.clone_watcher(), state.clone(), owner.clone(), track_id.clone());
+      watch!(($read(this).title.clone(), $read(this).offset)).subscribe(move |(title, _)| {
+        let ChildWindowState::Opened(id) = state.get() else { return };
+        let (Some(wnd), Some(host)) = (AppCtx::get_window(id), track_id.get()) else { return };
+        wnd.set_title(&title);
+        wnd.set_position(this.read().screen_position(&owner, host));
+      })
+    };
+
+    host.on_mounted({
+      let state = state.clone();
+      move |_| {
+        let this = this.clone_watcher();
+        // Open the window after the owner finishes its layout, so the position
+        // of the declaring widget is known.
+        owner.clone().once_frame_finished(move || {
+          AppCtx::spawn_local(async move {
+            let Some(host) = track_id.get() else { return };
+            if state.get() != ChildWindowState::Pending {
+              return;
+            }
+            let attrs = this.read().attributes(&owner, host);
+            let wnd = AppCtx::new_window(child, owner.flags(), attrs).await;
+            if state.get() == ChildWindowState::Closed {
+              // The declaring widget was disposed while the window was opening.
+              wnd.close();
+            } else {
+              state.set(ChildWindowState::Opened(wnd.id()));
+            }
+          })
+        });
+      }
+    });
+    host.on_disposed(move |_| {
+      sync.unsubscribe();
+      if let ChildWindowState::Opened(id) = state.replace(ChildWindowState::Closed)
+        && let Some(wnd) = AppCtx::get_window(id)
+      {
+        wnd.close();
+      }
+    });
+
+    host.into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::ElementState;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*, window::UiEvent};
+
+  fn child_windows(wnd: &Window) -> Vec<Rc<Window>> {
+    wnd
+      .owned_windows()
+      .into_iter()
+      .filter_map(AppCtx::get_window)
+      .collect()
+  }
+
+  #[test]
+  fn window_follows_declaring_widget() {
+    reset_test_env!();
+
+    let (open, w_open) = split_value(true);
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 100.),
+          @ {
+            pipe!(*$read(open)).map(move |open| open.then(|| fn_widget! {
+              @Window {
+                title: "Child",
+                window_size: Size::new(50., 40.),
+                @ { fn_widget! { @MockBox { size: Size::new(10., 10.) } } }
+              }
+            }))
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+
+    let children = child_windows(&wnd);
+    assert_eq!(children.len(), 1);
+    let child = &children[0];
+    assert_eq!(child.owner(), Some(wnd.id()));
+    assert_eq!(child.size(), Size::new(50., 40.));
+    assert_eq!(TestShellWindow::of(child).title, "Child");
+
+    let child_id = child.id();
+    *w_open.write() = false;
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    assert!(AppCtx::get_window(child_id).is_none());
+    assert!(wnd.owned_windows().is_empty());
+  }
+
+  #[test]
+  fn popup_extends_past_owner() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 20.),
+          x: 150.,
+          y: 180.,
+          @Window {
+            kind: WindowKind::Popup,
+            window_size: Size::new(120., 80.),
+            offset: Point::new(0., 20.),
+            @ { fn_widget! { @MockBox { size: Size::new(120., 80.) } } }
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.set_position(Point::new(300., 400.));
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+
+    let popup = child_windows(&wnd).pop().unwrap();
+    assert_eq!(popup.kind(), WindowKind::Popup);
+    assert_eq!(popup.position(), Point::new(450., 600.));
+    let shell = TestShellWindow::of(&popup);
+    assert_eq!(shell.kind, WindowKind::Popup);
+    assert_eq!(shell.owner, Some(wnd.id()));
+  }
+
+  #[test]
+  fn modal_blocks_owner_input() {
+    reset_test_env!();
+
+    let (clicks, w_clicks) = split_value(0);
+    let (modal, w_modal) = split_value(false);
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_tap: move |_| *$write(w_clicks) += 1,
+          @ {
+            pipe!(*$read(modal)).map(move |modal| modal.then(|| fn_widget! {
+              @Window {
+                kind: WindowKind::Modal,
+                @ { fn_widget! { @MockBox { size: Size::new(10., 10.) } } }
+              }
+            }))
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    let tap = |wnd: &TestWindow| {
+      let wnd_id = wnd.id();
+      AppCtx::send_ui_event(UiEvent::CursorMoved { wnd_id, pos: Point::new(50., 50.) });
+      for state in [ElementState::Pressed, ElementState::Released] {
+        AppCtx::send_ui_event(UiEvent::MouseInput {
+          wnd_id,
+          device_id: Box::new(DummyDeviceId),
+          button: MouseButtons::PRIMARY,
+          state,
+        });
+      }
+      AppCtx::run_until_stalled();
+    };
+
+    tap(&wnd);
+    assert_eq!(*clicks.read(), 1);
+
+    *w_modal.write() = true;
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    let dialog = wnd.modal_window().unwrap();
+
+    tap(&wnd);
+    assert_eq!(*clicks.read(), 1);
+    assert_eq!(TestShellWindow::of(&dialog).focus_count, 1);
+
+    *w_modal.write() = false;
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    assert!(wnd.modal_window().is_none());
+
+    tap(&wnd);
+    assert_eq!(*clicks.read(), 2);
+  }
+
+  #[test]
+  fn owned_windows_close_with_owner() {
+    reset_test_env!();
+
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @Window { @ { fn_widget! { @Void {} } } }
+    });
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+
+    let child = child_windows(&wnd).pop().unwrap();
+    wnd.dispose();
+    assert!(AppCtx::get_window(child.id()).is_none());
+    assert!(TestShellWindow::of(&child).closed.get());
+  }
+}
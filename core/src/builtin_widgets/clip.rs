@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+
 use crate::prelude::*;
 
 /// A widget that clips its child using a specified path.
@@ -19,15 +21,38 @@ use crate::prelude::*;
 ///   }
 /// };
 /// ```
+///
+/// The clip path can be combined by the boolean operations of `Path`, and the
+/// pointer only hits the child inside the clip path.
+///
+/// ```rust
+/// use ribir::prelude::*;
+///
+/// let ring =
+///   Path::circle(Point::new(50., 50.), 50.).difference(&Path::circle(Point::new(50., 50.), 25.));
+/// fn_widget! {
+///   @Clip {
+///     clip_path: ring.clone(),
+///     @Container {
+///       size: Size::new(100., 100.),
+///       background: Color::RED
+///     }
+///   }
+/// };
+/// ```
 #[derive(SingleChild, Declare)]
 pub struct Clip {
   pub clip_path: Path,
+  /// The flattened `clip_path` to hit-test, it's reset by the layout.
+  #[declare(skip)]
+  hit_area: RefCell<Option<FlattenedPath>>,
 }
 
 impl Render for Clip {
   fn size_affected_by_child(&self) -> bool { false }
 
   fn measure(&self, clamp: BoxClamp, ctx: &mut MeasureCtx) -> Size {
+    self.hit_area.take();
     ctx.perform_single_child_layout(clamp);
     self
       .clip_path
@@ -39,6 +64,61 @@ impl Render for Clip {
 
   fn paint(&self, ctx: &mut PaintingCtx) { ctx.painter().clip(self.clip_path.clone().into()); }
 
+  fn hit_test(&self, ctx: &mut HitTestCtx, pos: Point) -> HitTest {
+    let box_pos = ctx.box_pos().unwrap_or(Point::zero());
+    let hit = self
+      .hit_area
+      .borrow_mut()
+      .get_or_insert_with(|| self.clip_path.flatten())
+      .contains(pos - box_pos.to_vector());
+    HitTest { hit, can_hit_child: hit }
+  }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("clip") }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn hit_test_clip_path() {
+    reset_test_env!();
+
+    let center = Point::new(50., 50.);
+    let ring = Path::circle(center, 50.).difference(&Path::circle(center, 25.));
+    let (taps, w_taps) = split_value(0);
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Clip {
+          clip_path: ring.clone(),
+          @MockBox {
+            size: Size::new(100., 100.),
+            on_tap: move |_| *$write(w_taps) += 1,
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    let tap = |pos: Point| {
+      wnd.process_cursor_move(pos);
+      wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+      wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+      wnd.run_frame_tasks();
+    };
+    // In the ring.
+    tap(Point::new(10., 50.));
+    assert_eq!(*taps.read(), 1);
+    // In the hole and outside the corner of the ring.
+    tap(center);
+    tap(Point::new(2., 2.));
+    assert_eq!(*taps.read(), 1);
+    // The other side of the ring.
+    tap(Point::new(90., 50.));
+    assert_eq!(*taps.read(), 2);
+  }
+}
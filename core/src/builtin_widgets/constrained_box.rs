@@ -31,6 +31,20 @@ impl Declare for ConstrainedBox {
 
 impl_compose_child_for_wrap_render!(ConstrainedBox);
 
+impl ConstrainedBox {
+  fn clamp_width(&self, width: f32) -> f32 {
+    width
+      .max(self.clamp.min.width)
+      .min(self.clamp.max.width)
+  }
+
+  fn clamp_height(&self, height: f32) -> f32 {
+    height
+      .max(self.clamp.min.height)
+      .min(self.clamp.max.height)
+  }
+}
+
 impl WrapRender for ConstrainedBox {
   fn measure(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut MeasureCtx) -> Size {
     let max = clamp.clamp(self.clamp.max);
@@ -38,6 +52,26 @@ impl WrapRender for ConstrainedBox {
     host.measure(BoxClamp { min, max }, ctx)
   }
 
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    let width = host.min_intrinsic_width(self.clamp_height(height), ctx);
+    self.clamp_width(width)
+  }
+
+  fn max_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    let width = host.max_intrinsic_width(self.clamp_height(height), ctx);
+    self.clamp_width(width)
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let height = host.min_intrinsic_height(self.clamp_width(width), ctx);
+    self.clamp_height(height)
+  }
+
+  fn max_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let height = host.max_intrinsic_height(self.clamp_width(width), ctx);
+    self.clamp_height(height)
+  }
+
   fn size_affected_by_child(&self, host: &dyn Render) -> bool {
     let is_fixed = self.clamp.min == self.clamp.max;
     if is_fixed { false } else { host.size_affected_by_child() }
@@ -67,6 +67,71 @@ impl Render for Container {
   #[inline]
   fn size_affected_by_child(&self) -> bool { false }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .hint_size
+      .width
+      .fixed_pixel()
+      .unwrap_or_else(|| {
+        let height = self
+          .hint_size
+          .height
+          .fixed_pixel()
+          .unwrap_or(height);
+        ctx.children_intrinsic_size(IntrinsicSize::MinWidth, height)
+      })
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .hint_size
+      .width
+      .fixed_pixel()
+      .unwrap_or_else(|| {
+        let height = self
+          .hint_size
+          .height
+          .fixed_pixel()
+          .unwrap_or(height);
+        ctx.children_intrinsic_size(IntrinsicSize::MaxWidth, height)
+      })
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .hint_size
+      .height
+      .fixed_pixel()
+      .unwrap_or_else(|| {
+        let width = self
+          .hint_size
+          .width
+          .fixed_pixel()
+          .unwrap_or(width);
+        ctx.children_intrinsic_size(IntrinsicSize::MinHeight, width)
+      })
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .hint_size
+      .height
+      .fixed_pixel()
+      .unwrap_or_else(|| {
+        let width = self
+          .hint_size
+          .width
+          .fixed_pixel()
+          .unwrap_or(width);
+        ctx.children_intrinsic_size(IntrinsicSize::MaxHeight, width)
+      })
+  }
+
+  fn baseline(&self, _size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    let child = ctx.single_child()?;
+    ctx.child_baseline(child)
+  }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("container") }
 }
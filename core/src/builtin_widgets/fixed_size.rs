@@ -115,6 +115,14 @@ impl Dimension {
       Dimension::Fixed(m) => m.into_pixel(max),
     }
   }
+
+  /// The size in pixels if it's fixed and not relative to the parent.
+  pub(crate) fn fixed_pixel(self) -> Option<f32> {
+    match self {
+      Dimension::Fixed(Measure::Pixel(px)) => Some(px),
+      _ => None,
+    }
+  }
 }
 
 impl Declare for FixedSize {
@@ -141,6 +149,42 @@ impl WrapRender for FixedSize {
     host.measure(new_clamp, ctx)
   }
 
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    let height = self.size.height.fixed_pixel().unwrap_or(height);
+    self
+      .size
+      .width
+      .fixed_pixel()
+      .unwrap_or_else(|| host.min_intrinsic_width(height, ctx))
+  }
+
+  fn max_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    let height = self.size.height.fixed_pixel().unwrap_or(height);
+    self
+      .size
+      .width
+      .fixed_pixel()
+      .unwrap_or_else(|| host.max_intrinsic_width(height, ctx))
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let width = self.size.width.fixed_pixel().unwrap_or(width);
+    self
+      .size
+      .height
+      .fixed_pixel()
+      .unwrap_or_else(|| host.min_intrinsic_height(width, ctx))
+  }
+
+  fn max_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let width = self.size.width.fixed_pixel().unwrap_or(width);
+    self
+      .size
+      .height
+      .fixed_pixel()
+      .unwrap_or_else(|| host.max_intrinsic_height(width, ctx))
+  }
+
   #[inline]
   fn wrapper_dirty_phase(&self) -> DirtyPhase { DirtyPhase::Layout }
 
@@ -1,11 +1,14 @@
-//! WebP image with lazy per-frame streaming decode and caching.
+//! Image with lazy per-frame streaming decode and caching.
 //!
-//! This module only supports WebP format. Use the `asset!` macro to
-//! automatically convert other formats (PNG, JPEG, GIF, etc.) to WebP
-//! at compile time.
+//! WebP is always supported. PNG, JPEG, GIF and AVIF are decoded at runtime
+//! when the cargo features `png`, `jpeg`, `gif` and `avif` are enabled, they
+//! are disabled by default to keep the binary (especially the wasm) small. The
+//! `asset!` macro converts these formats to WebP at compile time, so the
+//! embedded assets not need the features.
 //!
-//! Frame decoding is lazy - pixels are only decoded when first accessed.
-//! For animated images, frames are decoded sequentially up to the requested
+//! Frame decoding is lazy - `Image::new` only reads the header, the frames are
+//! decoded when first accessed, such as when the image is painted. For
+//! animated WebP and GIF, frames are decoded sequentially up to the requested
 //! frame.
 //!
 //! # Usage as Widget
@@ -95,10 +98,11 @@ pub struct DecodedFrame {
   pub delay_ms: u32,
 }
 
-/// WebP image with lazy streaming decode and frame caching.
+/// Image with lazy streaming decode and frame caching.
 ///
-/// Source images (PNG, JPEG, GIF, etc.) are converted to WebP at compile time.
-/// At runtime, frames are decoded on-demand and cached for reuse.
+/// The assets of `asset!` are converted to WebP at compile time, and the
+/// images created at runtime are decoded from their own format. Frames are
+/// decoded on-demand and cached for reuse.
 ///
 /// Cloning an `Image` is cheap (reference-counted). All clones share the same
 /// decoded frame cache, avoiding redundant decoding when the same image is
@@ -106,6 +110,18 @@ pub struct DecodedFrame {
 #[derive(Clone)]
 pub struct Image(Arc<ImageInner>);
 
+/// The error of creating an [`Image`] from encoded data.
+#[derive(Debug)]
+pub enum ImageError {
+  /// The data is not in a known image format.
+  UnknownFormat,
+  /// The data is in the format, but the cargo feature to decode it is not
+  /// enabled.
+  FeatureDisabled(&'static str),
+  /// The data is broken.
+  Decode(Box<dyn std::error::Error + Send + Sync>),
+}
+
 /// Iterator over image frames.
 pub struct FrameIterator<'a> {
   image: &'a Image,
@@ -117,29 +133,29 @@ pub struct FrameIterator<'a> {
 // ============================================================================
 
 impl Image {
-  /// Creates an Image from raw WebP data.
+  /// Creates an Image from encoded data, the format is sniffed from the
+  /// signature of the data.
   ///
-  /// Only parses the WebP header for metadata. No frame decoding until access.
-  pub fn new(raw: impl Into<Cow<'static, [u8]>>) -> Result<Self, DecodingError> {
+  /// Only parses the header, so the size and the frame count are known
+  /// without decoding. The frames are decoded on access, a broken frame is
+  /// reported by [`Image::frame`] returning `None`.
+  pub fn new(raw: impl Into<Cow<'static, [u8]>>) -> Result<Self, ImageError> {
     let raw = StableData::from_cow(raw.into());
-    let decoder = WebPDecoder::new(std::io::Cursor::new(raw.as_slice()))?;
-
-    let (width, height) = decoder.dimensions();
-    let is_animated = decoder.is_animated();
-    let frame_count = if is_animated { decoder.num_frames() as usize } else { 1 };
-    let loop_count = match decoder.loop_count() {
-      image_webp::LoopCount::Forever => LoopCount::Infinite,
-      image_webp::LoopCount::Times(n) => LoopCount::Finite(n.get() as u32),
-    };
+    let format = ImageFormat::sniff(raw.as_slice()).ok_or(ImageError::UnknownFormat)?;
+    let header = format.read_header(raw.as_slice())?;
+    if header.frame_count == 0 {
+      return Err(ImageError::Decode("The image has no frames".into()));
+    }
 
     Ok(Self(Arc::new(ImageInner {
       decoder_state: Mutex::new(DecoderState::new()),
       raw,
-      width,
-      height,
-      loop_count,
-      is_animated,
-      frame_cache: new_frame_cache(frame_count),
+      format,
+      width: header.width,
+      height: header.height,
+      loop_count: header.loop_count,
+      is_animated: header.is_animated,
+      frame_cache: new_frame_cache(header.frame_count),
     })))
   }
 
@@ -163,6 +179,7 @@ impl Image {
     Self(Arc::new(ImageInner {
       decoder_state: Mutex::new(DecoderState::with_decoded(frame_count)),
       raw: StableData::from_cow(raw.into()),
+      format: ImageFormat::WebP,
       width,
       height,
       loop_count,
@@ -202,7 +219,7 @@ impl Image {
   /// Note: Requires decoding all frames to get their delays.
   pub fn total_duration_ms(&self) -> u64 {
     if !self.0.frame_cache.is_empty() {
-      self.ensure_decoded_up_to(self.0.frame_cache.len() - 1);
+      self.decode_up_to(self.0.frame_cache.len() - 1);
     }
     self
       .0
@@ -215,7 +232,8 @@ impl Image {
 
   // --- Frame Access ---
 
-  /// Returns the decoded frame at index, or None if out of bounds.
+  /// Returns the decoded frame at index, or None if out of bounds or the
+  /// frame is broken.
   ///
   /// Frames are decoded on first access. For animated images, all frames
   /// up to the requested index are decoded (WebP sequential dependency).
@@ -230,7 +248,7 @@ impl Image {
     }
 
     // Slow path: decode
-    self.ensure_decoded_up_to(index);
+    self.decode_up_to(index);
     self.0.frame_cache[index].get().cloned()
   }
 
@@ -262,37 +280,83 @@ impl Image {
 
   // --- Internal ---
 
-  fn ensure_decoded_up_to(&self, target: usize) {
+  /// The image of the frame, or a transparent image of the same size if the
+  /// frame is broken, so a broken image keeps its place in the layout.
+  fn frame_or_blank(&self, index: usize) -> Resource<PixelImage> {
+    self.frame(index).map_or_else(
+      || {
+        let (w, h) = (self.0.width, self.0.height);
+        let pixels = vec![0; w as usize * h as usize * 4];
+        Resource::new(PixelImage::new(pixels.into(), w, h, ColorFormat::Rgba8))
+      },
+      |f| f.image,
+    )
+  }
+
+  fn decode_up_to(&self, target: usize) {
+    if let Err(err) = self.ensure_decoded_up_to(target) {
+      tracing::error!("Failed to decode the frame {target} of the image: {err}");
+    }
+  }
+
+  fn ensure_decoded_up_to(&self, target: usize) -> Result<(), ImageError> {
     let inner = &self.0;
-    let mut state = inner.decoder_state.lock().unwrap();
+    let mut guard = inner.decoder_state.lock().unwrap();
+    let state = &mut *guard;
     if state.decoded_count > target {
-      return;
+      return Ok(());
     }
 
     let start = state.decoded_count;
-    let decoder = state
-      .decoder
-      .get_or_insert_with(|| WebPDecoder::new(RawPtrReader::new(inner.raw.as_ptr())).unwrap());
-
-    let buf_size = decoder.output_buffer_size().unwrap_or(0);
-    let mut buf = vec![0u8; buf_size];
-
-    if inner.is_animated {
-      for i in start..=target {
-        let delay_ms = decoder
-          .read_frame(&mut buf)
-          .expect("Failed to decode frame");
-        let _ = inner.frame_cache[i].set(create_frame(inner, buf.clone(), delay_ms));
+    let decoder = match &mut state.decoder {
+      Some(decoder) => decoder,
+      decoder => {
+        decoder.insert(FrameDecoder::new(inner.format, RawPtrReader::new(inner.raw.as_ptr()))?)
+      }
+    };
+
+    match decoder {
+      FrameDecoder::WebP(decoder) => {
+        let buf_size = decoder.output_buffer_size().unwrap_or(0);
+        let mut buf = vec![0u8; buf_size];
+
+        if inner.is_animated {
+          for i in start..=target {
+            let delay_ms = decoder.read_frame(&mut buf)?;
+            let _ = inner.frame_cache[i].set(create_frame(inner, buf.clone(), delay_ms));
+            state.decoded_count = i + 1;
+          }
+        } else {
+          decoder.read_image(&mut buf)?;
+          let _ = inner.frame_cache[0].set(create_frame(inner, buf, 0));
+          state.decoded_count = 1;
+        }
+      }
+      #[cfg(feature = "gif")]
+      FrameDecoder::Gif(frames) => {
+        for i in start..=target {
+          let frame = frames
+            .0
+            .next()
+            .ok_or(ImageError::Decode("The GIF frame is missing".into()))??;
+          let (numer, denom) = frame.delay().numer_denom_ms();
+          let delay_ms = numer / denom.max(1);
+          let buf = frame.into_buffer().into_raw();
+          let _ = inner.frame_cache[i].set(create_frame(inner, buf, delay_ms));
+          state.decoded_count = i + 1;
+        }
+      }
+      #[cfg(any(feature = "png", feature = "jpeg", feature = "avif"))]
+      FrameDecoder::Still(format) => {
+        let buf = ::image::load_from_memory_with_format(inner.raw.as_slice(), *format)?
+          .into_rgba8()
+          .into_raw();
+        let _ = inner.frame_cache[0].set(create_frame(inner, buf, 0));
+        state.decoded_count = 1;
       }
-    } else {
-      assert_eq!(target, 0, "Static image has only one frame");
-      decoder
-        .read_image(&mut buf)
-        .expect("Failed to decode image");
-      let _ = inner.frame_cache[0].set(create_frame(inner, buf, 0));
     }
 
-    state.decoded_count = target + 1;
+    Ok(())
   }
 }
 
@@ -346,15 +410,20 @@ impl Compose for Image {
         pipe! {
           let img = $read(this);
           let idx = *$read(frame_idx);
-          let frame = img.frame_by_global_idx(idx).expect("Invalid frame index");
-          if img.global_frame_count().is_none_or(|c| idx + 1 < c) {
-            Local::timer(Duration::from_millis(frame.delay_ms as u64))
-              .subscribe(move |_| *$write(frame_idx) += 1);
+          match img.frame_by_global_idx(idx) {
+            Some(frame) => {
+              if img.global_frame_count().is_none_or(|c| idx + 1 < c) {
+                Local::timer(Duration::from_millis(frame.delay_ms as u64))
+                  .subscribe(move |_| *$write(frame_idx) += 1);
+              }
+              frame.image
+            }
+            // A broken frame stops the animation at the first frame.
+            None => img.frame_or_blank(0),
           }
-          frame.image
         }.into_widget()
       } else {
-        $read(this).first_frame().image.into_widget()
+        $read(this).frame_or_blank(0).into_widget()
       }
     }
     .into_widget()
@@ -392,6 +461,7 @@ struct ImageInner {
   // a pointer to raw) is dropped before raw.
   decoder_state: Mutex<DecoderState>,
   raw: StableData,
+  format: ImageFormat,
   width: u32,
   height: u32,
   loop_count: LoopCount,
@@ -428,12 +498,192 @@ impl StableData {
   }
 }
 
+/// The encoded formats of the image, recognized by their signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImageFormat {
+  WebP,
+  Png,
+  Jpeg,
+  Gif,
+  Avif,
+}
+
+/// The metadata of an encoded image.
+struct ImageHeader {
+  width: u32,
+  height: u32,
+  frame_count: usize,
+  loop_count: LoopCount,
+  is_animated: bool,
+}
+
+impl ImageFormat {
+  fn sniff(data: &[u8]) -> Option<Self> {
+    match data {
+      [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(Self::WebP),
+      [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', ..] => Some(Self::Png),
+      [0xff, 0xd8, 0xff, ..] => Some(Self::Jpeg),
+      [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some(Self::Gif),
+      [_, _, _, _, b'f', b't', b'y', b'p', b'a', b'v', b'i', b'f' | b's', ..] => Some(Self::Avif),
+      _ => None,
+    }
+  }
+
+  fn read_header(self, data: &[u8]) -> Result<ImageHeader, ImageError> {
+    match self {
+      Self::WebP => {
+        let decoder = WebPDecoder::new(std::io::Cursor::new(data))?;
+        let (width, height) = decoder.dimensions();
+        let is_animated = decoder.is_animated();
+        let frame_count = if is_animated { decoder.num_frames() as usize } else { 1 };
+        let loop_count = match decoder.loop_count() {
+          image_webp::LoopCount::Forever => LoopCount::Infinite,
+          image_webp::LoopCount::Times(n) => LoopCount::Finite(n.get() as u32),
+        };
+        Ok(ImageHeader { width, height, frame_count, loop_count, is_animated })
+      }
+      #[cfg(feature = "gif")]
+      Self::Gif => {
+        use ::image::ImageDecoder;
+
+        let decoder = ::image::codecs::gif::GifDecoder::new(std::io::Cursor::new(data))?;
+        let (width, height) = decoder.dimensions();
+        let (frame_count, loop_count) =
+          gif_frames(data).ok_or(ImageError::Decode("The GIF data is truncated".into()))?;
+        let is_animated = frame_count > 1;
+        Ok(ImageHeader { width, height, frame_count, loop_count, is_animated })
+      }
+      #[cfg(feature = "png")]
+      Self::Png => still_header(data, ::image::ImageFormat::Png),
+      #[cfg(feature = "jpeg")]
+      Self::Jpeg => still_header(data, ::image::ImageFormat::Jpeg),
+      #[cfg(feature = "avif")]
+      Self::Avif => still_header(data, ::image::ImageFormat::Avif),
+      #[allow(unreachable_patterns)]
+      format => Err(ImageError::FeatureDisabled(format.feature())),
+    }
+  }
+
+  /// The cargo feature to decode the format.
+  fn feature(self) -> &'static str {
+    match self {
+      Self::WebP => "webp",
+      Self::Png => "png",
+      Self::Jpeg => "jpeg",
+      Self::Gif => "gif",
+      Self::Avif => "avif",
+    }
+  }
+}
+
+#[cfg(any(feature = "png", feature = "jpeg", feature = "avif"))]
+fn still_header(data: &[u8], format: ::image::ImageFormat) -> Result<ImageHeader, ImageError> {
+  let (width, height) =
+    ::image::ImageReader::with_format(std::io::Cursor::new(data), format).into_dimensions()?;
+  Ok(ImageHeader {
+    width,
+    height,
+    frame_count: 1,
+    loop_count: LoopCount::Infinite,
+    is_animated: false,
+  })
+}
+
+/// Scans the blocks of the GIF data for the number of frames and the loop
+/// count, without decoding the frames.
+#[cfg(feature = "gif")]
+fn gif_frames(data: &[u8]) -> Option<(usize, LoopCount)> {
+  fn skip_sub_blocks(data: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+      let size = *data.get(pos)? as usize;
+      pos += 1 + size;
+      if size == 0 {
+        return Some(pos);
+      }
+    }
+  }
+  fn color_table_size(packed: u8) -> usize {
+    if packed & 0x80 != 0 { 3 << ((packed & 0x07) + 1) } else { 0 }
+  }
+
+  let mut pos = 13 + color_table_size(*data.get(10)?);
+  let mut frames = 0;
+  // A GIF without the loop extension plays only once.
+  let mut loop_count = LoopCount::Finite(1);
+  loop {
+    match *data.get(pos)? {
+      // Extension
+      0x21 => {
+        let label = *data.get(pos + 1)?;
+        if label == 0xff && data.get(pos + 2..pos + 14)? == b"\x0bNETSCAPE2.0" {
+          let times = u16::from_le_bytes([*data.get(pos + 16)?, *data.get(pos + 17)?]);
+          // The loop count is the repeat times after the first play.
+          loop_count =
+            if times == 0 { LoopCount::Infinite } else { LoopCount::Finite(times as u32 + 1) };
+        }
+        pos = skip_sub_blocks(data, pos + 2)?;
+      }
+      // Image descriptor
+      0x2c => {
+        frames += 1;
+        // The descriptor, the local color table and the LZW code size.
+        pos += 10 + color_table_size(*data.get(pos + 9)?) + 1;
+        pos = skip_sub_blocks(data, pos)?;
+      }
+      // Trailer
+      0x3b => return Some((frames, loop_count)),
+      _ => return None,
+    }
+  }
+}
+
 /// Decoder state for streaming decode.
 struct DecoderState {
-  decoder: Option<WebPDecoder<RawPtrReader>>,
+  decoder: Option<FrameDecoder>,
   decoded_count: usize,
 }
 
+/// The decoder that keeps the progress of the sequential decode.
+enum FrameDecoder {
+  WebP(WebPDecoder<RawPtrReader>),
+  #[cfg(feature = "gif")]
+  Gif(GifFrames),
+  #[cfg(any(feature = "png", feature = "jpeg", feature = "avif"))]
+  Still(::image::ImageFormat),
+}
+
+impl FrameDecoder {
+  fn new(format: ImageFormat, reader: RawPtrReader) -> Result<Self, ImageError> {
+    let decoder = match format {
+      #[cfg(feature = "gif")]
+      ImageFormat::Gif => {
+        use ::image::AnimationDecoder;
+
+        let decoder = ::image::codecs::gif::GifDecoder::new(reader)?;
+        Self::Gif(GifFrames(decoder.into_frames()))
+      }
+      #[cfg(feature = "png")]
+      ImageFormat::Png => Self::Still(::image::ImageFormat::Png),
+      #[cfg(feature = "jpeg")]
+      ImageFormat::Jpeg => Self::Still(::image::ImageFormat::Jpeg),
+      #[cfg(feature = "avif")]
+      ImageFormat::Avif => Self::Still(::image::ImageFormat::Avif),
+      _ => Self::WebP(WebPDecoder::new(reader)?),
+    };
+    Ok(decoder)
+  }
+}
+
+/// The frames of a GIF composed one by one as they are read.
+#[cfg(feature = "gif")]
+struct GifFrames(::image::Frames<'static>);
+
+// SAFETY: The frames iterate a `GifDecoder` of a `RawPtrReader`, both are
+// `Send`, the iterator is only boxed to erase its type. It's protected by the
+// Mutex of the decoder state.
+#[cfg(feature = "gif")]
+unsafe impl Send for GifFrames {}
+
 impl DecoderState {
   fn new() -> Self { Self { decoder: None, decoded_count: 0 } }
 
@@ -502,6 +752,29 @@ impl BufRead for RawPtrReader {
   fn consume(&mut self, amt: usize) { self.pos += amt; }
 }
 
+impl std::fmt::Display for ImageError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::UnknownFormat => write!(f, "unknown image format"),
+      Self::FeatureDisabled(feature) => {
+        write!(f, "decoding the image requires the `{feature}` feature")
+      }
+      Self::Decode(err) => write!(f, "failed to decode the image: {err}"),
+    }
+  }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<DecodingError> for ImageError {
+  fn from(err: DecodingError) -> Self { Self::Decode(Box::new(err)) }
+}
+
+#[cfg(any(feature = "png", feature = "jpeg", feature = "gif", feature = "avif"))]
+impl From<::image::ImageError> for ImageError {
+  fn from(err: ::image::ImageError) -> Self { Self::Decode(Box::new(err)) }
+}
+
 fn new_frame_cache(count: usize) -> Box<[OnceLock<DecodedFrame>]> {
   let mut v = Vec::with_capacity(count);
   v.resize_with(count, OnceLock::new);
@@ -711,6 +984,83 @@ mod tests {
     assert!(debug.contains("Infinite"));
   }
 
+  #[test]
+  fn sniff_format() {
+    assert_eq!(ImageFormat::sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some(ImageFormat::WebP));
+    assert_eq!(ImageFormat::sniff(b"\x89PNG\r\n\x1a\n"), Some(ImageFormat::Png));
+    assert_eq!(ImageFormat::sniff(b"\xff\xd8\xff\xe0"), Some(ImageFormat::Jpeg));
+    assert_eq!(ImageFormat::sniff(b"GIF89a"), Some(ImageFormat::Gif));
+    assert_eq!(ImageFormat::sniff(b"\0\0\0\x1cftypavif"), Some(ImageFormat::Avif));
+    assert_eq!(ImageFormat::sniff(b"<svg"), None);
+    assert!(matches!(Image::new(&b"<svg></svg>"[..]), Err(ImageError::UnknownFormat)));
+  }
+
+  #[test]
+  fn broken_webp_reports_error() {
+    let pixels: Vec<u8> = (0..16 * 16 * 4).map(|i| (i * 7) as u8).collect();
+    let mut data = vec![];
+    image_webp::WebPEncoder::new(&mut data)
+      .encode(&pixels, 16, 16, image_webp::ColorType::Rgba8)
+      .unwrap();
+    assert!(
+      Image::new(data.clone())
+        .unwrap()
+        .frame(0)
+        .is_some()
+    );
+
+    // Keep the header but drop the most of the pixel data, the size is still
+    // known, and the frame is broken once it's decoded.
+    data.truncate(data.len() / 2);
+    let img = Image::new(data).unwrap();
+    assert_eq!(img.size(), DeviceSize::new(16, 16));
+    assert!(img.frame(0).is_none());
+  }
+
+  #[cfg(feature = "png")]
+  #[test]
+  fn decode_png() {
+    let img = Image::new(&include_bytes!("../../../tests/assets/test_image.png")[..]).unwrap();
+    assert_eq!(img.size(), DeviceSize::new(207, 136));
+    assert!(!img.is_animated());
+
+    let frame = img.first_frame();
+    assert_eq!(frame.image.width(), 207);
+    assert_eq!(frame.image.pixel_bytes().len(), 207 * 136 * 4);
+  }
+
+  #[cfg(feature = "png")]
+  #[test]
+  fn broken_png_reports_error() {
+    let mut data = include_bytes!("../../../tests/assets/test_image.png").to_vec();
+    // Keep the header but drop the most of the pixel data.
+    data.truncate(100);
+    let img = Image::new(data).unwrap();
+    assert_eq!(img.size(), DeviceSize::new(207, 136));
+    assert!(img.frame(0).is_none());
+  }
+
+  #[cfg(feature = "gif")]
+  #[test]
+  fn decode_animated_gif() {
+    let img = Image::new(&include_bytes!("../../../tests/assets/test_animated.gif")[..]).unwrap();
+    assert_eq!(img.size(), DeviceSize::new(32, 32));
+    assert!(img.is_animated());
+    assert_eq!(img.frame_count(), 2);
+    assert_eq!(img.loop_count(), LoopCount::Infinite);
+    // Nothing is decoded before the frames are accessed.
+    assert_eq!(img.0.decoder_state.lock().unwrap().decoded_count, 0);
+
+    let frame = img.frame(0).unwrap();
+    assert_eq!(img.0.decoder_state.lock().unwrap().decoded_count, 1);
+    assert_eq!(frame.image.width(), 32);
+
+    let frame = img.frame(1).unwrap();
+    assert_eq!(frame.delay_ms, 100);
+    assert_eq!(frame.image.pixel_bytes().len(), 32 * 32 * 4);
+    assert_eq!(img.total_duration_ms(), 200);
+  }
+
   // --- Widget Visual Tests ---
   // Note: Visual tests are in a separate module below with proper cfg
 }
@@ -62,6 +62,35 @@ impl Render for Margin {
     ctx.update_position(child, Point::new(self.margin.left, self.margin.top));
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    let m = &self.margin;
+    let height = (height - m.top - m.bottom).max(0.);
+    ctx.children_intrinsic_size(IntrinsicSize::MinWidth, height) + m.left + m.right
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    let m = &self.margin;
+    let height = (height - m.top - m.bottom).max(0.);
+    ctx.children_intrinsic_size(IntrinsicSize::MaxWidth, height) + m.left + m.right
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let m = &self.margin;
+    let width = (width - m.left - m.right).max(0.);
+    ctx.children_intrinsic_size(IntrinsicSize::MinHeight, width) + m.top + m.bottom
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let m = &self.margin;
+    let width = (width - m.left - m.right).max(0.);
+    ctx.children_intrinsic_size(IntrinsicSize::MaxHeight, width) + m.top + m.bottom
+  }
+
+  fn baseline(&self, _size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    let child = ctx.single_child()?;
+    ctx.child_baseline(child)
+  }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("margin") }
 }
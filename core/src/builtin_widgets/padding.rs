@@ -56,6 +56,31 @@ impl WrapRender for Padding {
     host.place_children(size - thickness, ctx);
   }
 
+  fn baseline(&self, host: &dyn Render, size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    let baseline = host.baseline(size - self.padding.thickness(), ctx)?;
+    Some(baseline + self.padding.top)
+  }
+
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    let p = &self.padding;
+    host.min_intrinsic_width((height - p.top - p.bottom).max(0.), ctx) + p.left + p.right
+  }
+
+  fn max_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    let p = &self.padding;
+    host.max_intrinsic_width((height - p.top - p.bottom).max(0.), ctx) + p.left + p.right
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let p = &self.padding;
+    host.min_intrinsic_height((width - p.left - p.right).max(0.), ctx) + p.top + p.bottom
+  }
+
+  fn max_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let p = &self.padding;
+    host.max_intrinsic_height((width - p.left - p.right).max(0.), ctx) + p.top + p.bottom
+  }
+
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
     ctx.content_only_transform_apply(&Transform::translation(self.padding.left, self.padding.top));
     host.paint(ctx);
@@ -800,6 +800,18 @@ impl Query for ProvidersRender {
   fn queryable(&self) -> bool { true }
 }
 
+impl ProvidersRender {
+  fn with_providers<R>(
+    &self, ctx: &mut MeasureCtx, f: impl FnOnce(&dyn Render, &mut MeasureCtx) -> R,
+  ) -> R {
+    let Self { render, providers } = self;
+    providers.setup_providers(ctx.as_mut());
+    let res = f(render.as_render(), ctx);
+    providers.restore_providers(ctx.as_mut());
+    res
+  }
+}
+
 impl Render for ProvidersRender {
   fn measure(&self, clamp: BoxClamp, ctx: &mut MeasureCtx) -> Size {
     let Self { render, providers } = self;
@@ -816,6 +828,22 @@ impl Render for ProvidersRender {
     providers.restore_providers(ctx.as_mut());
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.with_providers(ctx, |render, ctx| render.min_intrinsic_width(height, ctx))
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.with_providers(ctx, |render, ctx| render.max_intrinsic_width(height, ctx))
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.with_providers(ctx, |render, ctx| render.min_intrinsic_height(width, ctx))
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.with_providers(ctx, |render, ctx| render.max_intrinsic_height(width, ctx))
+  }
+
   fn adjust_position(&self, pos: Point, ctx: &mut PlaceCtx) -> Point {
     let Self { render, providers } = self;
     providers.setup_providers(ctx.as_mut());
@@ -824,6 +852,14 @@ impl Render for ProvidersRender {
     pos
   }
 
+  fn baseline(&self, size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    let Self { render, providers } = self;
+    providers.setup_providers(ctx.as_mut());
+    let baseline = render.baseline(size, ctx);
+    providers.restore_providers(ctx.as_mut());
+    baseline
+  }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let Self { render, providers } = self;
     let id = ctx.id();
@@ -847,6 +883,8 @@ impl Render for ProvidersRender {
 
   fn get_transform(&self) -> Option<Transform> { self.render.get_transform() }
 
+  fn a11y(&self) -> Option<A11y> { self.render.a11y() }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { self.render.as_render().debug_name() }
 
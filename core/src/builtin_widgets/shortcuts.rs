@@ -0,0 +1,695 @@
+//! Application-wide keyboard shortcuts.
+//!
+//! A [`Shortcuts`] widget declares the key bindings and the commands of its
+//! subtree. When a key is pressed and the focused widget doesn't prevent the
+//! default action, the framework resolves it along the focus chain:
+//!
+//! - the innermost `Shortcuts` whose binding matches the pressed keys wins,
+//! - the command of the binding is looked up from the focused widget to the
+//!   root, so an outer binding can trigger a command provided by an inner
+//!   widget, such as `edit.copy` of a text input,
+//! - disabled commands are skipped, so an outer scope can handle the keys.
+//!
+//! If nothing is focused, the keys are resolved from the root of the window.
+//! A multi-stroke binding, such as `Ctrl+K Ctrl+S`, is abandoned if its next
+//! stroke isn't pressed within [`KEY_SEQUENCE_TIMEOUT`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ribir::prelude::*;
+//!
+//! let w = fn_widget! {
+//!   let shortcuts = Shortcuts::new()
+//!     .with_binding("Mod+S", "file.save")
+//!     .with_binding("Ctrl+K Ctrl+S", "file.save_all")
+//!     .with_command("file.save", |_| println!("save"))
+//!     .with_command("file.save_all", |_| println!("save all"));
+//!   @(shortcuts) {
+//!     @Text { text: "Press Ctrl+S" }
+//!   }
+//! };
+//! App::run(w);
+//! ```
+use std::rc::Rc;
+
+use smallvec::SmallVec;
+
+use crate::{impl_common_event_deref, prelude::*};
+
+mod key_binding;
+pub use key_binding::*;
+
+/// The time to wait for the next stroke of a multi-stroke binding.
+pub const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// The declaration of key bindings and commands for a subtree, see the
+/// [module level documentation](self) for details.
+#[derive(Default)]
+pub struct Shortcuts {
+  bindings: Vec<(KeyBinding, CowArc<str>)>,
+  commands: Vec<Command>,
+}
+
+/// A named action that can be triggered by key bindings.
+pub struct Command {
+  name: CowArc<str>,
+  enabled: Option<Box<dyn StateWatcher<Value = bool>>>,
+  handler: Rc<dyn Fn(&mut CommandEvent)>,
+}
+
+/// The event passed to the handler of a [`Command`], its target is the widget
+/// that has the focus when the command is triggered.
+pub struct CommandEvent {
+  command: CowArc<str>,
+  common: CommonEvent,
+}
+
+/// A conflict between the key bindings of a [`Shortcuts`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShortcutConflict {
+  /// The same key binding is bound to different commands, only the first one
+  /// can be triggered.
+  Duplicate { binding: KeyBinding, commands: Vec<CowArc<str>> },
+  /// The `binding` starts with the `prefix` binding, it can never be
+  /// triggered because the `prefix` is triggered first.
+  Shadowed { prefix: KeyBinding, binding: KeyBinding },
+}
+
+#[derive(Debug)]
+pub enum ShortcutError {
+  /// The text is not a valid key binding.
+  InvalidBinding(String),
+  /// A line of the keymap is not in the format of `command = binding`.
+  Keymap {
+    line: usize,
+    content: String,
+  },
+  Io(std::io::Error),
+}
+
+impl Shortcuts {
+  pub fn new() -> Self { Self::default() }
+
+  /// Bind `binding` to `command` and return self.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `binding` is not a valid key binding. Use
+  /// [`Shortcuts::bind`] with a parsed [`KeyBinding`] for user input.
+  pub fn with_binding(mut self, binding: &str, command: impl Into<CowArc<str>>) -> Self {
+    let binding = binding.parse().unwrap_or_else(|e| panic!("{e}"));
+    self.bind(binding, command);
+    self
+  }
+
+  /// Register a command and return self.
+  pub fn with_command(
+    mut self, name: impl Into<CowArc<str>>, handler: impl Fn(&mut CommandEvent) + 'static,
+  ) -> Self {
+    self.add_command(Command::new(name, handler));
+    self
+  }
+
+  /// Bind `binding` to `command`, a command can be bound to multiple key
+  /// bindings.
+  pub fn bind(&mut self, binding: KeyBinding, command: impl Into<CowArc<str>>) {
+    let command = command.into();
+    if !self
+      .bindings
+      .iter()
+      .any(|(b, c)| b == &binding && c == &command)
+    {
+      self.bindings.push((binding, command));
+    }
+  }
+
+  /// Remove all the key bindings of `command`.
+  pub fn unbind(&mut self, command: &str) { self.bindings.retain(|(_, c)| &**c != command); }
+
+  /// Register a command, replacing the command with the same name.
+  pub fn add_command(&mut self, command: Command) {
+    self.remove_command(&command.name);
+    self.commands.push(command);
+  }
+
+  pub fn remove_command(&mut self, name: &str) -> Option<Command> {
+    let idx = self
+      .commands
+      .iter()
+      .position(|c| &*c.name == name)?;
+    Some(self.commands.remove(idx))
+  }
+
+  pub fn command(&self, name: &str) -> Option<&Command> {
+    self.commands.iter().find(|c| &*c.name == name)
+  }
+
+  /// Return the first key binding of `command`.
+  pub fn binding_of(&self, command: &str) -> Option<&KeyBinding> {
+    self
+      .bindings
+      .iter()
+      .find(|(_, c)| &**c == command)
+      .map(|(b, _)| b)
+  }
+
+  /// Return all the key bindings with their commands.
+  pub fn bindings(&self) -> impl Iterator<Item = (&KeyBinding, &CowArc<str>)> {
+    self.bindings.iter().map(|(b, c)| (b, c))
+  }
+
+  /// Return the commands that conflict with `binding` if it's added, that is,
+  /// bound to the same binding, or to a binding that is a prefix of it or that
+  /// it's a prefix of.
+  pub fn conflicts_with(&self, binding: &KeyBinding) -> Vec<CowArc<str>> {
+    self
+      .bindings
+      .iter()
+      .filter(|(b, _)| b.starts_with(binding) || binding.starts_with(b))
+      .map(|(_, c)| c.clone())
+      .collect()
+  }
+
+  /// Detect all the conflicts between the key bindings.
+  pub fn conflicts(&self) -> Vec<ShortcutConflict> {
+    let mut conflicts = vec![];
+    for (i, (binding, command)) in self.bindings.iter().enumerate() {
+      let before = &self.bindings[..i];
+      if before.iter().any(|(b, _)| b == binding) {
+        continue;
+      }
+      let mut commands = vec![command.clone()];
+      for (b, c) in &self.bindings[i + 1..] {
+        if b == binding && !commands.contains(c) {
+          commands.push(c.clone());
+        }
+      }
+      if commands.len() > 1 {
+        conflicts.push(ShortcutConflict::Duplicate { binding: binding.clone(), commands });
+      }
+    }
+
+    for (prefix, _) in &self.bindings {
+      for (binding, _) in &self.bindings {
+        let shadowed =
+          ShortcutConflict::Shadowed { prefix: prefix.clone(), binding: binding.clone() };
+        if binding != prefix && binding.starts_with(prefix) && !conflicts.contains(&shadowed) {
+          conflicts.push(shadowed);
+        }
+      }
+    }
+    conflicts
+  }
+
+  /// Load the key bindings from a keymap, the bindings of every command in the
+  /// keymap replace its existing bindings.
+  ///
+  /// Every line of the keymap is `command = binding`, multiple bindings are
+  /// separated by `,` and an empty binding removes all the bindings of the
+  /// command. Lines starting with `#` are comments.
+  ///
+  /// ```text
+  /// # My keymap
+  /// file.save = Mod+S
+  /// edit.redo = Mod+Y, Mod+Shift+Z
+  /// edit.cut =
+  /// ```
+  ///
+  /// Nothing is changed if the keymap has an error.
+  pub fn load_keymap(&mut self, keymap: &str) -> Result<(), ShortcutError> {
+    let mut entries = vec![];
+    for (idx, line) in keymap.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let invalid = || ShortcutError::Keymap { line: idx + 1, content: line.to_string() };
+      let (command, bindings) = line.split_once('=').ok_or_else(invalid)?;
+      let command = command.trim();
+      if command.is_empty() {
+        return Err(invalid());
+      }
+      let bindings = bindings
+        .split(',')
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .map(str::parse::<KeyBinding>)
+        .collect::<Result<Vec<_>, _>>()?;
+      entries.push((command.to_string(), bindings));
+    }
+
+    for (command, _) in &entries {
+      self.unbind(command);
+    }
+    for (command, bindings) in entries {
+      let command: CowArc<str> = command.into();
+      for binding in bindings {
+        self.bind(binding, command.clone());
+      }
+    }
+    Ok(())
+  }
+
+  /// Load the key bindings from a keymap file, see
+  /// [`Shortcuts::load_keymap`] for the format.
+  pub fn load_keymap_file(
+    &mut self, path: impl AsRef<std::path::Path>,
+  ) -> Result<(), ShortcutError> {
+    let keymap = std::fs::read_to_string(path).map_err(ShortcutError::Io)?;
+    self.load_keymap(&keymap)
+  }
+}
+
+impl<'c> ComposeChild<'c> for Shortcuts {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let provider = Provider::writer(this.clone_writer(), None);
+    Providers::new([provider]).with_child(child.attach_data(Box::new(this)))
+  }
+}
+
+impl Command {
+  pub fn new(name: impl Into<CowArc<str>>, handler: impl Fn(&mut CommandEvent) + 'static) -> Self {
+    Self { name: name.into(), enabled: None, handler: Rc::new(handler) }
+  }
+
+  /// The command is ignored by the key bindings when `enabled` is false.
+  pub fn with_enabled(mut self, enabled: impl StateWatcher<Value = bool>) -> Self {
+    self.enabled = Some(enabled.clone_boxed_watcher());
+    self
+  }
+
+  pub fn name(&self) -> &CowArc<str> { &self.name }
+
+  pub fn is_enabled(&self) -> bool { self.enabled.as_ref().is_none_or(|e| *e.read()) }
+
+  /// The watcher of the enabled state, `None` if the command is always
+  /// enabled.
+  pub fn enabled_watcher(&self) -> Option<&dyn StateWatcher<Value = bool>> {
+    self.enabled.as_deref()
+  }
+}
+
+impl CommandEvent {
+  /// The name of the triggered command.
+  pub fn command(&self) -> &CowArc<str> { &self.command }
+}
+
+impl_common_event_deref!(CommandEvent);
+
+impl std::fmt::Display for ShortcutError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ShortcutError::InvalidBinding(b) => write!(f, "Invalid key binding: `{b}`"),
+      ShortcutError::Keymap { line, content } => {
+        write!(f, "Invalid keymap at line {line}, expected `command = binding`: `{content}`")
+      }
+      ShortcutError::Io(err) => write!(f, "Failed to read the keymap: {err}"),
+    }
+  }
+}
+
+impl std::error::Error for ShortcutError {}
+
+type CommandHandler = (CowArc<str>, Rc<dyn Fn(&mut CommandEvent)>);
+
+/// The keys pressed for an unfinished multi-stroke binding.
+#[derive(Default)]
+pub(crate) struct PendingStrokes {
+  strokes: SmallVec<[KeyStroke; 2]>,
+  at: Option<Instant>,
+}
+
+enum Resolved {
+  Command(CommandHandler),
+  Pending,
+  Unmatched,
+}
+
+/// Resolve the key stroke pressed when `target` has the focus, return if it's
+/// consumed by a shortcut.
+pub(crate) fn dispatch_key_stroke(wnd: &Window, target: WidgetId, stroke: KeyStroke) -> bool {
+  if matches!(
+    stroke.key,
+    VirtualKey::Named(
+      NamedKey::Shift | NamedKey::Control | NamedKey::Alt | NamedKey::Super | NamedKey::Meta
+    )
+  ) {
+    return false;
+  }
+
+  let mut strokes = {
+    let mut pending = wnd.pending_strokes.borrow_mut();
+    let expired = pending
+      .at
+      .is_some_and(|at| Instant::now().duration_since(at) > KEY_SEQUENCE_TIMEOUT);
+    let strokes = std::mem::take(&mut pending.strokes);
+    if expired { SmallVec::new() } else { strokes }
+  };
+  strokes.push(stroke);
+  let mut resolved = resolve(wnd.tree(), target, &strokes);
+  if matches!(resolved, Resolved::Unmatched) && strokes.len() > 1 {
+    // The sequence is broken, try the last stroke as a new sequence.
+    strokes.drain(..strokes.len() - 1);
+    resolved = resolve(wnd.tree(), target, &strokes);
+  }
+
+  match resolved {
+    Resolved::Command(cmd) => {
+      execute(wnd, target, cmd);
+      true
+    }
+    Resolved::Pending => {
+      *wnd.pending_strokes.borrow_mut() = PendingStrokes { strokes, at: Some(Instant::now()) };
+      true
+    }
+    Resolved::Unmatched => false,
+  }
+}
+
+/// Execute the command `name` found from `target` to the root.
+pub(crate) fn execute_command(wnd: &Window, target: WidgetId, name: &str) -> bool {
+  let tree = wnd.tree();
+  let scopes = scopes_of(tree, target);
+  match find_command(tree, &scopes, name) {
+    Some(cmd) => {
+      execute(wnd, target, cmd);
+      true
+    }
+    None => false,
+  }
+}
+
+/// The key binding that triggers the command `name` when `target` has the
+/// focus, a binding shadowed by an inner scope is skipped.
+pub(crate) fn binding_of_command(
+  tree: &WidgetTree, target: WidgetId, name: &str,
+) -> Option<KeyBinding> {
+  let mut shadowed = vec![];
+  for id in scopes_of(tree, target) {
+    for shortcuts in id.query_all_iter::<Shortcuts>(tree) {
+      for (binding, command) in shortcuts.bindings.iter() {
+        if &**command != name {
+          shadowed.push(binding.clone());
+        } else if !shadowed.contains(binding) {
+          return Some(binding.clone());
+        }
+      }
+    }
+  }
+  None
+}
+
+fn execute(wnd: &Window, target: WidgetId, (command, handler): CommandHandler) {
+  let mut e = CommandEvent { command, common: CommonEvent::new(target, wnd.tree) };
+  handler(&mut e);
+}
+
+fn scopes_of(tree: &WidgetTree, target: WidgetId) -> Vec<WidgetId> {
+  target
+    .ancestors(tree)
+    .filter(|id| {
+      id.query_all_iter::<Shortcuts>(tree)
+        .next()
+        .is_some()
+    })
+    .collect()
+}
+
+fn resolve(tree: &WidgetTree, target: WidgetId, strokes: &[KeyStroke]) -> Resolved {
+  let scopes = scopes_of(tree, target);
+  let mut pending = false;
+  for id in scopes.iter() {
+    for shortcuts in id.query_all_iter::<Shortcuts>(tree) {
+      for (binding, command) in shortcuts.bindings.iter() {
+        match binding.match_strokes(strokes) {
+          Some(true) => {
+            if let Some(cmd) = find_command(tree, &scopes, command) {
+              return Resolved::Command(cmd);
+            }
+          }
+          Some(false) => pending = true,
+          None => {}
+        }
+      }
+    }
+  }
+  if pending { Resolved::Pending } else { Resolved::Unmatched }
+}
+
+fn find_command(tree: &WidgetTree, scopes: &[WidgetId], name: &str) -> Option<CommandHandler> {
+  scopes.iter().find_map(|id| {
+    id.query_all_iter::<Shortcuts>(tree)
+      .find_map(|s| {
+        s.command(name)
+          .filter(|c| c.is_enabled())
+          .map(|c| (c.name.clone(), c.handler.clone()))
+      })
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::{ElementState, KeyLocation};
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn press(wnd: &TestWindow, modifiers: ModifiersState, code: KeyCode, key: &str) {
+    wnd
+      .dispatcher
+      .borrow_mut()
+      .modifiers_changed(modifiers);
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Character(key.into()),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.run_frame_tasks();
+  }
+
+  #[test]
+  fn parse_binding() {
+    let binding: KeyBinding = "Ctrl+Shift+k Ctrl+S".parse().unwrap();
+    assert_eq!(
+      binding.chords(),
+      &[
+        KeyChord::new(
+          ModifiersState::CONTROL | ModifiersState::SHIFT,
+          VirtualKey::Character("k".into())
+        ),
+        KeyChord::new(ModifiersState::CONTROL, VirtualKey::Character("s".into())),
+      ]
+    );
+
+    let plus: KeyChord = "Ctrl++".parse().unwrap();
+    assert_eq!(plus, KeyChord::new(ModifiersState::CONTROL, VirtualKey::Character("+".into())));
+
+    let esc: KeyChord = "esc".parse().unwrap();
+    assert_eq!(esc.key, VirtualKey::Named(NamedKey::Escape));
+
+    let save: KeyChord = "Mod+S".parse().unwrap();
+    assert_eq!(save.modifiers, PRIMARY_MODIFIER);
+
+    assert!("Hyper+S".parse::<KeyBinding>().is_err());
+    assert!("Ctrl+".parse::<KeyBinding>().is_err());
+    assert!("".parse::<KeyBinding>().is_err());
+  }
+
+  #[cfg(not(target_os = "macos"))]
+  #[test]
+  fn display_binding() {
+    let binding: KeyBinding = "shift+ctrl+k ctrl+pageup".parse().unwrap();
+    assert_eq!(binding.to_string(), "Ctrl+Shift+K Ctrl+PageUp");
+  }
+
+  #[test]
+  fn conflicts() {
+    let shortcuts = Shortcuts::new()
+      .with_binding("Ctrl+K", "a")
+      .with_binding("Ctrl+K Ctrl+S", "b")
+      .with_binding("Ctrl+S", "c")
+      .with_binding("Ctrl+S", "d");
+
+    let k: KeyBinding = "Ctrl+K".parse().unwrap();
+    let ks: KeyBinding = "Ctrl+K Ctrl+S".parse().unwrap();
+    let s: KeyBinding = "Ctrl+S".parse().unwrap();
+    assert_eq!(
+      shortcuts.conflicts(),
+      vec![
+        ShortcutConflict::Duplicate { binding: s.clone(), commands: vec!["c".into(), "d".into()] },
+        ShortcutConflict::Shadowed { prefix: k.clone(), binding: ks.clone() },
+      ]
+    );
+    assert_eq!(shortcuts.conflicts_with(&k), vec![CowArc::from("a"), "b".into()]);
+  }
+
+  #[test]
+  fn keymap() {
+    let mut shortcuts = Shortcuts::new()
+      .with_binding("Ctrl+S", "save")
+      .with_binding("Ctrl+X", "cut");
+    shortcuts
+      .load_keymap("# comment\nsave = Ctrl+Shift+S, F2\ncut =\n")
+      .unwrap();
+    let bindings = shortcuts
+      .bindings()
+      .map(|(b, c)| (b.to_string(), c.to_string()))
+      .collect::<Vec<_>>();
+    let f2: KeyBinding = "F2".parse().unwrap();
+    let save: KeyBinding = "Ctrl+Shift+S".parse().unwrap();
+    assert_eq!(
+      bindings,
+      vec![(save.to_string(), "save".to_string()), (f2.to_string(), "save".to_string())]
+    );
+
+    let err = shortcuts.load_keymap("save = Ctrl+S\noops");
+    assert!(matches!(err, Err(ShortcutError::Keymap { line: 2, .. })));
+    assert_eq!(shortcuts.binding_of("save"), Some(&save));
+  }
+
+  #[test]
+  fn resolve_by_focus_chain() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let (enabled, w_enabled) = split_value(true);
+    let w = fn_widget! {
+      let outer = Shortcuts::new()
+        .with_binding("Ctrl+S", "save")
+        .with_binding("Ctrl+K Ctrl+S", "save_all")
+        .with_binding("Ctrl+C", "copy")
+        .with_command("save", move |_| $write(w_log).push("outer save"))
+        .with_command("save_all", move |_| $write(w_log).push("save all"))
+        .with_command("copy", move |_| $write(w_log).push("outer copy"));
+      let mut inner = Shortcuts::new()
+        .with_binding("Ctrl+S", "save")
+        .with_command("copy", move |_| $write(w_log).push("inner copy"));
+      inner.add_command(
+        Command::new("save", move |_| $write(w_log).push("inner save"))
+          .with_enabled(enabled.clone_watcher())
+      );
+      @(outer) {
+        @(inner) {
+          @MockBox { size: Size::zero(), auto_focus: true }
+        }
+      }
+    };
+    let wnd = TestWindow::from_widget(w);
+    wnd.draw_frame();
+
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyS, "s");
+    assert_eq!(&*log.read(), &["inner save"]);
+
+    *w_enabled.write() = false;
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyS, "s");
+    assert_eq!(&log.read()[1..], &["outer save"]);
+
+    // The outer binding triggers the command of the inner scope.
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyC, "c");
+    assert_eq!(&log.read()[2..], &["inner copy"]);
+
+    // Multi-stroke chord, and a layout that types another character.
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyK, "k");
+    assert_eq!(log.read().len(), 3);
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyS, "ы");
+    assert_eq!(&log.read()[3..], &["save all"]);
+  }
+
+  #[test]
+  fn qwertz_layout() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let w = fn_widget! {
+      let shortcuts = Shortcuts::new()
+        .with_binding("Ctrl+Z", "undo")
+        .with_binding("Ctrl+Y", "redo")
+        .with_binding("Ctrl+1", "first")
+        .with_command("undo", move |_| $write(w_log).push("undo"))
+        .with_command("redo", move |_| $write(w_log).push("redo"))
+        .with_command("first", move |_| $write(w_log).push("first"));
+      @(shortcuts) {
+        @MockBox { size: Size::zero(), auto_focus: true }
+      }
+    };
+    let wnd = TestWindow::from_widget(w);
+    wnd.draw_frame();
+
+    // QWERTZ swaps the `Y` and `Z` keys, the letters win.
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyY, "z");
+    assert_eq!(&*log.read(), &["undo"]);
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyZ, "y");
+    assert_eq!(&log.read()[1..], &["redo"]);
+
+    // A digit key typing a symbol still falls back to the physical key.
+    press(&wnd, ModifiersState::CONTROL, KeyCode::Digit1, "&");
+    assert_eq!(&log.read()[2..], &["first"]);
+  }
+
+  #[test]
+  fn key_sequence_timeout() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let w = fn_widget! {
+      let shortcuts = Shortcuts::new()
+        .with_binding("Ctrl+S", "save")
+        .with_binding("Ctrl+K Ctrl+S", "save_all")
+        .with_command("save", move |_| $write(w_log).push("save"))
+        .with_command("save_all", move |_| $write(w_log).push("save all"));
+      @(shortcuts) {
+        @MockBox { size: Size::zero(), auto_focus: true }
+      }
+    };
+    let wnd = TestWindow::from_widget(w);
+    wnd.draw_frame();
+
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyK, "k");
+    wnd.pending_strokes.borrow_mut().at = Some(Instant::now() - KEY_SEQUENCE_TIMEOUT * 2);
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyS, "s");
+    assert_eq!(&*log.read(), &["save"]);
+  }
+
+  #[test]
+  fn command_binding_by_focus_chain() {
+    reset_test_env!();
+
+    let (inner_id, w_inner_id) = split_value(None);
+    let w = fn_widget! {
+      let outer = Shortcuts::new()
+        .with_binding("Ctrl+S", "save")
+        .with_binding("Ctrl+C", "copy")
+        .with_binding("Ctrl+P", "print");
+      let inner = Shortcuts::new()
+        .with_binding("Ctrl+Shift+S", "save")
+        .with_binding("Ctrl+P", "preview");
+      @(outer) {
+        @(inner) {
+          @MockBox {
+            size: Size::zero(),
+            on_mounted: move |e| *$write(w_inner_id) = Some(e.current_target()),
+          }
+        }
+      }
+    };
+    let wnd = TestWindow::from_widget(w);
+    wnd.draw_frame();
+
+    let id = *inner_id.read();
+    let binding = |name: &str| {
+      wnd
+        .command_binding(id, name)
+        .map(|b| b.to_string())
+    };
+    let key = |b: &str| Some(b.parse::<KeyBinding>().unwrap().to_string());
+    assert_eq!(binding("save"), key("Ctrl+Shift+S"));
+    assert_eq!(binding("copy"), key("Ctrl+C"));
+    // `Ctrl+P` of the outer scope is shadowed by the inner one.
+    assert_eq!(binding("print"), None);
+  }
+}
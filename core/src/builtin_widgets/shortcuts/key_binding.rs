@@ -0,0 +1,324 @@
+use std::{fmt, str::FromStr};
+
+use smallvec::SmallVec;
+
+use super::ShortcutError;
+use crate::prelude::*;
+
+/// A key combined with modifiers, such as `Ctrl+S`.
+///
+/// Character keys are always stored in lowercase, use the `SHIFT` modifier to
+/// describe an uppercase letter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+  pub modifiers: ModifiersState,
+  pub key: VirtualKey,
+}
+
+/// A sequence of key chords that triggers a command, such as `Ctrl+K Ctrl+S`.
+///
+/// A binding is written as chords separated by whitespace, and every chord is
+/// its modifiers and key joined with `+`:
+///
+/// - modifiers: `Ctrl`/`Control`, `Shift`, `Alt`/`Option`,
+///   `Cmd`/`Meta`/`Super`/`Win`, and `Mod` that is `Cmd` on macOS and `Ctrl` on
+///   other platforms.
+/// - keys: a single character (`S`, `/`, `+`) or a named key (`Enter`, `Esc`,
+///   `Tab`, `Space`, `Backspace`, `Delete`, `Home`, `PageUp`, `Up`, `F1` ...).
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let save: KeyBinding = "Mod+S".parse().unwrap();
+/// assert_eq!(save.chords().len(), 1);
+///
+/// let save_all: KeyBinding = "Ctrl+K Ctrl+S".parse().unwrap();
+/// assert_eq!(save_all.chords().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyBinding(SmallVec<[KeyChord; 2]>);
+
+/// A key pressed by the user, with the modifiers hold at that time.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyStroke {
+  pub(crate) modifiers: ModifiersState,
+  pub(crate) key: VirtualKey,
+  pub(crate) physical_key: PhysicalKey,
+}
+
+const NAMED_KEYS: &[(&str, NamedKey)] = &[
+  ("Enter", NamedKey::Enter),
+  ("Esc", NamedKey::Escape),
+  ("Escape", NamedKey::Escape),
+  ("Tab", NamedKey::Tab),
+  ("Space", NamedKey::Space),
+  ("Backspace", NamedKey::Backspace),
+  ("Delete", NamedKey::Delete),
+  ("Del", NamedKey::Delete),
+  ("Insert", NamedKey::Insert),
+  ("Home", NamedKey::Home),
+  ("End", NamedKey::End),
+  ("PageUp", NamedKey::PageUp),
+  ("PageDown", NamedKey::PageDown),
+  ("Up", NamedKey::ArrowUp),
+  ("Down", NamedKey::ArrowDown),
+  ("Left", NamedKey::ArrowLeft),
+  ("Right", NamedKey::ArrowRight),
+  ("F1", NamedKey::F1),
+  ("F2", NamedKey::F2),
+  ("F3", NamedKey::F3),
+  ("F4", NamedKey::F4),
+  ("F5", NamedKey::F5),
+  ("F6", NamedKey::F6),
+  ("F7", NamedKey::F7),
+  ("F8", NamedKey::F8),
+  ("F9", NamedKey::F9),
+  ("F10", NamedKey::F10),
+  ("F11", NamedKey::F11),
+  ("F12", NamedKey::F12),
+  ("F13", NamedKey::F13),
+  ("F14", NamedKey::F14),
+  ("F15", NamedKey::F15),
+  ("F16", NamedKey::F16),
+  ("F17", NamedKey::F17),
+  ("F18", NamedKey::F18),
+  ("F19", NamedKey::F19),
+  ("F20", NamedKey::F20),
+  ("F21", NamedKey::F21),
+  ("F22", NamedKey::F22),
+  ("F23", NamedKey::F23),
+  ("F24", NamedKey::F24),
+];
+
+/// The modifier of the `Mod` notation.
+pub const PRIMARY_MODIFIER: ModifiersState =
+  if cfg!(target_os = "macos") { ModifiersState::SUPER } else { ModifiersState::CONTROL };
+
+impl KeyChord {
+  pub fn new(modifiers: ModifiersState, key: VirtualKey) -> Self {
+    let key = match key {
+      VirtualKey::Character(c) if c.chars().any(char::is_uppercase) => {
+        VirtualKey::Character(c.to_lowercase().into())
+      }
+      key => key,
+    };
+    Self { modifiers, key }
+  }
+
+  /// Whether the user pressed this chord.
+  pub(crate) fn is_pressed_by(&self, stroke: &KeyStroke) -> bool {
+    let mut modifiers = stroke.modifiers;
+    if let VirtualKey::Character(c) = &self.key
+      && !c.chars().all(char::is_alphanumeric)
+      && !self.modifiers.shift_key()
+    {
+      // Symbols like `+` or `?` need the shift key on some layouts.
+      modifiers.remove(ModifiersState::SHIFT);
+    }
+    if modifiers != self.modifiers {
+      return false;
+    }
+
+    let pressed = KeyChord::new(modifiers, stroke.key.clone());
+    if pressed.key == self.key {
+      return true;
+    }
+    // A Latin letter is matched by itself, so `Ctrl+Z` follows the letter on
+    // the layouts that move it, such as QWERTZ. Other layouts, like Cyrillic,
+    // or the digits typing symbols, fall back to the physical key.
+    if let VirtualKey::Character(c) = &stroke.key
+      && c.chars().all(|c| c.is_ascii_alphabetic())
+    {
+      return false;
+    }
+    match (&self.key, code_char(&stroke.physical_key)) {
+      (VirtualKey::Character(c), Some(code)) => {
+        let mut chars = c.chars();
+        chars.next() == Some(code) && chars.next().is_none()
+      }
+      _ => false,
+    }
+  }
+
+  fn parse(chord: &str) -> Result<Self, ShortcutError> {
+    let invalid = || ShortcutError::InvalidBinding(chord.to_string());
+    let (mods, key) = if chord == "+" {
+      ("", "+")
+    } else if let Some(mods) = chord.strip_suffix("++") {
+      (mods, "+")
+    } else {
+      chord.rsplit_once('+').unwrap_or(("", chord))
+    };
+
+    let mut modifiers = ModifiersState::empty();
+    for m in mods.split('+') {
+      modifiers |= match m.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => ModifiersState::CONTROL,
+        "shift" => ModifiersState::SHIFT,
+        "alt" | "option" => ModifiersState::ALT,
+        "cmd" | "command" | "meta" | "super" | "win" => ModifiersState::SUPER,
+        "mod" => PRIMARY_MODIFIER,
+        "" if mods.is_empty() => ModifiersState::empty(),
+        _ => return Err(invalid()),
+      };
+    }
+
+    let key = if key.chars().count() == 1 {
+      VirtualKey::Character(key.into())
+    } else {
+      NAMED_KEYS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, named)| VirtualKey::Named(*named))
+        .ok_or_else(invalid)?
+    };
+
+    Ok(KeyChord::new(modifiers, key))
+  }
+}
+
+impl KeyBinding {
+  pub fn new(chords: impl IntoIterator<Item = KeyChord>) -> Self {
+    Self(chords.into_iter().collect())
+  }
+
+  pub fn chords(&self) -> &[KeyChord] { &self.0 }
+
+  /// Whether this binding starts with all the chords of `other`.
+  pub fn starts_with(&self, other: &KeyBinding) -> bool { self.0.starts_with(&other.0) }
+
+  /// Compare the binding with the keys pressed by the user, return
+  /// `Some(true)` if the binding is fully pressed and `Some(false)` if the
+  /// strokes are only a prefix of it.
+  pub(crate) fn match_strokes(&self, strokes: &[KeyStroke]) -> Option<bool> {
+    let matched = strokes.len() <= self.0.len()
+      && self
+        .0
+        .iter()
+        .zip(strokes)
+        .all(|(chord, stroke)| chord.is_pressed_by(stroke));
+    matched.then_some(strokes.len() == self.0.len())
+  }
+}
+
+impl FromStr for KeyChord {
+  type Err = ShortcutError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> { KeyChord::parse(s.trim()) }
+}
+
+impl FromStr for KeyBinding {
+  type Err = ShortcutError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let chords = s
+      .split_whitespace()
+      .map(KeyChord::parse)
+      .collect::<Result<SmallVec<_>, _>>()?;
+    if chords.is_empty() {
+      Err(ShortcutError::InvalidBinding(s.to_string()))
+    } else {
+      Ok(KeyBinding(chords))
+    }
+  }
+}
+
+impl From<KeyChord> for KeyBinding {
+  fn from(chord: KeyChord) -> Self { KeyBinding(SmallVec::from_elem(chord, 1)) }
+}
+
+/// Displays the chord in the platform notation, `⌘⇧S` on macOS and
+/// `Ctrl+Shift+S` on other platforms.
+impl fmt::Display for KeyChord {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let m = self.modifiers;
+    if cfg!(target_os = "macos") {
+      let symbols = [
+        (ModifiersState::CONTROL, "⌃"),
+        (ModifiersState::ALT, "⌥"),
+        (ModifiersState::SHIFT, "⇧"),
+        (ModifiersState::SUPER, "⌘"),
+      ];
+      for (modifier, symbol) in symbols {
+        if m.contains(modifier) {
+          f.write_str(symbol)?;
+        }
+      }
+    } else {
+      let names = [
+        (ModifiersState::CONTROL, "Ctrl+"),
+        (ModifiersState::ALT, "Alt+"),
+        (ModifiersState::SHIFT, "Shift+"),
+        (ModifiersState::SUPER, "Win+"),
+      ];
+      for (modifier, name) in names {
+        if m.contains(modifier) {
+          f.write_str(name)?;
+        }
+      }
+    }
+
+    match &self.key {
+      VirtualKey::Character(c) => f.write_str(&c.to_uppercase()),
+      VirtualKey::Named(named) => match NAMED_KEYS.iter().find(|(_, n)| n == named) {
+        Some((name, _)) => f.write_str(name),
+        None => write!(f, "{named:?}"),
+      },
+      key => write!(f, "{key:?}"),
+    }
+  }
+}
+
+impl fmt::Display for KeyBinding {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, chord) in self.0.iter().enumerate() {
+      if i > 0 {
+        f.write_str(" ")?;
+      }
+      chord.fmt(f)?;
+    }
+    Ok(())
+  }
+}
+
+fn code_char(key: &PhysicalKey) -> Option<char> {
+  let PhysicalKey::Code(code) = key else { return None };
+  let c = match code {
+    KeyCode::KeyA => 'a',
+    KeyCode::KeyB => 'b',
+    KeyCode::KeyC => 'c',
+    KeyCode::KeyD => 'd',
+    KeyCode::KeyE => 'e',
+    KeyCode::KeyF => 'f',
+    KeyCode::KeyG => 'g',
+    KeyCode::KeyH => 'h',
+    KeyCode::KeyI => 'i',
+    KeyCode::KeyJ => 'j',
+    KeyCode::KeyK => 'k',
+    KeyCode::KeyL => 'l',
+    KeyCode::KeyM => 'm',
+    KeyCode::KeyN => 'n',
+    KeyCode::KeyO => 'o',
+    KeyCode::KeyP => 'p',
+    KeyCode::KeyQ => 'q',
+    KeyCode::KeyR => 'r',
+    KeyCode::KeyS => 's',
+    KeyCode::KeyT => 't',
+    KeyCode::KeyU => 'u',
+    KeyCode::KeyV => 'v',
+    KeyCode::KeyW => 'w',
+    KeyCode::KeyX => 'x',
+    KeyCode::KeyY => 'y',
+    KeyCode::KeyZ => 'z',
+    KeyCode::Digit0 => '0',
+    KeyCode::Digit1 => '1',
+    KeyCode::Digit2 => '2',
+    KeyCode::Digit3 => '3',
+    KeyCode::Digit4 => '4',
+    KeyCode::Digit5 => '5',
+    KeyCode::Digit6 => '6',
+    KeyCode::Digit7 => '7',
+    KeyCode::Digit8 => '8',
+    KeyCode::Digit9 => '9',
+    _ => return None,
+  };
+  Some(c)
+}
@@ -48,47 +48,59 @@ pub struct Text {
   layout: RefCell<Option<ParagraphLayoutRef>>,
 }
 
+/// The paragraph style of a single style text, with the [`TextDirection`] and
+/// the [`WritingMode`] provided by the ancestors.
+pub fn provided_paragraph_style(
+  text_style: &TextStyle, text_align: TextAlign, ctx: &impl AsRef<ProviderCtx>,
+) -> ParagraphStyle {
+  ParagraphStyle {
+    direction: Provider::of::<TextDirection>(ctx).map_or_else(Default::default, |d| *d),
+    writing_mode: Provider::of::<WritingMode>(ctx).map_or_else(Default::default, |m| *m),
+    ..single_style_paragraph_style(text_style, text_align)
+  }
+}
+
 fn text_layout(
   text: CowArc<str>, text_style: &TextStyle, text_decoration: Option<TextDecorationStyle>,
-  text_align: TextAlign, clamp: BoxClamp,
+  paragraph: &ParagraphStyle, clamp: BoxClamp,
 ) -> ParagraphLayoutRef {
   if text_style.overflow == TextOverflow::Ellipsis {
-    ellipsis_text_layout(text, text_style, text_decoration, text_align, clamp)
+    ellipsis_text_layout(text, text_style, text_decoration, paragraph, clamp)
   } else {
-    paragraph_layout_for_text(text, text_style, text_decoration, text_align, clamp)
+    paragraph_layout_for_text(text, text_style, text_decoration, paragraph, clamp)
   }
 }
 
 fn paragraph_layout_for_text(
   text: CowArc<str>, text_style: &TextStyle, text_decoration: Option<TextDecorationStyle>,
-  text_align: TextAlign, clamp: BoxClamp,
+  paragraph_style: &ParagraphStyle, clamp: BoxClamp,
 ) -> ParagraphLayoutRef {
-  let paragraph_style = single_style_paragraph_style(text_style, text_align);
   let paragraph = AppCtx::text_services().paragraph(AttributedText::styled(
     text.to_string(),
     single_style_span_style(text_style, text_decoration),
   ));
-  paragraph.layout(text_style, &paragraph_style, clamp)
+  paragraph.layout(text_style, paragraph_style, clamp)
 }
 
 fn ellipsis_text_layout(
   text: CowArc<str>, text_style: &TextStyle, text_decoration: Option<TextDecorationStyle>,
-  text_align: TextAlign, clamp: BoxClamp,
+  paragraph: &ParagraphStyle, clamp: BoxClamp,
 ) -> ParagraphLayoutRef {
   // Ellipsis selection should depend only on the available line width. Once the
   // truncated candidate is chosen, apply the requested alignment to the final
   // clamped widget size.
-  let measured = measure_ellipsized_text_layout(text, text_style, text_decoration, clamp.max);
+  let measured =
+    measure_ellipsized_text_layout(text, text_style, text_decoration, paragraph, clamp.max);
   let size = clamp.clamp(measured.size());
-  measured.aligned(text_align, size)
+  measured.aligned(paragraph.text_align, size)
 }
 
 fn measure_ellipsized_text_layout(
   text: CowArc<str>, text_style: &TextStyle, text_decoration: Option<TextDecorationStyle>,
-  bounds: Size,
+  paragraph: &ParagraphStyle, bounds: Size,
 ) -> ParagraphLayoutRef {
   let full_layout =
-    start_aligned_text_layout(text.clone(), text_style, text_decoration.clone(), bounds);
+    start_aligned_text_layout(text.clone(), text_style, text_decoration.clone(), paragraph, bounds);
   if text.is_empty()
     || !bounds.width.is_finite()
     || layout_fit_width(&full_layout) <= bounds.width + TEXT_FIT_TOLERANCE
@@ -96,11 +108,16 @@ fn measure_ellipsized_text_layout(
     return full_layout;
   }
 
-  let ellipsis_layout =
-    start_aligned_text_layout(ELLIPSIS.into(), text_style, text_decoration.clone(), bounds);
+  let ellipsis_layout = start_aligned_text_layout(
+    ELLIPSIS.into(),
+    text_style,
+    text_decoration.clone(),
+    paragraph,
+    bounds,
+  );
   let ellipsis_width = layout_fit_width(&ellipsis_layout);
   if ellipsis_width > bounds.width + TEXT_FIT_TOLERANCE {
-    return start_aligned_text_layout("".into(), text_style, text_decoration, bounds);
+    return start_aligned_text_layout("".into(), text_style, text_decoration, paragraph, bounds);
   }
 
   ellipsized_layout(
@@ -110,14 +127,16 @@ fn measure_ellipsized_text_layout(
     bounds.width - ellipsis_width,
     text_style,
     text_decoration,
+    paragraph,
     bounds,
   )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn ellipsized_layout(
   text: &CowArc<str>, full_layout: &ParagraphLayoutRef, ellipsis_layout: ParagraphLayoutRef,
   available_width: f32, text_style: &TextStyle, text_decoration: Option<TextDecorationStyle>,
-  bounds: Size,
+  paragraph: &ParagraphStyle, bounds: Size,
 ) -> ParagraphLayoutRef {
   let caret_y = full_layout
     .caret_rect(Caret::default())
@@ -139,6 +158,7 @@ fn ellipsized_layout(
       ellipsis_candidate(text.as_ref(), boundary),
       text_style,
       text_decoration.clone(),
+      paragraph,
       bounds,
     );
     if layout_fit_width(&layout) <= bounds.width + TEXT_FIT_TOLERANCE {
@@ -157,17 +177,41 @@ fn ellipsized_layout(
 
 fn start_aligned_text_layout(
   text: CowArc<str>, text_style: &TextStyle, text_decoration: Option<TextDecorationStyle>,
-  bounds: Size,
+  paragraph: &ParagraphStyle, bounds: Size,
 ) -> ParagraphLayoutRef {
+  let paragraph = ParagraphStyle { text_align: TextAlign::Start, ..paragraph.clone() };
   paragraph_layout_for_text(
     text,
     text_style,
     text_decoration,
-    TextAlign::Start,
+    &paragraph,
     BoxClamp::max_size(bounds),
   )
 }
 
+/// The intrinsic width of a text, only a wrapping text can be narrower than its
+/// single line.
+fn text_intrinsic_width(text: &CowArc<str>, min: bool, ctx: &MeasureCtx) -> f32 {
+  let style = Provider::of::<TextStyle>(ctx).unwrap();
+  let paragraph_style = provided_paragraph_style(&style, TextAlign::Start, ctx);
+  let paragraph = AppCtx::text_services()
+    .paragraph(AttributedText::styled(text.to_string(), single_style_span_style(&style, None)));
+  if min && style.overflow == TextOverflow::AutoWrap {
+    paragraph.min_intrinsic_width(&style, &paragraph_style)
+  } else {
+    paragraph.max_intrinsic_width(&style, &paragraph_style)
+  }
+}
+
+fn text_intrinsic_height(text: &CowArc<str>, width: f32, ctx: &MeasureCtx) -> f32 {
+  let style = Provider::of::<TextStyle>(ctx).unwrap();
+  let bounds = Size::new(width, f32::INFINITY);
+  let paragraph = provided_paragraph_style(&style, TextAlign::Start, ctx);
+  start_aligned_text_layout(text.clone(), &style, None, &paragraph, bounds)
+    .size()
+    .height
+}
+
 fn layout_fit_width(layout: &ParagraphLayoutRef) -> f32 { layout.draw_payload().bounds.width() }
 
 fn ellipsis_candidate(text: &str, boundary: usize) -> CowArc<str> {
@@ -184,20 +228,44 @@ impl Render for Text {
     let text_align = Provider::of::<TextAlign>(ctx)
       .map(|align| *align)
       .unwrap_or_default();
-    let layout = text_layout(self.text.clone(), &style, text_decoration, text_align, clamp);
+    let paragraph = provided_paragraph_style(&style, text_align, ctx);
+    let layout = text_layout(self.text.clone(), &style, text_decoration, &paragraph, clamp);
     let size = layout.size();
     *self.layout.borrow_mut() = Some(layout);
     size
   }
 
+  fn min_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 {
+    text_intrinsic_width(&self.text, true, ctx)
+  }
+
+  fn max_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 {
+    text_intrinsic_width(&self.text, false, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    text_intrinsic_height(&self.text, width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    text_intrinsic_height(&self.text, width, ctx)
+  }
+
   #[inline]
   fn size_affected_by_child(&self) -> bool { false }
 
+  fn a11y(&self) -> Option<A11y> { Some(A11y::new(Role::Label).with_label(self.text.clone())) }
+
+  fn baseline(&self, _size: Size, _ctx: &mut PlaceCtx) -> Option<f32> {
+    self.layout.borrow().as_ref()?.first_baseline()
+  }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let style = Provider::of::<PaintingStyle>(ctx).map(|p| p.clone());
     let Some(layout) = self.layout.borrow().clone() else {
       return;
     };
+    TextHighlightsWidget::paint_of(&**layout, ctx);
     let brush = ctx.painter().fill_brush().clone();
     if !brush.is_visible() {
       return;
@@ -282,6 +350,7 @@ mod tests {
       letter_space: 0.,
       line_height: crate::text::LineHeight::Px(16.),
       overflow,
+      ..<_>::default()
     }
   }
 
@@ -557,4 +626,115 @@ mod tests {
         .all(|decoration| decoration.brush == Some(Color::GREEN.into()))
     );
   }
+
+  #[test]
+  fn text_highlights_paint_before_glyphs() {
+    reset_test_env!();
+    register_test_font();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Text {
+          text: "Ribir, Ribir",
+          text_style: test_text_style(TextOverflow::Overflow),
+          text_highlights: TextHighlights::matches("Ribir, Ribir", "ir", Color::YELLOW),
+        }
+      },
+      Size::new(200., 40.),
+    );
+
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().expect("expected a frame");
+    let highlights = frame
+      .commands
+      .iter()
+      .take_while(|cmd| !matches!(cmd, PaintCommand::Text(_)))
+      .filter(|cmd| matches!(cmd, PaintCommand::Path(_)))
+      .count();
+    assert_eq!(highlights, 2);
+  }
+
+  #[test]
+  fn text_highlights_only_apply_to_host() {
+    reset_test_env!();
+    register_test_font();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(200., 40.),
+          text_highlights: TextHighlights::matches("Ribir, Ribir", "ir", Color::YELLOW),
+          @Text {
+            text: "Ribir, Ribir",
+            text_style: test_text_style(TextOverflow::Overflow),
+          }
+        }
+      },
+      Size::new(200., 40.),
+    );
+
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().expect("expected a frame");
+    assert!(
+      !frame
+        .commands
+        .iter()
+        .any(|cmd| matches!(cmd, PaintCommand::Path(_)))
+    );
+  }
+
+  #[test]
+  fn load_font_notifies_font_loaded() {
+    reset_test_env!();
+
+    let (loaded, w_loaded) = split_value(None);
+    let _subscription =
+      AppCtx::font_loaded_stream().subscribe(move |e| *w_loaded.write() = Some(e));
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf";
+    AppCtx::load_font(
+      async move { std::fs::read(path).map_err(|e| FontLoadError::new(e.to_string())) },
+    );
+    AppCtx::run_until_stalled();
+
+    let families = loaded
+      .read()
+      .as_ref()
+      .map(|e: &FontLoaded| e.families.clone());
+    assert_eq!(families.as_deref(), Some(&[CowArc::from("DejaVu Sans")][..]));
+  }
+
+  #[test]
+  fn load_font_relayouts_laid_out_text() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Text {
+          text: "Hello ribir",
+          text_style: test_text_style(TextOverflow::Overflow),
+        }
+      },
+      Size::new(200., 40.),
+    );
+    wnd.draw_frame();
+    let fallback = wnd
+      .layout_info_by_path(&[0])
+      .unwrap()
+      .size
+      .unwrap();
+
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf";
+    AppCtx::load_font(
+      async move { std::fs::read(path).map_err(|e| FontLoadError::new(e.to_string())) },
+    );
+    AppCtx::run_until_stalled();
+    wnd.draw_frame();
+
+    let loaded = wnd
+      .layout_info_by_path(&[0])
+      .unwrap()
+      .size
+      .unwrap();
+    assert_ne!(fallback.width, loaded.width);
+  }
 }
@@ -0,0 +1,137 @@
+use crate::{prelude::*, text::TextRange};
+
+/// A range of text painted with a background brush.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextHighlight {
+  pub range: TextRange,
+  pub brush: Brush,
+  /// The corner radius of each highlight rectangle.
+  pub radius: f32,
+}
+
+/// The highlights painted behind the glyphs of `Text` and `RichText`, such as
+/// the matches of a find bar.
+///
+/// Highlights follow the selection geometry of the laid out paragraph, so
+/// changing them only repaints the text and never re-shapes it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextHighlights {
+  pub highlights: Vec<TextHighlight>,
+}
+
+/// Attaches text highlights to its host text widget.
+///
+/// This is a built-in `FatObj` field. Setting `text_highlights` attaches a
+/// `TextHighlightsWidget` to the host, only a `Text` or `RichText` host paints
+/// the highlights, they are not inherited by the descendants, because the
+/// byte ranges only make sense for the text of the host.
+///
+/// # Example
+///
+/// ```rust
+/// use ribir::prelude::*;
+///
+/// let pattern = Stateful::new("rib".to_string());
+/// fn_widget! {
+///   let text = "ribir is a rust gui library, ribir";
+///   @Text {
+///     text,
+///     text_highlights: pipe!(TextHighlights::matches(text, &$read(pattern), Color::YELLOW)),
+///   }
+/// };
+/// ```
+#[derive(Default)]
+pub struct TextHighlightsWidget {
+  pub text_highlights: TextHighlights,
+}
+
+impl TextHighlights {
+  /// Creates highlights painting every range with the same brush.
+  pub fn new(ranges: impl IntoIterator<Item = TextRange>, brush: impl Into<Brush>) -> Self {
+    let brush = brush.into();
+    let highlights = ranges
+      .into_iter()
+      .map(|range| TextHighlight { range, brush: brush.clone(), radius: 0. })
+      .collect();
+    Self { highlights }
+  }
+
+  /// Creates highlights for every non-overlapping occurrence of `pattern` in
+  /// `text`.
+  pub fn matches(text: &str, pattern: &str, brush: impl Into<Brush>) -> Self {
+    if pattern.is_empty() {
+      return Self::default();
+    }
+    let ranges = text
+      .match_indices(pattern)
+      .map(|(start, m)| TextRange::new(start, start + m.len()));
+    Self::new(ranges, brush)
+  }
+
+  /// Sets the corner radius of all highlights.
+  pub fn with_radius(mut self, radius: f32) -> Self {
+    self
+      .highlights
+      .iter_mut()
+      .for_each(|h| h.radius = radius);
+    self
+  }
+
+  pub fn push(&mut self, highlight: TextHighlight) { self.highlights.push(highlight); }
+
+  pub fn is_empty(&self) -> bool { self.highlights.is_empty() }
+
+  /// Paints the highlights of `layout` with the painter of a text widget. It
+  /// should be called before the text payload is drawn.
+  pub fn paint(&self, layout: &ParagraphLayout, painter: &mut Painter) {
+    if self.is_empty() {
+      return;
+    }
+    let mut painter = painter.save_guard();
+    for TextHighlight { range, brush, radius } in self.highlights.iter() {
+      let rects = layout.selection_rects(*range);
+      if rects.is_empty() {
+        continue;
+      }
+      painter.set_fill_brush(brush.clone());
+      for rect in rects.iter() {
+        if *radius > 0. {
+          painter.rect_round(rect, &Radius::all(*radius), true);
+        } else {
+          painter.rect(rect, true);
+        }
+      }
+      painter.fill();
+    }
+  }
+}
+
+impl Declare for TextHighlightsWidget {
+  type Builder = FatObj<()>;
+
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl<'c> ComposeChild<'c> for TextHighlightsWidget {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    child
+      .dirty_on(this.raw_modifies(), DirtyPhase::Paint)
+      .attach_data(Box::new(this.clone_writer()))
+  }
+}
+
+impl TextHighlightsWidget {
+  /// Paints the highlights attached to the text widget of `ctx`, see
+  /// [`TextHighlights::paint`].
+  pub fn paint_of(layout: &ParagraphLayout, ctx: &mut PaintingCtx) {
+    let highlights = ctx
+      .query::<TextHighlightsWidget>()
+      .map(|w| w.text_highlights.clone());
+    if let Some(highlights) = highlights {
+      highlights.paint(layout, ctx.painter());
+    }
+  }
+}
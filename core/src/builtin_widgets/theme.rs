@@ -11,8 +11,13 @@ pub use palette::*;
 mod typography_theme;
 pub use ribir_painter::*;
 pub use typography_theme::*;
+#[cfg(feature = "theme-file")]
+mod theme_file;
+#[cfg(feature = "theme-file")]
+pub use theme_file::*;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Brightness {
   Dark,
   Light,
@@ -84,6 +89,11 @@ pub struct Theme {
   /// allows the application developer to supply the font file. Certainly, the
   /// icon also works with `SVG` and [`named_svgs`](super::named_svgs).
   pub icon_font: IconFont,
+  /// The file this theme was loaded from by [`Theme::from_file`] or
+  /// [`Theme::load_file`]. In debug builds, the file is watched while the theme
+  /// is in use and its changes are applied at runtime.
+  #[cfg(feature = "theme-file")]
+  pub file: Option<std::path::PathBuf>,
 }
 
 /// A type for providing the icon font of the widget.
@@ -120,6 +130,8 @@ impl Theme {
     }
 
     load_fonts(&this);
+    #[cfg(all(feature = "theme-file", debug_assertions, not(target_arch = "wasm32")))]
+    Theme::watch_file(this.clone_writer());
     let container_color =
       this.part_reader(|t| PartRef::from_value(ContainerColor(t.palette.secondary_container())));
     let default_text_style = this.part_reader(|theme| {
@@ -185,6 +197,8 @@ impl Default for Theme {
       font_bytes: vec![],
       font_files: vec![],
       icon_font: Default::default(),
+      #[cfg(feature = "theme-file")]
+      file: None,
     }
   }
 }
@@ -213,6 +227,7 @@ fn typography_theme() -> TypographyTheme {
         letter_space,
         font_face,
         overflow,
+        ..<_>::default()
       },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
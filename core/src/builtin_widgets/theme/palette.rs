@@ -25,6 +25,8 @@ use super::*;
 /// };
 /// ````
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Palette {
   // Accent colors: primary, secondary, and tertiary
   /// The primary key color is used to derive roles for key components across
@@ -79,6 +81,7 @@ pub struct Palette {
 
 /// The four light tone to generate compatible colors group for color.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LightnessGroup {
   /// The light amount of base color.
   pub base: LightnessTone,
@@ -92,6 +95,7 @@ pub struct LightnessGroup {
 
 /// Config the light tones of color
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LightnessCfg {
   /// The light tone group of color.
   pub color_group: LightnessGroup,
@@ -0,0 +1,332 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::*;
+
+/// The part of a [`Theme`] that can be shipped as a data file, so designers
+/// can tune a theme without touching Rust code.
+///
+/// Every field is optional, the missing parts keep the values of the theme the
+/// file is applied to. A file is parsed as TOML if its extension is `toml`,
+/// otherwise as JSON. A JSON export of the [Material Theme Builder] is also
+/// accepted, see [`Palette::from_material_theme_builder`].
+///
+/// # Example
+///
+/// ```toml
+/// font_files = ["assets/Inter.ttf"]
+///
+/// [palette]
+/// brightness = "Dark"
+/// primary = { red = 103, green = 80, blue = 164, alpha = 255 }
+/// ```
+///
+/// [Material Theme Builder]: https://material-foundation.github.io/material-theme-builder/
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeFile {
+  pub palette: Option<Palette>,
+  pub typography_theme: Option<TypographyTheme>,
+  pub font_files: Option<Vec<String>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThemeFileError {
+  #[error("Failed to read the theme file: {0}")]
+  Io(#[from] std::io::Error),
+  #[error("Invalid JSON theme: {0}")]
+  Json(#[from] serde_json::Error),
+  #[error("Invalid TOML theme: {0}")]
+  Toml(#[from] toml::de::Error),
+}
+
+impl ThemeFile {
+  /// Reads and parses the theme file at `path`.
+  pub fn from_path(path: &Path) -> Result<Self, ThemeFileError> {
+    let content = std::fs::read_to_string(path)?;
+    if path
+      .extension()
+      .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+    {
+      Self::from_toml(&content)
+    } else {
+      Self::from_json(&content)
+    }
+  }
+
+  pub fn from_toml(content: &str) -> Result<Self, ThemeFileError> { Ok(toml::from_str(content)?) }
+
+  /// Parses a JSON theme file. A Material Theme Builder export is mapped onto
+  /// the palette.
+  pub fn from_json(content: &str) -> Result<Self, ThemeFileError> {
+    let value: Value = serde_json::from_str(content)?;
+    if is_material_theme_builder(&value) {
+      let palette = Some(material_theme_builder_palette(&value));
+      Ok(Self { palette, ..Default::default() })
+    } else {
+      Ok(serde_json::from_value(value)?)
+    }
+  }
+
+  /// Overwrites the parts of `theme` this file defines. The font files, if
+  /// given, replace the pending font files of the theme, they are registered
+  /// when the theme is composed or reloaded.
+  pub fn apply(self, theme: &mut Theme) {
+    let Self { palette, typography_theme, font_files } = self;
+    if let Some(palette) = palette {
+      theme.palette = palette;
+    }
+    if let Some(typography_theme) = typography_theme {
+      theme.typography_theme = typography_theme;
+    }
+    if let Some(font_files) = font_files {
+      theme.font_files = font_files;
+    }
+  }
+}
+
+impl Theme {
+  /// Creates the default theme with the theme file at `path` applied.
+  ///
+  /// In debug builds, the file is watched once the theme is used, and every
+  /// change of it is applied to the theme at runtime.
+  pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, ThemeFileError> {
+    let mut theme = Theme::default();
+    theme.load_file(path)?;
+    Ok(theme)
+  }
+
+  /// Applies the theme file at `path` to this theme, and remembers it as the
+  /// [`file`](Theme::file) of the theme.
+  pub fn load_file(&mut self, path: impl Into<PathBuf>) -> Result<(), ThemeFileError> {
+    let path = path.into();
+    ThemeFile::from_path(&path)?.apply(self);
+    self.file = Some(path);
+    Ok(())
+  }
+
+  /// Applies the [`file`](Theme::file) of the theme again and registers its
+  /// font files.
+  pub fn reload_file(&mut self) -> Result<(), ThemeFileError> {
+    if let Some(path) = self.file.as_ref() {
+      ThemeFile::from_path(path)?.apply(self);
+      self.load_fonts();
+    }
+    Ok(())
+  }
+
+  /// Polls the modification time of the theme file and reloads it when it
+  /// changes. The watching stops once the `file` of the theme is changed.
+  #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+  pub(crate) fn watch_file(this: impl StateWriter<Value = Theme>) {
+    let Some(path) = this.read().file.clone() else { return };
+    let modified = |path: &Path| {
+      std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+    };
+
+    AppCtx::spawn_local(async move {
+      let mut last_modified = modified(&path);
+      loop {
+        AppCtx::timer(std::time::Duration::from_millis(500)).await;
+        if this.read().file.as_ref() != Some(&path) {
+          break;
+        }
+        let current = modified(&path);
+        if current == last_modified {
+          continue;
+        }
+        last_modified = current;
+        if let Err(err) = this.write().reload_file() {
+          tracing::warn!("Failed to reload the theme file {}: {err}", path.display());
+        }
+      }
+    });
+  }
+}
+
+impl Palette {
+  /// Creates a palette from a JSON export of the [Material Theme Builder].
+  ///
+  /// The key colors are read from `coreColors`, falling back to the tone 40 of
+  /// `palettes`, and the primary color falls back to the `seed`. The extended
+  /// colors named `warning` and `success` fill the functional colors. Colors
+  /// missing from the export keep the value of the default palette.
+  ///
+  /// [Material Theme Builder]: https://material-foundation.github.io/material-theme-builder/
+  pub fn from_material_theme_builder(json: &str) -> Result<Self, serde_json::Error> {
+    let value: Value = serde_json::from_str(json)?;
+    Ok(material_theme_builder_palette(&value))
+  }
+}
+
+fn is_material_theme_builder(value: &Value) -> bool {
+  ["coreColors", "schemes", "seed"]
+    .iter()
+    .any(|key| value.get(key).is_some())
+}
+
+fn material_theme_builder_palette(value: &Value) -> Palette {
+  let key_color = |core: &str, palette: &str| {
+    value
+      .pointer(&format!("/coreColors/{core}"))
+      .or_else(|| value.pointer(&format!("/palettes/{palette}/40")))
+      .and_then(Value::as_str)
+      .and_then(parse_hex_color)
+  };
+  let extended_color = |name: &str| {
+    value
+      .get("extendedColors")?
+      .as_array()?
+      .iter()
+      .find(|c| {
+        c.get("name")
+          .and_then(Value::as_str)
+          .is_some_and(|n| n.eq_ignore_ascii_case(name))
+      })?
+      .get("color")?
+      .as_str()
+      .and_then(parse_hex_color)
+  };
+
+  let mut palette = Palette::default();
+  let seed = value
+    .get("seed")
+    .and_then(Value::as_str)
+    .and_then(parse_hex_color);
+  if let Some(primary) = key_color("primary", "primary").or(seed) {
+    palette.primary = primary;
+  }
+  let roles = [
+    (&mut palette.secondary, key_color("secondary", "secondary")),
+    (&mut palette.tertiary, key_color("tertiary", "tertiary")),
+    (&mut palette.neutral, key_color("neutral", "neutral")),
+    (&mut palette.neutral_variant, key_color("neutralVariant", "neutral-variant")),
+    (&mut palette.error, key_color("error", "error")),
+    (&mut palette.warning, extended_color("warning")),
+    (&mut palette.success, extended_color("success")),
+  ];
+  for (role, color) in roles {
+    if let Some(color) = color {
+      *role = color;
+    }
+  }
+  palette
+}
+
+/// Parses a `#RRGGBB` or `#RRGGBBAA` color.
+fn parse_hex_color(hex: &str) -> Option<Color> {
+  let hex = hex.strip_prefix('#').unwrap_or(hex);
+  let value = u32::from_str_radix(hex, 16).ok()?;
+  match hex.len() {
+    6 => Some(Color::from_u32((value << 8) | 0xFF)),
+    8 => Some(Color::from_u32(value)),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn toml_theme_overrides_palette() {
+    let file = ThemeFile::from_toml(
+      r#"
+        font_files = ["fonts/Inter.ttf"]
+
+        [palette]
+        brightness = "Dark"
+        primary = { red = 1, green = 2, blue = 3, alpha = 255 }
+      "#,
+    )
+    .unwrap();
+
+    let mut theme = Theme::default();
+    let typography = theme.typography_theme.clone();
+    file.apply(&mut theme);
+
+    assert_eq!(theme.palette.brightness, Brightness::Dark);
+    assert_eq!(theme.palette.primary, Color::from_rgb(1, 2, 3));
+    assert_eq!(theme.palette.error, Palette::default().error);
+    assert_eq!(theme.typography_theme, typography);
+    assert_eq!(theme.font_files, ["fonts/Inter.ttf"]);
+  }
+
+  #[test]
+  fn typography_round_trip() {
+    let theme = Theme::default();
+    let file =
+      ThemeFile { typography_theme: Some(theme.typography_theme.clone()), ..Default::default() };
+    let json = serde_json::to_string(&file).unwrap();
+    let file = ThemeFile::from_json(&json).unwrap();
+    assert_eq!(file.typography_theme, Some(theme.typography_theme));
+  }
+
+  #[test]
+  fn keep_font_files_if_missing() {
+    let mut theme = Theme::default();
+    theme.font_files = vec!["fonts/Inter.ttf".into()];
+
+    let file = ThemeFile::from_toml("[palette]\nbrightness = \"Dark\"").unwrap();
+    assert!(file.font_files.is_none());
+    file.apply(&mut theme);
+    assert_eq!(theme.font_files, ["fonts/Inter.ttf"]);
+
+    ThemeFile::from_toml("font_files = []")
+      .unwrap()
+      .apply(&mut theme);
+    assert!(theme.font_files.is_empty());
+  }
+
+  #[test]
+  fn material_theme_builder_export() {
+    let file = ThemeFile::from_json(
+      r##"{
+        "seed": "#6750A4",
+        "coreColors": { "primary": "#6750A4", "tertiary": "#7D5260" },
+        "extendedColors": [{ "name": "Success", "color": "#81C784" }],
+        "palettes": { "secondary": { "40": "#625B71" } }
+      }"##,
+    )
+    .unwrap();
+    let palette = file.palette.unwrap();
+
+    assert_eq!(palette.primary, Color::from_u32(0x6750A4FF));
+    assert_eq!(palette.secondary, Color::from_u32(0x625B71FF));
+    assert_eq!(palette.tertiary, Color::from_u32(0x7D5260FF));
+    assert_eq!(palette.success, Color::from_u32(0x81C784FF));
+    assert_eq!(palette.error, Palette::default().error);
+  }
+
+  #[test]
+  fn reload_replaces_and_registers_fonts() {
+    crate::reset_test_env!();
+
+    let fonts = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/";
+    let path = std::env::temp_dir().join(format!("ribir_theme_reload_{}.toml", std::process::id()));
+    let write_fonts = |font: &str| {
+      std::fs::write(&path, format!("font_files = [{:?}]", fonts.clone() + font)).unwrap()
+    };
+
+    write_fonts("DejaVuSans.ttf");
+    let mut theme = Theme::from_file(&path).unwrap();
+    assert_eq!(theme.font_files, [fonts.clone() + "DejaVuSans.ttf"]);
+
+    write_fonts("GaramondNo8-Reg.ttf");
+    theme.reload_file().unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    // The list is replaced and registered, not appended to the previous one.
+    assert!(theme.font_files.is_empty());
+    assert!(
+      AppCtx::text_services()
+        .font_families()
+        .iter()
+        .any(|family| family.name.contains("GaramondNo8"))
+    );
+  }
+}
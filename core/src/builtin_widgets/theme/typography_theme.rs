@@ -9,6 +9,7 @@ pub type TextDecorationStyle = ribir_text::TextDecorationStyle;
 /// The names of the TextTheme properties from the [Material Design
 /// spec](https://m3.material.io/styles/typography/type-scale-tokens)
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypographyTheme {
   pub display_large: TextTheme,
   pub display_medium: TextTheme,
@@ -28,6 +29,7 @@ pub struct TypographyTheme {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextTheme {
   pub text: ribir_painter::TextStyle,
   pub decoration: TextDecorationStyle,
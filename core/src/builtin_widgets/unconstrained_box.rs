@@ -75,6 +75,11 @@ impl Render for UnconstrainedBox {
   #[inline]
   fn paint(&self, _: &mut PaintingCtx) {}
 
+  fn baseline(&self, _size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    let child = ctx.single_child()?;
+    ctx.child_baseline(child)
+  }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> {
     std::borrow::Cow::Borrowed("unconstrainedBox")
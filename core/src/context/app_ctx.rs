@@ -1,19 +1,23 @@
-use std::{cell::RefCell, future::Future, pin::Pin, sync::LazyLock};
+use std::{cell::RefCell, convert::Infallible, future::Future, pin::Pin, sync::LazyLock};
 
-use ribir_algo::Rc;
-use rxrust::LocalScheduler;
+use ribir_algo::{CowArc, Rc};
+use rxrust::{LocalScheduler, prelude::*, subject::LocalSubject};
 use tracing::warn;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 #[cfg(target_arch = "wasm32")]
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
-use ribir_painter::{TextServices, new_text_services};
+use ribir_painter::{FontLoadError, TextServices, new_text_services};
 use smallvec::SmallVec;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::{runtime::Runtime, task::LocalSet};
 
+#[cfg(feature = "i18n")]
+use crate::locale::Locale;
+#[cfg(feature = "persist")]
+use crate::persist::Persist;
 use crate::{
   builtin_widgets::Theme,
   clipboard::{Clipboard, MockClipboard},
@@ -41,18 +45,33 @@ use crate::{
 /// - ⚠️ Using uninitialized context may lead to undefined behavior
 pub struct AppCtx {
   app_theme: Stateful<Theme>,
+  #[cfg(feature = "i18n")]
+  app_locale: Stateful<Locale>,
+  #[cfg(feature = "persist")]
+  app_persist: RefCell<Persist>,
   windows: RefCell<ahash::HashMap<WindowId, Rc<Window>>>,
   text_services: Box<TextServices>,
   clipboard: RefCell<Box<dyn Clipboard>>,
   core_event_sender: RefCell<Option<UnboundedSender<CoreMsg>>>,
   shell: RefCell<Option<BoxShell>>,
   change_dataset: ChangeDataset,
+  font_loaded: FontLoadedSubject,
   #[cfg(not(target_arch = "wasm32"))]
   pub(crate) local_set: RefCell<LocalSet>,
   #[cfg(all(not(target_arch = "wasm32"), feature = "test-utils"))]
   pub(crate) spawn_count: std::cell::Cell<usize>,
 }
 
+/// The notification emitted when a font loaded by `AppCtx::load_font` is
+/// registered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontLoaded {
+  /// The families the loaded font adds.
+  pub families: Box<[CowArc<str>]>,
+}
+
+pub type FontLoadedSubject = LocalSubject<'static, FontLoaded, Infallible>;
+
 #[derive(Default)]
 struct ChangeDataset(RefCell<ChangeDatasetInner>);
 
@@ -186,6 +205,8 @@ impl AppCtx {
   pub fn shared() -> &'static Self { &APP_CTX }
 
   pub fn exit() {
+    #[cfg(feature = "persist")]
+    Self::app_persist().flush();
     let _ = AppCtx::send_event(CoreMsg::Exit);
     AppCtx::spawn_local(async move {
       AppCtx::shared()
@@ -199,14 +220,37 @@ impl AppCtx {
   #[track_caller]
   pub fn app_theme() -> &'static Stateful<Theme> { &Self::shared().app_theme }
 
+  /// Get the locale of the application, it's provided at the root of every
+  /// window.
+  #[track_caller]
+  #[cfg(feature = "i18n")]
+  pub fn app_locale() -> &'static Stateful<Locale> { &Self::shared().app_locale }
+
+  /// Get the persist of the application, it keeps the states in memory only
+  /// until another one is set by [`AppCtx::set_app_persist`].
+  #[cfg(feature = "persist")]
+  pub fn app_persist() -> Persist { Self::shared().app_persist.borrow().clone() }
+
+  /// Set the persist of the application, set it before the windows are created
+  /// to restore their states.
+  #[cfg(feature = "persist")]
+  pub fn set_app_persist(persist: Persist) { *Self::shared().app_persist.borrow_mut() = persist; }
+
   /// Scheduler accessor used by codepaths that pass an explicit scheduler to
   /// rxrust observables (e.g. `observable::{timer,timer_at,interval}`).
   #[inline]
   pub fn scheduler() -> LocalScheduler { LocalScheduler }
 
   pub async fn new_window(
-    content: GenWidget, flags: WindowFlags, attrs: WindowAttributes,
+    content: GenWidget, flags: WindowFlags, #[allow(unused_mut)] mut attrs: WindowAttributes,
   ) -> Rc<Window> {
+    #[cfg(feature = "persist")]
+    let persist_key = attrs.persist_key().cloned();
+    #[cfg(feature = "persist")]
+    if let Some(key) = &persist_key {
+      Self::app_persist().restore_window(key, &mut attrs);
+    }
+    let (owner, kind) = (attrs.owner(), attrs.kind());
     let fut = Self::shared()
       .shell
       .borrow()
@@ -221,6 +265,11 @@ impl AppCtx {
       .windows
       .borrow_mut()
       .insert(id, wnd.clone());
+    if let Some(owner) = owner {
+      wnd.attach_owner(owner, kind);
+    }
+    #[cfg(feature = "persist")]
+    wnd.set_persist_key(persist_key);
 
     wnd.init(content);
     // After init the tree is dirty; request the platform to schedule the first
@@ -280,6 +329,63 @@ impl AppCtx {
   #[track_caller]
   pub fn text_services() -> &'static TextServices { AppCtx::shared().text_services.as_ref() }
 
+  /// Loads a font asynchronously, the `font` future may fetch it from a URL or
+  /// read it from an asset.
+  ///
+  /// Texts keep rendering with their fallback font until the font is
+  /// registered, then every window relayouts to swap it in and a `FontLoaded`
+  /// is emitted by `font_loaded_stream`.
+  pub fn load_font(font: impl Future<Output = Result<Vec<u8>, FontLoadError>> + 'static) {
+    AppCtx::spawn_local(async move {
+      let loaded = font
+        .await
+        .and_then(|data| AppCtx::text_services().load_font_bytes(data));
+      match loaded {
+        Ok(families) => AppCtx::on_font_loaded(FontLoaded { families }),
+        Err(err) => warn!("Failed to load font: {}", err.message),
+      }
+    });
+  }
+
+  /// Fetches a font from `url` and loads it, see `AppCtx::load_font`.
+  #[cfg(target_arch = "wasm32")]
+  pub fn load_font_url(url: impl Into<String>) {
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::wasm_bindgen::{JsCast, JsValue};
+
+    let url = url.into();
+    AppCtx::load_font(async move {
+      let js_err = |e: JsValue| FontLoadError::new(format!("{e:?}"));
+      let window = web_sys::window().ok_or_else(|| FontLoadError::new("no window to fetch"))?;
+      let response = JsFuture::from(window.fetch_with_str(&url))
+        .await
+        .map_err(js_err)?
+        .dyn_into::<web_sys::Response>()
+        .map_err(js_err)?;
+      if !response.ok() {
+        return Err(FontLoadError::new(format!("fetch {url} failed: {}", response.status())));
+      }
+      let buffer = JsFuture::from(response.array_buffer().map_err(js_err)?)
+        .await
+        .map_err(js_err)?;
+      Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+    });
+  }
+
+  /// Returns the stream of fonts loaded by `AppCtx::load_font`.
+  pub fn font_loaded_stream() -> FontLoadedSubject { AppCtx::shared().font_loaded.clone() }
+
+  fn on_font_loaded(loaded: FontLoaded) {
+    let windows = AppCtx::shared()
+      .windows
+      .borrow()
+      .values()
+      .cloned()
+      .collect::<Vec<_>>();
+    windows.iter().for_each(|wnd| wnd.relayout_all());
+    AppCtx::shared().font_loaded.clone().next(loaded);
+  }
+
   /// Set the theme of the application
   ///
   /// # Safety
@@ -288,6 +394,12 @@ impl AppCtx {
   #[track_caller]
   pub fn set_app_theme(theme: Theme) { *Self::shared().app_theme.write() = theme; }
 
+  /// Set the locale of the application, switch its language at runtime by
+  /// [`Locale::set_lang`] instead.
+  #[track_caller]
+  #[cfg(feature = "i18n")]
+  pub fn set_app_locale(locale: Locale) { *Self::shared().app_locale.write() = locale; }
+
   /// Set the shared clipboard of the application, this should be called before
   /// application startup.
   ///
@@ -431,9 +543,14 @@ impl Default for AppCtx {
     AppCtx {
       text_services,
       app_theme,
+      #[cfg(feature = "i18n")]
+      app_locale: Stateful::new(Locale::default()),
+      #[cfg(feature = "persist")]
+      app_persist: RefCell::new(Persist::default()),
       clipboard: RefCell::new(Box::new(MockClipboard {})),
       windows: RefCell::new(ahash::HashMap::default()),
       change_dataset: ChangeDataset::default(),
+      font_loaded: Local::subject(),
       core_event_sender: RefCell::new(None),
       shell: RefCell::new(None),
       #[cfg(not(target_arch = "wasm32"))]
@@ -2,7 +2,7 @@ use ribir_types::Size;
 
 use super::{WidgetCtx, WidgetCtxImpl};
 use crate::{
-  prelude::{Point, ProviderCtx},
+  prelude::{IntrinsicSize, Point, ProviderCtx},
   widget::{BoxClamp, WidgetTree},
   widget_tree::WidgetId,
 };
@@ -109,6 +109,92 @@ impl<'a> MeasureCtx<'a> {
       })
   }
 
+  /// Return the intrinsic size of `child`, `extent` is its size in the other
+  /// axis, see `Render::min_intrinsic_width` and its siblings.
+  ///
+  /// The result is cached until the child needs layout, so it's cheap to query
+  /// the same size many times.
+  pub fn intrinsic_size(&mut self, child: WidgetId, kind: IntrinsicSize, extent: f32) -> f32 {
+    if let Some(size) = self
+      .tree
+      .store
+      .layout_info(child)
+      .and_then(|info| info.intrinsic_size(kind, extent))
+    {
+      return size;
+    }
+
+    // Safety: the `tree` just use to get the widget of `child`, and `tree2` not
+    // drop or modify it during the query.
+    let tree2 = unsafe { &*(self.tree as *mut WidgetTree) };
+    let render = child.assert_get(tree2);
+    let id = std::mem::replace(&mut self.id, child);
+    let size = match kind {
+      IntrinsicSize::MinWidth => render.min_intrinsic_width(extent, self),
+      IntrinsicSize::MaxWidth => render.max_intrinsic_width(extent, self),
+      IntrinsicSize::MinHeight => render.min_intrinsic_height(extent, self),
+      IntrinsicSize::MaxHeight => render.max_intrinsic_height(extent, self),
+    };
+    self.provider_ctx.pop_providers_for(child);
+    self.id = id;
+
+    self
+      .tree
+      .store
+      .layout_info_or_default(child)
+      .intrinsic_sizes
+      .push((kind, extent, size));
+    size
+  }
+
+  /// Return the largest intrinsic size of the children of this widget.
+  pub fn children_intrinsic_size(&mut self, kind: IntrinsicSize, extent: f32) -> f32 {
+    let (ctx, children) = self.split_children();
+    children
+      .map(|c| ctx.intrinsic_size(c, kind, extent))
+      .fold(0., f32::max)
+  }
+
+  /// Return the intrinsic size of a widget without children by `measure` it
+  /// with the axis of `kind` unlimited and the other axis at most `extent`. A
+  /// widget that fills the unlimited axis has no intrinsic size, return zero.
+  ///
+  /// The widget is measured on a scratch context, its layout information is
+  /// restored after, so the query doesn't clobber its last layout.
+  pub fn leaf_intrinsic_size(
+    &mut self, kind: IntrinsicSize, extent: f32,
+    measure: impl FnOnce(BoxClamp, &mut MeasureCtx) -> Size,
+  ) -> f32 {
+    let max = if kind.is_width() {
+      Size::new(f32::INFINITY, extent)
+    } else {
+      Size::new(extent, f32::INFINITY)
+    };
+    let clamp = BoxClamp::max_size(max);
+    let id = self.id;
+    let info = self.tree.store.layout_info(id).cloned();
+    self.tree.store.layout_info_or_default(id).clamp = clamp;
+
+    let mut laid_out_queue = vec![];
+    let mut scratch = MeasureCtx {
+      id,
+      tree: &mut *self.tree,
+      provider_ctx: std::mem::take(&mut self.provider_ctx),
+      laid_out_queue: &mut laid_out_queue,
+    };
+    let size = measure(clamp, &mut scratch);
+    self.provider_ctx = scratch.provider_ctx;
+    match info {
+      Some(info) => *self.tree.store.layout_info_or_default(id) = info,
+      None => {
+        self.tree.store.remove(id);
+      }
+    }
+
+    let size = if kind.is_width() { size.width } else { size.height };
+    if size.is_finite() { size } else { 0. }
+  }
+
   pub fn clamp(&self) -> BoxClamp {
     self
       .tree
@@ -167,6 +253,16 @@ impl<'a> MeasureCtx<'a> {
     self.tree.store.force_layout(child).is_some()
   }
 
+  /// Return the baseline of a laid out widget, the distance from its top to
+  /// the baseline of its first line of text.
+  pub fn widget_baseline(&self, widget: WidgetId) -> Option<f32> {
+    self
+      .tree
+      .store
+      .layout_info(widget)
+      .and_then(|info| info.baseline)
+  }
+
   fn get_calculated_size(&self, child: WidgetId, clamp: BoxClamp) -> Option<Size> {
     let info = self.tree.store.layout_info(child)?;
     if info.clamp == clamp { info.size } else { None }
@@ -178,6 +274,7 @@ impl<'a> PlaceCtx<'a> {
   /// 1. Reset all children positions to zero
   /// 2. Call `Render::place_children` to let the parent place children
   /// 3. Apply `adjust_position` to all children to finalize their positions
+  /// 4. Record the baseline reported by `Render::baseline`
   ///
   /// This ensures that even if the parent doesn't explicitly call
   /// `update_position` for some children, their `adjust_position` will still be
@@ -220,6 +317,14 @@ impl<'a> PlaceCtx<'a> {
     // Pop parent's providers and restore original id
     self.provider_ctx.pop_providers_for(id);
     self.id = id;
+
+    // Step 4: Record the baseline, the positions of the children are final now.
+    let baseline = id.assert_get(tree2).baseline(size, self);
+    self
+      .tree
+      .store
+      .layout_info_or_default(id)
+      .baseline = baseline;
   }
 
   /// Place the child at the given position.
@@ -267,6 +372,23 @@ impl<'a> PlaceCtx<'a> {
       .layout_info(widget)
       .and_then(|info| info.size)
   }
+
+  /// Return the baseline of a laid out widget, the distance from its top to
+  /// the baseline of its first line of text.
+  pub fn widget_baseline(&self, widget: WidgetId) -> Option<f32> {
+    self
+      .tree
+      .store
+      .layout_info(widget)
+      .and_then(|info| info.baseline)
+  }
+
+  /// Return the baseline of the `child` in the coordinate of this widget, that
+  /// is its baseline plus its position.
+  pub fn child_baseline(&self, child: WidgetId) -> Option<f32> {
+    let info = self.tree.store.layout_info(child)?;
+    info.baseline.map(|b| b + info.pos.y)
+  }
 }
 
 impl<'w> AsRef<ProviderCtx> for MeasureCtx<'w> {
@@ -168,6 +168,17 @@ impl EventLoop {
     let Some(wnd_id) = event.wnd_id() else { return };
     let Some(wnd) = self.scheduler.get_window(wnd_id) else { return };
 
+    if event.is_user_input()
+      && let Some(modal) = wnd.modal_window()
+    {
+      // A modal window swallows the input of its owner, a click on the owner
+      // brings the modal window to the front instead.
+      if matches!(event, UiEvent::MouseInput { state: ElementState::Pressed, .. }) {
+        modal.shell_wnd().borrow_mut().focus_window();
+      }
+      return;
+    }
+
     match event {
       UiEvent::RedrawRequest { demand, .. } => {
         self.scheduler.on_redraw_ready(wnd, demand);
@@ -186,6 +197,9 @@ impl EventLoop {
         .borrow_mut()
         .modifiers_changed(state),
       UiEvent::ReceiveChars { chars, .. } => wnd.process_receive_chars(chars),
+      UiEvent::A11yActivated { .. } => wnd.set_a11y_active(true),
+      UiEvent::A11yDeactivated { .. } => wnd.set_a11y_active(false),
+      UiEvent::A11yAction { request, .. } => wnd.process_a11y_action(request),
       UiEvent::CursorLeft { .. } => wnd.process_cursor_leave(),
       UiEvent::MouseWheel { delta_x, delta_y, .. } => wnd.process_wheel(delta_x, delta_y),
       UiEvent::CursorMoved { pos, .. } => wnd.process_cursor_move(pos),
@@ -252,7 +266,10 @@ impl Scheduler {
     }
   }
 
-  fn on_resize(&mut self, wnd: Rc<Window>) { self.request_redraw(wnd, RedrawDemand::Normal); }
+  fn on_resize(&mut self, wnd: Rc<Window>) {
+    wnd.persist_geometry();
+    self.request_redraw(wnd, RedrawDemand::Normal);
+  }
 
   fn request_redraw(&mut self, wnd: Rc<Window>, demand: RedrawDemand) {
     let wnd_id = wnd.id();
@@ -376,6 +393,7 @@ impl Scheduler {
     }
 
     assert!(layout_converged, "Layout failed to converge within {MAX_LAYOUT_ITERS} iterations");
+    wnd.update_a11y_tree();
 
     let did_draw_frame = (need_redraw || wnd.need_draw()) && wnd.draw_frame(Some(wnd.size()));
 
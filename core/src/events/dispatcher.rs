@@ -93,8 +93,12 @@ impl Dispatcher {
     if let Some(id) = wnd.focusing() {
       let e = DelayEvent::KeyBoard { key, state, physical_key, is_repeat, location, id };
       wnd.add_delay_event(e);
-    } else if key == VirtualKey::Named(NamedKey::Tab) && state == ElementState::Pressed {
-      wnd.add_delay_event(DelayEvent::TabFocusMove);
+    } else if state == ElementState::Pressed {
+      if key == VirtualKey::Named(NamedKey::Tab) {
+        wnd.add_delay_event(DelayEvent::TabFocusMove);
+      } else {
+        wnd.add_delay_event(DelayEvent::Shortcut { physical_key, key });
+      }
     }
   }
 
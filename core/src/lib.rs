@@ -18,10 +18,15 @@ pub mod widget;
 pub mod widget_children;
 pub(crate) mod widget_tree;
 pub mod window;
+pub use accesskit;
 pub use ribir_text as text;
 pub use rxrust;
 pub mod convert;
 pub mod event_loop;
+#[cfg(feature = "i18n")]
+pub mod locale;
+#[cfg(feature = "persist")]
+pub mod persist;
 pub mod query;
 pub mod reusable;
 
@@ -49,6 +54,10 @@ pub mod prelude {
   pub use smallvec;
   pub use tracing;
 
+  #[cfg(feature = "i18n")]
+  pub use super::locale::*;
+  #[cfg(feature = "persist")]
+  pub use super::persist::*;
   pub use super::{
     Measure, MeasureExt,
     animation::*,
@@ -68,8 +77,10 @@ pub mod prelude {
     ticker::{Duration, Instant},
     widget::*,
     widget_children::*,
-    widget_tree::{Anchor, AnchorX, AnchorY, DirtyPhase, LayoutInfo, TrackId, WidgetId},
-    window::{MountHandle, Window, WindowLevel},
+    widget_tree::{
+      Anchor, AnchorX, AnchorY, DirtyPhase, IntrinsicSize, LayoutInfo, TrackId, WidgetId,
+    },
+    window::{MountHandle, Window, WindowKind, WindowLevel},
   };
   pub use crate::*;
 }
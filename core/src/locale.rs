@@ -0,0 +1,611 @@
+//! Localized strings of the application, backed by [Fluent].
+//!
+//! The [`Locale`] is provided at the root of every window like the [`Theme`],
+//! use the `t!` macro to declare a text that follows it, or the `tr!` macro to
+//! translate a string once.
+//!
+//! [Fluent]: https://projectfluent.org
+
+use std::{borrow::Cow, cell::RefCell, sync::Arc};
+
+use chrono::{Datelike, TimeZone, Timelike};
+use fixed_decimal::{FixedDecimal, FloatPrecision};
+pub use fluent_bundle::{FluentArgs, FluentValue};
+use fluent_bundle::{
+  FluentBundle, FluentResource,
+  types::{FluentNumberStyle, FluentType},
+};
+use icu_datetime::{DateTimeFormatter, options::length};
+use icu_decimal::{
+  FixedDecimalFormatter,
+  options::{FixedDecimalFormatterOptions, GroupingStrategy},
+};
+use intl_memoizer::{IntlLangMemoizer, Memoizable};
+pub use unic_langid::{LanguageIdentifier, langid};
+
+use crate::prelude::*;
+
+/// A function callable from the FTL sources, such as `NUMBER` or `DATETIME`.
+pub type FluentFn =
+  Arc<dyn for<'a> Fn(&[FluentValue<'a>], &FluentArgs) -> FluentValue<'a> + Send + Sync>;
+
+/// The language of the application and the localized strings of the languages
+/// it supports.
+///
+/// Messages are looked up in the current language first, then in the
+/// fallback languages in order. A bundle of a language without region, such
+/// as `en`, also serves its regional variants, such as `en-GB`.
+///
+/// Besides the plural and the other selectors of Fluent, the sources can call
+/// `NUMBER` to format a number, and `DATETIME` to format a Unix timestamp in
+/// seconds. Both follow the conventions of the language of the message, such
+/// as `1,234.5` and `Jan 31, 2024` in `en-US`. `NUMBER` accepts the fraction,
+/// significant and integer digits options and `useGrouping`; `DATETIME`
+/// accepts `dateStyle` and `timeStyle` as `full`, `long`, `medium` or `short`,
+/// and `timeZone: "UTC"` to not convert the time to the local time zone.
+/// Register your own functions by [`Locale::add_function`].
+///
+/// A message that can't be found is reported once by a warning and resolves
+/// to its id, [`Locale::missing_keys`] lists all of them.
+///
+/// # Example
+///
+/// ```rust ignore
+/// use ribir::prelude::*;
+///
+/// let mut locale = Locale::new(langid!("zh-CN"));
+/// let en = "unread = { $count ->
+///   [one] One message
+///  *[other] { $count } messages
+/// }";
+/// locale.add_ftl(langid!("en-US"), en).unwrap();
+/// locale.add_ftl(langid!("zh-CN"), "unread = { $count } 条消息").unwrap();
+/// locale.set_fallbacks([langid!("en-US")]);
+/// AppCtx::set_app_locale(locale);
+///
+/// let count = Stateful::new(1);
+/// let w = fn_widget! {
+///   @Text {
+///     text: t!("unread", count = *$read(count)),
+///     on_tap: move |_| *$write(count) += 1,
+///   }
+/// };
+/// App::run(w);
+/// ```
+pub struct Locale {
+  lang: LanguageIdentifier,
+  fallbacks: Vec<LanguageIdentifier>,
+  bundles: Vec<LangBundle>,
+  functions: Vec<(String, FluentFn)>,
+  missing: RefCell<ahash::HashSet<(LanguageIdentifier, String)>>,
+}
+
+struct LangBundle {
+  bundle: FluentBundle<FluentResource>,
+  /// The ids of the messages in the bundle.
+  ids: ahash::HashSet<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocaleError {
+  #[error("Invalid FTL source: {}", .0.join("; "))]
+  Parse(Vec<String>),
+}
+
+impl Locale {
+  pub fn new(lang: LanguageIdentifier) -> Self {
+    let functions: Vec<(String, FluentFn)> = vec![("DATETIME".into(), Arc::new(datetime))];
+    Self { lang, fallbacks: vec![], bundles: vec![], functions, missing: <_>::default() }
+  }
+
+  pub fn of(ctx: &impl AsRef<ProviderCtx>) -> QueryRef<'_, Self> { Provider::of(ctx).unwrap() }
+
+  pub fn write_of(ctx: &impl AsRef<ProviderCtx>) -> WriteRef<'_, Self> {
+    Provider::write_of(ctx).unwrap()
+  }
+
+  /// Returns a watcher of the locale provided by `ctx`, both `t!` and `tr!`
+  /// format the messages by it.
+  ///
+  /// A locale provided as a plain value can't be watched, it's ignored with a
+  /// warning and the application locale is returned, provide it by
+  /// `Provider::writer` instead.
+  pub fn watcher(ctx: &impl AsRef<ProviderCtx>) -> Box<dyn StateWatcher<Value = Self>> {
+    Provider::watcher_of::<Self>(ctx).unwrap_or_else(|| {
+      if Provider::of::<Self>(ctx).is_some() {
+        tracing::warn!(
+          "A `Locale` provided as a plain value is ignored by `t!` and `tr!`, provide it by \
+           `Provider::writer` instead."
+        );
+      }
+      AppCtx::app_locale().clone_boxed_watcher()
+    })
+  }
+
+  pub fn lang(&self) -> &LanguageIdentifier { &self.lang }
+
+  /// Switches the current language, the texts declared by `t!` follow it.
+  pub fn set_lang(&mut self, lang: LanguageIdentifier) { self.lang = lang; }
+
+  pub fn fallbacks(&self) -> &[LanguageIdentifier] { &self.fallbacks }
+
+  pub fn set_fallbacks(&mut self, langs: impl IntoIterator<Item = LanguageIdentifier>) {
+    self.fallbacks = langs.into_iter().collect();
+  }
+
+  /// The languages that have localized strings.
+  pub fn langs(&self) -> impl Iterator<Item = &LanguageIdentifier> {
+    self.bundles.iter().map(|b| &b.bundle.locales[0])
+  }
+
+  /// Adds the messages of an FTL source to `lang`, a message already in the
+  /// language is overwritten.
+  ///
+  /// The source can be loaded by the `asset!` macro with the `ftl` type, which
+  /// checks its syntax at compile time.
+  ///
+  /// ```ignore
+  /// locale.add_ftl(langid!("en-US"), asset!("../locales/en-US.ftl", "ftl"));
+  /// ```
+  ///
+  /// The valid messages are still added when the source has syntax errors.
+  pub fn add_ftl(
+    &mut self, lang: LanguageIdentifier, source: impl Into<String>,
+  ) -> Result<(), LocaleError> {
+    let (res, errors) = match FluentResource::try_new(source.into()) {
+      Ok(res) => (res, vec![]),
+      Err((res, errors)) => (res, errors),
+    };
+
+    let idx = match self
+      .bundles
+      .iter()
+      .position(|b| b.bundle.locales[0] == lang)
+    {
+      Some(idx) => idx,
+      None => {
+        let bundle = self.new_bundle(lang);
+        self.bundles.push(bundle);
+        self.bundles.len() - 1
+      }
+    };
+    let LangBundle { bundle, ids } = &mut self.bundles[idx];
+    ids.extend(res.entries().filter_map(|entry| match entry {
+      fluent_syntax::ast::Entry::Message(m) => Some(m.id.name.to_string()),
+      _ => None,
+    }));
+    bundle.add_resource_overriding(res);
+    self.missing.borrow_mut().clear();
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(LocaleError::Parse(errors.iter().map(ToString::to_string).collect()))
+    }
+  }
+
+  /// Makes `f` callable by `name` in the FTL sources of all languages.
+  pub fn add_function(&mut self, name: impl Into<String>, f: FluentFn) {
+    let name = name.into();
+    for b in self.bundles.iter_mut() {
+      let f = f.clone();
+      let _ = b
+        .bundle
+        .add_function(&name, move |positional, named| f(positional, named));
+    }
+    self.functions.retain(|(n, _)| *n != name);
+    self.functions.push((name, f));
+  }
+
+  /// Formats the message `id` in the current language. Use `message.attr` to
+  /// format an attribute of a message.
+  ///
+  /// Returns `id` itself if the message can't be found in any language.
+  pub fn tr(&self, id: &str, args: Option<&FluentArgs>) -> CowArc<str> {
+    let (msg_id, attr) = match id.split_once('.') {
+      Some((msg_id, attr)) => (msg_id, Some(attr)),
+      None => (id, None),
+    };
+
+    for b in self.lookup_chain() {
+      let Some(msg) = b.bundle.get_message(msg_id) else { continue };
+      let pattern = match attr {
+        Some(attr) => msg.get_attribute(attr).map(|a| a.value()),
+        None => msg.value(),
+      };
+      let Some(pattern) = pattern else { continue };
+
+      if b.bundle.locales[0].language != self.lang.language {
+        self.report_missing(id);
+      }
+      let mut errors = vec![];
+      let text = b
+        .bundle
+        .format_pattern(pattern, args, &mut errors);
+      if !errors.is_empty() {
+        tracing::warn!("Failed to format the message `{id}` in `{}`: {errors:?}", self.lang);
+      }
+      return text.into_owned().into();
+    }
+
+    self.report_missing(id);
+    id.to_owned().into()
+  }
+
+  /// The messages looked up but not found in the current language, with the
+  /// language they were looked up in.
+  pub fn missing_keys(&self) -> Vec<(LanguageIdentifier, String)> {
+    let mut keys: Vec<_> = self.missing.borrow().iter().cloned().collect();
+    keys.sort_by(|a, b| (a.0.to_string(), &a.1).cmp(&(b.0.to_string(), &b.1)));
+    keys
+  }
+
+  /// The ids of the messages that other languages have but `lang` has not,
+  /// useful to check the translations are complete.
+  pub fn untranslated(&self, lang: &LanguageIdentifier) -> Vec<String> {
+    let empty = ahash::HashSet::default();
+    let own = self
+      .bundles
+      .iter()
+      .find(|b| &b.bundle.locales[0] == lang)
+      .map_or(&empty, |b| &b.ids);
+    let mut ids: Vec<_> = self
+      .bundles
+      .iter()
+      .flat_map(|b| b.ids.iter())
+      .filter(|id| !own.contains(*id))
+      .cloned()
+      .collect();
+    ids.sort();
+    ids.dedup();
+    ids
+  }
+
+  fn new_bundle(&self, lang: LanguageIdentifier) -> LangBundle {
+    let mut bundle = FluentBundle::new(vec![lang]);
+    // The isolation marks are for the bidirectional texts of the browsers, the
+    // text layout of Ribir doesn't need them.
+    bundle.set_use_isolating(false);
+    bundle.set_formatter(Some(format_number));
+    let _ = bundle.add_builtins();
+    for (name, f) in self.functions.iter() {
+      let f = f.clone();
+      let _ = bundle.add_function(name, move |positional, named| f(positional, named));
+    }
+    LangBundle { bundle, ids: <_>::default() }
+  }
+
+  /// The bundles to look up a message in order.
+  fn lookup_chain(&self) -> impl Iterator<Item = &LangBundle> {
+    std::iter::once(&self.lang)
+      .chain(self.fallbacks.iter())
+      .flat_map(move |lang| {
+        let exact = self
+          .bundles
+          .iter()
+          .filter(move |b| &b.bundle.locales[0] == lang);
+        let language = self.bundles.iter().filter(move |b| {
+          let l = &b.bundle.locales[0];
+          l != lang && l.language == lang.language && l.region.is_none()
+        });
+        exact.chain(language)
+      })
+  }
+
+  fn report_missing(&self, id: &str) {
+    let key = (self.lang.clone(), id.to_owned());
+    if self.missing.borrow_mut().insert(key) {
+      tracing::warn!("The message `{id}` is missing in `{}`.", self.lang);
+    }
+  }
+}
+
+impl Default for Locale {
+  fn default() -> Self { Self::new(langid!("en-US")) }
+}
+
+/// Formats the numbers of the messages by the language of the bundle, the
+/// currencies and the percentages are left to Fluent.
+fn format_number(value: &FluentValue, intls: &IntlLangMemoizer) -> Option<String> {
+  let FluentValue::Number(n) = value else { return None };
+  let opts = &n.options;
+  if opts.style != FluentNumberStyle::Decimal {
+    return None;
+  }
+
+  let min_fraction = opts.minimum_fraction_digits.unwrap_or(0);
+  let mut num = match opts.maximum_significant_digits {
+    Some(digits) => {
+      let digits = digits.clamp(1, u8::MAX as usize) as u8;
+      FixedDecimal::try_from_f64(n.value, FloatPrecision::SignificantDigits(digits)).ok()?
+    }
+    None => {
+      let mut num = FixedDecimal::try_from_f64(n.value, FloatPrecision::Floating).ok()?;
+      let max_fraction = opts
+        .maximum_fraction_digits
+        .unwrap_or(3)
+        .max(min_fraction);
+      num.half_even(-(max_fraction as i16));
+      num
+    }
+  };
+  num.trim_end();
+  num.pad_end(-(min_fraction as i16));
+  if let Some(min_integer) = opts.minimum_integer_digits {
+    num.pad_start(min_integer as i16);
+  }
+
+  intls
+    .with_try_get::<DecimalFormat, _, _>(opts.use_grouping, |f| f.0.format_to_string(&num))
+    .ok()
+}
+
+struct DecimalFormat(FixedDecimalFormatter);
+
+impl Memoizable for DecimalFormat {
+  /// Whether to group the integer digits.
+  type Args = bool;
+  type Error = icu_decimal::DecimalError;
+
+  fn construct(lang: LanguageIdentifier, grouping: bool) -> Result<Self, Self::Error> {
+    let mut options = FixedDecimalFormatterOptions::default();
+    options.grouping_strategy =
+      if grouping { GroupingStrategy::Auto } else { GroupingStrategy::Never };
+    FixedDecimalFormatter::try_new(&icu_locale(&lang).into(), options).map(Self)
+  }
+}
+
+/// The `DATETIME` function, formats a Unix timestamp in seconds.
+fn datetime<'a>(positional: &[FluentValue<'a>], named: &FluentArgs) -> FluentValue<'a> {
+  let Some(FluentValue::Number(n)) = positional.first() else {
+    return FluentValue::Error;
+  };
+  let style = |name| match named.get(name) {
+    Some(FluentValue::String(s)) => Some(s.to_string()),
+    _ => None,
+  };
+  let date = style("dateStyle").map(|s| match s.as_str() {
+    "full" => length::Date::Full,
+    "long" => length::Date::Long,
+    "short" => length::Date::Short,
+    _ => length::Date::Medium,
+  });
+  let time = style("timeStyle").map(|s| match s.as_str() {
+    "full" => length::Time::Full,
+    "long" => length::Time::Long,
+    "short" => length::Time::Short,
+    _ => length::Time::Medium,
+  });
+  // Only the date is formatted without the styles.
+  let date = if time.is_none() { date.or(Some(length::Date::Medium)) } else { date };
+  let utc = style("timeZone").is_some_and(|tz| tz.eq_ignore_ascii_case("utc"));
+  FluentValue::Custom(Box::new(FluentDateTime { secs: n.value.floor() as i64, date, time, utc }))
+}
+
+/// The value of `DATETIME`, formatted when the message is written by the
+/// language of the bundle.
+#[derive(Debug, Clone, PartialEq)]
+struct FluentDateTime {
+  secs: i64,
+  date: Option<length::Date>,
+  time: Option<length::Time>,
+  utc: bool,
+}
+
+struct DateTimeFormat(DateTimeFormatter);
+
+impl Memoizable for DateTimeFormat {
+  type Args = (Option<length::Date>, Option<length::Time>);
+  type Error = icu_datetime::DateTimeError;
+
+  fn construct(lang: LanguageIdentifier, (date, time): Self::Args) -> Result<Self, Self::Error> {
+    let bag = match (date, time) {
+      (Some(date), Some(time)) => length::Bag::from_date_time_style(date, time),
+      (None, Some(time)) => length::Bag::from_time_style(time),
+      (date, None) => length::Bag::from_date_style(date.unwrap_or(length::Date::Medium)),
+    };
+    DateTimeFormatter::try_new(&icu_locale(&lang).into(), bag.into()).map(Self)
+  }
+}
+
+/// The language of a bundle, to construct the formatters that can't be
+/// shared between threads.
+struct BundleLang(LanguageIdentifier);
+
+impl Memoizable for BundleLang {
+  type Args = ();
+  type Error = ();
+
+  fn construct(lang: LanguageIdentifier, _: ()) -> Result<Self, ()> { Ok(Self(lang)) }
+}
+
+impl FluentDateTime {
+  fn civil_time(&self) -> Option<icu_calendar::DateTime<icu_calendar::AnyCalendar>> {
+    let time = if self.utc {
+      chrono::DateTime::from_timestamp(self.secs, 0)?.naive_utc()
+    } else {
+      chrono::Local
+        .timestamp_opt(self.secs, 0)
+        .earliest()?
+        .naive_local()
+    };
+    let time = icu_calendar::DateTime::try_new_iso_datetime(
+      time.year(),
+      time.month() as u8,
+      time.day() as u8,
+      time.hour() as u8,
+      time.minute() as u8,
+      time.second() as u8,
+    )
+    .ok()?;
+    Some(time.to_any())
+  }
+
+  fn format(&self, f: &DateTimeFormat) -> Cow<'static, str> {
+    self
+      .civil_time()
+      .and_then(|time| f.0.format_to_string(&time).ok())
+      .map_or(Cow::Borrowed(""), Cow::Owned)
+  }
+}
+
+impl FluentType for FluentDateTime {
+  fn duplicate(&self) -> Box<dyn FluentType + Send> { Box::new(self.clone()) }
+
+  fn as_string(&self, intls: &IntlLangMemoizer) -> Cow<'static, str> {
+    intls
+      .with_try_get::<DateTimeFormat, _, _>((self.date, self.time), |f| self.format(f))
+      .unwrap_or_default()
+  }
+
+  fn as_string_threadsafe(
+    &self, intls: &intl_memoizer::concurrent::IntlLangMemoizer,
+  ) -> Cow<'static, str> {
+    intls
+      .with_try_get::<BundleLang, _, _>((), |lang| lang.0.clone())
+      .ok()
+      .and_then(|lang| DateTimeFormat::construct(lang, (self.date, self.time)).ok())
+      .map_or(Cow::Borrowed(""), |f| self.format(&f))
+  }
+}
+
+/// Converts the language to the locale of the ICU formatters.
+fn icu_locale(lang: &LanguageIdentifier) -> icu_locid::Locale {
+  lang
+    .to_string()
+    .parse()
+    .unwrap_or(icu_locid::Locale::UND)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn locale() -> Locale {
+    let mut locale = Locale::new(langid!("en-US"));
+    locale
+      .add_ftl(
+        langid!("en-US"),
+        "hello = Hello, { $name }!
+emails = { $count ->
+    [one] One email
+   *[other] { $count } emails
+}
+price = { NUMBER($value, minimumFractionDigits: 2) }
+date = { DATETIME($time, dateStyle: \"medium\", timeStyle: \"short\", timeZone: \"UTC\") }
+save = Save
+    .tooltip = Save the file",
+      )
+      .unwrap();
+    locale
+      .add_ftl(langid!("zh"), "hello = 你好，{ $name }！")
+      .unwrap();
+    locale.set_fallbacks([langid!("en-US")]);
+    locale
+  }
+
+  #[test]
+  fn format() {
+    let locale = locale();
+    let mut args = FluentArgs::new();
+    args.set("name", "Ribir");
+    assert_eq!(&*locale.tr("hello", Some(&args)), "Hello, Ribir!");
+
+    let mut args = FluentArgs::new();
+    args.set("count", 1);
+    assert_eq!(&*locale.tr("emails", Some(&args)), "One email");
+    args.set("count", 5);
+    assert_eq!(&*locale.tr("emails", Some(&args)), "5 emails");
+
+    let mut args = FluentArgs::new();
+    args.set("value", 3);
+    assert_eq!(&*locale.tr("price", Some(&args)), "3.00");
+    args.set("value", 1234.5);
+    assert_eq!(&*locale.tr("price", Some(&args)), "1,234.50");
+
+    let mut args = FluentArgs::new();
+    args.set("time", 1706689800);
+    assert_eq!(&*locale.tr("date", Some(&args)), "Jan 31, 2024, 8:30\u{202f}AM");
+
+    assert_eq!(&*locale.tr("save.tooltip", None), "Save the file");
+  }
+
+  #[test]
+  fn fallback_and_missing() {
+    let mut locale = locale();
+    locale.set_lang(langid!("zh-CN"));
+
+    let mut args = FluentArgs::new();
+    args.set("name", "Ribir");
+    // `zh` serves `zh-CN`.
+    assert_eq!(&*locale.tr("hello", Some(&args)), "你好，Ribir！");
+    assert_eq!(&*locale.tr("save", None), "Save");
+    assert_eq!(&*locale.tr("not-exist", None), "not-exist");
+
+    assert_eq!(
+      locale.missing_keys(),
+      vec![(langid!("zh-CN"), "not-exist".to_string()), (langid!("zh-CN"), "save".to_string())]
+    );
+    assert_eq!(locale.untranslated(&langid!("zh")), vec!["date", "emails", "price", "save"]);
+  }
+
+  #[test]
+  fn follow_locale() {
+    reset_test_env!();
+
+    AppCtx::set_app_locale(locale());
+    let (count, w_count) = split_value(1);
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @Text { text: t!("emails", count = *$read(count)) }
+    });
+    wnd.enable_a11y();
+    wnd.draw_frame();
+    let has_text = |text: &str| wnd.a11y_tree().find_by_label(text).is_some();
+    assert!(has_text("One email"));
+
+    *w_count.write() = 3;
+    wnd.draw_frame();
+    assert!(has_text("3 emails"));
+
+    AppCtx::app_locale()
+      .write()
+      .add_ftl(langid!("zh"), "emails = { $count } 封邮件")
+      .unwrap();
+    AppCtx::app_locale()
+      .write()
+      .set_lang(langid!("zh"));
+    wnd.draw_frame();
+    assert!(has_text("3 封邮件"));
+  }
+
+  #[test]
+  fn tr_agrees_with_t() {
+    reset_test_env!();
+
+    AppCtx::set_app_locale(locale());
+    let mut zh = locale();
+    zh.set_lang(langid!("zh"));
+    let zh = Stateful::new(zh);
+    let (translated, w_translated) = split_value(String::new());
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @Providers {
+        providers: [Provider::writer(zh.clone_writer(), None)],
+        @ {
+          *w_translated.write() = tr!(BuildCtx::get(), "hello", name = "Ribir").to_string();
+          @Text { text: t!("hello", name = "Ribir") }
+        }
+      }
+    });
+    wnd.enable_a11y();
+    wnd.draw_frame();
+
+    assert_eq!(*translated.read(), "你好，Ribir！");
+    assert!(
+      wnd
+        .a11y_tree()
+        .find_by_label("你好，Ribir！")
+        .is_some()
+    );
+  }
+}
@@ -0,0 +1,517 @@
+//! Keeps the window geometry and the UI states across launches.
+//!
+//! A [`Persist`] serializes the registered states with serde into a
+//! [`PersistStorage`], and restores them when they are registered again on the
+//! next launch. The application persist is used unless a widget provides
+//! another one.
+//!
+//! - Register a `Stateful` by [`Persist::register`], or create one by
+//!   [`Persist::state`].
+//! - The `persist: "key"` builtin field keeps the scroll position of a
+//!   scrollable widget, it does nothing on a widget without `scrollable`.
+//! - [`WindowAttributes::with_persist`] keeps the size, the position and the
+//!   maximized state of a window. The shell drops a restored position that
+//!   isn't on any current monitor, see [`WindowAttributes::fit_monitors`].
+
+use std::cell::{Cell, RefCell};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{prelude::*, window::WindowAttributes};
+
+/// A key-value store where a [`Persist`] keeps the serialized states.
+pub trait PersistStorage {
+  /// Returns the value stored with `key`.
+  fn read(&self, key: &str) -> Option<String>;
+
+  /// Stores the `value` with `key`, the value may stay in memory until
+  /// [`PersistStorage::flush`].
+  fn write(&mut self, key: &str, value: String);
+
+  /// Removes the value stored with `key`.
+  fn remove(&mut self, key: &str);
+
+  /// Writes the pending values to the backend.
+  fn flush(&mut self) {}
+}
+
+/// A storage in memory, the clones share the same values, so it can simulate a
+/// relaunch in tests.
+#[derive(Default, Clone)]
+pub struct MemoryStorage(Rc<RefCell<ahash::HashMap<String, String>>>);
+
+impl MemoryStorage {
+  /// Returns how many values are stored.
+  pub fn len(&self) -> usize { self.0.borrow().len() }
+
+  pub fn is_empty(&self) -> bool { self.0.borrow().is_empty() }
+}
+
+impl PersistStorage for MemoryStorage {
+  fn read(&self, key: &str) -> Option<String> { self.0.borrow().get(key).cloned() }
+
+  fn write(&mut self, key: &str, value: String) { self.0.borrow_mut().insert(key.into(), value); }
+
+  fn remove(&mut self, key: &str) { self.0.borrow_mut().remove(key); }
+}
+
+/// A storage kept in a JSON file, the file is rewritten when the storage
+/// flushes.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStorage {
+  path: PathBuf,
+  values: std::collections::BTreeMap<String, String>,
+  dirty: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+  /// Opens the storage kept in the file of `path`, the file is created on the
+  /// first flush.
+  pub fn new(path: impl Into<PathBuf>) -> Self {
+    let path = path.into();
+    let values = std::fs::read_to_string(&path)
+      .ok()
+      .and_then(|content| {
+        serde_json::from_str(&content)
+          .map_err(|err| tracing::warn!("Ignore the broken persist file {path:?}: {err}"))
+          .ok()
+      })
+      .unwrap_or_default();
+    Self { path, values, dirty: false }
+  }
+
+  /// Opens the storage of the application in the data directory of the user,
+  /// that is `<data_dir>/<app_name>/persist.json`.
+  pub fn for_app(app_name: &str) -> Self {
+    let dir = dirs::data_dir().unwrap_or_default();
+    Self::new(dir.join(app_name).join("persist.json"))
+  }
+
+  pub fn path(&self) -> &std::path::Path { &self.path }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PersistStorage for FileStorage {
+  fn read(&self, key: &str) -> Option<String> { self.values.get(key).cloned() }
+
+  fn write(&mut self, key: &str, value: String) {
+    if self.values.get(key) != Some(&value) {
+      self.values.insert(key.into(), value);
+      self.dirty = true;
+    }
+  }
+
+  fn remove(&mut self, key: &str) { self.dirty |= self.values.remove(key).is_some(); }
+
+  fn flush(&mut self) {
+    if !self.dirty {
+      return;
+    }
+    let content = serde_json::to_string_pretty(&self.values).unwrap();
+    let res = self
+      .path
+      .parent()
+      .map_or(Ok(()), std::fs::create_dir_all)
+      .and_then(|_| std::fs::write(&self.path, content));
+    match res {
+      Ok(()) => self.dirty = false,
+      Err(err) => tracing::warn!("Failed to write the persist file {:?}: {err}", self.path),
+    }
+  }
+}
+
+/// A storage kept in the `localStorage` of the browser, the keys are prefixed
+/// to share the origin with other applications.
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage {
+  prefix: String,
+  storage: Option<web_sys::Storage>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+  pub fn new(prefix: impl Into<String>) -> Self {
+    let storage = web_sys::window().and_then(|w| w.local_storage().ok().flatten());
+    if storage.is_none() {
+      tracing::warn!("`localStorage` is unavailable, the states will not be persisted.");
+    }
+    Self { prefix: prefix.into(), storage }
+  }
+
+  fn key(&self, key: &str) -> String { format!("{}{key}", self.prefix) }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl PersistStorage for LocalStorage {
+  fn read(&self, key: &str) -> Option<String> {
+    self
+      .storage
+      .as_ref()?
+      .get_item(&self.key(key))
+      .ok()?
+  }
+
+  fn write(&mut self, key: &str, value: String) {
+    if let Some(storage) = &self.storage {
+      let _ = storage.set_item(&self.key(key), &value);
+    }
+  }
+
+  fn remove(&mut self, key: &str) {
+    if let Some(storage) = &self.storage {
+      let _ = storage.remove_item(&self.key(key));
+    }
+  }
+}
+
+/// Serializes the registered states into a [`PersistStorage`] and restores
+/// them on the next launch.
+///
+/// It's a cheap handle, the clones share the same storage. The writes are
+/// flushed to the storage in batch after the current task.
+///
+/// # Example
+///
+/// ```rust ignore
+/// use ribir::prelude::*;
+///
+/// AppCtx::set_app_persist(Persist::platform("my_app"));
+/// let w = fn_widget! {
+///   let ratio = Persist::of(BuildCtx::get()).state("split_ratio", 0.3f32);
+///   @ScrollableWidget {
+///     persist: "main_list",
+///     scrollable: Scrollable::Y,
+///     @Text { text: pipe!($read(ratio).to_string()) }
+///   }
+/// };
+/// App::run(w).with_persist("main_window");
+/// ```
+#[derive(Clone)]
+pub struct Persist(Rc<PersistInner>);
+
+struct PersistInner {
+  storage: RefCell<Box<dyn PersistStorage>>,
+  flush_scheduled: Cell<bool>,
+}
+
+/// The geometry of a window saved by the persist.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+struct WindowGeometry {
+  width: f32,
+  height: f32,
+  x: f32,
+  y: f32,
+  maximized: bool,
+}
+
+impl Persist {
+  pub fn new(storage: impl PersistStorage + 'static) -> Self {
+    Self(Rc::new(PersistInner {
+      storage: RefCell::new(Box::new(storage)),
+      flush_scheduled: Cell::new(false),
+    }))
+  }
+
+  /// A persist that keeps the states in memory only.
+  pub fn memory() -> Self { Self::new(MemoryStorage::default()) }
+
+  /// The persist of the platform, a file in the data directory of the user on
+  /// native, or the `localStorage` on web.
+  pub fn platform(app_name: &str) -> Self {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Self::new(FileStorage::for_app(app_name));
+    #[cfg(target_arch = "wasm32")]
+    return Self::new(LocalStorage::new(format!("{app_name}:")));
+  }
+
+  /// Returns the persist provided by `ctx`, or the application persist if
+  /// there isn't one.
+  pub fn of(ctx: &impl AsRef<ProviderCtx>) -> Self {
+    Provider::of::<Self>(ctx).map_or_else(AppCtx::app_persist, |p| Persist::clone(&p))
+  }
+
+  /// Returns the value stored with `key`, `None` if there isn't one or it
+  /// can't be deserialized.
+  pub fn load<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+    let value = self.0.storage.borrow().read(key)?;
+    serde_json::from_str(&value)
+      .map_err(|err| tracing::warn!("Ignore the broken persisted value `{key}`: {err}"))
+      .ok()
+  }
+
+  /// Stores the `value` with `key`.
+  pub fn save<T: Serialize + ?Sized>(&self, key: &str, value: &T) {
+    match serde_json::to_string(value) {
+      Ok(value) => {
+        self.0.storage.borrow_mut().write(key, value);
+        self.schedule_flush();
+      }
+      Err(err) => tracing::warn!("Failed to persist `{key}`: {err}"),
+    }
+  }
+
+  /// Removes the value stored with `key`.
+  pub fn remove(&self, key: &str) {
+    self.0.storage.borrow_mut().remove(key);
+    self.schedule_flush();
+  }
+
+  /// Writes the pending values to the storage right now.
+  pub fn flush(&self) {
+    self.0.flush_scheduled.set(false);
+    self.0.storage.borrow_mut().flush();
+  }
+
+  /// Restores the `state` from the value stored with `key`, and saves it
+  /// whenever it changes until the state is dropped.
+  pub fn register<S>(&self, key: impl Into<CowArc<str>>, state: &S)
+  where
+    S: StateWriter,
+    S::Value: Serialize + DeserializeOwned + Sized,
+  {
+    let key = key.into();
+    if let Some(value) = self.load(&key) {
+      *state.write() = value;
+    }
+    self.keep(key, state);
+  }
+
+  /// Creates a state restored from the value stored with `key`, or `default`
+  /// if there isn't one, and saves it whenever it changes.
+  pub fn state<T>(&self, key: impl Into<CowArc<str>>, default: T) -> Stateful<T>
+  where
+    T: Serialize + DeserializeOwned + 'static,
+  {
+    let key = key.into();
+    let state = Stateful::new(self.load(&key).unwrap_or(default));
+    self.keep(key, &state);
+    state
+  }
+
+  /// Applies the geometry stored with `key` to the window attributes.
+  pub(crate) fn restore_window(&self, key: &str, attrs: &mut WindowAttributes) {
+    if let Some(g) = self.load::<WindowGeometry>(key) {
+      attrs
+        .with_size(Size::new(g.width, g.height))
+        .with_maximized(g.maximized);
+      attrs.0.position = Some(winit::dpi::LogicalPosition::new(g.x, g.y).into());
+    }
+  }
+
+  /// Stores the geometry of the window with `key`.
+  pub(crate) fn save_window(&self, key: &str, wnd: &Window) {
+    let shell = wnd.shell_wnd().borrow();
+    if shell.is_minimized() {
+      // The geometry of a minimized window isn't meaningful to restore.
+      return;
+    }
+    let (size, pos) = (shell.inner_size(), shell.position());
+    let maximized = shell.is_maximized();
+    drop(shell);
+
+    // Keep the normal geometry of a maximized window, so it restores to it.
+    let geometry = match self.load::<WindowGeometry>(key) {
+      Some(old) if maximized => WindowGeometry { maximized, ..old },
+      _ => WindowGeometry { width: size.width, height: size.height, x: pos.x, y: pos.y, maximized },
+    };
+    self.save(key, &geometry);
+  }
+
+  fn keep<S>(&self, key: CowArc<str>, state: &S)
+  where
+    S: StateWriter,
+    S::Value: Serialize + Sized,
+  {
+    let this = self.clone();
+    let watcher = state.clone_watcher();
+    let _ = state
+      .modifies()
+      .subscribe(move |_| this.save(&key, &*watcher.read()));
+  }
+
+  fn schedule_flush(&self) {
+    if !self.0.flush_scheduled.replace(true) {
+      let this = self.clone();
+      AppCtx::spawn_local(async move {
+        if this.0.flush_scheduled.get() {
+          this.flush();
+        }
+      });
+    }
+  }
+}
+
+impl Default for Persist {
+  fn default() -> Self { Self::memory() }
+}
+
+/// Keeps the scroll position of the `scrollable` with `key`, it's restored
+/// once the content and the view of the scrollable are laid out.
+pub(crate) fn persist_scroll_pos(
+  key: CowArc<str>, scrollable: &Stateful<ScrollableWidget>, mix: &MixBuiltin,
+) {
+  #[derive(Serialize, Deserialize, PartialEq)]
+  struct ScrollPos {
+    x: f32,
+    y: f32,
+  }
+
+  let persist: Rc<RefCell<Option<Persist>>> = Rc::default();
+  mix.on_mounted({
+    let persist = persist.clone();
+    move |e| *persist.borrow_mut() = Some(Persist::of(e))
+  });
+
+  let restored = Cell::new(false);
+  let writer = scrollable.clone_writer();
+  let sub = scrollable.modifies().subscribe(move |_| {
+    let Some(persist) = &*persist.borrow() else { return };
+    let this = writer.read();
+    if restored.get() {
+      let pos = this.get_scroll_pos();
+      persist.save(&key, &ScrollPos { x: pos.x, y: pos.y });
+    } else if !this.scroll_content_size().is_empty() && !this.scroll_view_size().is_empty() {
+      // Don't overwrite the stored position before it's restored.
+      restored.set(true);
+      drop(this);
+      if let Some(ScrollPos { x, y }) = persist.load(&key) {
+        writer.write().jump_to(Point::new(x, y));
+      }
+    }
+  });
+  mix.on_disposed(move |_| sub.unsubscribe());
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*, window::WindowFlags};
+
+  #[test]
+  fn restore_registered_state() {
+    reset_test_env!();
+
+    let storage = MemoryStorage::default();
+    let persist = Persist::new(storage.clone());
+    let tab = persist.state("tab", 0usize);
+    assert_eq!(*tab.read(), 0);
+    *tab.write() = 2;
+    AppCtx::run_until_stalled();
+    assert_eq!(storage.len(), 1);
+
+    // Launch again with the same storage.
+    let persist = Persist::new(storage);
+    let tab = Stateful::new(0usize);
+    persist.register("tab", &tab);
+    assert_eq!(*tab.read(), 2);
+    assert_eq!(*persist.state("ratio", 0.5f32).read(), 0.5);
+  }
+
+  #[test]
+  fn broken_value_falls_back_to_default() {
+    reset_test_env!();
+
+    let mut storage = MemoryStorage::default();
+    storage.write("tab", "not json".into());
+    let persist = Persist::new(storage);
+    assert_eq!(*persist.state("tab", 1usize).read(), 1);
+  }
+
+  #[test]
+  fn persist_scroll_position() {
+    reset_test_env!();
+
+    type Slot = Rc<RefCell<Option<Stateful<ScrollableWidget>>>>;
+    let launch = |storage: &MemoryStorage| {
+      let slot = Slot::default();
+      let (storage, c_slot) = (storage.clone(), slot.clone());
+      let wnd = TestWindow::from_widget(fn_widget! {
+        let mut list = @MockBox {
+          size: Size::new(100., 100.),
+          persist: "list",
+          scrollable: Scrollable::Y,
+        };
+        *c_slot.borrow_mut() = Some(list.scrollable_widget().clone_writer());
+        @MockBox {
+          size: Size::new(100., 100.),
+          providers: [Provider::new(Persist::new(storage.clone()))],
+          @(list) { @MockBox { size: Size::new(100., 1000.) } }
+        }
+      });
+      wnd.draw_frame();
+      AppCtx::run_until_stalled();
+      let scrollable = slot.borrow_mut().take().unwrap();
+      (wnd, scrollable)
+    };
+
+    let storage = MemoryStorage::default();
+    let (wnd, scrollable) = launch(&storage);
+    assert_eq!(scrollable.read().get_scroll_pos(), Point::zero());
+    scrollable.write().jump_to(Point::new(0., 300.));
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    assert_eq!(storage.len(), 1);
+    wnd.dispose();
+
+    let (wnd, scrollable) = launch(&storage);
+    wnd.draw_frame();
+    assert_eq!(scrollable.read().get_scroll_pos(), Point::new(0., 300.));
+  }
+
+  #[test]
+  fn persist_window_geometry() {
+    reset_test_env!();
+
+    let storage = MemoryStorage::default();
+    AppCtx::set_app_persist(Persist::new(storage.clone()));
+
+    let open = || {
+      let mut attrs = WindowAttributes::default();
+      attrs
+        .with_size(Size::new(300., 200.))
+        .with_persist("main");
+      AppCtx::run_until(AppCtx::new_window(
+        GenWidget::new(|| Void::default().into_widget()),
+        WindowFlags::empty(),
+        attrs,
+      ))
+    };
+
+    let wnd = open();
+    assert_eq!(wnd.size(), Size::new(300., 200.));
+    wnd.set_position(Point::new(40., 50.));
+    wnd.request_resize(Size::new(640., 480.));
+    wnd.dispose();
+
+    let wnd = open();
+    assert_eq!(wnd.size(), Size::new(640., 480.));
+    assert_eq!(wnd.position(), Point::new(40., 50.));
+    assert!(!TestShellWindow::of(&wnd).maximized);
+  }
+
+  #[test]
+  fn drop_position_out_of_monitors() {
+    let monitors = [Rect::new(Point::zero(), Size::new(1920., 1080.))];
+    let restored = |pos: Point| {
+      let mut attrs = WindowAttributes::default();
+      attrs.with_size(Size::new(640., 480.));
+      attrs = attrs.position(pos);
+      attrs.fit_monitors(&monitors);
+      attrs.0.position
+    };
+
+    assert!(restored(Point::new(100., 100.)).is_some());
+    // Partly out of the monitor, but the title bar is reachable.
+    assert!(restored(Point::new(-600., 1000.)).is_some());
+    // On a disconnected monitor.
+    assert!(restored(Point::new(2000., 100.)).is_none());
+    assert!(restored(Point::new(100., -400.)).is_none());
+
+    let mut attrs = WindowAttributes::default().position(Point::new(5000., 0.));
+    attrs.fit_monitors(&[]);
+    assert!(attrs.0.position.is_some());
+  }
+}
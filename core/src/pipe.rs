@@ -569,6 +569,34 @@ impl Render for PipeNode {
     self.as_ref().data.place_children(size, ctx)
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .min_intrinsic_width(height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .max_intrinsic_width(height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .min_intrinsic_height(width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .as_ref()
+      .data
+      .max_intrinsic_height(width, ctx)
+  }
+
   fn paint(&self, ctx: &mut PaintingCtx) { self.as_ref().data.paint(ctx) }
 
   fn size_affected_by_child(&self) -> bool {
@@ -583,6 +611,12 @@ impl Render for PipeNode {
 
   fn get_transform(&self) -> Option<Transform> { self.as_ref().data.get_transform() }
 
+  fn a11y(&self) -> Option<A11y> { self.as_ref().data.a11y() }
+
+  fn baseline(&self, size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    self.as_ref().data.baseline(size, ctx)
+  }
+
   fn dirty_phase(&self) -> DirtyPhase { self.as_ref().data.dirty_phase() }
 
   fn adjust_position(&self, pos: Point, ctx: &mut PlaceCtx) -> Point {
@@ -51,6 +51,26 @@ where
     self.proxy().adjust_position(pos, ctx)
   }
 
+  #[inline]
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.proxy().min_intrinsic_width(height, ctx)
+  }
+
+  #[inline]
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.proxy().max_intrinsic_width(height, ctx)
+  }
+
+  #[inline]
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.proxy().min_intrinsic_height(width, ctx)
+  }
+
+  #[inline]
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.proxy().max_intrinsic_height(width, ctx)
+  }
+
   #[inline]
   fn paint(&self, ctx: &mut PaintingCtx) { self.proxy().paint(ctx) }
 
@@ -65,6 +85,14 @@ where
   #[inline]
   fn get_transform(&self) -> Option<Transform> { self.proxy().get_transform() }
 
+  #[inline]
+  fn a11y(&self) -> Option<A11y> { self.proxy().a11y() }
+
+  #[inline]
+  fn baseline(&self, size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    self.proxy().baseline(size, ctx)
+  }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { self.proxy().debug_name() }
 
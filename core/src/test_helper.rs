@@ -14,7 +14,7 @@ wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 use crate::{
   prelude::*,
   widget_tree::WidgetTree,
-  window::{BoxShellWindow, RedrawDemand, Shell, ShellWindow, WindowFlags, WindowId},
+  window::{BoxShellWindow, RedrawDemand, Shell, ShellWindow, WindowFlags, WindowId, WindowKind},
 };
 
 pub struct Frame {
@@ -28,6 +28,13 @@ pub fn split_value<T: 'static>(v: T) -> (Watcher<Reader<T>>, Stateful<T>) {
   (src.clone_watcher(), src.clone_writer())
 }
 
+/// A static [`Image`] of an opaque white frame.
+pub fn white_image(width: u32, height: u32) -> Image {
+  let image = ribir_painter::test_utils::white_image(width, height);
+  let frame = DecodedFrame { image, delay_ms: 0 };
+  Image::from_parts(vec![], width, height, LoopCount::Finite(1), vec![frame])
+}
+
 /// The Window assists in writing unit tests; animations are disabled by
 /// default.
 #[derive(Clone)]
@@ -47,13 +54,24 @@ impl Shell for TestShell {
 
   fn new_shell_window(&self, attr: window::WindowAttributes) -> BoxFuture<'static, BoxShellWindow> {
     Box::pin(async move {
-      Box::new(TestShellWindow::new(attr.0.inner_size.map_or_else(
+      let size = attr.0.inner_size.map_or_else(
         || Size::new(1024., 1024.),
         |s| {
           let s = s.to_logical(1.);
           Size::new(s.width, s.height)
         },
-      ))) as BoxShellWindow
+      );
+      let mut wnd = TestShellWindow::new(size);
+      wnd.title = attr.0.title.clone();
+      wnd.visible = attr.0.visible;
+      wnd.maximized = attr.0.maximized;
+      wnd.owner = attr.owner();
+      wnd.kind = attr.kind();
+      if let Some(pos) = attr.0.position {
+        let pos = pos.to_logical::<f32>(1.);
+        wnd.position = Point::new(pos.x, pos.y);
+      }
+      Box::new(wnd) as BoxShellWindow
     })
   }
 
@@ -175,6 +193,94 @@ impl TestWindow {
 
   /// Count the number of widgets in the subtree rooted at `id`.
   pub fn count(&self, id: WidgetId) -> usize { id.descendants(self.0.tree()).count() }
+
+  /// Starts to build the accessibility tree as if a screen reader activates
+  /// it, the tree is available by [`TestWindow::a11y_tree`] after a frame.
+  pub fn enable_a11y(&self) { self.0.set_a11y_active(true); }
+
+  /// Returns the accessibility tree received by the window.
+  pub fn a11y_tree(&self) -> std::cell::Ref<'_, TestA11yTree> {
+    std::cell::Ref::map(self.shell_wnd().borrow(), |shell| {
+      &shell
+        .as_any()
+        .downcast_ref::<TestShellWindow>()
+        .unwrap()
+        .a11y
+    })
+  }
+
+  /// Requests an action on the node of the accessibility tree.
+  pub fn a11y_action(&self, target: accesskit::NodeId, action: Action) {
+    self.process_a11y_action(accesskit::ActionRequest { action, target, data: None });
+  }
+}
+
+/// An in-memory consumer of the accessibility tree updates, it keeps the
+/// nodes reachable from the root.
+#[derive(Default)]
+pub struct TestA11yTree {
+  root: Option<accesskit::NodeId>,
+  focus: Option<accesskit::NodeId>,
+  nodes: ahash::HashMap<accesskit::NodeId, accesskit::Node>,
+  last_update_len: usize,
+}
+
+impl TestA11yTree {
+  pub fn root(&self) -> Option<accesskit::NodeId> { self.root }
+
+  pub fn focus(&self) -> Option<accesskit::NodeId> { self.focus }
+
+  pub fn node(&self, id: accesskit::NodeId) -> Option<&accesskit::Node> { self.nodes.get(&id) }
+
+  pub fn children(&self, id: accesskit::NodeId) -> &[accesskit::NodeId] {
+    self.nodes.get(&id).map_or(&[], |n| n.children())
+  }
+
+  /// The count of the nodes in the last update.
+  pub fn last_update_len(&self) -> usize { self.last_update_len }
+
+  /// Finds the first node in depth-first order that matches `f`.
+  pub fn find(&self, f: impl Fn(&accesskit::Node) -> bool) -> Option<accesskit::NodeId> {
+    let mut stack: Vec<_> = self.root.into_iter().collect();
+    while let Some(id) = stack.pop() {
+      let node = self.nodes.get(&id)?;
+      if f(node) {
+        return Some(id);
+      }
+      stack.extend(node.children().iter().rev());
+    }
+    None
+  }
+
+  pub fn find_by_role(&self, role: Role) -> Option<accesskit::NodeId> {
+    self.find(|n| n.role() == role)
+  }
+
+  pub fn find_by_label(&self, label: &str) -> Option<accesskit::NodeId> {
+    self.find(|n| n.label() == Some(label))
+  }
+
+  fn apply(&mut self, update: accesskit::TreeUpdate) {
+    if let Some(tree) = update.tree {
+      self.root = Some(tree.root);
+      self.nodes.clear();
+    }
+    self.last_update_len = update.nodes.len();
+    self.nodes.extend(update.nodes);
+    self.focus = Some(update.focus);
+
+    // Drop the nodes no longer referenced by the tree.
+    let mut reachable = ahash::HashSet::default();
+    let mut stack: Vec<_> = self.root.into_iter().collect();
+    while let Some(id) = stack.pop() {
+      if reachable.insert(id)
+        && let Some(node) = self.nodes.get(&id)
+      {
+        stack.extend(node.children().iter().copied());
+      }
+    }
+    self.nodes.retain(|id, _| reachable.contains(id));
+  }
 }
 
 impl std::ops::Deref for TestWindow {
@@ -190,6 +296,17 @@ pub struct TestShellWindow {
   pub last_frame: Option<Frame>,
   pub request_draw_count: Cell<usize>,
   pub size: Size,
+  pub a11y: TestA11yTree,
+  pub title: String,
+  pub position: Point,
+  pub visible: bool,
+  pub maximized: bool,
+  /// The window that owns this window.
+  pub owner: Option<WindowId>,
+  pub kind: WindowKind,
+  /// How many times the window has been asked to take the focus.
+  pub focus_count: usize,
+  pub closed: Cell<bool>,
 }
 
 impl ShellWindow for TestShellWindow {
@@ -203,26 +320,28 @@ impl ShellWindow for TestShellWindow {
 
   fn cursor(&self) -> CursorIcon { self.cursor }
 
-  fn set_title(&mut self, _: &str) {}
+  fn set_title(&mut self, title: &str) { self.title = title.to_string(); }
 
   fn set_icon(&mut self, _: &PixelImage) {}
 
   fn set_ime_cursor_area(&mut self, _: &Rect) {}
 
-  fn set_visible(&mut self, _: bool) {}
+  fn set_visible(&mut self, visible: bool) { self.visible = visible; }
 
-  fn is_visible(&self) -> Option<bool> { Some(true) }
+  fn is_visible(&self) -> Option<bool> { Some(self.visible) }
 
   fn set_resizable(&mut self, _: bool) {}
 
   fn is_resizable(&self) -> bool { true }
 
-  fn focus_window(&mut self) {}
+  fn focus_window(&mut self) { self.focus_count += 1; }
 
   // fn set_decorations(&mut self, _: bool) {}
 
   fn is_minimized(&self) -> bool { false }
 
+  fn is_maximized(&self) -> bool { self.maximized }
+
   fn set_minimized(&mut self, _: bool) {}
 
   fn set_window_level(&mut self, _: WindowLevel) {}
@@ -240,6 +359,8 @@ impl ShellWindow for TestShellWindow {
       Some(Frame { commands: commands.to_owned(), viewport, surface: surface_color });
   }
 
+  fn update_a11y_tree(&mut self, update: accesskit::TreeUpdate) { self.a11y.apply(update); }
+
   fn request_draw(&self, _demand: RedrawDemand) {
     self
       .request_draw_count
@@ -248,14 +369,24 @@ impl ShellWindow for TestShellWindow {
 
   fn id(&self) -> WindowId { self.id }
 
-  fn position(&self) -> Point { Point::new(0., 0.) }
+  fn position(&self) -> Point { self.position }
 
-  fn set_position(&mut self, _: Point) {}
+  fn set_position(&mut self, pos: Point) { self.position = pos; }
 
-  fn close(&self) {}
+  fn close(&self) { self.closed.set(true); }
 }
 
 impl TestShellWindow {
+  /// Returns the test shell behind the window.
+  pub fn of(wnd: &Window) -> std::cell::Ref<'_, TestShellWindow> {
+    std::cell::Ref::map(wnd.shell_wnd().borrow(), |shell| {
+      shell
+        .as_any()
+        .downcast_ref::<TestShellWindow>()
+        .unwrap()
+    })
+  }
+
   fn new(size: Size) -> Self {
     static ID: AtomicU64 = AtomicU64::new(0);
     TestShellWindow {
@@ -265,6 +396,15 @@ impl TestShellWindow {
       request_draw_count: Cell::new(0),
       surface_color: Color::WHITE,
       size,
+      a11y: <_>::default(),
+      title: String::new(),
+      position: Point::zero(),
+      visible: true,
+      maximized: false,
+      owner: None,
+      kind: WindowKind::Normal,
+      focus_count: 0,
+      closed: Cell::new(false),
     }
   }
 
@@ -67,6 +67,56 @@ pub trait Render: 'static {
     }
   }
 
+  /// The smallest width the widget can be laid out in without overflowing its
+  /// content, given its height is `height`.
+  ///
+  /// The intrinsic sizes let a parent size a child by its content without
+  /// laying it out with fake constraints, query them of a child with
+  /// `MeasureCtx::intrinsic_size`, which caches the result until the child
+  /// needs layout. `height` may be infinite if it's unknown.
+  ///
+  /// Default implementation returns the largest one of its children, or
+  /// measures a widget without children with the width unlimited, such as an
+  /// image. Override it if the size isn't decided that way, such as a
+  /// wrapping text or a widget not sized by its children.
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    if ctx.has_child() {
+      ctx.children_intrinsic_size(IntrinsicSize::MinWidth, height)
+    } else {
+      ctx.leaf_intrinsic_size(IntrinsicSize::MinWidth, height, |c, ctx| self.measure(c, ctx))
+    }
+  }
+
+  /// The width the widget takes when it has unlimited width, given its height
+  /// is `height`, such as the width of a text without wrapping.
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    if ctx.has_child() {
+      ctx.children_intrinsic_size(IntrinsicSize::MaxWidth, height)
+    } else {
+      ctx.leaf_intrinsic_size(IntrinsicSize::MaxWidth, height, |c, ctx| self.measure(c, ctx))
+    }
+  }
+
+  /// The smallest height the widget can be laid out in without overflowing
+  /// its content, given its width is `width`.
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    if ctx.has_child() {
+      ctx.children_intrinsic_size(IntrinsicSize::MinHeight, width)
+    } else {
+      ctx.leaf_intrinsic_size(IntrinsicSize::MinHeight, width, |c, ctx| self.measure(c, ctx))
+    }
+  }
+
+  /// The height the widget takes when it has unlimited height, given its
+  /// width is `width`.
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    if ctx.has_child() {
+      ctx.children_intrinsic_size(IntrinsicSize::MaxHeight, width)
+    } else {
+      ctx.leaf_intrinsic_size(IntrinsicSize::MaxHeight, width, |c, ctx| self.measure(c, ctx))
+    }
+  }
+
   /// Custom painting implementation
   ///
   /// Use `PaintingCtx::painter` for drawing operations. Child widgets are
@@ -105,6 +155,22 @@ pub trait Render: 'static {
   /// Used for widgets with custom positioning or transformation effects.
   fn get_transform(&self) -> Option<Transform> { None }
 
+  /// Built-in semantics for assistive technologies
+  ///
+  /// Return `Some(A11y)` to describe the render widget without the `a11y`
+  /// attribute, such as the label of a text. The `a11y` attribute takes
+  /// precedence over it.
+  fn a11y(&self) -> Option<A11y> { None }
+
+  /// The distance from the top of the widget to the baseline of its first
+  /// line of text.
+  ///
+  /// It's called after `place_children`, so the children's positions and
+  /// baselines are available from the `ctx`. Parents aligning by baseline read
+  /// it with `MeasureCtx::widget_baseline`. Default implementation returns
+  /// `None`, which means the widget has no baseline.
+  fn baseline(&self, _size: Size, _ctx: &mut PlaceCtx) -> Option<f32> { None }
+
   /// Adjust the position assigned by the parent's `place_children` method.
   ///
   /// Override this to apply custom position adjustments (e.g., anchor-based
@@ -76,6 +76,8 @@ impl WidgetTree {
     let location = Location::stateful();
     providers.push(Provider::writer(location.clone_writer(), None));
     providers.push(ReuseScope::root_provider());
+    #[cfg(feature = "i18n")]
+    providers.push(Provider::writer(AppCtx::app_locale().clone_writer(), None));
 
     let mut root = FatObj::new(child);
     root
@@ -235,11 +237,11 @@ impl WidgetTree {
       if dirty == DirtyPhase::LayoutSubtree {
         for w in id.0.descendants(&self.arena).map(WidgetId) {
           if let Some(info) = self.store.get_mut(&w) {
-            info.size.take();
+            info.mark_needs_layout();
           }
         }
       } else if let Some(info) = self.store.get_mut(&id) {
-        info.size.take();
+        info.mark_needs_layout();
       }
 
       let mut relayout_root = id;
@@ -255,7 +257,7 @@ impl WidgetTree {
 
         relayout_root = p;
         if let Some(info) = self.store.get_mut(&p) {
-          info.size.take();
+          info.mark_needs_layout();
         }
 
         if !p.assert_get(self).size_affected_by_child() {
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 pub use ribir_types::BoxClamp;
+use smallvec::SmallVec;
 
 use super::{Lerp, WidgetId, WidgetTree};
 use crate::prelude::{Measure, Point, RFrom, Size};
@@ -247,6 +248,50 @@ pub struct LayoutInfo {
   pub size: Option<Size>,
   /// The position of the widget relative to its parent.
   pub pos: Point,
+  /// The distance from the top of the widget to its first baseline, reported
+  /// by `Render::baseline` after the widget placed its children.
+  pub baseline: Option<f32>,
+  /// The intrinsic sizes queried by the parent, cleared when the widget needs
+  /// layout.
+  pub(crate) intrinsic_sizes: SmallVec<[(IntrinsicSize, f32, f32); 2]>,
+}
+
+/// The kinds of the intrinsic size of a widget, see
+/// [`Render::min_intrinsic_width`](crate::prelude::Render::min_intrinsic_width)
+/// and its siblings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntrinsicSize {
+  /// The smallest width the widget can be without overflowing its content.
+  MinWidth,
+  /// The width the widget takes when it has unlimited width.
+  MaxWidth,
+  /// The smallest height the widget can be without overflowing its content.
+  MinHeight,
+  /// The height the widget takes when it has unlimited height.
+  MaxHeight,
+}
+
+impl IntrinsicSize {
+  /// Returns `true` if it's a size of the horizontal axis.
+  pub fn is_width(self) -> bool {
+    matches!(self, IntrinsicSize::MinWidth | IntrinsicSize::MaxWidth)
+  }
+}
+
+impl LayoutInfo {
+  /// Mark the layout result and the cached intrinsic sizes out of date.
+  pub(crate) fn mark_needs_layout(&mut self) {
+    self.size = None;
+    self.intrinsic_sizes.clear();
+  }
+
+  pub(crate) fn intrinsic_size(&self, kind: IntrinsicSize, extent: f32) -> Option<f32> {
+    self
+      .intrinsic_sizes
+      .iter()
+      .find(|(k, e, _)| *k == kind && e.to_bits() == extent.to_bits())
+      .map(|(.., size)| *size)
+  }
 }
 
 /// Store the render object's place relative to parent coordinate and the
@@ -337,6 +382,8 @@ impl std::ops::DerefMut for LayoutStore {
 
 #[cfg(test)]
 mod tests {
+  use std::{cell::Cell, rc::Rc};
+
   use super::*;
   use crate::{prelude::*, reset_test_env, test_helper::*};
 
@@ -363,6 +410,22 @@ mod tests {
     fn paint(&self, _: &mut PaintingCtx) {}
   }
 
+  /// Lays out its child, then queries the max intrinsic width of the child.
+  #[derive(Declare, SingleChild)]
+  struct IntrinsicProbe {
+    width: Rc<Cell<f32>>,
+  }
+
+  impl Render for IntrinsicProbe {
+    fn measure(&self, clamp: BoxClamp, ctx: &mut MeasureCtx) -> Size {
+      let child = ctx.assert_single_child();
+      let size = ctx.layout_child(child, clamp);
+      let width = ctx.intrinsic_size(child, IntrinsicSize::MaxWidth, f32::INFINITY);
+      self.width.set(width);
+      size
+    }
+  }
+
   #[test]
   fn fix_incorrect_relayout_root() {
     reset_test_env!();
@@ -502,6 +565,32 @@ mod tests {
     assert_eq!(*cnt.read(), 2);
   }
 
+  #[test]
+  fn needs_layout_clears_intrinsic_sizes() {
+    reset_test_env!();
+
+    let width = Rc::new(Cell::new(0.));
+    let (size, w_size) = split_value(Size::new(10., 10.));
+    let w = fn_widget! {
+      @IntrinsicProbe {
+        width: width.clone(),
+        @MockBox { size: pipe!(*$read(size)) }
+      }
+    };
+
+    let wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    assert_eq!(width.get(), 10.);
+    // The intrinsic query doesn't clobber the layout of the child.
+    let info = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(info.clamp, BoxClamp::max_size(Size::new(100., 100.)));
+    assert_eq!(info.intrinsic_sizes.len(), 1);
+
+    *w_size.write() = Size::new(20., 20.);
+    wnd.draw_frame();
+    assert_eq!(width.get(), 20.);
+  }
+
   #[test]
   fn anchor_unit_lerp_same_align() {
     // Same align type: lerp pixel and percent offsets independently
@@ -11,6 +11,7 @@ use winit::event::{ElementState, Ime};
 pub use winit::window::{CursorIcon, WindowLevel};
 
 use crate::{
+  builtin_widgets::{KeyStroke, PendingStrokes, dispatch_key_stroke},
   events::{
     dispatcher::Dispatcher,
     focus_mgr::{FocusManager, FocusType},
@@ -19,14 +20,55 @@ use crate::{
   ticker::{FrameMsg, FrameTicker},
 };
 
+mod a11y;
 mod mount;
 
+use a11y::A11yTree;
 pub use mount::MountHandle;
 use mount::MountStore;
 
 /// The attributes use to create a window.
+///
+/// Create it from the winit attributes by `WindowAttributes::from`.
 #[derive(Default)]
-pub struct WindowAttributes(pub winit::window::WindowAttributes);
+pub struct WindowAttributes(pub winit::window::WindowAttributes, CoreAttributes);
+
+impl From<winit::window::WindowAttributes> for WindowAttributes {
+  fn from(attrs: winit::window::WindowAttributes) -> Self { Self(attrs, <_>::default()) }
+}
+
+/// How a window behaves relative to the window that owns it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WindowKind {
+  /// A regular window. If it has an owner, it stays above the owner and is
+  /// closed with it.
+  #[default]
+  Normal,
+  /// A borderless window that floats above its owner without taking the
+  /// focus, used by menus and tooltips that may extend past the owner's
+  /// bounds.
+  Popup,
+  /// A window that blocks the user input of its owner until it is closed.
+  ///
+  /// The blocking is done by the framework, which drops the user input of the
+  /// owner while it has a modal window. The platform is only hinted where it
+  /// supports it, such as X11, so the owner still can be moved or closed by
+  /// the window manager.
+  Modal,
+}
+
+#[derive(Default)]
+struct CoreAttributes {
+  relation: WindowRelation,
+  #[cfg(feature = "persist")]
+  persist_key: Option<CowArc<str>>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct WindowRelation {
+  owner: Option<WindowId>,
+  kind: WindowKind,
+}
 
 fn into_winit_size(size: Size) -> winit::dpi::Size {
   winit::dpi::LogicalSize::new(size.width, size.height).into()
@@ -97,6 +139,68 @@ impl WindowAttributes {
     self
   }
 
+  /// The window that owns this window. An owned window stays above its owner
+  /// and is closed with it.
+  pub fn with_owner(&mut self, owner: WindowId) -> &mut Self {
+    self.1.relation.owner = Some(owner);
+    self
+  }
+
+  /// How the window behaves relative to its owner.
+  ///
+  /// Default: `WindowKind::Normal`
+  pub fn with_kind(&mut self, kind: WindowKind) -> &mut Self {
+    self.1.relation.kind = kind;
+    self
+  }
+
+  /// Return the window that owns this window.
+  pub fn owner(&self) -> Option<WindowId> { self.1.relation.owner }
+
+  /// Return how the window behaves relative to its owner.
+  pub fn kind(&self) -> WindowKind { self.1.relation.kind }
+
+  /// Keep the size, the position and the maximized state of the window with
+  /// `key` in the application [`Persist`], and restore them when a window
+  /// with the same key is created.
+  #[cfg(feature = "persist")]
+  pub fn with_persist(&mut self, key: impl Into<CowArc<str>>) -> &mut Self {
+    self.1.persist_key = Some(key.into());
+    self
+  }
+
+  /// Drops the initial position if the window would not be visible on any of
+  /// the `monitors`, so the platform places it instead. For example, a
+  /// restored position may be on a monitor that is disconnected now.
+  ///
+  /// The `monitors` are in logical coordinates, nothing is checked if it's
+  /// empty because the platform may not report them.
+  pub fn fit_monitors(&mut self, monitors: &[Rect]) {
+    let Some(pos) = self.0.position else { return };
+    if monitors.is_empty() {
+      return;
+    }
+    let pos = pos.to_logical::<f32>(1.);
+    let width = self
+      .0
+      .inner_size
+      .map_or(0., |s| s.to_logical::<f32>(1.).width);
+    // The top edge of the window must be reachable to move it by the title bar.
+    const GRIP: f32 = 32.;
+    let top = Rect::new(Point::new(pos.x, pos.y), Size::new(width.max(GRIP), GRIP));
+    let visible = monitors.iter().any(|m| {
+      m.intersection(&top)
+        .is_some_and(|r| r.width() >= GRIP)
+    });
+    if !visible {
+      self.0.position = None;
+    }
+  }
+
+  /// Return the key that the geometry of the window is persisted with.
+  #[cfg(feature = "persist")]
+  pub fn persist_key(&self) -> Option<&CowArc<str>> { self.1.persist_key.as_ref() }
+
   /// Window icon in RGBA8 format.
   pub fn with_icon(&mut self, icon: &PixelImage) -> &mut Self {
     debug_assert!(icon.color_format() == ColorFormat::Rgba8, "Icon must be in RGBA8 format");
@@ -158,6 +262,17 @@ pub enum UiEvent {
   CloseRequest {
     wnd_id: WindowId,
   },
+  /// An assistive technology starts to listen to the accessibility tree.
+  A11yActivated {
+    wnd_id: WindowId,
+  },
+  A11yDeactivated {
+    wnd_id: WindowId,
+  },
+  A11yAction {
+    wnd_id: WindowId,
+    request: accesskit::ActionRequest,
+  },
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -191,9 +306,27 @@ impl UiEvent {
       | UiEvent::ImePreEdit { wnd_id, .. }
       | UiEvent::ReceiveChars { wnd_id, .. }
       | UiEvent::MouseInput { wnd_id, .. }
-      | UiEvent::CloseRequest { wnd_id } => Some(*wnd_id),
+      | UiEvent::CloseRequest { wnd_id }
+      | UiEvent::A11yActivated { wnd_id }
+      | UiEvent::A11yDeactivated { wnd_id }
+      | UiEvent::A11yAction { wnd_id, .. } => Some(*wnd_id),
     }
   }
+
+  /// Whether the event is an input from the user, these events are blocked
+  /// when the window has a modal window opened.
+  pub fn is_user_input(&self) -> bool {
+    matches!(
+      self,
+      UiEvent::CursorMoved { .. }
+        | UiEvent::MouseWheel { .. }
+        | UiEvent::KeyBoard { .. }
+        | UiEvent::ImePreEdit { .. }
+        | UiEvent::ReceiveChars { .. }
+        | UiEvent::MouseInput { .. }
+        | UiEvent::A11yAction { .. }
+    )
+  }
 }
 
 /// Window is the root to represent.
@@ -211,6 +344,7 @@ pub struct Window {
   pub(crate) focus_mgr: RefCell<FocusManager>,
   pub(crate) running_animates: Rc<Cell<u32>>,
   pre_edit: RefCell<Option<String>>,
+  ime_allowed: Cell<bool>,
   /// This vector store the task to emit events. When perform layout, dispatch
   /// event and so on, some part of window may be already mutable borrowed and
   /// the user event callback may also query borrow that part, so we can't emit
@@ -223,6 +357,14 @@ pub struct Window {
   pub(crate) priority_task_queue: PriorityTaskQueue,
   shell_wnd: RefCell<BoxShellWindow>,
   mounts: RefCell<MountStore>,
+  a11y: RefCell<A11yTree>,
+  /// The keys pressed for an unfinished multi-stroke shortcut.
+  pub(crate) pending_strokes: RefCell<PendingStrokes>,
+  relation: Cell<WindowRelation>,
+  /// The windows owned by this window, in the order they were opened.
+  owned: RefCell<SmallVec<[WindowId; 1]>>,
+  #[cfg(feature = "persist")]
+  persist_key: RefCell<Option<CowArc<str>>>,
 
   flags: Cell<WindowFlags>,
 }
@@ -270,6 +412,7 @@ pub trait ShellWindow {
   fn is_resizable(&self) -> bool;
   fn set_resizable(&mut self, resizable: bool);
   fn is_minimized(&self) -> bool;
+  fn is_maximized(&self) -> bool { false }
   fn set_minimized(&mut self, minimized: bool);
   fn focus_window(&mut self);
   fn request_resize(&mut self, size: Size);
@@ -277,7 +420,11 @@ pub trait ShellWindow {
   // fn set_decorations(&mut self, decorations: bool);
   fn as_any(&self) -> &dyn Any;
   fn as_any_mut(&mut self) -> &mut dyn Any;
+  /// The position of the window, including its decorations.
   fn position(&self) -> Point;
+  /// The position of the client area of the window, the origin of the
+  /// widgets. The shells without decorations use the window position.
+  fn client_position(&self) -> Point { self.position() }
   fn set_position(&mut self, point: Point);
 
   fn close(&self);
@@ -292,6 +439,10 @@ pub trait ShellWindow {
   fn draw_commands(
     &mut self, wnd_size: Size, viewport: Rect, surface_color: Color, commands: &[PaintCommand],
   );
+
+  /// Sends the update of the accessibility tree to the platform, the shells
+  /// without an accessibility adapter ignore it.
+  fn update_a11y_tree(&mut self, _update: accesskit::TreeUpdate) {}
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -452,6 +603,10 @@ impl Window {
 
     let tree = self.tree_mut();
     let is_need_redraw = tree.is_dirty();
+    self
+      .a11y
+      .borrow_mut()
+      .mark_dirty(tree.dirty_set.borrow().keys().copied());
     tree.layout(size, &mut layout_queue);
 
     // Process layout completion events
@@ -465,6 +620,19 @@ impl Window {
     is_need_redraw
   }
 
+  /// Relayouts the whole tree, used when something every text depends on
+  /// changes, such as a font being loaded.
+  pub(crate) fn relayout_all(&self) {
+    let tree = self.tree();
+    tree
+      .dirty_marker()
+      .mark(tree.root(), DirtyPhase::LayoutSubtree);
+    self
+      .shell_wnd
+      .borrow()
+      .request_draw(RedrawDemand::Normal);
+  }
+
   pub fn update_painter_viewport(&self) {
     let size = self.shell_wnd.borrow().inner_size();
     if self.painter.borrow().viewport().size != size {
@@ -478,6 +646,55 @@ impl Window {
     }
   }
 
+  /// Starts or stops building the accessibility tree, it's driven by the
+  /// shell when an assistive technology activates or deactivates.
+  pub fn set_a11y_active(&self, active: bool) {
+    self.a11y.borrow_mut().set_active(active);
+    if active {
+      let tree = self.tree();
+      tree
+        .dirty_marker()
+        .mark(tree.root(), DirtyPhase::Paint);
+    }
+  }
+
+  pub fn is_a11y_active(&self) -> bool { self.a11y.borrow().is_active() }
+
+  /// Sends the changes of the accessibility tree to the shell, called after
+  /// the layout of a frame is done.
+  pub fn update_a11y_tree(&self) {
+    let update = self
+      .a11y
+      .borrow_mut()
+      .update(self.tree(), self.focusing());
+    if let Some(update) = update {
+      self
+        .shell_wnd
+        .borrow_mut()
+        .update_a11y_tree(update);
+    }
+  }
+
+  /// Performs the action requested by an assistive technology.
+  pub fn process_a11y_action(&self, request: accesskit::ActionRequest) {
+    let accesskit::ActionRequest { action, target, data } = request;
+    let Some(id) = self.a11y.borrow().widget_of(target) else { return };
+    if id.is_dropped(self.tree()) {
+      return;
+    }
+    match action {
+      Action::Focus => {
+        self.request_focus(id, FocusReason::Other);
+      }
+      Action::Click => self.simulate_tap(id),
+      _ => self.bubble_custom_event(id, A11yActionEvent { action, data }),
+    }
+  }
+
+  /// Emits a tap to the widget directly, so the tap reaches it even if it is
+  /// covered or scrolled out of the view.
+  fn simulate_tap(&self, id: WidgetId) { self.add_delay_event(DelayEvent::Tap(id)); }
+
   pub fn need_draw(&self) -> bool { self.tree().is_dirty() || self.running_animates.get() > 0 }
 
   pub fn new(shell_wnd: BoxShellWindow, flags: WindowFlags) -> Rc<Self> {
@@ -498,8 +715,15 @@ impl Window {
       priority_task_queue: PriorityTaskQueue::default(),
       shell_wnd: RefCell::new(shell_wnd),
       mounts: <_>::default(),
+      a11y: <_>::default(),
+      pending_strokes: <_>::default(),
+      relation: <_>::default(),
+      owned: <_>::default(),
+      #[cfg(feature = "persist")]
+      persist_key: <_>::default(),
       flags: Cell::new(flags),
       pre_edit: <_>::default(),
+      ime_allowed: Cell::new(true),
     };
 
     Rc::new(window)
@@ -507,6 +731,9 @@ impl Window {
 
   pub fn position(&self) -> Point { self.shell_wnd.borrow().position() }
 
+  /// The screen position of the client area, where the root widget starts.
+  pub fn client_position(&self) -> Point { self.shell_wnd.borrow().client_position() }
+
   pub fn set_position(&self, pos: Point) { self.shell_wnd.borrow_mut().set_position(pos); }
 
   pub fn init(&self, content: GenWidget) {
@@ -525,6 +752,40 @@ impl Window {
 
   pub fn shell_wnd(&self) -> &RefCell<BoxShellWindow> { &self.shell_wnd }
 
+  /// Return the window that owns this window.
+  pub fn owner(&self) -> Option<WindowId> { self.relation.get().owner }
+
+  /// Return how this window behaves relative to its owner.
+  pub fn kind(&self) -> WindowKind { self.relation.get().kind }
+
+  /// Return the windows owned by this window, in the order they were opened.
+  pub fn owned_windows(&self) -> SmallVec<[WindowId; 1]> { self.owned.borrow().clone() }
+
+  /// Return the latest opened modal window that blocks the input of this
+  /// window.
+  pub fn modal_window(&self) -> Option<Rc<Window>> {
+    self
+      .owned
+      .borrow()
+      .iter()
+      .rev()
+      .filter_map(|id| AppCtx::get_window(*id))
+      .find(|wnd| wnd.kind() == WindowKind::Modal)
+  }
+
+  /// Link this window to the window that owns it.
+  pub(crate) fn attach_owner(&self, owner: WindowId, kind: WindowKind) {
+    let Some(owner_wnd) = AppCtx::get_window(owner) else { return };
+    self
+      .relation
+      .set(WindowRelation { owner: Some(owner), kind });
+    owner_wnd.owned.borrow_mut().push(self.id());
+    if kind == WindowKind::Modal {
+      // The owner no longer receives the pointer, release its hover state.
+      owner_wnd.process_cursor_leave();
+    }
+  }
+
   pub fn flags(&self) -> WindowFlags { self.flags.get() }
 
   pub fn set_flags(&self, flags: WindowFlags) { self.flags.set(flags) }
@@ -687,11 +948,20 @@ impl Window {
           self.bottom_up_emit(&mut event, None);
           if let Event::KeyDown(e) = event
             && !e.is_prevent_default()
-            && *e.key() == VirtualKey::Named(NamedKey::Tab)
           {
-            self.add_delay_event(DelayEvent::TabFocusMove);
+            let modifiers = e.modifiers();
+            let is_tab = *e.key() == VirtualKey::Named(NamedKey::Tab);
+            let stroke = KeyStroke { modifiers, key: e.key().clone(), physical_key };
+            if !dispatch_key_stroke(self, id, stroke) && is_tab {
+              self.add_delay_event(DelayEvent::TabFocusMove);
+            }
           }
         }
+        DelayEvent::Shortcut { physical_key, key } => {
+          let modifiers = self.dispatcher.borrow().info.modifiers();
+          let stroke = KeyStroke { modifiers, key, physical_key };
+          dispatch_key_stroke(self, self.tree().root(), stroke);
+        }
         DelayEvent::TabFocusMove => {
           let pressed_shift = {
             let dispatcher = self.dispatcher.borrow();
@@ -859,6 +1129,24 @@ impl Window {
 
   pub fn widget_pos(&self, id: WidgetId) -> Option<Point> { self.tree().store.layout_box_pos(id) }
 
+  /// Paints the subtree of `id` and returns the size of the widget with its
+  /// paint commands, the commands are relative to the top-left of the widget.
+  /// Returns `None` if the widget is not laid out.
+  ///
+  /// The commands can be exported to other formats, such as SVG or PDF.
+  pub fn subtree_paint_commands(&self, id: WidgetId) -> Option<(Size, Vec<PaintCommand>)> {
+    let size = self.widget_size(id)?;
+    let pos = self.widget_pos(id)?;
+    // Fork to keep the default brush of the window painter.
+    let mut painter = self.painter.borrow().fork();
+    painter.set_viewport(Rect::from_size(size));
+    painter.reset();
+    painter.translate(-pos.x, -pos.y);
+    id.paint_subtree(self.tree(), &mut painter);
+    let commands = painter.finish().to_vec();
+    Some((size, commands))
+  }
+
   /// Update the position of a widget. This is used by widgets like `Follow`
   /// that need to update position after layout is complete.
   pub(crate) fn update_widget_position(&self, id: WidgetId, pos: Point) {
@@ -896,6 +1184,35 @@ impl Window {
   /// Return the current focused widget id.
   pub fn focusing(&self) -> Option<WidgetId> { self.focus_mgr.borrow().focusing() }
 
+  /// Execute the command `name` of the [`Shortcuts`] from the focused widget
+  /// to the root, return `false` if the command is not found or disabled.
+  pub fn execute_command(&self, name: &str) -> bool {
+    let target = self
+      .focusing()
+      .unwrap_or_else(|| self.tree().root());
+    crate::builtin_widgets::execute_command(self, target, name)
+  }
+
+  /// Execute the command `name` of the [`Shortcuts`] from `target` to the
+  /// root, as if `target` has the focus. The root is used if `target` is
+  /// `None` or disposed.
+  pub fn execute_command_from(&self, target: Option<WidgetId>, name: &str) -> bool {
+    crate::builtin_widgets::execute_command(self, self.command_target(target), name)
+  }
+
+  /// The key binding that triggers the command `name` when `target` has the
+  /// focus, resolved from the innermost [`Shortcuts`] like the key strokes.
+  pub fn command_binding(&self, target: Option<WidgetId>, name: &str) -> Option<KeyBinding> {
+    crate::builtin_widgets::binding_of_command(self.tree(), self.command_target(target), name)
+  }
+
+  fn command_target(&self, target: Option<WidgetId>) -> WidgetId {
+    let tree = self.tree();
+    target
+      .filter(|id| !id.is_dropped(tree))
+      .unwrap_or_else(|| tree.root())
+  }
+
   pub fn set_title(&self, title: &str) -> &Self {
     self.shell_wnd.borrow_mut().set_title(title);
     self
@@ -925,7 +1242,10 @@ impl Window {
     self
   }
 
+  /// Allow or disallow the IME of the window, the focused editor disallows it
+  /// if it only accepts the text that the IME can't compose, such as digits.
   pub fn set_ime_allowed(&self, allowed: bool) -> &Self {
+    self.ime_allowed.set(allowed);
     self
       .shell_wnd
       .borrow_mut()
@@ -957,14 +1277,17 @@ impl Window {
 
   pub fn is_pre_editing(&self) -> bool { self.pre_edit.borrow().is_some() }
 
+  pub fn is_ime_allowed(&self) -> bool { self.ime_allowed.get() }
+
   pub fn force_exit_pre_edit(&self) {
     if self.is_pre_editing() {
+      let allowed = self.is_ime_allowed();
       self.set_ime_allowed(false);
       self.dispatch_ime_pre_edit(ImePreEdit::End);
       if let Some(s) = self.pre_edit.borrow_mut().take() {
         self.process_receive_chars(s.into());
       }
-      self.set_ime_allowed(true);
+      self.set_ime_allowed(allowed);
     }
   }
 
@@ -972,12 +1295,35 @@ impl Window {
     AppCtx::send_event(event_loop::CoreMsg::CloseWindow { wnd_id: self.id() });
   }
 
+  #[cfg(feature = "persist")]
+  pub(crate) fn set_persist_key(&self, key: Option<CowArc<str>>) {
+    *self.persist_key.borrow_mut() = key;
+  }
+
+  /// Saves the geometry of the window if it was created with a persist key.
+  pub(crate) fn persist_geometry(&self) {
+    #[cfg(feature = "persist")]
+    if let Some(key) = &*self.persist_key.borrow() {
+      AppCtx::app_persist().save_window(key, self);
+    }
+  }
+
   pub(crate) fn dispose(&self) {
+    self.persist_geometry();
     self.tree_mut().disposed();
     self.run_frame_tasks();
 
-    AppCtx::windows().borrow_mut().remove(&self.id());
+    let id = self.id();
+    AppCtx::windows().borrow_mut().remove(&id);
     self.shell_wnd.borrow().close();
+
+    if let Some(owner) = self.owner().and_then(AppCtx::get_window) {
+      owner.owned.borrow_mut().retain(|w| *w != id);
+    }
+    let owned = std::mem::take(&mut *self.owned.borrow_mut());
+    for wnd in owned.into_iter().filter_map(AppCtx::get_window) {
+      wnd.dispose();
+    }
   }
 
   pub fn exit_pre_edit(&self) {
@@ -1000,7 +1346,10 @@ impl Window {
     match ime {
       Ime::Enabled => {}
       Ime::Preedit(txt, cursor) => {
-        if txt.is_empty() {
+        // The shell may still compose after the IME is disallowed, don't show the
+        // composing text to the editor. The committed text is received as chars, so
+        // the editor still formats it.
+        if txt.is_empty() || !self.is_ime_allowed() {
           self.exit_pre_edit();
         } else {
           self.update_pre_edit(&txt, &cursor);
@@ -1086,6 +1435,11 @@ pub(crate) enum DelayEvent {
     location: KeyLocation,
     state: ElementState,
   },
+  /// A key pressed when no widget has the focus.
+  Shortcut {
+    physical_key: PhysicalKey,
+    key: VirtualKey,
+  },
   TabFocusMove,
   Chars {
     id: WidgetId,
@@ -0,0 +1,200 @@
+use accesskit::{Node, NodeId, Role, Tree, TreeUpdate};
+
+use crate::{prelude::*, widget_tree::WidgetTree};
+
+/// Builds the accessibility tree of a window from its widget tree.
+///
+/// Only the root and the widgets with [`A11y`] become nodes. The text labels
+/// inside a labelled node are skipped, so its content is not announced twice.
+/// The tree is not built until an assistive technology activates it, then
+/// every frame rebuilds the semantic subtrees that contain the dirty widgets of
+/// the frame.
+#[derive(Default)]
+pub(super) struct A11yTree {
+  active: bool,
+  /// Rebuilds the whole tree in the next update.
+  full: bool,
+  dirty: ahash::HashSet<WidgetId>,
+  /// The semantic widgets of the tree by their node ids, with their semantic
+  /// parents.
+  nodes: ahash::HashMap<NodeId, (WidgetId, Option<WidgetId>)>,
+  focus: Option<NodeId>,
+}
+
+impl A11yTree {
+  pub(super) fn is_active(&self) -> bool { self.active }
+
+  pub(super) fn set_active(&mut self, active: bool) {
+    self.active = active;
+    self.full = active;
+    self.dirty.clear();
+    self.nodes.clear();
+    self.focus = None;
+  }
+
+  pub(super) fn mark_dirty(&mut self, ids: impl Iterator<Item = WidgetId>) {
+    if self.active && !self.full {
+      self.dirty.extend(ids);
+    }
+  }
+
+  pub(super) fn widget_of(&self, node: NodeId) -> Option<WidgetId> {
+    self.nodes.get(&node).map(|(wid, _)| *wid)
+  }
+
+  /// Returns the update of the tree since the last call, or `None` if nothing
+  /// changed.
+  pub(super) fn update(
+    &mut self, tree: &WidgetTree, focusing: Option<WidgetId>,
+  ) -> Option<TreeUpdate> {
+    if !self.active {
+      return None;
+    }
+
+    let root = tree.root();
+    let mut nodes = vec![];
+    let full = std::mem::take(&mut self.full);
+    if full {
+      self.build_subtree(root, semantics(root, tree), None, tree, &mut nodes);
+    } else if !self.dirty.is_empty() {
+      let mut roots: Vec<WidgetId> = vec![];
+      for id in std::mem::take(&mut self.dirty) {
+        let Some(mut s) = self.semantic_ancestor(id, tree) else { continue };
+        // A new semantic widget changes the children of its semantic parent.
+        while !self.nodes.contains_key(&node_id(s)) {
+          match s
+            .parent(tree)
+            .and_then(|p| self.semantic_ancestor(p, tree))
+          {
+            Some(p) => s = p,
+            None => break,
+          }
+        }
+        if !roots.contains(&s) {
+          roots.push(s);
+        }
+      }
+      // The subtrees inside another dirty subtree are rebuilt with it.
+      let nested = |id: WidgetId| {
+        id.ancestors(tree)
+          .skip(1)
+          .any(|p| roots.contains(&p))
+      };
+      let roots: Vec<_> = roots
+        .iter()
+        .copied()
+        .filter(|id| !nested(*id))
+        .collect();
+      for s in roots {
+        let parent = self.nodes.get(&node_id(s)).and_then(|(_, p)| *p);
+        self.build_subtree(s, semantics(s, tree), parent, tree, &mut nodes);
+      }
+      self
+        .nodes
+        .retain(|_, (wid, _)| !wid.is_dropped(tree));
+    }
+
+    let focus = focusing
+      .and_then(|id| self.semantic_ancestor(id, tree))
+      .map_or_else(|| node_id(root), node_id);
+    if nodes.is_empty() && self.focus == Some(focus) {
+      return None;
+    }
+    self.focus = Some(focus);
+    let tree = full.then(|| Tree::new(node_id(root)));
+    Some(TreeUpdate { nodes, tree, focus })
+  }
+
+  /// Returns the nearest semantic widget of `id`, including itself. If `id`
+  /// is dropped, returns the semantic parent it had.
+  fn semantic_ancestor(&self, id: WidgetId, tree: &WidgetTree) -> Option<WidgetId> {
+    if id.is_dropped(tree) {
+      return self
+        .nodes
+        .get(&node_id(id))
+        .and_then(|(_, p)| *p)
+        .filter(|p| !p.is_dropped(tree));
+    }
+    let root = tree.root();
+    id.ancestors(tree)
+      .find(|w| *w == root || semantics(*w, tree).is_some())
+  }
+
+  fn build_subtree(
+    &mut self, id: WidgetId, a11y: Option<A11y>, parent: Option<WidgetId>, tree: &WidgetTree,
+    nodes: &mut Vec<(NodeId, Node)>,
+  ) {
+    let labelled = a11y.as_ref().is_some_and(|a| a.label.is_some());
+    let mut children = vec![];
+    self.collect_children(id, id, labelled, tree, &mut children, nodes);
+
+    let mut node = match a11y {
+      Some(a11y) => a11y.to_node(),
+      None => Node::new(Role::Window),
+    };
+    if let Some(size) = tree.store.layout_box_size(id) {
+      let pos = tree.map_to_global(Point::zero(), id);
+      node.set_bounds(accesskit::Rect {
+        x0: pos.x as f64,
+        y0: pos.y as f64,
+        x1: (pos.x + size.width) as f64,
+        y1: (pos.y + size.height) as f64,
+      });
+    }
+    node.set_children(children);
+    self.nodes.insert(node_id(id), (id, parent));
+    nodes.push((node_id(id), node));
+  }
+
+  fn collect_children(
+    &mut self, semantic: WidgetId, id: WidgetId, labelled: bool, tree: &WidgetTree,
+    children: &mut Vec<NodeId>, nodes: &mut Vec<(NodeId, Node)>,
+  ) {
+    for c in id.children(tree) {
+      if let Some(a11y) = semantics(c, tree) {
+        if labelled && is_text_label(c, &a11y, tree) {
+          continue;
+        }
+        children.push(node_id(c));
+        self.build_subtree(c, Some(a11y), Some(semantic), tree, nodes);
+      } else {
+        self.collect_children(semantic, c, labelled, tree, children, nodes);
+      }
+    }
+  }
+}
+
+/// The semantics of the widget, the semantics attached to the same widget are
+/// merged, and the built-in semantics of its render come last.
+fn semantics(id: WidgetId, tree: &WidgetTree) -> Option<A11y> {
+  let attached = id
+    .query_all_iter::<A11yWidget>(tree)
+    .fold(None, |acc: Option<A11y>, w| {
+      Some(match acc {
+        Some(mut acc) => {
+          acc.merge(&w.a11y);
+          acc
+        }
+        None => w.a11y.clone(),
+      })
+    });
+  match (attached, id.assert_get(tree).a11y()) {
+    (Some(mut a11y), Some(builtin)) => {
+      a11y.merge(&builtin);
+      Some(a11y)
+    }
+    (attached, builtin) => attached.or(builtin),
+  }
+}
+
+/// Whether the semantics is only the built-in label of a text, without an
+/// `a11y` attribute.
+fn is_text_label(id: WidgetId, a11y: &A11y, tree: &WidgetTree) -> bool {
+  a11y.role == Role::Label
+    && id
+      .query_all_iter::<A11yWidget>(tree)
+      .next()
+      .is_none()
+}
+
+pub(super) fn node_id(id: WidgetId) -> NodeId { NodeId(usize::from(id.0) as u64) }
@@ -22,6 +22,22 @@ pub trait WrapRender {
     host.place_children(size, ctx)
   }
 
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    host.min_intrinsic_width(height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    host.max_intrinsic_width(height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    host.min_intrinsic_height(width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    host.max_intrinsic_height(width, ctx)
+  }
+
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) { host.paint(ctx) }
 
   fn size_affected_by_child(&self, host: &dyn Render) -> bool {
@@ -35,6 +51,12 @@ pub trait WrapRender {
 
   fn get_transform(&self, host: &dyn Render) -> Option<Transform> { host.get_transform() }
 
+  fn a11y(&self, host: &dyn Render) -> Option<A11y> { host.a11y() }
+
+  fn baseline(&self, host: &dyn Render, size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    host.baseline(size, ctx)
+  }
+
   fn dirty_phase(&self, host: &dyn Render) -> DirtyPhase { host.dirty_phase() }
 
   fn adjust_position(&self, host: &dyn Render, pos: Point, ctx: &mut PlaceCtx) -> Point {
@@ -112,6 +134,30 @@ impl Render for RenderPair {
       .place_children(size, &*self.host, ctx)
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .wrapper
+      .min_intrinsic_width(&*self.host, height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .wrapper
+      .max_intrinsic_width(&*self.host, height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .wrapper
+      .min_intrinsic_height(&*self.host, width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .wrapper
+      .max_intrinsic_height(&*self.host, width, ctx)
+  }
+
   fn paint(&self, ctx: &mut PaintingCtx) { self.wrapper.paint(&*self.host, ctx); }
 
   fn size_affected_by_child(&self) -> bool { self.wrapper.size_affected_by_child(&*self.host) }
@@ -126,6 +172,14 @@ impl Render for RenderPair {
 
   fn get_transform(&self) -> Option<Transform> { self.wrapper.get_transform(self.host.as_render()) }
 
+  fn a11y(&self) -> Option<A11y> { self.wrapper.a11y(self.host.as_render()) }
+
+  fn baseline(&self, size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    self
+      .wrapper
+      .baseline(self.host.as_render(), size, ctx)
+  }
+
   fn adjust_position(&self, pos: Point, ctx: &mut PlaceCtx) -> Point {
     self
       .wrapper
@@ -190,6 +244,22 @@ where
     self.read().place_children(size, host, ctx)
   }
 
+  fn min_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.read().min_intrinsic_width(host, height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, host: &dyn Render, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.read().max_intrinsic_width(host, height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.read().min_intrinsic_height(host, width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, host: &dyn Render, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.read().max_intrinsic_height(host, width, ctx)
+  }
+
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) { self.read().paint(host, ctx) }
 
   fn size_affected_by_child(&self, host: &dyn Render) -> bool {
@@ -204,6 +274,12 @@ where
     self.read().get_transform(host)
   }
 
+  fn a11y(&self, host: &dyn Render) -> Option<A11y> { self.read().a11y(host) }
+
+  fn baseline(&self, host: &dyn Render, size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    self.read().baseline(host, size, ctx)
+  }
+
   /// Returns the dirty phase of the wrapped render, this value should
   /// always be the same.
   fn wrapper_dirty_phase(&self) -> DirtyPhase { self.read().wrapper_dirty_phase() }
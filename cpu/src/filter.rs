@@ -0,0 +1,232 @@
+//! Applies `FilterLayer`s to the painted pixels, following the same sampling
+//! rules as the filter shader of the GPU backend.
+
+use ribir_painter::{BlendMode, ColorMatrix, FilterComposite, FilterLayer, FilterOp};
+use ribir_types::DeviceRect;
+use tiny_skia::{Mask, Pixmap};
+
+/// A rectangular area of premultiplied pixels with float channels.
+#[derive(Clone)]
+struct Region {
+  width: i32,
+  height: i32,
+  pixels: Vec<[f32; 4]>,
+}
+
+/// Applies `layers` on the `area` of `pixmap`. The pixels outside `mask` keep
+/// their color.
+pub(crate) fn apply_filters(
+  pixmap: &mut Pixmap, area: &DeviceRect, mask: &Mask, layers: &[FilterLayer],
+) {
+  let identity = FilterOp::Color(ColorMatrix::identity().to_matrix());
+  let mut input = Region::read(pixmap, area);
+  for layer in layers {
+    let mut ops: Vec<_> = layer.ops.iter().map(|op| (op, [0.; 2])).collect();
+    // A layer without operations can still shift the content.
+    if ops.is_empty() && layer.offset != [0., 0.] {
+      ops.push((&identity, [0.; 2]));
+    }
+    let Some(last) = ops.last_mut() else { continue };
+    last.1 = layer.offset;
+
+    let mut output = input.clone();
+    for (op, offset) in ops {
+      output = apply_op(&output, op, offset, mask, area);
+    }
+    if layer.composite == FilterComposite::ExcludeSource {
+      output.draw_source_over(&input);
+    }
+    if layer.blend_mode != BlendMode::Normal {
+      output.blend_with(&input, layer.blend_mode, mask, area);
+    }
+    input = output;
+  }
+  input.write(pixmap, area);
+}
+
+fn apply_op(
+  src: &Region, op: &FilterOp, offset: [f32; 2], mask: &Mask, area: &DeviceRect,
+) -> Region {
+  let (kernel_w, kernel_h, kernel, matrix, base) = match op {
+    FilterOp::Color(m) => {
+      let base = m
+        .base_color
+        .map_or([0.; 4], |c| c.into_f32_components());
+      (1, 1, &[1.][..], m.matrix, base)
+    }
+    FilterOp::Convolution(m) => {
+      let identity = ColorMatrix::identity().to_matrix().matrix;
+      (m.width as i32, m.height as i32, &m.matrix[..], identity, [0.; 4])
+    }
+  };
+  let (radius_x, radius_y) = (kernel_w >> 1, kernel_h >> 1);
+  let (dx, dy) = (offset[0].round() as i32, offset[1].round() as i32);
+  let mask_width = mask.width() as i32;
+
+  let mut dst = src.clone();
+  for y in 0..src.height {
+    for x in 0..src.width {
+      let mask_idx = (area.min_y() + y) * mask_width + area.min_x() + x;
+      if mask.data()[mask_idx as usize] < 128 {
+        // Out of the filter area, keep the origin color.
+        continue;
+      }
+
+      let mut sum = [0.; 4];
+      for j in 0..kernel_h {
+        for i in 0..kernel_w {
+          let weight = kernel[(j * kernel_w + i) as usize];
+          let color = src.get(x - dx + i - radius_x, y - dy + j - radius_y);
+          sum
+            .iter_mut()
+            .zip(color)
+            .for_each(|(s, c)| *s += c * weight);
+        }
+      }
+
+      let mut color = [0.; 4];
+      for (row, c) in color.iter_mut().enumerate() {
+        let weights = &matrix[row * 4..row * 4 + 4];
+        let v: f32 = weights.iter().zip(sum).map(|(w, s)| w * s).sum();
+        *c = (v + base[row]).clamp(0., 1.);
+      }
+      // Keep the color a valid premultiplied one.
+      let alpha = color[3];
+      color[..3]
+        .iter_mut()
+        .for_each(|c| *c = c.min(alpha));
+      dst.pixels[(y * src.width + x) as usize] = color;
+    }
+  }
+  dst
+}
+
+impl Region {
+  fn read(pixmap: &Pixmap, area: &DeviceRect) -> Self {
+    let stride = pixmap.width() as i32;
+    let data = pixmap.data();
+    let mut pixels = Vec::with_capacity((area.width() * area.height()) as usize);
+    for y in area.min_y()..area.max_y() {
+      for x in area.min_x()..area.max_x() {
+        let idx = ((y * stride + x) * 4) as usize;
+        pixels.push([0, 1, 2, 3].map(|i| data[idx + i] as f32 / 255.));
+      }
+    }
+    Self { width: area.width(), height: area.height(), pixels }
+  }
+
+  fn write(&self, pixmap: &mut Pixmap, area: &DeviceRect) {
+    let stride = pixmap.width() as i32;
+    let data = pixmap.data_mut();
+    for y in 0..self.height {
+      for x in 0..self.width {
+        let idx = (((area.min_y() + y) * stride + area.min_x() + x) * 4) as usize;
+        let color = self.pixels[(y * self.width + x) as usize];
+        data[idx..idx + 4]
+          .iter_mut()
+          .zip(color)
+          .for_each(|(b, c)| *b = (c * 255.).round() as u8);
+      }
+    }
+  }
+
+  /// The pixel at `(x, y)`, the position is clamped to the region.
+  fn get(&self, x: i32, y: i32) -> [f32; 4] {
+    let x = x.clamp(0, self.width - 1);
+    let y = y.clamp(0, self.height - 1);
+    self.pixels[(y * self.width + x) as usize]
+  }
+
+  /// Blends this region as the source with `backdrop` in `mode`, and draws the
+  /// blended color over `backdrop`. The pixels outside `mask` keep the color
+  /// of `backdrop`.
+  fn blend_with(&mut self, backdrop: &Region, mode: BlendMode, mask: &Mask, area: &DeviceRect) {
+    let mask_width = mask.width() as i32;
+    for y in 0..self.height {
+      for x in 0..self.width {
+        let idx = (y * self.width + x) as usize;
+        let b = backdrop.pixels[idx];
+        let mask_idx = (area.min_y() + y) * mask_width + area.min_x() + x;
+        if mask.data()[mask_idx as usize] < 128 {
+          self.pixels[idx] = b;
+          continue;
+        }
+
+        let s = self.pixels[idx];
+        let unpremultiply = |c: [f32; 4]| {
+          if c[3] > 0. { [c[0] / c[3], c[1] / c[3], c[2] / c[3]] } else { [0.; 3] }
+        };
+        let (cs, cb) = (unpremultiply(s), unpremultiply(b));
+        let mixed = blend(mode, cb, cs);
+        let (sa, ba) = (s[3], b[3]);
+        let mut color = [0.; 4];
+        for i in 0..3 {
+          let c = cs[i] * (1. - ba) + mixed[i].clamp(0., 1.) * ba;
+          color[i] = c * sa + b[i] * (1. - sa);
+        }
+        color[3] = sa + ba * (1. - sa);
+        self.pixels[idx] = color;
+      }
+    }
+  }
+
+  /// Draws `source` over this region.
+  fn draw_source_over(&mut self, source: &Region) {
+    for (dst, src) in self.pixels.iter_mut().zip(&source.pixels) {
+      let inv = 1. - src[3];
+      dst
+        .iter_mut()
+        .zip(src)
+        .for_each(|(d, s)| *d = s + *d * inv);
+    }
+  }
+}
+
+/// The separable blend functions of the W3C Compositing and Blending spec, the
+/// same as the texture shader of the GPU backend.
+fn blend(mode: BlendMode, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+  fn hard_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 { cb * 2. * cs } else { 2. * cs - 1. + cb - cb * (2. * cs - 1.) }
+  }
+  fn color_dodge(cb: f32, cs: f32) -> f32 {
+    if cb == 0. {
+      0.
+    } else if cs >= 1. {
+      1.
+    } else {
+      (cb / (1. - cs)).min(1.)
+    }
+  }
+  fn color_burn(cb: f32, cs: f32) -> f32 {
+    if cb >= 1. {
+      1.
+    } else if cs <= 0. {
+      0.
+    } else {
+      1. - ((1. - cb) / cs).min(1.)
+    }
+  }
+  fn soft_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 {
+      return cb - (1. - 2. * cs) * cb * (1. - cb);
+    }
+    let d = if cb <= 0.25 { ((16. * cb - 12.) * cb + 4.) * cb } else { cb.sqrt() };
+    cb + (2. * cs - 1.) * (d - cb)
+  }
+
+  let f: fn(f32, f32) -> f32 = match mode {
+    BlendMode::Normal => |_, cs| cs,
+    BlendMode::Multiply => |cb, cs| cb * cs,
+    BlendMode::Screen => |cb, cs| cb + cs - cb * cs,
+    BlendMode::Overlay => |cb, cs| hard_light(cs, cb),
+    BlendMode::Darken => f32::min,
+    BlendMode::Lighten => f32::max,
+    BlendMode::ColorDodge => color_dodge,
+    BlendMode::ColorBurn => color_burn,
+    BlendMode::HardLight => hard_light,
+    BlendMode::SoftLight => soft_light,
+    BlendMode::Difference => |cb, cs| (cb - cs).abs(),
+    BlendMode::Exclusion => |cb, cs| cb + cs - 2. * cb * cs,
+  };
+  [0, 1, 2].map(|i| f(cb[i], cs[i]))
+}
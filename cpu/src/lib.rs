@@ -0,0 +1,717 @@
+//! A pure CPU implementation of the [`PainterBackend`].
+//!
+//! `CpuBackend` rasterizes the same `PaintCommand` stream as the GPU backend
+//! into a [`CpuTexture`], so a frame can be rendered without any GPU or
+//! graphics driver, e.g. in CI, for server-side thumbnails or on virtual
+//! machines.
+
+use std::borrow::Cow;
+
+use ahash::HashMap;
+use ribir_algo::Resource;
+use ribir_painter::{
+  Brush, Color, ColorFormat, ColorMatrix, CommandBrush, FaceId, FilterLayer, GlyphId,
+  GlyphRasterSource, PaintCommand, PaintPath, PaintPathAction, PainterBackend, PaintingStyle, Path,
+  PathCommand, PixelImage, RasterBitmapFormat, Svg, TextCommand,
+};
+use ribir_types::{DeviceRect, DeviceSize, Point, Rect, Transform, transform_to_device_rect};
+use tiny_skia::{FillRule, FilterQuality, Mask, Paint, Pixmap, PixmapPaint, Shader};
+
+mod filter;
+mod raster;
+use raster::*;
+
+type RasterImageGlyphs = HashMap<(FaceId, u16, u16), Option<(Resource<PixelImage>, Point)>>;
+
+/// A `PainterBackend` that draws the paint commands by the CPU.
+#[derive(Default)]
+pub struct CpuBackend {
+  surface: Option<Color>,
+  raster_image_glyphs: RasterImageGlyphs,
+  svg_glyphs: HashMap<(FaceId, u16), Option<Svg>>,
+}
+
+/// The texture a `CpuBackend` draws on, the pixels are stored as premultiplied
+/// RGBA.
+pub struct CpuTexture(Pixmap);
+
+/// The drawing target of a `draw_commands` call.
+struct Canvas<'a> {
+  pixmap: &'a mut Pixmap,
+  viewport: DeviceRect,
+  /// The stack of the clip masks, the first one is the viewport. A `None`
+  /// means nothing is visible in the clip area.
+  clips: Vec<Option<Mask>>,
+}
+
+impl PainterBackend for CpuBackend {
+  type Texture = CpuTexture;
+
+  fn begin_frame(&mut self, surface: Color) { self.surface = Some(surface); }
+
+  fn draw_commands(
+    &mut self, viewport: DeviceRect, commands: &[PaintCommand], global_matrix: &Transform,
+    output: &mut Self::Texture, glyph_provider: &dyn GlyphRasterSource,
+  ) {
+    if let Some(surface) = self.surface.take() {
+      output.0.fill(skia_color(surface));
+    }
+    if let Some(mut canvas) = Canvas::new(&mut output.0, viewport) {
+      self.draw_commands_on(&mut canvas, commands, global_matrix, glyph_provider);
+    }
+  }
+
+  fn end_frame(&mut self) { self.surface = None; }
+}
+
+impl CpuBackend {
+  fn draw_commands_on(
+    &mut self, canvas: &mut Canvas, commands: &[PaintCommand], global_matrix: &Transform,
+    glyph_provider: &dyn GlyphRasterSource,
+  ) {
+    for cmd in commands {
+      self.draw_command(canvas, cmd, global_matrix, glyph_provider);
+    }
+  }
+
+  fn draw_command(
+    &mut self, canvas: &mut Canvas, cmd: &PaintCommand, global_matrix: &Transform,
+    glyph_provider: &dyn GlyphRasterSource,
+  ) {
+    match cmd {
+      PaintCommand::Path(PathCommand { path, paint_bounds, transform, action }) => {
+        let bounds = transform_to_device_rect(paint_bounds, global_matrix);
+        let area = canvas.viewport.intersection(&bounds);
+        let matrix = transform.then(global_matrix);
+        match action {
+          PaintPathAction::Clip => {
+            let mask = area
+              .and_then(|_| canvas.clip().cloned())
+              .and_then(|mut mask| intersect_path(&mut mask, path, &matrix).map(|_| mask));
+            canvas.clips.push(mask);
+          }
+          PaintPathAction::Paint { brush, painting_style } => {
+            if let Some(area) = area {
+              paint_path(canvas, path, &matrix, brush, painting_style, &area);
+            }
+          }
+        }
+      }
+      PaintCommand::PopClip => {
+        // The viewport clip is never popped.
+        if canvas.clips.len() > 1 {
+          canvas.clips.pop();
+        }
+      }
+      PaintCommand::Bundle { transform, color_filter, blend_mode, mask, bounds, cmds } => {
+        let matrix = transform.then(global_matrix);
+        let Some(area) = canvas
+          .viewport
+          .intersection(&transform_to_device_rect(bounds, &matrix))
+        else {
+          return;
+        };
+        if canvas.clip().is_none() {
+          return;
+        }
+        let Some(mut layer) = Pixmap::new(area.width() as u32, area.height() as u32) else {
+          return;
+        };
+
+        // Draw the bundle in a layer, so the color filter applies to the bundle as a
+        // whole.
+        let to_layer = Transform::translation(-area.min_x() as f32, -area.min_y() as f32);
+        let matrix = matrix.then(&to_layer);
+        if let Some(mut layer_canvas) = Canvas::new(&mut layer, DeviceRect::from_size(area.size)) {
+          self.draw_commands_on(&mut layer_canvas, cmds, &matrix, glyph_provider);
+        }
+        if let Some(mask) = mask {
+          let Some(mut mask_layer) = Pixmap::new(area.width() as u32, area.height() as u32) else {
+            return;
+          };
+          let mask_area = DeviceRect::from_size(area.size);
+          if let Some(mut mask_canvas) = Canvas::new(&mut mask_layer, mask_area) {
+            self.draw_commands_on(&mut mask_canvas, &mask.cmds, &matrix, glyph_provider);
+          }
+          mask_pixels(&mut layer, &mask_layer, mask.mode);
+        }
+        let opacity = match color_filter {
+          ColorMatrix::Opacity(opacity) => *opacity,
+          filter => {
+            filter_pixels(&mut layer, filter);
+            1.
+          }
+        };
+        let blend_mode = skia_blend_mode(*blend_mode);
+        let paint = PixmapPaint { opacity, blend_mode, ..Default::default() };
+        let identity = tiny_skia::Transform::identity();
+        canvas.pixmap.draw_pixmap(
+          area.min_x(),
+          area.min_y(),
+          layer.as_ref(),
+          &paint,
+          identity,
+          canvas.clips.last().unwrap().as_ref(),
+        );
+      }
+      PaintCommand::Filter { path, transform, filter_bounds, filters } => {
+        let bounds = transform_to_device_rect(filter_bounds, global_matrix);
+        let Some(area) = canvas.viewport.intersection(&bounds) else {
+          return;
+        };
+        let Some(mut mask) = canvas.clip().cloned() else {
+          return;
+        };
+        if intersect_path(&mut mask, path, &transform.then(global_matrix)).is_none() {
+          return;
+        }
+        let layers: Vec<_> = filters
+          .iter()
+          .map(|f| FilterLayer {
+            ops: f.ops.clone(),
+            offset: global_matrix
+              .transform_vector(f.offset.into())
+              .into(),
+            composite: f.composite,
+            blend_mode: f.blend_mode,
+          })
+          .collect();
+        filter::apply_filters(canvas.pixmap, &area, &mask, &layers);
+      }
+      PaintCommand::Text(text_cmd) => {
+        if canvas.clip().is_some() {
+          self.draw_text_command(canvas, text_cmd, global_matrix, glyph_provider);
+        }
+      }
+    }
+  }
+
+  fn draw_text_command(
+    &mut self, canvas: &mut Canvas, text_cmd: &TextCommand, global_matrix: &Transform,
+    glyph_provider: &dyn GlyphRasterSource,
+  ) {
+    let text_matrix = text_cmd
+      .payload
+      .transform
+      .then(&text_cmd.transform)
+      .then(global_matrix);
+    let run_brush = |brush: &Option<Brush>| {
+      brush
+        .clone()
+        .map(|brush| {
+          let mut brush = CommandBrush::from(brush);
+          brush.apply_color_filter(&text_cmd.color_filter);
+          brush
+        })
+        .unwrap_or_else(|| text_cmd.default_brush.clone())
+    };
+
+    for background in text_cmd.payload.backgrounds.iter() {
+      let path = if background.radius > 0. {
+        Path::rect_round(&background.rect, &ribir_painter::Radius::all(background.radius))
+      } else {
+        Path::rect(&background.rect)
+      };
+      let brush = run_brush(&Some(background.brush.clone()));
+      self.draw_text_path(canvas, path, &background.rect, brush, &text_matrix, glyph_provider);
+    }
+
+    for run in text_cmd.payload.runs.iter() {
+      let brush = run_brush(&run.brush);
+      let offset = text_cmd.payload.origin_offset;
+      for glyph in run.glyphs.iter() {
+        let baseline_origin = glyph.baseline_origin + offset;
+        let glyph_matrix = run
+          .glyph_transform(glyph, offset)
+          .then(&text_matrix);
+        self.draw_glyph(
+          canvas,
+          glyph_provider,
+          run.face_id,
+          glyph.glyph_id,
+          run.logical_font_size,
+          baseline_origin,
+          &brush,
+          &glyph_matrix,
+        );
+      }
+    }
+
+    for decoration in text_cmd.payload.decorations.iter() {
+      let brush = run_brush(&decoration.brush);
+      let path = Path::rect(&decoration.rect);
+      self.draw_text_path(canvas, path, &decoration.rect, brush, &text_matrix, glyph_provider);
+    }
+  }
+
+  fn draw_text_path(
+    &mut self, canvas: &mut Canvas, path: Path, rect: &Rect, brush: CommandBrush,
+    text_matrix: &Transform, glyph_provider: &dyn GlyphRasterSource,
+  ) {
+    let cmd = PaintCommand::Path(PathCommand {
+      path: PaintPath::Own(path),
+      paint_bounds: text_matrix.outer_transformed_rect(rect),
+      transform: *text_matrix,
+      action: PaintPathAction::Paint { brush, painting_style: PaintingStyle::Fill },
+    });
+    self.draw_command(canvas, &cmd, &Transform::identity(), glyph_provider);
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn draw_glyph(
+    &mut self, canvas: &mut Canvas, glyph_provider: &dyn GlyphRasterSource, face_id: FaceId,
+    glyph_id: GlyphId, font_size: f32, baseline_origin: Point, brush: &CommandBrush,
+    text_matrix: &Transform,
+  ) {
+    let Transform { m11, m12, m21, m22, .. } = *text_matrix;
+    let scale = m11.hypot(m12).max(m21.hypot(m22));
+    let physical_size = (font_size * scale).ceil().max(1.) as u16;
+
+    if let Some(svg) = self.glyph_svg_image(glyph_provider, face_id, glyph_id) {
+      let Some(metrics) = glyph_provider.face_metrics(face_id) else {
+        return;
+      };
+      let unit = metrics.units_per_em as f32;
+      let grid_scale = metrics
+        .vertical_height
+        .map(|h| h / unit)
+        .unwrap_or(1.)
+        .max(1.);
+      let s = font_size / svg.size().height.max(1.) / grid_scale;
+      let svg_matrix =
+        Transform::new(s, 0., 0., s, baseline_origin.x, baseline_origin.y - font_size)
+          .then(text_matrix);
+      let brush = Brush::from(Color::BLACK);
+      for cmd in svg.commands(&brush, &brush).iter() {
+        let cmd = match cmd.clone() {
+          PaintCommand::Path(mut path) => {
+            path.transform(&svg_matrix);
+            PaintCommand::Path(path)
+          }
+          other => other,
+        };
+        self.draw_command(canvas, &cmd, &Transform::identity(), glyph_provider);
+      }
+      return;
+    }
+
+    let Some((image, offset)) =
+      self.glyph_raster_image(glyph_provider, face_id, glyph_id, physical_size)
+    else {
+      return;
+    };
+    let s = font_size / physical_size as f32;
+    let glyph_matrix = Transform::new(
+      s,
+      0.,
+      0.,
+      s,
+      baseline_origin.x + offset.x * s,
+      baseline_origin.y + offset.y * s,
+    )
+    .then(text_matrix);
+
+    if image.color_format() == ColorFormat::Rgba8 {
+      let Some(pixmap) = image_pixmap(&image, &ColorMatrix::identity()) else {
+        return;
+      };
+      let Canvas { pixmap: target, clips, .. } = canvas;
+      let Some(clip) = clips.last().and_then(Option::as_ref) else { return };
+      let paint = PixmapPaint { quality: FilterQuality::Bilinear, ..Default::default() };
+      target.draw_pixmap(0, 0, pixmap.as_ref(), &paint, skia_transform(&glyph_matrix), Some(clip));
+    } else {
+      let path = PaintPath::PixelImage(image);
+      let bounds = path.bounds(None);
+      let cmd = PaintCommand::Path(PathCommand {
+        path,
+        paint_bounds: glyph_matrix.outer_transformed_rect(&bounds),
+        transform: glyph_matrix,
+        action: PaintPathAction::Paint {
+          brush: brush.clone(),
+          painting_style: PaintingStyle::Fill,
+        },
+      });
+      self.draw_command(canvas, &cmd, &Transform::identity(), glyph_provider);
+    }
+  }
+
+  fn glyph_raster_image(
+    &mut self, glyph_provider: &dyn GlyphRasterSource, face_id: FaceId, glyph_id: GlyphId,
+    img_size: u16,
+  ) -> Option<(Resource<PixelImage>, Point)> {
+    let key = (face_id, glyph_id.0, img_size);
+    self
+      .raster_image_glyphs
+      .entry(key)
+      .or_insert_with(|| {
+        glyph_provider
+          .raster_bitmap(face_id, glyph_id, img_size)
+          .map(|bitmap| {
+            let format = match bitmap.format {
+              RasterBitmapFormat::Rgba8 => ColorFormat::Rgba8,
+              RasterBitmapFormat::Alpha8 => ColorFormat::Alpha8,
+            };
+            let image =
+              PixelImage::new(Cow::Owned(bitmap.data), bitmap.width, bitmap.height, format);
+            (Resource::new(image), bitmap.placement)
+          })
+      })
+      .clone()
+  }
+
+  fn glyph_svg_image(
+    &mut self, glyph_provider: &dyn GlyphRasterSource, face_id: FaceId, glyph_id: GlyphId,
+  ) -> Option<Svg> {
+    let key = (face_id, glyph_id.0);
+    self
+      .svg_glyphs
+      .entry(key)
+      .or_insert_with(|| {
+        glyph_provider
+          .raster_svg(face_id, glyph_id)
+          .and_then(|svg| Svg::parse_from_bytes(svg.as_bytes(), true, false).ok())
+      })
+      .clone()
+  }
+}
+
+/// Paints `path` with `brush` in the `area` of the canvas.
+fn paint_path(
+  canvas: &mut Canvas, path: &PaintPath, matrix: &Transform, brush: &CommandBrush,
+  style: &PaintingStyle, area: &DeviceRect,
+) {
+  let Canvas { pixmap, clips, .. } = canvas;
+  let Some(clip) = clips.last().and_then(Option::as_ref) else {
+    return;
+  };
+
+  let path = match path {
+    PaintPath::Share(path) => &**path,
+    PaintPath::Own(path) => path,
+    PaintPath::PixelImage(img) => {
+      // Fill the area with the brush only where the image covers.
+      let Some(mut layer) = Pixmap::new(area.width() as u32, area.height() as u32) else {
+        return;
+      };
+      let Some(coverage) = image_pixmap(img, &ColorMatrix::identity()) else {
+        return;
+      };
+      let to_layer = Transform::translation(-area.min_x() as f32, -area.min_y() as f32);
+      let matrix = matrix.then(&to_layer);
+      let paint = PixmapPaint { quality: FilterQuality::Bilinear, ..Default::default() };
+      layer.draw_pixmap(0, 0, coverage.as_ref(), &paint, skia_transform(&matrix), None);
+
+      let rect = tiny_skia::Rect::from_xywh(0., 0., area.width() as f32, area.height() as f32);
+      let layer_area = DeviceRect::from_size(area.size);
+      with_shader(brush, &matrix, &layer_area, |shader| {
+        let paint =
+          Paint { shader, blend_mode: tiny_skia::BlendMode::SourceIn, ..Default::default() };
+        if let Some(rect) = rect {
+          layer.fill_rect(rect, &paint, tiny_skia::Transform::identity(), None);
+        }
+      });
+      let identity = tiny_skia::Transform::identity();
+      let paint = PixmapPaint::default();
+      pixmap.draw_pixmap(area.min_x(), area.min_y(), layer.as_ref(), &paint, identity, Some(clip));
+      return;
+    }
+  };
+
+  let Some(path) = skia_path(path) else { return };
+  let ts = skia_transform(matrix);
+  with_shader(brush, matrix, area, |shader: Shader| {
+    let paint = Paint { shader, anti_alias: true, ..Default::default() };
+    match style {
+      PaintingStyle::Fill => pixmap.fill_path(&path, &paint, FillRule::Winding, ts, Some(clip)),
+      PaintingStyle::Stroke(options) => {
+        pixmap.stroke_path(&path, &paint, &skia_stroke(options), ts, Some(clip))
+      }
+    }
+  });
+}
+
+impl<'a> Canvas<'a> {
+  fn new(pixmap: &'a mut Pixmap, viewport: DeviceRect) -> Option<Self> {
+    let size = DeviceSize::new(pixmap.width() as i32, pixmap.height() as i32);
+    let viewport = DeviceRect::from_size(size).intersection(&viewport)?;
+    let rect = tiny_skia::Rect::from_xywh(
+      viewport.min_x() as f32,
+      viewport.min_y() as f32,
+      viewport.width() as f32,
+      viewport.height() as f32,
+    )?;
+    let mut mask = Mask::new(pixmap.width(), pixmap.height())?;
+    let path = tiny_skia::PathBuilder::from_rect(rect);
+    mask.fill_path(&path, FillRule::Winding, false, tiny_skia::Transform::identity());
+    Some(Self { pixmap, viewport, clips: vec![Some(mask)] })
+  }
+
+  /// The current clip mask, or `None` if nothing is visible.
+  fn clip(&self) -> Option<&Mask> { self.clips.last().and_then(Option::as_ref) }
+}
+
+impl CpuTexture {
+  /// Creates a transparent texture, an empty size is treated as one pixel.
+  pub fn new(size: DeviceSize) -> Self {
+    let width = size.width.max(1) as u32;
+    let height = size.height.max(1) as u32;
+    Self(Pixmap::new(width, height).expect("The texture size is too large."))
+  }
+
+  pub fn size(&self) -> DeviceSize {
+    DeviceSize::new(self.0.width() as i32, self.0.height() as i32)
+  }
+
+  /// The premultiplied RGBA bytes of the texture, row by row.
+  pub fn premultiplied_bytes(&self) -> &[u8] { self.0.data() }
+
+  /// Copies the `rect` area of the texture as an image.
+  pub fn copy_as_image(&self, rect: &DeviceRect) -> PixelImage {
+    let stride = self.0.width() as i32;
+    let data = self.0.data();
+    let mut bytes = Vec::with_capacity((rect.width() * rect.height() * 4) as usize);
+    for y in rect.min_y()..rect.max_y() {
+      for x in rect.min_x()..rect.max_x() {
+        let idx = ((y * stride + x) * 4) as usize;
+        let color = unpremultiply(&data[idx..idx + 4]);
+        bytes.extend_from_slice(&color.into_components());
+      }
+    }
+    PixelImage::new(
+      Cow::Owned(bytes),
+      rect.width() as u32,
+      rect.height() as u32,
+      ColorFormat::Rgba8,
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::prelude::*;
+
+  use super::*;
+
+  fn render(mut painter: Painter) -> PixelImage {
+    let viewport = painter.viewport().to_i32().cast_unit();
+    let mut backend = CpuBackend::default();
+    let mut texture = CpuTexture::new(viewport.size);
+    let glyph_provider = AppCtx::text_services().raster_source();
+    backend.begin_frame(Color::WHITE);
+    backend.draw_commands(
+      viewport,
+      &painter.finish(),
+      &Transform::identity(),
+      &mut texture,
+      glyph_provider.as_ref().as_ref(),
+    );
+    backend.end_frame();
+    texture.copy_as_image(&viewport)
+  }
+
+  fn pixel(img: &PixelImage, x: u32, y: u32) -> [u8; 4] {
+    let idx = ((y * img.width() + x) * 4) as usize;
+    img.pixel_bytes()[idx..idx + 4]
+      .try_into()
+      .unwrap()
+  }
+
+  #[test]
+  fn fill_and_clip() {
+    reset_test_env!();
+
+    let mut painter = Painter::new(Rect::from_size(Size::new(40., 40.)));
+    painter
+      .clip(Path::rect(&Rect::from_size(Size::new(20., 40.))).into())
+      .set_fill_brush(Color::RED)
+      .rect(&Rect::from_size(Size::new(40., 40.)), true)
+      .fill();
+    let img = render(painter);
+
+    assert_eq!(pixel(&img, 10, 10), [255, 0, 0, 255]);
+    assert_eq!(pixel(&img, 30, 10), [255, 255, 255, 255]);
+  }
+
+  #[test]
+  fn apply_alpha() {
+    reset_test_env!();
+
+    let mut painter = Painter::new(Rect::from_size(Size::new(20., 20.)));
+    painter
+      .apply_alpha(0.5)
+      .set_fill_brush(Color::BLACK)
+      .rect(&Rect::from_size(Size::new(20., 20.)), true)
+      .fill();
+    let img = render(painter);
+
+    let [r, g, b, a] = pixel(&img, 10, 10);
+    assert_eq!(a, 255);
+    assert!((r as i32 - 128).abs() <= 1 && r == g && g == b);
+  }
+
+  #[test]
+  fn blur_filter_spreads_color() {
+    reset_test_env!();
+
+    let mut painter = Painter::new(Rect::from_size(Size::new(40., 40.)));
+    let area = Path::rect(&Rect::from_size(Size::new(40., 40.)));
+    painter
+      .set_fill_brush(Color::BLACK)
+      .rect(&Rect::new(Point::new(10., 10.), Size::new(20., 20.)), true)
+      .fill()
+      .filter_path(area.into(), Filter::blur(4.));
+    let img = render(painter);
+
+    let [r, ..] = pixel(&img, 8, 20);
+    assert!(r > 0 && r < 255);
+  }
+
+  #[test]
+  fn multiply_blend() {
+    reset_test_env!();
+
+    let mut painter = Painter::new(Rect::from_size(Size::new(40., 20.)));
+    painter
+      .set_fill_brush(Color::from_rgb(255, 0, 0))
+      .rect(&Rect::from_size(Size::new(20., 20.)), true)
+      .fill()
+      .save()
+      .set_blend_mode(BlendMode::Multiply)
+      .set_fill_brush(Color::from_rgb(255, 255, 0))
+      .rect(&Rect::from_size(Size::new(40., 20.)), true)
+      .fill()
+      .restore();
+    let img = render(painter);
+
+    // Yellow multiplies red to red, and keeps its color over the white.
+    assert_eq!(pixel(&img, 10, 10), [255, 0, 0, 255]);
+    assert_eq!(pixel(&img, 30, 10), [255, 255, 0, 255]);
+  }
+
+  #[test]
+  fn filter_layer_blend() {
+    reset_test_env!();
+
+    let mut painter = Painter::new(Rect::from_size(Size::new(40., 20.)));
+    let area = Path::rect(&Rect::from_size(Size::new(40., 20.)));
+    // Fills the filter area with opaque yellow.
+    let yellow = ribir_painter::color::ColorFilterMatrix {
+      matrix: [0.; 16],
+      base_color: Some(Color::from_rgb(255, 255, 0)),
+    };
+    painter
+      .set_fill_brush(Color::from_rgb(255, 0, 0))
+      .rect(&Rect::from_size(Size::new(20., 20.)), true)
+      .fill()
+      .filter_path(area.into(), Filter::color(yellow).blend_mode(BlendMode::Multiply));
+    let img = render(painter);
+
+    // The yellow result multiplies the content instead of replacing it.
+    assert_eq!(pixel(&img, 10, 10), [255, 0, 0, 255]);
+    assert_eq!(pixel(&img, 30, 10), [255, 255, 0, 255]);
+  }
+
+  #[test]
+  fn luminance_mask() {
+    reset_test_env!();
+
+    let bounds = Rect::from_size(Size::new(40., 20.));
+    let mut mask = Painter::new(bounds);
+    mask
+      .set_fill_brush(Color::WHITE)
+      .rect(&Rect::from_size(Size::new(20., 20.)), true)
+      .fill()
+      .set_fill_brush(Color::BLACK)
+      .rect(&Rect::new(Point::new(20., 0.), Size::new(20., 20.)), true)
+      .fill();
+    let mask = Resource::new(mask.finish().to_vec().into_boxed_slice());
+
+    let mut painter = Painter::new(bounds);
+    painter
+      .save()
+      .set_mask(MaskMode::Luminance, mask)
+      .set_fill_brush(Color::from_rgb(255, 0, 0))
+      .rect(&bounds, true)
+      .fill()
+      .restore();
+    let img = render(painter);
+
+    // The white of the mask shows the content, and the black hides it.
+    assert_eq!(pixel(&img, 10, 10), [255, 0, 0, 255]);
+    assert_eq!(pixel(&img, 30, 10), [255, 255, 255, 255]);
+  }
+
+  #[test]
+  fn sweep_gradient() {
+    reset_test_env!();
+
+    let stops = vec![
+      GradientStop::new(Color::RED, 0.),
+      GradientStop::new(Color::RED, 0.5),
+      GradientStop::new(Color::BLUE, 0.5),
+      GradientStop::new(Color::BLUE, 1.),
+    ];
+    let mut painter = Painter::new(Rect::from_size(Size::new(40., 40.)));
+    painter
+      .set_fill_brush(SweepGradient::new(Point::new(20., 20.), stops))
+      .rect(&Rect::from_size(Size::new(40., 40.)), true)
+      .fill();
+    let img = render(painter);
+
+    // The colors sweep clockwise from the right.
+    assert_eq!(pixel(&img, 30, 25), [255, 0, 0, 255]);
+    assert_eq!(pixel(&img, 20, 10), [0, 0, 255, 255]);
+  }
+
+  #[test]
+  fn two_point_radial_gradient() {
+    reset_test_env!();
+
+    let stops = vec![
+      GradientStop::new(Color::RED, 0.),
+      GradientStop::new(Color::RED, 0.3),
+      GradientStop::new(Color::BLUE, 0.3),
+      GradientStop::new(Color::BLUE, 1.),
+    ];
+    let center = Point::new(20., 20.);
+    let radial = ribir_painter::color::RadialGradient {
+      start_center: center,
+      start_radius: 10.,
+      end_center: center,
+      end_radius: 20.,
+      stops,
+      spread_method: SpreadMethod::Pad,
+    };
+    let mut painter = Painter::new(Rect::from_size(Size::new(40., 40.)));
+    painter
+      .set_fill_brush(Resource::new(radial))
+      .rect(&Rect::from_size(Size::new(40., 40.)), true)
+      .fill();
+    let img = render(painter);
+
+    // The offsets count from the start circle, the inside of it pads to red.
+    assert_eq!(pixel(&img, 28, 20), [255, 0, 0, 255]);
+    assert_eq!(pixel(&img, 35, 20), [0, 0, 255, 255]);
+  }
+
+  #[test]
+  fn pattern_tiles() {
+    reset_test_env!();
+
+    let data = vec![255, 0, 0, 255, 0, 0, 255, 255];
+    let img = Resource::new(PixelImage::new(data.into(), 2, 1, ColorFormat::Rgba8));
+    let pattern = ImagePattern::new(img).with_tile(TileMode::Repeat, TileMode::NoRepeat);
+    let mut painter = Painter::new(Rect::from_size(Size::new(10., 4.)));
+    painter
+      .set_fill_brush(pattern)
+      .rect(&Rect::from_size(Size::new(10., 4.)), true)
+      .fill();
+    let img = render(painter);
+
+    assert_eq!(pixel(&img, 0, 0), [255, 0, 0, 255]);
+    assert_eq!(pixel(&img, 1, 0), [0, 0, 255, 255]);
+    assert_eq!(pixel(&img, 2, 0), [255, 0, 0, 255]);
+    // Not repeat vertically, the background shows.
+    assert_eq!(pixel(&img, 2, 2), [255, 255, 255, 255]);
+  }
+}
@@ -0,0 +1,287 @@
+//! Conversions between the painter types and the `tiny-skia` rasterizer.
+
+use ribir_painter::{
+  BlendMode, Color, ColorFormat, ColorMatrix, CommandBrush, GradientStop, ImagePattern, LineCap,
+  LineJoin, MaskMode, PaintPath, Path, PathSegment, PixelImage, SpreadMethod, StrokeOptions,
+};
+use ribir_types::{DeviceRect, Point, Transform};
+use tiny_skia::{
+  FillRule, FilterQuality, IntSize, LinearGradient, Mask, MaskType, Pattern, Pixmap, PixmapPaint,
+  RadialGradient, Shader, SpreadMode,
+};
+
+pub(crate) fn skia_transform(t: &Transform) -> tiny_skia::Transform {
+  tiny_skia::Transform::from_row(t.m11, t.m12, t.m21, t.m22, t.m31, t.m32)
+}
+
+pub(crate) fn skia_color(c: Color) -> tiny_skia::Color {
+  tiny_skia::Color::from_rgba8(c.red, c.green, c.blue, c.alpha)
+}
+
+pub(crate) fn skia_path(path: &Path) -> Option<tiny_skia::Path> {
+  let mut builder = tiny_skia::PathBuilder::new();
+  for seg in path.segments() {
+    match seg {
+      PathSegment::MoveTo(p) => builder.move_to(p.x, p.y),
+      PathSegment::LineTo(p) => builder.line_to(p.x, p.y),
+      PathSegment::QuadTo { ctrl, to } => builder.quad_to(ctrl.x, ctrl.y, to.x, to.y),
+      PathSegment::CubicTo { to, ctrl1, ctrl2 } => {
+        builder.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y)
+      }
+      PathSegment::Close(true) => builder.close(),
+      PathSegment::Close(false) => {}
+    }
+  }
+  builder.finish()
+}
+
+pub(crate) fn skia_stroke(options: &StrokeOptions) -> tiny_skia::Stroke {
+  let line_cap = match options.line_cap {
+    LineCap::Butt => tiny_skia::LineCap::Butt,
+    LineCap::Round => tiny_skia::LineCap::Round,
+    LineCap::Square => tiny_skia::LineCap::Square,
+  };
+  let line_join = match options.line_join {
+    LineJoin::Miter => tiny_skia::LineJoin::Miter,
+    LineJoin::MiterClip => tiny_skia::LineJoin::MiterClip,
+    LineJoin::Round => tiny_skia::LineJoin::Round,
+    LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
+  };
+  // `tiny-skia` requires an even dash pattern.
+  let dash = options
+    .dash
+    .as_ref()
+    .and_then(|(pattern, offset)| {
+      let repeat = if pattern.len().is_multiple_of(2) { 1 } else { 2 };
+      let pattern = pattern
+        .iter()
+        .copied()
+        .cycle()
+        .take(pattern.len() * repeat);
+      tiny_skia::StrokeDash::new(pattern.collect(), *offset)
+    });
+  tiny_skia::Stroke {
+    width: options.width,
+    miter_limit: options.miter_limit,
+    line_cap,
+    line_join,
+    dash,
+  }
+}
+
+pub(crate) fn skia_blend_mode(mode: BlendMode) -> tiny_skia::BlendMode {
+  match mode {
+    BlendMode::Normal => tiny_skia::BlendMode::SourceOver,
+    BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+    BlendMode::Screen => tiny_skia::BlendMode::Screen,
+    BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
+    BlendMode::Darken => tiny_skia::BlendMode::Darken,
+    BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+    BlendMode::ColorDodge => tiny_skia::BlendMode::ColorDodge,
+    BlendMode::ColorBurn => tiny_skia::BlendMode::ColorBurn,
+    BlendMode::HardLight => tiny_skia::BlendMode::HardLight,
+    BlendMode::SoftLight => tiny_skia::BlendMode::SoftLight,
+    BlendMode::Difference => tiny_skia::BlendMode::Difference,
+    BlendMode::Exclusion => tiny_skia::BlendMode::Exclusion,
+  }
+}
+
+pub(crate) fn premultiply(c: Color) -> [u8; 4] {
+  let a = c.alpha as u16;
+  let mul = |v: u8| ((v as u16 * a + 127) / 255) as u8;
+  [mul(c.red), mul(c.green), mul(c.blue), c.alpha]
+}
+
+pub(crate) fn unpremultiply(px: &[u8]) -> Color {
+  let a = px[3] as u16;
+  if a == 0 {
+    return Color::TRANSPARENT;
+  }
+  let div = |v: u8| ((v as u16 * 255 + a / 2) / a).min(255) as u8;
+  Color::new(div(px[0]), div(px[1]), div(px[2]), px[3])
+}
+
+/// Converts an image to a premultiplied pixmap, an alpha image becomes a white
+/// image with the same coverage.
+pub(crate) fn image_pixmap(img: &PixelImage, color_filter: &ColorMatrix) -> Option<Pixmap> {
+  let filter = |c: Color| match color_filter {
+    ColorMatrix::Opacity(a) if *a == 1. => c,
+    filter => filter.apply_to(&c),
+  };
+  let data = match img.color_format() {
+    ColorFormat::Rgba8 => img
+      .pixel_bytes()
+      .chunks_exact(4)
+      .flat_map(|px| premultiply(filter(Color::new(px[0], px[1], px[2], px[3]))))
+      .collect(),
+    ColorFormat::Alpha8 => img
+      .pixel_bytes()
+      .iter()
+      .flat_map(|a| premultiply(filter(Color::new(255, 255, 255, *a))))
+      .collect(),
+  };
+  Pixmap::from_vec(data, IntSize::from_wh(img.width(), img.height())?)
+}
+
+/// Applies a color matrix to every pixel of a premultiplied pixmap.
+pub(crate) fn filter_pixels(pixmap: &mut Pixmap, color_filter: &ColorMatrix) {
+  for px in pixmap.data_mut().chunks_exact_mut(4) {
+    let color = color_filter.apply_to(&unpremultiply(px));
+    px.copy_from_slice(&premultiply(color));
+  }
+}
+
+/// Multiplies the pixels of `layer` by the coverage of `mask` in `mode`, they
+/// have the same size.
+pub(crate) fn mask_pixels(layer: &mut Pixmap, mask: &Pixmap, mode: MaskMode) {
+  let mask_type = match mode {
+    MaskMode::Luminance => MaskType::Luminance,
+    MaskMode::Alpha => MaskType::Alpha,
+  };
+  let coverage = Mask::from_pixmap(mask.as_ref(), mask_type);
+  for (px, c) in layer
+    .data_mut()
+    .chunks_exact_mut(4)
+    .zip(coverage.data())
+  {
+    px.iter_mut()
+      .for_each(|v| *v = ((*v as u16 * *c as u16 + 127) / 255) as u8);
+  }
+}
+
+/// Rasterizes the alpha of `img` placed by `matrix` to a mask of the size
+/// `width` x `height`.
+pub(crate) fn image_mask(
+  img: &PixelImage, matrix: &Transform, width: u32, height: u32,
+) -> Option<Mask> {
+  let mut layer = Pixmap::new(width, height)?;
+  let img = image_pixmap(img, &ColorMatrix::identity())?;
+  let paint = PixmapPaint { quality: FilterQuality::Bilinear, ..Default::default() };
+  layer.draw_pixmap(0, 0, img.as_ref(), &paint, skia_transform(matrix), None);
+  Some(Mask::from_pixmap(layer.as_ref(), MaskType::Alpha))
+}
+
+/// Shrinks `mask` to its intersection with the area of `path`.
+pub(crate) fn intersect_path(mask: &mut Mask, path: &PaintPath, matrix: &Transform) -> Option<()> {
+  let path = match path {
+    PaintPath::Share(path) => &**path,
+    PaintPath::Own(path) => path,
+    PaintPath::PixelImage(img) => {
+      let coverage = image_mask(img, matrix, mask.width(), mask.height())?;
+      mask
+        .data_mut()
+        .iter_mut()
+        .zip(coverage.data())
+        .for_each(|(dst, src)| *dst = ((*dst as u16 * *src as u16 + 127) / 255) as u8);
+      return Some(());
+    }
+  };
+  mask.intersect_path(&skia_path(path)?, FillRule::Winding, true, skia_transform(matrix));
+  Some(())
+}
+
+/// Calls `f` with the shader of `brush`, where `matrix` maps the brush space to
+/// the device space. Returns `None` if the brush paints nothing.
+///
+/// The brushes `tiny-skia` not supports are shaded pixel by pixel, only the
+/// `area` of the device is shaded for them.
+pub(crate) fn with_shader<R>(
+  brush: &CommandBrush, matrix: &Transform, area: &DeviceRect, f: impl FnOnce(Shader) -> R,
+) -> Option<R> {
+  let ts = skia_transform(matrix);
+  match brush {
+    CommandBrush::Color(color) => Some(f(Shader::SolidColor(skia_color(*color)))),
+    CommandBrush::Image { img, color_filter } => {
+      let pixmap = image_pixmap(img, color_filter)?;
+      let quality = FilterQuality::Bilinear;
+      Some(f(Pattern::new(pixmap.as_ref(), SpreadMode::Repeat, quality, 1., ts)))
+    }
+    CommandBrush::Linear(linear) => LinearGradient::new(
+      skia_point(linear.start),
+      skia_point(linear.end),
+      gradient_stops(&linear.stops),
+      spread_mode(linear.spread_method),
+      ts,
+    )
+    .map(f),
+    // `tiny-skia` only supports a focal point without radius as the start
+    // circle, a start circle with radius is shaded pixel by pixel.
+    CommandBrush::Radial(radial) if radial.start_radius > 0. => {
+      let to_brush = matrix.inverse()?;
+      let pixmap = shade_area(area, |p| radial.color_at(to_brush.transform_point(p)))?;
+      Some(f(area_pattern(&pixmap, area)))
+    }
+    CommandBrush::Radial(radial) => RadialGradient::new(
+      skia_point(radial.start_center),
+      skia_point(radial.end_center),
+      radial.end_radius,
+      gradient_stops(&radial.stops),
+      spread_mode(radial.spread_method),
+      ts,
+    )
+    .map(f),
+    CommandBrush::Sweep(sweep) => {
+      let to_brush = matrix.inverse()?;
+      let pixmap = shade_area(area, |p| Some(sweep.color_at(to_brush.transform_point(p))))?;
+      Some(f(area_pattern(&pixmap, area)))
+    }
+    CommandBrush::Pattern { pattern, color_filter } => {
+      let img = image_pixmap(&pattern.image, color_filter)?;
+      let to_img = matrix
+        .inverse()?
+        .then(&pattern.transform.inverse()?);
+      let (w, h) = (img.width() as f32, img.height() as f32);
+      let pixmap = shade_area(area, |p| {
+        let p = to_img.transform_point(p);
+        let x = ImagePattern::tile(pattern.tile_x, p.x, w)?;
+        let y = ImagePattern::tile(pattern.tile_y, p.y, h)?;
+        // Nearest sampling, the edge of a tile maps to the last pixel.
+        let x = (x as u32).min(img.width() - 1);
+        let y = (y as u32).min(img.height() - 1);
+        img.pixel(x, y).map(|c| {
+          let c = c.demultiply();
+          Color::new(c.red(), c.green(), c.blue(), c.alpha())
+        })
+      })?;
+      Some(f(area_pattern(&pixmap, area)))
+    }
+  }
+}
+
+/// Shades every pixel of `area` by its center, `None` leaves the pixel
+/// transparent.
+fn shade_area(area: &DeviceRect, shade: impl Fn(Point) -> Option<Color>) -> Option<Pixmap> {
+  let mut pixmap = Pixmap::new(area.width() as u32, area.height() as u32)?;
+  let width = area.width();
+  for (idx, px) in pixmap.data_mut().chunks_exact_mut(4).enumerate() {
+    let (x, y) = (idx as i32 % width, idx as i32 / width);
+    let p = Point::new((area.min_x() + x) as f32 + 0.5, (area.min_y() + y) as f32 + 0.5);
+    if let Some(color) = shade(p) {
+      px.copy_from_slice(&premultiply(color));
+    }
+  }
+  Some(pixmap)
+}
+
+/// A shader draws the pixels shaded by `shade_area` back to the `area`.
+fn area_pattern<'a>(pixmap: &'a Pixmap, area: &DeviceRect) -> Shader<'a> {
+  let ts = tiny_skia::Transform::from_translate(area.min_x() as f32, area.min_y() as f32);
+  Pattern::new(pixmap.as_ref(), SpreadMode::Pad, FilterQuality::Nearest, 1., ts)
+}
+
+fn skia_point(p: ribir_types::Point) -> tiny_skia::Point { tiny_skia::Point::from_xy(p.x, p.y) }
+
+fn gradient_stops(stops: &[GradientStop]) -> Vec<tiny_skia::GradientStop> {
+  stops
+    .iter()
+    .map(|s| tiny_skia::GradientStop::new(s.offset, skia_color(s.color)))
+    .collect()
+}
+
+fn spread_mode(spread: SpreadMethod) -> SpreadMode {
+  match spread {
+    SpreadMethod::Pad => SpreadMode::Pad,
+    SpreadMethod::Reflect => SpreadMode::Reflect,
+    SpreadMethod::Repeat => SpreadMode::Repeat,
+  }
+}
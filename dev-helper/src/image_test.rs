@@ -17,6 +17,10 @@ use ribir_types::Transform;
 /// - the `{function  name}` is the function you pass to the macro.
 /// - the `{fmt}` is the file format the backend wants to check.
 ///
+/// With the `cpu-backend` feature, a test prefixed by `cpu` is generated too,
+/// it renders the painter by the CPU backend and compares it to the same image
+/// file with a looser tolerance.
+///
 /// You can run the test with `RIBIR_IMG_TEST=overwrite` to overwrite the image
 /// file, for example ```
 /// RIBIR_IMG_TEST=overwrite cargo test -- smoke
@@ -31,7 +35,7 @@ macro_rules! painter_backend_eq_image_test {
         let mut painter = $painter_fn();
         let viewport = painter.viewport().to_i32().cast_unit();
         let glyph_provider = AppCtx::text_services().raster_source();
-        let img = wgpu_render_commands(
+        let img = $crate::wgpu_render_commands(
           &painter.finish(),
           viewport,
           Color::TRANSPARENT,
@@ -43,10 +47,47 @@ macro_rules! painter_backend_eq_image_test {
           $(.with_comparison($comparison))?
           .test();
       }
+
+      $crate::cpu_backend_test! {
+        #[test]
+        fn [<cpu_ $painter_fn>]() {
+          ribir_core::reset_test_env!();
+          let mut painter = $painter_fn();
+          let viewport = painter.viewport().to_i32().cast_unit();
+          let glyph_provider = AppCtx::text_services().raster_source();
+          let img = $crate::cpu_render_commands(
+            &painter.finish(),
+            viewport,
+            Color::TRANSPARENT,
+            glyph_provider.as_ref().as_ref(),
+          );
+          let name = format!("{}_wgpu", std::stringify!($painter_fn));
+          let file_path = test_case_name!(name, "webp");
+          ImageTest::new(img, &file_path)
+            $(.with_comparison($comparison))?
+            .by_cpu()
+            .test();
+        }
+      }
     }
   };
 }
 
+/// Keeps the items only if the `cpu-backend` feature of this crate is enabled.
+#[cfg(feature = "cpu-backend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! cpu_backend_test {
+  ($($item:item)*) => { $($item)* };
+}
+
+#[cfg(not(feature = "cpu-backend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! cpu_backend_test {
+  ($($item:item)*) => {};
+}
+
 #[macro_export]
 macro_rules! test_case_name {
   ($name:expr, $format:literal) => {{
@@ -59,17 +100,31 @@ macro_rules! test_case_name {
   }};
 }
 
+#[cfg(feature = "cpu-backend")]
+const CPU_BACKEND_COMPARISON: f64 = 0.005;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub struct ImageTest<'a> {
   test_img: PixelImage,
   ref_path: &'a std::path::Path,
   comparison: f64,
+  by_cpu: bool,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl<'a> ImageTest<'a> {
   pub fn new(test_img: PixelImage, ref_path: &'a std::path::Path) -> Self {
-    Self { test_img, ref_path, comparison: 0.000025 }
+    Self { test_img, ref_path, comparison: 0.000025, by_cpu: false }
+  }
+
+  /// The image is rendered by the CPU backend. The reference images are
+  /// rendered by the GPU backend, so the comparison is looser for the
+  /// different anti-aliasing, and the reference is never overwritten.
+  #[cfg(feature = "cpu-backend")]
+  pub fn by_cpu(mut self) -> Self {
+    self.comparison = self.comparison.max(CPU_BACKEND_COMPARISON);
+    self.by_cpu = true;
+    self
   }
 
   pub fn with_comparison(mut self, comparison: f64) -> Self {
@@ -79,12 +134,15 @@ impl<'a> ImageTest<'a> {
 
   #[track_caller]
   pub fn test(self) {
-    let Self { test_img, ref_path, comparison } = self;
+    let Self { test_img, ref_path, comparison, by_cpu } = self;
 
     let overwrite = std::ffi::OsStr::new("overwrite");
     let dir = ref_path.parent().unwrap();
     let stem = ref_path.file_stem().unwrap().to_str().unwrap();
     if std::env::var_os("RIBIR_IMG_TEST").is_some_and(|var| var == overwrite) {
+      if by_cpu {
+        return;
+      }
       std::fs::create_dir_all(dir).unwrap();
       Self::write_webp(&test_img, ref_path);
     } else {
@@ -114,8 +172,9 @@ impl<'a> ImageTest<'a> {
       let (v, mut diffs) = dssim.compare(&d_ref, d_test);
       let dssim: f64 = v.into();
 
-      let diff_path = dir.join(format!("{stem}_diff.webp"));
-      let actual_path = dir.join(format!("{stem}_actual.webp"));
+      let backend = if by_cpu { "_cpu" } else { "" };
+      let diff_path = dir.join(format!("{stem}{backend}_diff.webp"));
+      let actual_path = dir.join(format!("{stem}{backend}_actual.webp"));
       if dssim > f64::EPSILON {
         Self::write_webp(&test_img, &actual_path);
         Self::write_ssim_maps(diffs.pop().unwrap(), &diff_path);
@@ -175,6 +234,25 @@ pub fn assert_texture_eq_png(test_img: PixelImage, ref_path: &std::path::Path) {
   ImageTest::new(test_img, ref_path).test();
 }
 
+/// Render painter by cpu backend, and return the image.
+#[cfg(feature = "cpu-backend")]
+pub fn cpu_render_commands(
+  commands: &[ribir_painter::PaintCommand], viewport: ribir_types::DeviceRect,
+  surface: ribir_painter::Color, glyph_provider: &dyn GlyphRasterSource,
+) -> PixelImage {
+  use ribir_cpu::{CpuBackend, CpuTexture};
+  use ribir_painter::PainterBackend;
+  use ribir_types::{DeviceRect, DeviceSize};
+
+  let rect = DeviceRect::from_size(DeviceSize::new(viewport.max_x() + 2, viewport.max_y() + 2));
+  let mut backend = CpuBackend::default();
+  let mut texture = CpuTexture::new(rect.size);
+  backend.begin_frame(surface);
+  backend.draw_commands(rect, commands, &Transform::identity(), &mut texture, glyph_provider);
+  backend.end_frame();
+  texture.copy_as_image(&rect)
+}
+
 /// Render painter by wgpu backend, and return the image.
 pub fn wgpu_render_commands(
   commands: &[ribir_painter::PaintCommand], viewport: ribir_types::DeviceRect,
@@ -70,6 +70,28 @@ macro_rules! widget_image_tests {
         let name = format!("{}_with_material_by_wgpu", std::stringify!($name));
         $crate::assert_widget_eq_image!($widget_tester, name);
       }
+
+      $crate::cpu_backend_test! {
+        #[test]
+        fn [<$name _with_default_by_cpu>]() {
+          let _scope = unsafe { AppCtx::new_lock_scope() };
+          svg::svg_registry::clear();
+          unsafe { AppCtx::set_app_theme(ribir_slim::purple()) };
+
+          let name = format!("{}_with_default_by_wgpu", std::stringify!($name));
+          $crate::assert_widget_eq_image!($widget_tester, name, by_cpu);
+        }
+
+        #[test]
+        fn [<$name _with_material_by_cpu>]() {
+          let _scope = unsafe { AppCtx::new_lock_scope() };
+          svg::svg_registry::clear();
+          unsafe { AppCtx::set_app_theme(ribir_material::purple::light()) };
+
+          let name = format!("{}_with_material_by_wgpu", std::stringify!($name));
+          $crate::assert_widget_eq_image!($widget_tester, name, by_cpu);
+        }
+      }
     }
   };
 }
@@ -77,6 +99,17 @@ macro_rules! widget_image_tests {
 #[macro_export]
 macro_rules! assert_widget_eq_image {
   ($widget_tester:expr, $name:expr) => {
+    $crate::assert_widget_eq_image!($widget_tester, $name, wgpu_render_commands, |t| t);
+  };
+  ($widget_tester:expr, $name:expr,by_cpu) => {
+    $crate::assert_widget_eq_image!(
+      $widget_tester,
+      $name,
+      cpu_render_commands,
+      |t: $crate::ImageTest<'_>| t.by_cpu()
+    );
+  };
+  ($widget_tester:expr, $name:expr, $render:ident, $backend:expr) => {
     let img_path = $crate::test_case_name!($name, "webp");
 
     let mut wnd = $widget_tester.create_wnd();
@@ -87,10 +120,9 @@ macro_rules! assert_widget_eq_image {
 
     let font_db = AppCtx::text_services();
     let glyph_provider = font_db.raster_source();
-    let img =
-      $crate::wgpu_render_commands(&commands, viewport, surface, glyph_provider.as_ref().as_ref());
+    let img = $crate::$render(&commands, viewport, surface, glyph_provider.as_ref().as_ref());
 
-    let mut img_test = $crate::ImageTest::new(img, &img_path);
+    let mut img_test = ($backend)($crate::ImageTest::new(img, &img_path));
     if let Some(c) = $widget_tester.comparison {
       img_test = img_test.with_comparison(c);
     }
@@ -26,10 +26,7 @@ fn section_card(title: &'static str, background: Color, content: GenWidget) -> G
       padding: EdgeInsets::new(20., 40., 20., 40.),
       background,
       radius: Radius::all(4.),
-      border: Border::all(BorderSide {
-        color: palette.primary().into(),
-        width: 1.,
-      }),
+      border: Border::all(BorderSide::new(1., palette.primary().into())),
       @ { content.clone() }
     }
   }
@@ -0,0 +1,378 @@
+//! Exports the paint commands to vector documents.
+//!
+//! The exporters read the same `PaintCommand` stream as the painter backends,
+//! e.g. the commands of a widget subtree from
+//! `Window::subtree_paint_commands`. Paths, gradients, clips and images keep
+//! their vector form, and text is exported as the outlines of its glyphs, so
+//! the document not depends on the fonts of its reader.
+//!
+//! Some effects have no vector form and are approximated:
+//! - the filters are skipped, the content below them is exported unfiltered.
+//! - the sweep gradients are exported as images.
+//! - PDF only: the transparency of the gradient stops is dropped, and the color
+//!   filter and the blend mode of a bundle apply to its commands one by one.
+
+use std::borrow::Cow;
+
+use ribir_algo::Resource;
+use ribir_painter::{
+  Color, ColorFormat, ColorMatrix, CommandBrush, PaintPath, Path, PixelImage, SweepGradient,
+  TileMode,
+};
+use ribir_types::{Point, Rect, Size, Transform};
+
+mod pdf;
+mod svg;
+mod text;
+
+pub use pdf::{PdfOptions, to_pdf};
+pub use svg::to_svg;
+
+/// The tile step of a `TileMode::NoRepeat` axis, large enough to never show
+/// a second tile.
+const NO_REPEAT_STEP: f32 = 1e5;
+
+/// The maximum pixels of a side of a rasterized sweep gradient.
+const MAX_SWEEP_SIZE: f32 = 1024.;
+
+/// An image that fills a path, `img_to_brush` places the image pixels in the
+/// brush space.
+struct ImagePaint {
+  rgba: Vec<u8>,
+  width: u32,
+  height: u32,
+  img_to_brush: Transform,
+  tile_x: TileMode,
+  tile_y: TileMode,
+}
+
+impl ImagePaint {
+  fn new(
+    img: &PixelImage, color_filter: &ColorMatrix, img_to_brush: Transform, tile_x: TileMode,
+    tile_y: TileMode,
+  ) -> Self {
+    let rgba = rgba_pixels(img, color_filter);
+    Self { rgba, width: img.width(), height: img.height(), img_to_brush, tile_x, tile_y }
+  }
+
+  /// Rasterizes the sweep gradient in `bounds` of the brush space, `scale` is
+  /// the pixels per brush unit.
+  fn sweep(sweep: &SweepGradient, bounds: &Rect, scale: f32) -> Option<Self> {
+    let scale = scale.min(MAX_SWEEP_SIZE / bounds.width().max(bounds.height()));
+    let width = (bounds.width() * scale).ceil() as u32;
+    let height = (bounds.height() * scale).ceil() as u32;
+    if width == 0 || height == 0 {
+      return None;
+    }
+    let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+      for x in 0..width {
+        let p = Point::new(
+          bounds.min_x() + (x as f32 + 0.5) / scale,
+          bounds.min_y() + (y as f32 + 0.5) / scale,
+        );
+        let c = sweep.color_at(p);
+        rgba.extend([c.red, c.green, c.blue, c.alpha]);
+      }
+    }
+    let img_to_brush =
+      Transform::scale(1. / scale, 1. / scale).then_translate(bounds.origin.to_vector());
+    Some(Self {
+      rgba,
+      width,
+      height,
+      img_to_brush,
+      tile_x: TileMode::NoRepeat,
+      tile_y: TileMode::NoRepeat,
+    })
+  }
+
+  /// The size of a tile along an axis, a mirrored tile contains two images.
+  fn tile_step(mode: TileMode, len: u32) -> f32 {
+    match mode {
+      TileMode::Repeat => len as f32,
+      TileMode::Mirror => 2. * len as f32,
+      TileMode::NoRepeat => NO_REPEAT_STEP.max(len as f32),
+    }
+  }
+
+  /// The images of a tile, every item is the offset of the image in the tile
+  /// and whether it flips horizontally and vertically.
+  fn tile_images(&self) -> Vec<(f32, f32, bool, bool)> {
+    let xs: &[bool] = if self.tile_x == TileMode::Mirror { &[false, true] } else { &[false] };
+    let ys: &[bool] = if self.tile_y == TileMode::Mirror { &[false, true] } else { &[false] };
+    let (w, h) = (self.width as f32, self.height as f32);
+    let mut images = vec![];
+    for &flip_y in ys {
+      for &flip_x in xs {
+        let x = if flip_x { w } else { 0. };
+        let y = if flip_y { h } else { 0. };
+        images.push((x, y, flip_x, flip_y));
+      }
+    }
+    images
+  }
+}
+
+/// The path and the brush to paint `path` with `brush`. An image path paints
+/// its rectangle, tinted by the color of a color brush where the image covers.
+fn resolve_path<'a>(path: &'a PaintPath, brush: &CommandBrush) -> (Cow<'a, Path>, CommandBrush) {
+  match path {
+    PaintPath::Share(path) => (Cow::Borrowed(&**path), brush.clone()),
+    PaintPath::Own(path) => (Cow::Borrowed(path), brush.clone()),
+    PaintPath::PixelImage(img) => {
+      let rect = Rect::from_size(Size::new(img.width() as f32, img.height() as f32));
+      let img = match brush {
+        CommandBrush::Color(c) => {
+          let alpha: Box<dyn Iterator<Item = u8>> = match img.color_format() {
+            ColorFormat::Rgba8 => Box::new(img.pixel_bytes().chunks_exact(4).map(|px| px[3])),
+            ColorFormat::Alpha8 => Box::new(img.pixel_bytes().iter().copied()),
+          };
+          let data = alpha
+            .flat_map(|a| [c.red, c.green, c.blue, (c.alpha as u16 * a as u16 / 255) as u8])
+            .collect::<Vec<_>>();
+          let img =
+            PixelImage::new(Cow::Owned(data), img.width(), img.height(), ColorFormat::Rgba8);
+          Resource::new(img)
+        }
+        // Not a vector effect, paints the image itself.
+        _ => img.clone(),
+      };
+      let brush = CommandBrush::Image { img, color_filter: ColorMatrix::identity() };
+      (Cow::Owned(Path::rect(&rect)), brush)
+    }
+  }
+}
+
+/// The non-premultiplied RGBA pixels of `img` after the color filter, an alpha
+/// image becomes a white image with the same coverage.
+fn rgba_pixels(img: &PixelImage, color_filter: &ColorMatrix) -> Vec<u8> {
+  let filter = |c: Color| match color_filter {
+    ColorMatrix::Opacity(a) if *a == 1. => c,
+    filter => filter.apply_to(&c),
+  };
+  let colors: Box<dyn Iterator<Item = Color>> = match img.color_format() {
+    ColorFormat::Rgba8 => Box::new(
+      img
+        .pixel_bytes()
+        .chunks_exact(4)
+        .map(|px| Color::new(px[0], px[1], px[2], px[3])),
+    ),
+    ColorFormat::Alpha8 => Box::new(
+      img
+        .pixel_bytes()
+        .iter()
+        .map(|a| Color::new(255, 255, 255, *a)),
+    ),
+  };
+  colors
+    .map(filter)
+    .flat_map(|c| [c.red, c.green, c.blue, c.alpha])
+    .collect()
+}
+
+/// The scale a transform applies to the lengths, the larger one of the axes.
+fn transform_scale(t: &Transform) -> f32 { t.m11.hypot(t.m12).max(t.m21.hypot(t.m22)) }
+
+fn png(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
+  use image::{ExtendedColorType, ImageEncoder, codecs::png::PngEncoder};
+
+  let mut bytes = vec![];
+  PngEncoder::new(&mut bytes)
+    .write_image(rgba, width, height, ExtendedColorType::Rgba8)
+    .expect("encoding a PNG to memory never fails");
+  bytes
+}
+
+fn base64(bytes: &[u8]) -> String {
+  const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+  for chunk in bytes.chunks(3) {
+    let n = chunk
+      .iter()
+      .enumerate()
+      .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - i * 8));
+    for i in 0..4 {
+      if i <= chunk.len() {
+        out.push(TABLE[(n >> (18 - i * 6)) as usize & 63] as char);
+      } else {
+        out.push('=');
+      }
+    }
+  }
+  out
+}
+
+/// Formats a number without the trailing zeros.
+fn num(v: f32) -> String {
+  let v = if v.is_finite() { v } else { 0. };
+  let s = format!("{v:.4}");
+  let s = s.trim_end_matches('0').trim_end_matches('.');
+  if s == "-0" { "0".to_string() } else { s.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::prelude::*;
+
+  use super::*;
+
+  fn linear_gradient_painter(size: Size) -> Painter {
+    let mut painter = Painter::new(Rect::from_size(size));
+    let gradient = color::LinearGradient {
+      start: Point::zero(),
+      end: Point::new(size.width, 0.),
+      stops: vec![GradientStop::new(Color::RED, 0.), GradientStop::new(Color::BLUE, 1.)],
+      spread_method: SpreadMethod::Pad,
+    };
+    painter
+      .clip(Path::rect(&Rect::from_size(Size::new(size.width / 2., size.height))).into())
+      .set_fill_brush(gradient)
+      .rect(&Rect::from_size(size), true)
+      .fill();
+    painter
+  }
+
+  #[test]
+  fn export_svg() {
+    reset_test_env!();
+
+    let size = Size::new(40., 40.);
+    let mut painter = linear_gradient_painter(size);
+    let glyphs = AppCtx::text_services().raster_source();
+    let svg = to_svg(&painter.finish(), size, glyphs.as_ref().as_ref());
+
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<clipPath").count(), 1);
+    assert_eq!(svg.matches("<linearGradient").count(), 1);
+    assert_eq!(svg.matches("<stop ").count(), 2);
+    // The clip path and the filled rect.
+    assert_eq!(svg.matches("<path d=\"M0 0").count(), 2);
+    assert!(svg.ends_with("</svg>\n"));
+  }
+
+  #[test]
+  fn export_pdf_pages() {
+    reset_test_env!();
+
+    let size = Size::new(100., 2000.);
+    let mut painter = linear_gradient_painter(size);
+    let glyphs = AppCtx::text_services().raster_source();
+    let pdf = to_pdf(&painter.finish(), size, glyphs.as_ref().as_ref(), &PdfOptions::default());
+    let pdf = String::from_utf8_lossy(&pdf);
+
+    assert!(pdf.starts_with("%PDF-1.4"));
+    assert!(pdf.ends_with("%%EOF\n"));
+    // The content height 770 of an A4 page fits 2000 in three pages.
+    assert_eq!(pdf.matches("/Count 3").count(), 1);
+    assert_eq!(pdf.matches("/Type /Page ").count(), 3);
+    // Every page has its own shading, the page matrices differ.
+    assert_eq!(pdf.matches("/ShadingType 2").count(), 3);
+    // The catalog, the page tree and a shading, a content and a page per page.
+    assert!(pdf.contains("/Size 12 "));
+  }
+
+  #[test]
+  fn export_pdf_shares_images_and_skips_hidden() {
+    reset_test_env!();
+
+    let size = Size::new(100., 2000.);
+    let img = Resource::new(PixelImage::new(vec![255; 64].into(), 4, 4, ColorFormat::Rgba8));
+    let mut painter = Painter::new(Rect::from_size(size));
+    for y in [10., 900., 1700.] {
+      painter.draw_img(img.clone(), &Rect::new(Point::new(10., y), Size::new(20., 20.)), &None);
+    }
+    let glyphs = AppCtx::text_services().raster_source();
+    let pdf = to_pdf(&painter.finish(), size, glyphs.as_ref().as_ref(), &PdfOptions::default());
+    let pdf = String::from_utf8_lossy(&pdf);
+
+    assert_eq!(pdf.matches("/Type /Page ").count(), 3);
+    // The image is embedded once, and every page has its own tiling pattern.
+    assert_eq!(pdf.matches("/Subtype /Image").count(), 1);
+    assert_eq!(pdf.matches("/PatternType 1").count(), 3);
+    // Every page only refers to the image on it.
+    assert_eq!(pdf.matches("/Pattern << /P0 ").count(), 3);
+    assert!(!pdf.contains("/P1 "));
+  }
+
+  fn masked_painter(size: Size) -> Painter {
+    let mut mask = Painter::new(Rect::from_size(size));
+    mask
+      .set_fill_brush(Color::WHITE)
+      .circle(Point::new(10., 10.), 5., true)
+      .fill();
+    let mask_cmds: Box<[PaintCommand]> = mask.finish().to_vec().into_boxed_slice();
+
+    let mut painter = Painter::new(Rect::from_size(size));
+    painter
+      .save()
+      .set_mask(MaskMode::Alpha, Resource::new(mask_cmds))
+      .set_fill_brush(Color::RED)
+      .rect(&Rect::from_size(size), true)
+      .fill()
+      .restore();
+    painter
+  }
+
+  #[test]
+  fn export_svg_mask() {
+    reset_test_env!();
+
+    let size = Size::new(20., 20.);
+    let mut painter = masked_painter(size);
+    let glyphs = AppCtx::text_services().raster_source();
+    let svg = to_svg(&painter.finish(), size, glyphs.as_ref().as_ref());
+
+    assert_eq!(svg.matches("<mask id=\"mask1\"").count(), 1);
+    assert!(svg.contains("mask-type=\"alpha\""));
+    assert_eq!(svg.matches("<g mask=\"url(#mask1)\">").count(), 1);
+    // The circle of the mask is in the mask, the rect is in the masked group.
+    let mask = &svg[svg.find("<mask").unwrap()..svg.find("</mask>").unwrap()];
+    assert_eq!(mask.matches("<path").count(), 1);
+    assert!(mask.contains("fill=\"#ffffff\""));
+    let group = &svg[svg.find("<g mask").unwrap()..];
+    assert!(group.contains("fill=\"#ff0000\""));
+  }
+
+  #[test]
+  fn export_pdf_soft_mask() {
+    reset_test_env!();
+
+    let size = Size::new(20., 20.);
+    let mut painter = masked_painter(size);
+    let glyphs = AppCtx::text_services().raster_source();
+    let pdf = to_pdf(&painter.finish(), size, glyphs.as_ref().as_ref(), &PdfOptions::default());
+    let pdf = String::from_utf8_lossy(&pdf);
+
+    assert_eq!(
+      pdf
+        .matches("/SMask << /Type /Mask /S /Alpha /G ")
+        .count(),
+      1
+    );
+    assert_eq!(pdf.matches("/Subtype /Form").count(), 1);
+    assert_eq!(
+      pdf
+        .matches("/Group << /S /Transparency /CS /DeviceRGB >>")
+        .count(),
+      1
+    );
+  }
+
+  #[test]
+  fn format_number() {
+    assert_eq!(num(1.), "1");
+    assert_eq!(num(0.5), "0.5");
+    assert_eq!(num(-0.00001), "0");
+    assert_eq!(num(f32::NAN), "0");
+  }
+
+  #[test]
+  fn encode_base64() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"f"), "Zg==");
+    assert_eq!(base64(b"fo"), "Zm8=");
+    assert_eq!(base64(b"foo"), "Zm9v");
+    assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+  }
+}
@@ -0,0 +1,695 @@
+use std::{collections::HashMap, fmt::Write};
+
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use ribir_algo::Resource;
+use ribir_painter::{
+  BlendMode, BundleMask, Color, ColorMatrix, CommandBrush, GlyphRasterSource, GradientStop,
+  LineCap, LineJoin, MaskMode, PaintCommand, PaintPathAction, PaintingStyle, Path, PathSegment,
+  PixelImage, SpreadMethod, StrokeOptions, TileMode,
+};
+use ribir_types::{Point, Rect, Size, Transform};
+
+use crate::{ImagePaint, num, resolve_path, text::text_to_paths, transform_scale};
+
+/// The most copies of the stops a repeated or reflected gradient expands to.
+const MAX_GRADIENT_REPEAT: i32 = 64;
+
+/// The page setup of the exported PDF.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfOptions {
+  /// The page size in points, 1/72 inch.
+  pub page_size: Size,
+  /// The blank space around the content of every page, in points.
+  pub margin: f32,
+}
+
+impl Default for PdfOptions {
+  /// An A4 page with a half inch margin.
+  fn default() -> Self { Self { page_size: Size::new(595., 842.), margin: 36. } }
+}
+
+/// Exports the commands of a `size` canvas to a PDF document.
+///
+/// The content is scaled down to fit the page width, and split into as many
+/// pages as its height needs. `glyphs` provides the glyph outlines of the text
+/// commands.
+pub fn to_pdf(
+  commands: &[PaintCommand], size: Size, glyphs: &dyn GlyphRasterSource, options: &PdfOptions,
+) -> Vec<u8> {
+  let PdfOptions { page_size, margin } = *options;
+  let content_w = (page_size.width - 2. * margin).max(1.);
+  let content_h = (page_size.height - 2. * margin).max(1.);
+  let scale = (content_w / size.width.max(1.)).min(1.);
+  let pages = ((size.height * scale / content_h).ceil() as usize).max(1);
+
+  let mut doc = PdfDoc::default();
+  let catalog = doc.reserve();
+  let pages_id = doc.reserve();
+  let mut kids = vec![];
+  let page_area = Size::new(content_w / scale, content_h / scale);
+  for i in 0..pages {
+    // The part of the canvas the page shows, the commands out of it are skipped.
+    let visible = Rect::new(Point::new(0., i as f32 * page_area.height), page_area);
+    let commands = commands
+      .iter()
+      .filter(|cmd| canvas_bounds(cmd).is_none_or(|b| b.intersects(&visible)));
+    // From the canvas to the page, PDF puts the origin at the bottom left.
+    let page_matrix = Transform::scale(scale, scale)
+      .then_translate([margin, margin - i as f32 * content_h].into())
+      .then(&Transform::new(1., 0., 0., -1., 0., page_size.height));
+    let mut page = PageWriter::new(&mut doc, glyphs);
+    let _ = writeln!(
+      page.content,
+      "{} {} {} {} re W n",
+      num(margin),
+      num(margin),
+      num(content_w),
+      num(content_h)
+    );
+    page.write_commands(commands, &page_matrix, &ColorMatrix::identity());
+    let resources = page.resources();
+    let content = page.content;
+
+    let content = doc.add_stream("", content.into_bytes());
+    let page_id = doc.add(format!(
+      "<< /Type /Page /Parent {pages_id} 0 R /MediaBox [0 0 {} {}] /Resources {resources} \
+       /Contents {content} 0 R >>",
+      num(page_size.width),
+      num(page_size.height),
+    ));
+    kids.push(format!("{page_id} 0 R"));
+  }
+  doc.set(pages_id, format!("<< /Type /Pages /Kids [{}] /Count {pages} >>", kids.join(" ")));
+  doc.set(catalog, format!("<< /Type /Catalog /Pages {pages_id} 0 R >>"));
+  doc.finish(catalog)
+}
+
+/// The objects of a PDF document, the object number is the index plus one.
+///
+/// The images and the patterns are shared by all the pages, an image is
+/// embedded once for every color filter it is painted with, and the same
+/// pattern is only added once.
+#[derive(Default)]
+struct PdfDoc {
+  objects: Vec<Vec<u8>>,
+  images: HashMap<(*const (), String), usize>,
+  shared: HashMap<Vec<u8>, usize>,
+}
+
+impl PdfDoc {
+  fn reserve(&mut self) -> usize {
+    self.objects.push(vec![]);
+    self.objects.len()
+  }
+
+  fn set(&mut self, id: usize, dict: String) { self.objects[id - 1] = dict.into_bytes(); }
+
+  fn add(&mut self, dict: String) -> usize {
+    let id = self.reserve();
+    self.set(id, dict);
+    id
+  }
+
+  /// Adds the object if no same object was added by this method, otherwise
+  /// returns the added one.
+  fn add_shared(&mut self, dict: String) -> usize {
+    let key = dict.into_bytes();
+    if let Some(id) = self.shared.get(&key) {
+      return *id;
+    }
+    let id = self.reserve();
+    self.objects[id - 1] = key.clone();
+    self.shared.insert(key, id);
+    id
+  }
+
+  /// The stream version of [`PdfDoc::add_shared`].
+  fn add_shared_stream(&mut self, dict: &str, data: Vec<u8>) -> usize {
+    let mut key = dict.as_bytes().to_vec();
+    key.push(0);
+    key.extend(&data);
+    if let Some(id) = self.shared.get(&key) {
+      return *id;
+    }
+    let id = self.add_stream(dict, data);
+    self.shared.insert(key, id);
+    id
+  }
+
+  /// Adds a stream compressed by Flate, `dict` is the extra entries of the
+  /// stream dictionary.
+  fn add_stream(&mut self, dict: &str, data: Vec<u8>) -> usize {
+    let data = compress_to_vec_zlib(&data, 6);
+    let mut obj =
+      format!("<< {dict} /Filter /FlateDecode /Length {} >>\nstream\n", data.len()).into_bytes();
+    obj.extend(data);
+    obj.extend(b"\nendstream");
+    let id = self.reserve();
+    self.objects[id - 1] = obj;
+    id
+  }
+
+  fn finish(self, root: usize) -> Vec<u8> {
+    let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = Vec::with_capacity(self.objects.len());
+    for (i, obj) in self.objects.iter().enumerate() {
+      offsets.push(out.len());
+      out.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+      out.extend(obj);
+      out.extend(b"\nendobj\n");
+    }
+    let xref = out.len();
+    let size = self.objects.len() + 1;
+    let mut tail = format!("xref\n0 {size}\n0000000000 65535 f \n");
+    for offset in offsets {
+      let _ = writeln!(tail, "{offset:010} 00000 n ");
+    }
+    let _ =
+      write!(tail, "trailer\n<< /Size {size} /Root {root} 0 R >>\nstartxref\n{xref}\n%%EOF\n");
+    out.extend(tail.into_bytes());
+    out
+  }
+}
+
+/// Writes the content stream of a page. The current transform is always the
+/// page default between commands, every command carries its full matrix.
+struct PageWriter<'a> {
+  doc: &'a mut PdfDoc,
+  glyphs: &'a dyn GlyphRasterSource,
+  content: String,
+  patterns: Vec<usize>,
+  states: HashMap<String, String>,
+}
+
+impl<'a> PageWriter<'a> {
+  fn new(doc: &'a mut PdfDoc, glyphs: &'a dyn GlyphRasterSource) -> Self {
+    Self { doc, glyphs, content: String::new(), patterns: vec![], states: HashMap::new() }
+  }
+
+  fn write_commands<'c>(
+    &mut self, commands: impl IntoIterator<Item = &'c PaintCommand>, matrix: &Transform,
+    filter: &ColorMatrix,
+  ) {
+    let mut clips = 0;
+    for cmd in commands {
+      match cmd {
+        PaintCommand::Path(cmd) => {
+          let matrix = cmd.transform.then(matrix);
+          match &cmd.action {
+            PaintPathAction::Paint { brush, painting_style } => {
+              let mut brush = brush.clone();
+              brush.apply_color_filter(filter);
+              let (path, brush) = resolve_path(&cmd.path, &brush);
+              self.write_path(&path, &matrix, &brush, painting_style);
+            }
+            PaintPathAction::Clip => {
+              let (path, _) = resolve_path(&cmd.path, &CommandBrush::Color(Color::BLACK));
+              self.content.push_str("q ");
+              path_ops(&mut self.content, &path, Some(&matrix));
+              self.content.push_str("W n\n");
+              clips += 1;
+            }
+          }
+        }
+        PaintCommand::PopClip => {
+          if clips > 0 {
+            self.content.push_str("Q\n");
+            clips -= 1;
+          }
+        }
+        PaintCommand::Bundle { transform, color_filter, blend_mode, mask, bounds, cmds } => {
+          let matrix = transform.then(matrix);
+          let mut filter_chain = *color_filter;
+          filter_chain.chains(filter);
+          self.content.push_str("q\n");
+          if let Some(mode) = blend_mode_name(*blend_mode) {
+            let gs = self.ext_state(format!("/BM /{mode}"));
+            let _ = writeln!(self.content, "/{gs} gs");
+          }
+          if let Some(mask) = mask {
+            let gs = self.soft_mask(mask, &matrix.outer_transformed_rect(bounds), &matrix);
+            let _ = writeln!(self.content, "/{gs} gs");
+          }
+          self.write_commands(cmds.iter(), &matrix, &filter_chain);
+          self.content.push_str("Q\n");
+        }
+        // The filters work on the backdrop pixels, they are not exported.
+        PaintCommand::Filter { .. } => {}
+        PaintCommand::Text(text) => {
+          let paths = text_to_paths(text, self.glyphs);
+          self.write_commands(paths.iter(), matrix, filter);
+        }
+      }
+    }
+    for _ in 0..clips {
+      self.content.push_str("Q\n");
+    }
+  }
+
+  fn write_path(
+    &mut self, path: &Path, matrix: &Transform, brush: &CommandBrush, style: &PaintingStyle,
+  ) {
+    let stroke = matches!(style, PaintingStyle::Stroke(_));
+    let Some((paint, alpha)) = self.paint(brush, &path.bounds(None), matrix, stroke) else {
+      return;
+    };
+    self.content.push_str("q\n");
+    if alpha < 1. {
+      let gs = self.ext_state(format!("/ca {a} /CA {a}", a = num(alpha)));
+      let _ = writeln!(self.content, "/{gs} gs");
+    }
+    self.content.push_str(&paint);
+    let _ = writeln!(self.content, "{} cm", pdf_matrix(matrix));
+    path_ops(&mut self.content, path, None);
+    match style {
+      PaintingStyle::Fill => self.content.push_str("f\nQ\n"),
+      PaintingStyle::Stroke(options) => {
+        stroke_ops(&mut self.content, options);
+        self.content.push_str("S\nQ\n");
+      }
+    }
+  }
+
+  /// The operators that set the brush as the fill or the stroke paint, and the
+  /// opacity of the brush. `bounds` is the path bounds in the brush space.
+  fn paint(
+    &mut self, brush: &CommandBrush, bounds: &Rect, matrix: &Transform, stroke: bool,
+  ) -> Option<(String, f32)> {
+    let (cs, sc) = if stroke { ("CS", "SCN") } else { ("cs", "scn") };
+    let pattern = |name: String| format!("/Pattern {cs} /{name} {sc}\n");
+    match brush {
+      CommandBrush::Color(c) => {
+        let [r, g, b, a] = c.into_f32_components();
+        let op = if stroke { "RG" } else { "rg" };
+        Some((format!("{} {} {} {op}\n", num(r), num(g), num(b)), a))
+      }
+      CommandBrush::Linear(linear) => {
+        let (t0, t1) = t_range(linear.spread_method, || {
+          let axis = linear.end - linear.start;
+          let len = axis.square_length().max(f32::EPSILON);
+          corners(bounds).map(|p| (p - linear.start).dot(axis) / len)
+        });
+        let at = |t: f32| linear.start.lerp(linear.end, t);
+        let (p0, p1) = (at(t0), at(t1));
+        let coords = [p0.x, p0.y, p1.x, p1.y];
+        let name = self.shading(2, &coords, &linear.stops, linear.spread_method, (t0, t1), matrix);
+        Some((pattern(name), max_alpha(&linear.stops)))
+      }
+      CommandBrush::Radial(radial) => {
+        let (_, t1) = t_range(radial.spread_method, || {
+          let r = (radial.end_radius - radial.start_radius).max(f32::EPSILON);
+          corners(bounds)
+            .map(|p| ((p - radial.start_center).length() - radial.start_radius).max(0.) / r)
+        });
+        let center = radial.start_center.lerp(radial.end_center, t1);
+        let r = radial.start_radius + (radial.end_radius - radial.start_radius) * t1;
+        let c0 = radial.start_center;
+        let coords = [c0.x, c0.y, radial.start_radius, center.x, center.y, r];
+        let name = self.shading(3, &coords, &radial.stops, radial.spread_method, (0., t1), matrix);
+        Some((pattern(name), max_alpha(&radial.stops)))
+      }
+      CommandBrush::Sweep(sweep) => {
+        let img = ImagePaint::sweep(sweep, bounds, transform_scale(matrix))?;
+        let xobject = self.image(&img);
+        Some((pattern(self.tiling(&img, xobject, matrix)), 1.))
+      }
+      CommandBrush::Image { img: res, color_filter } => {
+        let tile = TileMode::Repeat;
+        let img = ImagePaint::new(res, color_filter, Transform::identity(), tile, tile);
+        let xobject = self.shared_image(res, color_filter, &img);
+        Some((pattern(self.tiling(&img, xobject, matrix)), 1.))
+      }
+      CommandBrush::Pattern { pattern: p, color_filter } => {
+        let img = ImagePaint::new(&p.image, color_filter, p.transform, p.tile_x, p.tile_y);
+        let xobject = self.shared_image(&p.image, color_filter, &img);
+        Some((pattern(self.tiling(&img, xobject, matrix)), 1.))
+      }
+    }
+  }
+
+  /// A shading pattern of `ty` (2 axial, 3 radial) whose colors over the
+  /// `domain` repeat the stops in the `spread` way.
+  fn shading(
+    &mut self, ty: u8, coords: &[f32], stops: &[GradientStop], spread: SpreadMethod,
+    domain: (f32, f32), matrix: &Transform,
+  ) -> String {
+    let coords = coords
+      .iter()
+      .map(|v| num(*v))
+      .collect::<Vec<_>>()
+      .join(" ");
+    let (t0, t1) = domain;
+    let function = stops_function(stops, spread, domain);
+    let id = self.doc.add_shared(format!(
+      "<< /Type /Pattern /PatternType 2 /Matrix [{}] /Shading << /ShadingType {ty} /ColorSpace \
+       /DeviceRGB /Coords [{coords}] /Domain [{} {}] /Function {function} /Extend [true true] >> \
+       >>",
+      pdf_matrix(matrix),
+      num(t0),
+      num(t1),
+    ));
+    self.add_pattern(id)
+  }
+
+  /// A tiling pattern of the image embedded as `xobject`, `matrix` maps the
+  /// brush space to the page.
+  fn tiling(&mut self, img: &ImagePaint, xobject: usize, matrix: &Transform) -> String {
+    let (w, h) = (img.width as f32, img.height as f32);
+    let step_x = ImagePaint::tile_step(img.tile_x, img.width);
+    let step_y = ImagePaint::tile_step(img.tile_y, img.height);
+    let mut content = String::new();
+    for (x, y, flip_x, flip_y) in img.tile_images() {
+      // An image fills the unit square with its first row at the top, the
+      // pattern space has the y-axis pointing down as the brush space.
+      let place = Transform::new(
+        if flip_x { -w } else { w },
+        0.,
+        0.,
+        if flip_y { h } else { -h },
+        if flip_x { x + w } else { x },
+        if flip_y { y } else { y + h },
+      );
+      let _ = writeln!(content, "q {} cm /Im0 Do Q", pdf_matrix(&place));
+    }
+    let dict = format!(
+      "/Type /Pattern /PatternType 1 /PaintType 1 /TilingType 1 /BBox [0 0 {sx} {sy}] /XStep {sx} \
+       /YStep {sy} /Matrix [{}] /Resources << /XObject << /Im0 {xobject} 0 R >> >>",
+      pdf_matrix(&img.img_to_brush.then(matrix)),
+      sx = num(step_x),
+      sy = num(step_y),
+    );
+    let id = self
+      .doc
+      .add_shared_stream(&dict, content.into_bytes());
+    self.add_pattern(id)
+  }
+
+  /// The image XObject of `res` painted with `filter`, only embedded once in
+  /// the document.
+  fn shared_image(
+    &mut self, res: &Resource<PixelImage>, filter: &ColorMatrix, img: &ImagePaint,
+  ) -> usize {
+    let key = (Resource::as_ptr(res), format!("{filter:?}"));
+    if let Some(id) = self.doc.images.get(&key) {
+      return *id;
+    }
+    let id = self.image(img);
+    self.doc.images.insert(key, id);
+    id
+  }
+
+  /// An image XObject of the RGB channels, and the alpha as its soft mask.
+  fn image(&mut self, img: &ImagePaint) -> usize {
+    let (w, h) = (img.width, img.height);
+    let rgb = img
+      .rgba
+      .chunks_exact(4)
+      .flat_map(|px| [px[0], px[1], px[2]])
+      .collect::<Vec<_>>();
+    let mut dict =
+      format!("/Type /XObject /Subtype /Image /Width {w} /Height {h} /ColorSpace /DeviceRGB");
+    if img.rgba.chunks_exact(4).any(|px| px[3] < 255) {
+      let alpha = img
+        .rgba
+        .chunks_exact(4)
+        .map(|px| px[3])
+        .collect::<Vec<_>>();
+      let mask = self.doc.add_stream(
+        &format!(
+          "/Type /XObject /Subtype /Image /Width {w} /Height {h} /ColorSpace /DeviceGray \
+           /BitsPerComponent 8"
+        ),
+        alpha,
+      );
+      let _ = write!(dict, " /SMask {mask} 0 R");
+    }
+    dict.push_str(" /BitsPerComponent 8");
+    self.doc.add_stream(&dict, rgb)
+  }
+
+  /// The graphics state of a soft mask painted by the commands of `mask`, they
+  /// are in the space of `matrix`. `bbox` is the area the mask covers on the
+  /// page.
+  fn soft_mask(&mut self, mask: &BundleMask, bbox: &Rect, matrix: &Transform) -> String {
+    let mut form = PageWriter::new(&mut *self.doc, self.glyphs);
+    form.write_commands(mask.cmds.iter(), matrix, &ColorMatrix::identity());
+    let resources = form.resources();
+    let content = form.content;
+    let dict = format!(
+      "/Type /XObject /Subtype /Form /BBox [{} {} {} {}] /Group << /S /Transparency /CS \
+       /DeviceRGB >> /Resources {resources}",
+      num(bbox.min_x()),
+      num(bbox.min_y()),
+      num(bbox.max_x()),
+      num(bbox.max_y()),
+    );
+    let form = self.doc.add_stream(&dict, content.into_bytes());
+    let subtype = match mask.mode {
+      MaskMode::Luminance => "Luminosity",
+      MaskMode::Alpha => "Alpha",
+    };
+    self.ext_state(format!("/SMask << /Type /Mask /S /{subtype} /G {form} 0 R >>"))
+  }
+
+  fn add_pattern(&mut self, id: usize) -> String {
+    let idx = self
+      .patterns
+      .iter()
+      .position(|p| *p == id)
+      .unwrap_or_else(|| {
+        self.patterns.push(id);
+        self.patterns.len() - 1
+      });
+    format!("P{idx}")
+  }
+
+  /// The name of a graphics state of the `entries`, shared in the page.
+  fn ext_state(&mut self, entries: String) -> String {
+    let len = self.states.len();
+    self
+      .states
+      .entry(entries)
+      .or_insert_with(|| format!("GS{len}"))
+      .clone()
+  }
+
+  fn resources(&self) -> String {
+    let mut states = self
+      .states
+      .iter()
+      .map(|(entries, name)| format!("/{name} << /Type /ExtGState {entries} >>"))
+      .collect::<Vec<_>>();
+    states.sort();
+    let patterns = self
+      .patterns
+      .iter()
+      .enumerate()
+      .map(|(i, id)| format!("/P{i} {id} 0 R"))
+      .collect::<Vec<_>>();
+    format!("<< /Pattern << {} >> /ExtGState << {} >> >>", patterns.join(" "), states.join(" "))
+  }
+}
+
+/// The bounds of a command in the canvas, `None` for the commands changing the
+/// state, such as the clips, that are always written.
+fn canvas_bounds(cmd: &PaintCommand) -> Option<Rect> {
+  match cmd {
+    PaintCommand::Path(cmd) if matches!(cmd.action, PaintPathAction::Paint { .. }) => {
+      Some(cmd.paint_bounds)
+    }
+    PaintCommand::Bundle { transform, bounds, .. } => {
+      Some(transform.outer_transformed_rect(bounds))
+    }
+    PaintCommand::Text(text) => Some(text.paint_bounds),
+    _ => None,
+  }
+}
+
+/// The range of the gradient offset to cover `offsets`, the offsets of the
+/// painted area. A padded gradient only needs its own range.
+fn t_range(spread: SpreadMethod, offsets: impl FnOnce() -> [f32; 4]) -> (f32, f32) {
+  if spread == SpreadMethod::Pad {
+    return (0., 1.);
+  }
+  let offsets = offsets();
+  let min = offsets
+    .iter()
+    .copied()
+    .fold(f32::MAX, f32::min)
+    .floor();
+  let max = offsets
+    .iter()
+    .copied()
+    .fold(f32::MIN, f32::max)
+    .ceil();
+  let min = min.max(-MAX_GRADIENT_REPEAT as f32).min(0.);
+  let max = max.min(min + MAX_GRADIENT_REPEAT as f32).max(1.);
+  (min, max)
+}
+
+/// A function from the offset in `domain` to the color, stitched by a linear
+/// function between every two stops.
+fn stops_function(stops: &[GradientStop], spread: SpreadMethod, domain: (f32, f32)) -> String {
+  let mut base: Vec<(f32, Color)> = stops
+    .iter()
+    .map(|s| (s.offset, s.color))
+    .collect();
+  if base.is_empty() {
+    base.push((0., Color::TRANSPARENT));
+  }
+  if base[0].0 > 0. {
+    base.insert(0, (0., base[0].1));
+  }
+  if base[base.len() - 1].0 < 1. {
+    base.push((1., base[base.len() - 1].1));
+  }
+
+  let mut points = vec![];
+  for k in domain.0 as i32..domain.1 as i32 {
+    let reflect = spread == SpreadMethod::Reflect && k.rem_euclid(2) == 1;
+    let copy: Vec<_> = if reflect {
+      base
+        .iter()
+        .rev()
+        .map(|(t, c)| (k as f32 + 1. - t, *c))
+        .collect()
+    } else {
+      base
+        .iter()
+        .map(|(t, c)| (k as f32 + t, *c))
+        .collect()
+    };
+    points.extend(copy);
+  }
+
+  let rgb = |c: &Color| {
+    let [r, g, b, _] = c.into_f32_components();
+    format!("{} {} {}", num(r), num(g), num(b))
+  };
+  let mut functions = vec![];
+  let mut bounds = vec![];
+  for (i, w) in points.windows(2).enumerate() {
+    let ((t0, c0), (_, c1)) = (w[0], w[1]);
+    if i > 0 {
+      bounds.push(num(t0));
+    }
+    functions.push(format!(
+      "<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>",
+      rgb(&c0),
+      rgb(&c1)
+    ));
+  }
+  let encode = vec!["0 1"; functions.len()].join(" ");
+  format!(
+    "<< /FunctionType 3 /Domain [{} {}] /Functions [{}] /Bounds [{}] /Encode [{encode}] >>",
+    num(domain.0),
+    num(domain.1),
+    functions.join(" "),
+    bounds.join(" ")
+  )
+}
+
+fn max_alpha(stops: &[GradientStop]) -> f32 {
+  stops
+    .iter()
+    .map(|s| s.color.alpha)
+    .max()
+    .unwrap_or(255) as f32
+    / 255.
+}
+
+fn corners(rect: &Rect) -> [Point; 4] {
+  [
+    rect.min(),
+    Point::new(rect.max_x(), rect.min_y()),
+    Point::new(rect.min_x(), rect.max_y()),
+    rect.max(),
+  ]
+}
+
+/// Writes the path construction operators, the points are mapped by `matrix`
+/// if any.
+fn path_ops(out: &mut String, path: &Path, matrix: Option<&Transform>) {
+  let p = |p: Point| {
+    let p = matrix.map_or(p, |m| m.transform_point(p));
+    format!("{} {}", num(p.x), num(p.y))
+  };
+  let mut current = Point::zero();
+  for seg in path.segments() {
+    let _ = match seg {
+      PathSegment::MoveTo(to) => {
+        current = to;
+        writeln!(out, "{} m", p(to))
+      }
+      PathSegment::LineTo(to) => {
+        current = to;
+        writeln!(out, "{} l", p(to))
+      }
+      PathSegment::QuadTo { ctrl, to } => {
+        // Elevates to a cubic, PDF has no quadratic curve.
+        let ctrl1 = current + (ctrl - current) * (2. / 3.);
+        let ctrl2 = to + (ctrl - to) * (2. / 3.);
+        current = to;
+        writeln!(out, "{} {} {} c", p(ctrl1), p(ctrl2), p(to))
+      }
+      PathSegment::CubicTo { to, ctrl1, ctrl2 } => {
+        current = to;
+        writeln!(out, "{} {} {} c", p(ctrl1), p(ctrl2), p(to))
+      }
+      PathSegment::Close(true) => writeln!(out, "h"),
+      PathSegment::Close(false) => Ok(()),
+    };
+  }
+}
+
+fn stroke_ops(out: &mut String, options: &StrokeOptions) {
+  let cap = match options.line_cap {
+    LineCap::Butt => 0,
+    LineCap::Round => 1,
+    LineCap::Square => 2,
+  };
+  let join = match options.line_join {
+    LineJoin::Miter | LineJoin::MiterClip => 0,
+    LineJoin::Round => 1,
+    LineJoin::Bevel => 2,
+  };
+  let _ = writeln!(out, "{} w {cap} J {join} j {} M", num(options.width), num(options.miter_limit));
+  if let Some((pattern, offset)) = &options.dash {
+    let pattern = pattern
+      .iter()
+      .map(|v| num(*v))
+      .collect::<Vec<_>>()
+      .join(" ");
+    let _ = writeln!(out, "[{pattern}] {} d", num(*offset));
+  }
+}
+
+fn pdf_matrix(t: &Transform) -> String {
+  format!(
+    "{} {} {} {} {} {}",
+    num(t.m11),
+    num(t.m12),
+    num(t.m21),
+    num(t.m22),
+    num(t.m31),
+    num(t.m32)
+  )
+}
+
+fn blend_mode_name(mode: BlendMode) -> Option<&'static str> {
+  let name = match mode {
+    BlendMode::Normal => return None,
+    BlendMode::Multiply => "Multiply",
+    BlendMode::Screen => "Screen",
+    BlendMode::Overlay => "Overlay",
+    BlendMode::Darken => "Darken",
+    BlendMode::Lighten => "Lighten",
+    BlendMode::ColorDodge => "ColorDodge",
+    BlendMode::ColorBurn => "ColorBurn",
+    BlendMode::HardLight => "HardLight",
+    BlendMode::SoftLight => "SoftLight",
+    BlendMode::Difference => "Difference",
+    BlendMode::Exclusion => "Exclusion",
+  };
+  Some(name)
+}
@@ -0,0 +1,390 @@
+use std::fmt::Write;
+
+use ribir_painter::{
+  BlendMode, BundleMask, Color, ColorMatrix, CommandBrush, GlyphRasterSource, GradientStop,
+  LineCap, LineJoin, MaskMode, PaintCommand, PaintPathAction, PaintingStyle, Path, PathSegment,
+  SpreadMethod, StrokeOptions, TileMode,
+};
+use ribir_types::{Rect, Size, Transform};
+
+use crate::{ImagePaint, base64, num, png, resolve_path, text::text_to_paths, transform_scale};
+
+/// Exports the commands to a standalone SVG document of `size`.
+///
+/// `glyphs` provides the glyph outlines of the text commands, usually the
+/// glyph source of the text services that shaped the text.
+pub fn to_svg(commands: &[PaintCommand], size: Size, glyphs: &dyn GlyphRasterSource) -> String {
+  let mut writer = SvgWriter { glyphs, defs: String::new(), body: String::new(), ids: 0 };
+  writer.write_commands(commands, &Transform::identity());
+
+  let (w, h) = (num(size.width), num(size.height));
+  let mut svg = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+     width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n"
+  );
+  if !writer.defs.is_empty() {
+    let _ = write!(svg, "<defs>\n{}</defs>\n", writer.defs);
+  }
+  svg.push_str(&writer.body);
+  svg.push_str("</svg>\n");
+  svg
+}
+
+/// All elements are in the root user space, every path carries its full
+/// transform, so the clip paths and the gradients need no extra mapping.
+struct SvgWriter<'a> {
+  glyphs: &'a dyn GlyphRasterSource,
+  defs: String,
+  body: String,
+  ids: usize,
+}
+
+impl SvgWriter<'_> {
+  fn write_commands(&mut self, commands: &[PaintCommand], matrix: &Transform) {
+    let mut clips = 0;
+    for cmd in commands {
+      match cmd {
+        PaintCommand::Path(cmd) => {
+          let matrix = cmd.transform.then(matrix);
+          match &cmd.action {
+            PaintPathAction::Paint { brush, painting_style } => {
+              let (path, brush) = resolve_path(&cmd.path, brush);
+              self.write_path(&path, &matrix, &brush, painting_style);
+            }
+            PaintPathAction::Clip => {
+              let (path, _) = resolve_path(&cmd.path, &CommandBrush::Color(Color::BLACK));
+              let id = self.new_id("clip");
+              let _ = writeln!(
+                self.defs,
+                "<clipPath id=\"{id}\"><path d=\"{}\" transform=\"{}\"/></clipPath>",
+                path_data(&path),
+                svg_matrix(&matrix)
+              );
+              let _ = writeln!(self.body, "<g clip-path=\"url(#{id})\">");
+              clips += 1;
+            }
+          }
+        }
+        PaintCommand::PopClip => {
+          if clips > 0 {
+            self.body.push_str("</g>\n");
+            clips -= 1;
+          }
+        }
+        PaintCommand::Bundle { transform, color_filter, blend_mode, mask, cmds, .. } => {
+          let matrix = transform.then(matrix);
+          let mut attrs = String::new();
+          if let Some(mask) = mask {
+            let id = self.mask(mask, &matrix);
+            let _ = write!(attrs, " mask=\"url(#{id})\"");
+          }
+          match color_filter {
+            ColorMatrix::Opacity(a) if *a == 1. => {}
+            ColorMatrix::Opacity(a) => {
+              let _ = write!(attrs, " opacity=\"{}\"", num(*a));
+            }
+            ColorMatrix::Matrix(_) => {
+              let id = self.color_filter(color_filter);
+              let _ = write!(attrs, " filter=\"url(#{id})\"");
+            }
+          }
+          if let Some(mode) = blend_mode_name(*blend_mode) {
+            let _ = write!(attrs, " style=\"mix-blend-mode:{mode}\"");
+          }
+          let _ = writeln!(self.body, "<g{attrs}>");
+          self.write_commands(cmds, &matrix);
+          self.body.push_str("</g>\n");
+        }
+        // The filters work on the backdrop pixels, they are not exported.
+        PaintCommand::Filter { .. } => {}
+        PaintCommand::Text(text) => {
+          let paths = text_to_paths(text, self.glyphs);
+          self.write_commands(&paths, matrix);
+        }
+      }
+    }
+    for _ in 0..clips {
+      self.body.push_str("</g>\n");
+    }
+  }
+
+  fn write_path(
+    &mut self, path: &Path, matrix: &Transform, brush: &CommandBrush, style: &PaintingStyle,
+  ) {
+    let bounds = path.bounds(None);
+    let (paint, opacity) = self.paint(brush, &bounds, matrix);
+    let mut attrs = String::new();
+    match style {
+      PaintingStyle::Fill => {
+        let _ = write!(attrs, " fill=\"{paint}\"");
+        if opacity < 1. {
+          let _ = write!(attrs, " fill-opacity=\"{}\"", num(opacity));
+        }
+      }
+      PaintingStyle::Stroke(options) => {
+        let _ = write!(attrs, " fill=\"none\" stroke=\"{paint}\"");
+        if opacity < 1. {
+          let _ = write!(attrs, " stroke-opacity=\"{}\"", num(opacity));
+        }
+        stroke_attrs(&mut attrs, options);
+      }
+    }
+    let _ = writeln!(
+      self.body,
+      "<path d=\"{}\" transform=\"{}\"{attrs}/>",
+      path_data(path),
+      svg_matrix(matrix)
+    );
+  }
+
+  /// The paint of the brush and its opacity. `bounds` is the path bounds in
+  /// the brush space, and `matrix` maps the brush space to the document.
+  fn paint(&mut self, brush: &CommandBrush, bounds: &Rect, matrix: &Transform) -> (String, f32) {
+    match brush {
+      CommandBrush::Color(c) => (hex_color(c), c.alpha as f32 / 255.),
+      CommandBrush::Linear(linear) => {
+        let id = self.new_id("linear");
+        let _ = writeln!(
+          self.defs,
+          "<linearGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" x1=\"{}\" y1=\"{}\" \
+           x2=\"{}\" y2=\"{}\" spreadMethod=\"{}\">{}</linearGradient>",
+          num(linear.start.x),
+          num(linear.start.y),
+          num(linear.end.x),
+          num(linear.end.y),
+          spread_name(linear.spread_method),
+          stops(&linear.stops)
+        );
+        (format!("url(#{id})"), 1.)
+      }
+      CommandBrush::Radial(radial) => {
+        let id = self.new_id("radial");
+        let _ = writeln!(
+          self.defs,
+          "<radialGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" cx=\"{}\" cy=\"{}\" \
+           r=\"{}\" fx=\"{}\" fy=\"{}\" fr=\"{}\" spreadMethod=\"{}\">{}</radialGradient>",
+          num(radial.end_center.x),
+          num(radial.end_center.y),
+          num(radial.end_radius),
+          num(radial.start_center.x),
+          num(radial.start_center.y),
+          num(radial.start_radius),
+          spread_name(radial.spread_method),
+          stops(&radial.stops)
+        );
+        (format!("url(#{id})"), 1.)
+      }
+      CommandBrush::Sweep(sweep) => {
+        match ImagePaint::sweep(sweep, bounds, transform_scale(matrix)) {
+          Some(img) => (self.image_pattern(&img), 1.),
+          None => ("none".to_string(), 1.),
+        }
+      }
+      CommandBrush::Image { img, color_filter } => {
+        let tile = TileMode::Repeat;
+        let img = ImagePaint::new(img, color_filter, Transform::identity(), tile, tile);
+        (self.image_pattern(&img), 1.)
+      }
+      CommandBrush::Pattern { pattern, color_filter } => {
+        let img = ImagePaint::new(
+          &pattern.image,
+          color_filter,
+          pattern.transform,
+          pattern.tile_x,
+          pattern.tile_y,
+        );
+        (self.image_pattern(&img), 1.)
+      }
+    }
+  }
+
+  fn image_pattern(&mut self, img: &ImagePaint) -> String {
+    let id = self.new_id("pattern");
+    let href = format!("data:image/png;base64,{}", base64(&png(&img.rgba, img.width, img.height)));
+    let (w, h) = (img.width as f32, img.height as f32);
+    let _ = write!(
+      self.defs,
+      "<pattern id=\"{id}\" patternUnits=\"userSpaceOnUse\" width=\"{}\" height=\"{}\" \
+       patternTransform=\"{}\">",
+      num(ImagePaint::tile_step(img.tile_x, img.width)),
+      num(ImagePaint::tile_step(img.tile_y, img.height)),
+      svg_matrix(&img.img_to_brush)
+    );
+    for (x, y, flip_x, flip_y) in img.tile_images() {
+      let place = Transform::new(
+        if flip_x { -1. } else { 1. },
+        0.,
+        0.,
+        if flip_y { -1. } else { 1. },
+        if flip_x { x + w } else { x },
+        if flip_y { y + h } else { y },
+      );
+      let _ = write!(
+        self.defs,
+        "<image width=\"{}\" height=\"{}\" transform=\"{}\" preserveAspectRatio=\"none\" \
+         xlink:href=\"{href}\"/>",
+        num(w),
+        num(h),
+        svg_matrix(&place)
+      );
+    }
+    self.defs.push_str("</pattern>\n");
+    format!("url(#{id})")
+  }
+
+  /// A `<mask>` of the commands of `mask`, they're in the space of `matrix`.
+  fn mask(&mut self, mask: &BundleMask, matrix: &Transform) -> String {
+    let body = std::mem::take(&mut self.body);
+    self.write_commands(&mask.cmds, matrix);
+    let content = std::mem::replace(&mut self.body, body);
+    let id = self.new_id("mask");
+    let mask_type = match mask.mode {
+      MaskMode::Luminance => "luminance",
+      MaskMode::Alpha => "alpha",
+    };
+    let _ = write!(
+      self.defs,
+      "<mask id=\"{id}\" maskUnits=\"userSpaceOnUse\" \
+       mask-type=\"{mask_type}\">\n{content}</mask>\n"
+    );
+    id
+  }
+
+  fn color_filter(&mut self, filter: &ColorMatrix) -> String {
+    let id = self.new_id("filter");
+    let m = filter.to_matrix();
+    let base = m
+      .base_color
+      .map(|c| c.into_f32_components())
+      .unwrap_or([0.; 4]);
+    let values = (0..4)
+      .map(|row| {
+        let mut v = m.matrix[row * 4..row * 4 + 4]
+          .iter()
+          .map(|v| num(*v))
+          .collect::<Vec<_>>();
+        v.push(num(base[row]));
+        v.join(" ")
+      })
+      .collect::<Vec<_>>()
+      .join(" ");
+    let _ = writeln!(
+      self.defs,
+      "<filter id=\"{id}\" color-interpolation-filters=\"sRGB\"><feColorMatrix type=\"matrix\" \
+       values=\"{values}\"/></filter>"
+    );
+    id
+  }
+
+  fn new_id(&mut self, kind: &str) -> String {
+    self.ids += 1;
+    format!("{kind}{}", self.ids)
+  }
+}
+
+fn path_data(path: &Path) -> String {
+  let mut d = String::new();
+  for seg in path.segments() {
+    let _ = match seg {
+      PathSegment::MoveTo(p) => write!(d, "M{} {}", num(p.x), num(p.y)),
+      PathSegment::LineTo(p) => write!(d, "L{} {}", num(p.x), num(p.y)),
+      PathSegment::QuadTo { ctrl, to } => {
+        write!(d, "Q{} {} {} {}", num(ctrl.x), num(ctrl.y), num(to.x), num(to.y))
+      }
+      PathSegment::CubicTo { to, ctrl1, ctrl2 } => write!(
+        d,
+        "C{} {} {} {} {} {}",
+        num(ctrl1.x),
+        num(ctrl1.y),
+        num(ctrl2.x),
+        num(ctrl2.y),
+        num(to.x),
+        num(to.y)
+      ),
+      PathSegment::Close(true) => write!(d, "Z"),
+      PathSegment::Close(false) => Ok(()),
+    };
+  }
+  d
+}
+
+fn svg_matrix(t: &Transform) -> String {
+  format!(
+    "matrix({} {} {} {} {} {})",
+    num(t.m11),
+    num(t.m12),
+    num(t.m21),
+    num(t.m22),
+    num(t.m31),
+    num(t.m32)
+  )
+}
+
+fn stroke_attrs(attrs: &mut String, options: &StrokeOptions) {
+  let _ = write!(attrs, " stroke-width=\"{}\"", num(options.width));
+  let cap = match options.line_cap {
+    LineCap::Butt => "butt",
+    LineCap::Round => "round",
+    LineCap::Square => "square",
+  };
+  let join = match options.line_join {
+    LineJoin::Miter => "miter",
+    LineJoin::MiterClip => "miter-clip",
+    LineJoin::Round => "round",
+    LineJoin::Bevel => "bevel",
+  };
+  let _ = write!(
+    attrs,
+    " stroke-linecap=\"{cap}\" stroke-linejoin=\"{join}\" stroke-miterlimit=\"{}\"",
+    num(options.miter_limit)
+  );
+  if let Some((pattern, offset)) = &options.dash {
+    let pattern = pattern
+      .iter()
+      .map(|v| num(*v))
+      .collect::<Vec<_>>()
+      .join(" ");
+    let _ = write!(attrs, " stroke-dasharray=\"{pattern}\" stroke-dashoffset=\"{}\"", num(*offset));
+  }
+}
+
+fn stops(stops: &[GradientStop]) -> String {
+  let mut s = String::new();
+  for stop in stops {
+    let _ =
+      write!(s, "<stop offset=\"{}\" stop-color=\"{}\"", num(stop.offset), hex_color(&stop.color));
+    if stop.color.alpha < 255 {
+      let _ = write!(s, " stop-opacity=\"{}\"", num(stop.color.alpha as f32 / 255.));
+    }
+    s.push_str("/>");
+  }
+  s
+}
+
+fn hex_color(c: &Color) -> String { format!("#{:02x}{:02x}{:02x}", c.red, c.green, c.blue) }
+
+fn spread_name(spread: SpreadMethod) -> &'static str {
+  match spread {
+    SpreadMethod::Pad => "pad",
+    SpreadMethod::Reflect => "reflect",
+    SpreadMethod::Repeat => "repeat",
+  }
+}
+
+fn blend_mode_name(mode: BlendMode) -> Option<&'static str> {
+  let name = match mode {
+    BlendMode::Normal => return None,
+    BlendMode::Multiply => "multiply",
+    BlendMode::Screen => "screen",
+    BlendMode::Overlay => "overlay",
+    BlendMode::Darken => "darken",
+    BlendMode::Lighten => "lighten",
+    BlendMode::ColorDodge => "color-dodge",
+    BlendMode::ColorBurn => "color-burn",
+    BlendMode::HardLight => "hard-light",
+    BlendMode::SoftLight => "soft-light",
+    BlendMode::Difference => "difference",
+    BlendMode::Exclusion => "exclusion",
+  };
+  Some(name)
+}
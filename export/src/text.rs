@@ -0,0 +1,162 @@
+//! Lowers the text commands to path commands, so the exporters only need to
+//! handle paths.
+
+use std::borrow::Cow;
+
+use ribir_algo::Resource;
+use ribir_painter::{
+  Brush, Color, ColorFormat, ColorMatrix, CommandBrush, FaceId, GlyphId, GlyphRasterSource,
+  OutlineSegment, PaintCommand, PaintPath, PaintPathAction, PaintingStyle, Path, PathCommand,
+  PixelImage, Radius, RasterBitmapFormat, Svg, TextCommand,
+};
+use ribir_types::{Rect, Size, Transform};
+
+/// The path commands that paint the backgrounds, the glyphs and the
+/// decorations of `cmd`, in the same space as `cmd`.
+///
+/// The glyphs are painted by their outlines, the glyphs without outline fall
+/// back to their SVG or bitmap images.
+pub(crate) fn text_to_paths(
+  cmd: &TextCommand, glyphs: &dyn GlyphRasterSource,
+) -> Vec<PaintCommand> {
+  let matrix = cmd.payload.transform.then(&cmd.transform);
+  let run_brush = |brush: &Option<Brush>| {
+    brush
+      .clone()
+      .map(|brush| {
+        let mut brush = CommandBrush::from(brush);
+        brush.apply_color_filter(&cmd.color_filter);
+        brush
+      })
+      .unwrap_or_else(|| cmd.default_brush.clone())
+  };
+
+  let mut out = vec![];
+  for background in cmd.payload.backgrounds.iter() {
+    let path = if background.radius > 0. {
+      Path::rect_round(&background.rect, &Radius::all(background.radius))
+    } else {
+      Path::rect(&background.rect)
+    };
+    out.push(fill_path(path, matrix, run_brush(&Some(background.brush.clone()))));
+  }
+
+  for run in cmd.payload.runs.iter() {
+    let brush = run_brush(&run.brush);
+    for glyph in run.glyphs.iter() {
+      let origin = glyph.baseline_origin + cmd.payload.origin_offset;
+      let glyph_matrix = Transform::translation(origin.x, origin.y)
+        .then(&run.glyph_transform(glyph, cmd.payload.origin_offset))
+        .then(&matrix);
+      let font_size = run.logical_font_size;
+      if let Some(svg) = glyphs
+        .raster_svg(run.face_id, glyph.glyph_id)
+        .and_then(|svg| Svg::parse_from_bytes(svg.as_bytes(), true, false).ok())
+      {
+        let Some(metrics) = glyphs.face_metrics(run.face_id) else { continue };
+        let unit = metrics.units_per_em as f32;
+        let grid_scale = metrics
+          .vertical_height
+          .map(|h| h / unit)
+          .unwrap_or(1.)
+          .max(1.);
+        let s = font_size / svg.size().height.max(1.) / grid_scale;
+        let svg_matrix = Transform::new(s, 0., 0., s, 0., -font_size).then(&glyph_matrix);
+        let black = Brush::from(Color::BLACK);
+        for cmd in svg.commands(&black, &black).iter() {
+          if let PaintCommand::Path(path) = cmd {
+            let mut path = path.clone();
+            path.transform(&svg_matrix);
+            out.push(PaintCommand::Path(path));
+          }
+        }
+      } else if let Some(outline) = glyphs.glyph_outline(run.face_id, glyph.glyph_id, font_size) {
+        out.push(fill_path(outline_path(&outline), glyph_matrix, brush.clone()));
+      } else if let Some(mut cmd) =
+        bitmap_glyph(glyphs, run.face_id, glyph.glyph_id, font_size, &brush)
+      {
+        cmd.transform(&glyph_matrix);
+        out.push(PaintCommand::Path(cmd));
+      }
+    }
+  }
+
+  for decoration in cmd.payload.decorations.iter() {
+    let brush = run_brush(&decoration.brush);
+    out.push(fill_path(Path::rect(&decoration.rect), matrix, brush));
+  }
+  out
+}
+
+fn fill_path(path: Path, transform: Transform, brush: CommandBrush) -> PaintCommand {
+  let paint_bounds = transform.outer_transformed_rect(&path.bounds(None));
+  PaintCommand::Path(PathCommand {
+    path: PaintPath::Own(path),
+    paint_bounds,
+    transform,
+    action: PaintPathAction::Paint { brush, painting_style: PaintingStyle::Fill },
+  })
+}
+
+fn outline_path(outline: &[OutlineSegment]) -> Path {
+  let mut builder = Path::builder();
+  let mut open = false;
+  for seg in outline {
+    match *seg {
+      OutlineSegment::MoveTo(p) => {
+        if open {
+          builder.end_path(false);
+        }
+        builder.begin_path(p);
+        open = true;
+      }
+      OutlineSegment::LineTo(p) => {
+        builder.line_to(p);
+      }
+      OutlineSegment::QuadTo { ctrl, to } => builder.quadratic_curve_to(ctrl, to),
+      OutlineSegment::CubicTo { ctrl1, ctrl2, to } => builder.bezier_curve_to(ctrl1, ctrl2, to),
+      OutlineSegment::Close => {
+        if open {
+          builder.end_path(true);
+          open = false;
+        }
+      }
+    }
+  }
+  if open {
+    builder.end_path(false);
+  }
+  builder.build()
+}
+
+/// The bitmap of the glyph placed relative to the baseline origin. An alpha
+/// bitmap is painted by `brush` where it covers.
+fn bitmap_glyph(
+  glyphs: &dyn GlyphRasterSource, face_id: FaceId, glyph_id: GlyphId, font_size: f32,
+  brush: &CommandBrush,
+) -> Option<PathCommand> {
+  // Rasterize at the double size, so the bitmap keeps sharp when zoomed in.
+  let ppem = (font_size * 2.).ceil().max(1.) as u16;
+  let bitmap = glyphs.raster_bitmap(face_id, glyph_id, ppem)?;
+  let format = match bitmap.format {
+    RasterBitmapFormat::Rgba8 => ColorFormat::Rgba8,
+    RasterBitmapFormat::Alpha8 => ColorFormat::Alpha8,
+  };
+  let img =
+    Resource::new(PixelImage::new(Cow::Owned(bitmap.data), bitmap.width, bitmap.height, format));
+  let rect = Rect::from_size(Size::new(bitmap.width as f32, bitmap.height as f32));
+  let (path, brush) = if format == ColorFormat::Rgba8 {
+    let brush = CommandBrush::Image { img, color_filter: ColorMatrix::identity() };
+    (PaintPath::Own(Path::rect(&rect)), brush)
+  } else {
+    (PaintPath::PixelImage(img), brush.clone())
+  };
+  let s = font_size / ppem as f32;
+  let transform = Transform::new(s, 0., 0., s, bitmap.placement.x * s, bitmap.placement.y * s);
+  Some(PathCommand {
+    path,
+    paint_bounds: transform.outer_transformed_rect(&rect),
+    transform,
+    action: PaintPathAction::Paint { brush, painting_style: PaintingStyle::Fill },
+  })
+}
@@ -4,20 +4,22 @@ use ahash::HashMap;
 use guillotiere::euclid::Vector2D;
 use ribir_algo::Resource;
 use ribir_painter::{
-  Color, ColorFormat, ColorMatrix, CommandBrush, FaceId, FilterComposite, FilterLayer, FilterOp,
-  GlyphId, GlyphRasterSource, LineCap, LineJoin, PaintCommand, PaintPath, PaintPathAction,
-  PainterBackend, PaintingStyle, Path, PathCommand, PathKind, PixelImage, RasterBitmapFormat,
-  StrokeOptions, Svg, TextCommand, Vertex, VertexBuffers, color::ColorFilterMatrix,
+  BlendMode, Color, ColorFormat, ColorMatrix, CommandBrush, FaceId, FilterComposite, FilterLayer,
+  FilterOp, GlyphId, GlyphRasterSource, ImagePattern, LineCap, LineJoin, MaskMode, PaintCommand,
+  PaintPath, PaintPathAction, PainterBackend, PaintingStyle, Path, PathCommand, PathKind,
+  PixelImage, RasterBitmapFormat, StrokeOptions, Svg, TextCommand, Vertex, VertexBuffers,
+  color::ColorFilterMatrix,
 };
 use ribir_types::{
-  DevicePoint, DeviceRect, DeviceSize, Point, Transform, Vector, rect_corners,
+  DevicePoint, DeviceRect, DeviceSize, Point, Rect, Transform, Vector, rect_corners,
   transform_to_device_rect,
 };
 
 use crate::{
   ColorAttr, FilterPrimitive, GPUBackendImpl, GradientStopPrimitive, ImagePrimIndex, ImgPrimitive,
-  LinearGradientPrimIndex, LinearGradientPrimitive, MaskKind, MaskLayer, RadialGradientPrimIndex,
-  RadialGradientPrimitive, TexturePrimIndex, TexturePrimitive,
+  LinearGradientPrimIndex, LinearGradientPrimitive, MaskKind, MaskLayer, PatternPrimIndex,
+  PatternPrimitive, RadialGradientPrimIndex, RadialGradientPrimitive, SweepGradientPrimIndex,
+  SweepGradientPrimitive, TexturePrimIndex, TexturePrimitive,
 };
 
 mod atlas;
@@ -48,6 +50,11 @@ pub struct GPUBackend<Impl: GPUBackendImpl> {
   linear_gradient_prims: Vec<LinearGradientPrimitive>,
   linear_gradient_stops: Vec<GradientStopPrimitive>,
   linear_gradient_vertices_buffer: VertexBuffers<LinearGradientPrimIndex>,
+  sweep_gradient_prims: Vec<SweepGradientPrimitive>,
+  sweep_gradient_stops: Vec<GradientStopPrimitive>,
+  sweep_gradient_vertices_buffer: VertexBuffers<SweepGradientPrimIndex>,
+  pattern_prims: Vec<PatternPrimitive>,
+  pattern_vertices_buffer: VertexBuffers<PatternPrimIndex>,
   texture_vertices_buffer: VertexBuffers<TexturePrimIndex>,
   current_phase: CurrentPhase,
   tex_ids_map: TextureIdxMap,
@@ -68,6 +75,8 @@ enum CurrentPhase {
   Img,
   RadialGradient,
   LinearGradient,
+  SweepGradient,
+  Pattern,
   Filter(Box<FilterPhase>),
 }
 
@@ -160,6 +169,7 @@ where
       && style.miter_limit.is_finite()
       && matches!(style.line_cap, LineCap::Butt)
       && matches!(style.line_join, LineJoin::Miter | LineJoin::Bevel)
+      && style.dash.is_none()
   }
 
   fn normalize_round_rect_radii(
@@ -228,6 +238,11 @@ where
       linear_gradient_vertices_buffer: VertexBuffers::with_capacity(256, 512),
       linear_gradient_stops: vec![],
       linear_gradient_prims: vec![],
+      sweep_gradient_vertices_buffer: VertexBuffers::with_capacity(256, 512),
+      sweep_gradient_stops: vec![],
+      sweep_gradient_prims: vec![],
+      pattern_vertices_buffer: VertexBuffers::with_capacity(256, 512),
+      pattern_prims: vec![],
       img_prims: vec![],
       current_phase: CurrentPhase::None,
       viewport: DeviceRect::zero(),
@@ -261,7 +276,9 @@ where
       mask_head: -1,
       opacity: 1.0,
       is_premultiplied: 1,
-      _padding: [0; 3],
+      blend_mode: 0,
+      mask_mode: 0,
+      _padding: 0,
     };
     let prims_offset = self
       .gpu_impl
@@ -272,6 +289,7 @@ where
       indices,
       Some(Color::TRANSPARENT),
       source,
+      None,
       0,
       prims_offset,
     );
@@ -384,6 +402,45 @@ where
               add_rect_vertices(rect, output_tex_size, LinearGradientPrimIndex(prim_idx), buffer);
               self.current_phase = CurrentPhase::LinearGradient;
             }
+            CommandBrush::Sweep(sweep) => {
+              let stop = (self.sweep_gradient_stops.len() << 16 | sweep.stops.len()) as u32;
+              let mask_head_and_spread = mask_head << 16 | sweep.spread_method as i32;
+              let prim = SweepGradientPrimitive {
+                transform: matrix.inverse().unwrap().to_array(),
+                center: sweep.center.to_array(),
+                start_angle: sweep.start_angle.radians,
+                end_angle: sweep.end_angle.radians,
+                stop,
+                mask_head_and_spread,
+              };
+              let stops = sweep.stops.iter().map(GradientStopPrimitive::new);
+              self.sweep_gradient_stops.extend(stops);
+              let prim_idx = self.sweep_gradient_prims.len() as u32;
+              self.sweep_gradient_prims.push(prim);
+              let buffer = &mut self.sweep_gradient_vertices_buffer;
+              add_rect_vertices(rect, output_tex_size, SweepGradientPrimIndex(prim_idx), buffer);
+              self.current_phase = CurrentPhase::SweepGradient;
+            }
+            CommandBrush::Pattern { pattern, color_filter } => {
+              let slice = self
+                .tex_mgr
+                .store_image(&pattern.image, &mut self.gpu_impl);
+              let ts = matrix.inverse().unwrap().then(
+                &pattern
+                  .transform
+                  .inverse()
+                  .unwrap_or_else(Transform::identity),
+              );
+              self.draw_pattern_slice(
+                slice,
+                &ts,
+                pattern,
+                mask_head,
+                color_filter,
+                output_tex_size,
+                rect,
+              );
+            }
           },
         }
       }
@@ -394,63 +451,33 @@ where
           self.clip_layer_stack.pop();
         }
       }
-      PaintCommand::Bundle { transform, color_filter, bounds, cmds } => {
+      PaintCommand::Bundle { transform, color_filter, blend_mode, mask, bounds, cmds } => {
         let matrix = transform.then(global_matrix);
-        let scale = self.tex_mgr.cache_scale(&bounds.size, &matrix);
-        let cache_size = bounds.size * scale;
-
-        let this = self as *mut Self;
-        let (cache_scale, slice) = self.tex_mgr.store_commands(
-          cache_size.to_i32().cast_unit(),
-          cmds.clone().into_any(),
-          scale,
-          &mut self.gpu_impl,
-          |slice, tex, _| {
-            // SAFETY: We already hold a mut reference to the texture in the texture
-            // manager, so we cant use `self` here, but this texture should always exist
-            // within the frame, and no modifications will be made to the slice
-            // that has already been allocated.
-            let this = unsafe { &mut *this };
-
-            // Initiate a new drawing phase to ensure a clean state for rendering in a new
-            // texture.
-            this.new_draw_phase(output);
-
-            // store the viewport
-            let viewport = self.viewport;
-            // Overwrite the viewport to the slice bounds.
-            self
-              .clip_layer_stack
-              .push(ClipLayer { viewport: *slice, mask_head: -1 });
-
-            let matrix = Transform::translation(-bounds.origin.x, -bounds.origin.y)
-              .then_scale(scale, scale)
-              .then_translate(slice.origin.to_f32().cast_unit().to_vector());
-
-            let surface_color = this.surface_color.take();
-            this.surface_color = Some(Color::TRANSPARENT);
-            this.draw_commands(*slice, cmds, &matrix, tex, glyph_provider);
-            this.surface_color = surface_color;
-
-            // restore the clip layer and viewport
-            self.clip_layer_stack.pop();
-            this.viewport = viewport;
-          },
-        );
-
+        let (slice, view_to_slice) =
+          self.store_bundle(bounds, &matrix, cmds, output, glyph_provider);
         let mut points: [_; 4] = rect_corners(&bounds.to_f32().cast_unit());
         for p in points.iter_mut() {
           *p = matrix.transform_point(*p);
         }
 
-        let view_to_slice = matrix
-          // point back to the bundle commands axis.
-          .inverse()
-          .unwrap()
-          // align to the zero point, draw image slice is start from zero.
-          .then_translate(Vector2D::new(-bounds.origin.x, -bounds.origin.y))
-          // scale to the cache size.
-          .then_scale(cache_scale, cache_scale);
+        if let Some(mask) = mask {
+          let (mask_slice, mask_to_slice) =
+            self.store_bundle(bounds, &matrix, &mask.cmds, output, glyph_provider);
+          self.draw_mask_layer(
+            (slice, &view_to_slice),
+            (mask_slice, &mask_to_slice),
+            mask.mode,
+            color_filter,
+            points,
+            output,
+          );
+          return;
+        }
+
+        if *blend_mode != BlendMode::Normal {
+          self.draw_blend_layer(slice, &view_to_slice, color_filter, points, *blend_mode, output);
+          return;
+        }
 
         if !self.can_batch_img_path() {
           self.new_draw_phase(output);
@@ -491,6 +518,7 @@ where
                 .transform_vector(f.offset.into())
                 .into(),
               composite: f.composite,
+              blend_mode: f.blend_mode,
             })
             .collect(),
         }));
@@ -509,7 +537,28 @@ where
       return;
     }
 
-    let text_matrix = text_cmd.transform.then(global_matrix);
+    let text_matrix = text_cmd
+      .payload
+      .transform
+      .then(&text_cmd.transform)
+      .then(global_matrix);
+    for background in text_cmd.payload.backgrounds.iter() {
+      let mut brush = CommandBrush::from(background.brush.clone());
+      brush.apply_color_filter(&text_cmd.color_filter);
+      let path = if background.radius > 0. {
+        Path::rect_round(&background.rect, &ribir_painter::Radius::all(background.radius))
+      } else {
+        Path::rect(&background.rect)
+      };
+      let cmd = PaintCommand::Path(PathCommand {
+        path: PaintPath::Share(Resource::new(path)),
+        paint_bounds: text_matrix.outer_transformed_rect(&background.rect),
+        transform: text_matrix,
+        action: PaintPathAction::Paint { brush, painting_style: PaintingStyle::Fill },
+      });
+      self.draw_command(&cmd, &Transform::identity(), output_tex_size, output, glyph_provider);
+    }
+
     for run in text_cmd.payload.runs.iter() {
       let brush = run
         .brush
@@ -557,8 +606,17 @@ where
     origin_offset: Vector, brush: &CommandBrush, text_matrix: &Transform,
     output_tex_size: DeviceSize, output: &mut Impl::Texture,
   ) {
-    let mut ctx = GlyphDrawCtx { glyph_provider, brush, text_matrix, output_tex_size, output };
     for glyph in run.glyphs.iter() {
+      let glyph_matrix = run
+        .glyph_transform(glyph, origin_offset)
+        .then(text_matrix);
+      let mut ctx = GlyphDrawCtx {
+        glyph_provider,
+        brush,
+        text_matrix: &glyph_matrix,
+        output_tex_size,
+        output: &mut *output,
+      };
       self.draw_rasterized_glyph(
         &mut ctx,
         run.face_id,
@@ -576,11 +634,10 @@ where
     &mut self, ctx: &mut GlyphDrawCtx<'_, Impl::Texture>, face_id: FaceId, glyph_id: GlyphId,
     font_size: f32, baseline_origin: Point,
   ) {
-    let scale = ctx
-      .text_matrix
-      .m11
-      .abs()
-      .max(ctx.text_matrix.m22.abs());
+    // Use the length of the transformed axes, so a rotated text still
+    // rasterizes at its real scale.
+    let Transform { m11, m12, m21, m22, .. } = *ctx.text_matrix;
+    let scale = m11.hypot(m12).max(m21.hypot(m22));
     let physical_size = (font_size * scale).ceil().max(1.) as u16;
 
     if let Some(svg) = self.glyph_svg_image(ctx.glyph_provider, face_id, glyph_id) {
@@ -800,6 +857,19 @@ where
       .indices
       .clear();
     self.linear_gradient_stops.clear();
+    self.sweep_gradient_prims.clear();
+    self
+      .sweep_gradient_vertices_buffer
+      .vertices
+      .clear();
+    self
+      .sweep_gradient_vertices_buffer
+      .indices
+      .clear();
+    self.sweep_gradient_stops.clear();
+    self.pattern_prims.clear();
+    self.pattern_vertices_buffer.vertices.clear();
+    self.pattern_vertices_buffer.indices.clear();
     self.filter_vertices_buffer.vertices.clear();
     self.filter_vertices_buffer.indices.clear();
   }
@@ -868,6 +938,29 @@ where
     self.current_phase = CurrentPhase::Img;
   }
 
+  #[allow(clippy::too_many_arguments)]
+  fn draw_pattern_slice(
+    &mut self, img_slice: TextureSlice, transform: &Transform, pattern: &ImagePattern,
+    mask_head: i32, color_filter: &ColorMatrix, output_tex_size: DeviceSize, rect: [Point; 4],
+  ) {
+    let mask_head_and_tex_idx = mask_head << 16 | self.tex_ids_map.tex_idx(img_slice.tex_id) as i32;
+    let ColorFilterMatrix { matrix, base_color } = color_filter.to_matrix();
+    let prim = PatternPrimitive {
+      transform: transform.to_array(),
+      img_start: img_slice.rect.origin.to_f32().to_array(),
+      img_size: img_slice.rect.size.to_f32().to_array(),
+      mask_head_and_tex_idx,
+      tile: (pattern.tile_y as u32) << 16 | pattern.tile_x as u32,
+      base_color: base_color.map_or([0.; 4], |c| c.into_f32_components()),
+      color_matrix: matrix,
+    };
+    let prim_idx = self.pattern_prims.len() as u32;
+    self.pattern_prims.push(prim);
+    let buffer = &mut self.pattern_vertices_buffer;
+    add_rect_vertices(rect, output_tex_size, PatternPrimIndex(prim_idx), buffer);
+    self.current_phase = CurrentPhase::Pattern;
+  }
+
   fn can_batch_path_command(&self, cmd: &PathCommand) -> bool {
     let limits = self.gpu_impl.limits();
     let tex_used = self.tex_ids_map.len();
@@ -901,6 +994,15 @@ where
           && self.linear_gradient_prims.len() < limits.max_linear_gradient_primitives
           && self.linear_gradient_stops.len() < limits.max_gradient_stop_primitives
       }
+      (CurrentPhase::SweepGradient, CommandBrush::Sweep(_)) => {
+        tex_used < limits.max_tex_load
+          && self.sweep_gradient_prims.len() < limits.max_sweep_gradient_primitives
+          && self.sweep_gradient_stops.len() < limits.max_gradient_stop_primitives
+      }
+      (CurrentPhase::Pattern, CommandBrush::Pattern { .. }) => {
+        tex_used < limits.max_tex_load - 1
+          && self.pattern_prims.len() < limits.max_pattern_primitives
+      }
       _ => false,
     }
   }
@@ -997,8 +1099,9 @@ where
     (points, node_idx)
   }
 
-  fn draw_triangles(&mut self, output: &mut Impl::Texture) {
-    let mut color = self.surface_color.take();
+  /// Load the mask layers and textures of the current draw phase, return the
+  /// offset of the mask layers.
+  fn load_phase_resources(&mut self) -> u32 {
     let gpu_impl = &mut self.gpu_impl;
     self.tex_mgr.draw_alpha_textures(gpu_impl);
     let mask_offset =
@@ -1015,6 +1118,13 @@ where
       });
     }
     gpu_impl.load_textures(&tex_buffer);
+    mask_offset
+  }
+
+  fn draw_triangles(&mut self, output: &mut Impl::Texture) {
+    let mut color = self.surface_color.take();
+    let mask_offset = self.load_phase_resources();
+    let gpu_impl = &mut self.gpu_impl;
 
     let current_phase = std::mem::replace(&mut self.current_phase, CurrentPhase::None);
     match current_phase {
@@ -1075,6 +1185,33 @@ where
           stops_offset,
         )
       }
+      CurrentPhase::SweepGradient
+        if !self
+          .sweep_gradient_vertices_buffer
+          .indices
+          .is_empty() =>
+      {
+        let (prims_offset, stops_offset) = gpu_impl.load_sweep_gradient_data(
+          &self.sweep_gradient_prims,
+          &self.sweep_gradient_stops,
+          &self.sweep_gradient_vertices_buffer,
+        );
+        let rg = 0..self.sweep_gradient_vertices_buffer.indices.len() as u32;
+        gpu_impl.draw_sweep_gradient_triangles(
+          output,
+          rg,
+          color.take(),
+          mask_offset,
+          prims_offset,
+          stops_offset,
+        )
+      }
+      CurrentPhase::Pattern if !self.pattern_vertices_buffer.indices.is_empty() => {
+        let prims_offset =
+          gpu_impl.load_pattern_data(&self.pattern_prims, &self.pattern_vertices_buffer);
+        let rg = 0..self.pattern_vertices_buffer.indices.len() as u32;
+        gpu_impl.draw_pattern_triangles(output, rg, color.take(), mask_offset, prims_offset)
+      }
       CurrentPhase::Filter(filters) => {
         self.draw_filter(&filters, output, mask_offset);
       }
@@ -1082,6 +1219,191 @@ where
     }
   }
 
+  /// Draws the bundle `cmds` to a cached texture slice, returns the slice and
+  /// the transform from the view to the slice.
+  fn store_bundle(
+    &mut self, bounds: &Rect, matrix: &Transform, cmds: &Resource<Box<[PaintCommand]>>,
+    output: &mut Impl::Texture, glyph_provider: &dyn GlyphRasterSource,
+  ) -> (TextureSlice, Transform) {
+    let scale = self.tex_mgr.cache_scale(&bounds.size, matrix);
+    let cache_size = bounds.size * scale;
+
+    let this = self as *mut Self;
+    let (cache_scale, slice) = self.tex_mgr.store_commands(
+      cache_size.to_i32().cast_unit(),
+      cmds.clone().into_any(),
+      scale,
+      &mut self.gpu_impl,
+      |slice, tex, _| {
+        // SAFETY: We already hold a mut reference to the texture in the texture
+        // manager, so we cant use `self` here, but this texture should always exist
+        // within the frame, and no modifications will be made to the slice
+        // that has already been allocated.
+        let this = unsafe { &mut *this };
+
+        // Initiate a new drawing phase to ensure a clean state for rendering in a new
+        // texture.
+        this.new_draw_phase(output);
+
+        // store the viewport
+        let viewport = self.viewport;
+        // Overwrite the viewport to the slice bounds.
+        self
+          .clip_layer_stack
+          .push(ClipLayer { viewport: *slice, mask_head: -1 });
+
+        let matrix = Transform::translation(-bounds.origin.x, -bounds.origin.y)
+          .then_scale(scale, scale)
+          .then_translate(slice.origin.to_f32().cast_unit().to_vector());
+
+        let surface_color = this.surface_color.take();
+        this.surface_color = Some(Color::TRANSPARENT);
+        this.draw_commands(*slice, cmds, &matrix, tex, glyph_provider);
+        this.surface_color = surface_color;
+
+        // restore the clip layer and viewport
+        self.clip_layer_stack.pop();
+        this.viewport = viewport;
+      },
+    );
+
+    let view_to_slice = matrix
+      // point back to the bundle commands axis.
+      .inverse()
+      .unwrap()
+      // align to the zero point, draw image slice is start from zero.
+      .then_translate(Vector2D::new(-bounds.origin.x, -bounds.origin.y))
+      // scale to the cache size.
+      .then_scale(cache_scale, cache_scale);
+
+    (slice, view_to_slice)
+  }
+
+  /// Draw a cached bundle slice to a layer, and then composite the layer to
+  /// the output with `blend_mode`. The shader reads the backdrop from a copy of
+  /// the output, so the pending draws are flushed first.
+  fn draw_blend_layer(
+    &mut self, slice: TextureSlice, view_to_slice: &Transform, color_filter: &ColorMatrix,
+    points: [Point; 4], blend_mode: BlendMode, output: &mut Impl::Texture,
+  ) {
+    let Some(view_rect) = self.layer_view_rect(&points) else {
+      return;
+    };
+
+    self.new_draw_phase(output);
+
+    // 1. Draw the bundle to a transparent layer of the view size.
+    let layer = self.draw_slice_to_layer(slice, view_to_slice, color_filter, &view_rect, points);
+
+    // 2. Copy the backdrop the layer blends with.
+    let mut backdrop = self
+      .gpu_impl
+      .new_texture(view_rect.size, ColorFormat::Rgba8);
+    self.gpu_impl.flush_draw_commands();
+    self
+      .gpu_impl
+      .copy_texture_from_texture(&mut backdrop, DevicePoint::zero(), output, &view_rect);
+
+    // 3. Composite the layer to the output.
+    let mask_offset = self.load_phase_resources();
+    let mask_head = self.current_clip_mask_index();
+    let prim = layer_primitive(&view_rect, mask_head, blend_mode as u32, 0);
+    self.composite_layer(&layer, &backdrop, &view_rect, prim, mask_offset, output);
+    self.end_draw_phase();
+    self.begin_draw_phase();
+  }
+
+  /// Draw a cached bundle slice and its cached mask slice to two layers, and
+  /// then composite the bundle layer to the output covered by the mask layer.
+  fn draw_mask_layer(
+    &mut self, (slice, view_to_slice): (TextureSlice, &Transform),
+    (mask_slice, mask_to_slice): (TextureSlice, &Transform), mode: MaskMode,
+    color_filter: &ColorMatrix, points: [Point; 4], output: &mut Impl::Texture,
+  ) {
+    let Some(view_rect) = self.layer_view_rect(&points) else {
+      return;
+    };
+
+    self.new_draw_phase(output);
+    let layer = self.draw_slice_to_layer(slice, view_to_slice, color_filter, &view_rect, points);
+    let identity = ColorMatrix::identity();
+    let mask = self.draw_slice_to_layer(mask_slice, mask_to_slice, &identity, &view_rect, points);
+
+    let mask_offset = self.load_phase_resources();
+    let mask_head = self.current_clip_mask_index();
+    let mask_mode = match mode {
+      MaskMode::Luminance => 1,
+      MaskMode::Alpha => 2,
+    };
+    let prim = layer_primitive(&view_rect, mask_head, 0, mask_mode);
+    self.composite_layer(&layer, &mask, &view_rect, prim, mask_offset, output);
+    self.end_draw_phase();
+    self.begin_draw_phase();
+  }
+
+  /// The device rect of the layer of the `points` in the viewport.
+  fn layer_view_rect(&self, points: &[Point; 4]) -> Option<DeviceRect> {
+    let bounds = Rect::from_points(points);
+    let device_bounds = bounds.round_out().to_i32().cast_unit();
+    self.viewport().intersection(&device_bounds)
+  }
+
+  /// Draws a cached slice to a new transparent layer of the `view_rect` size,
+  /// the `points` and `view_to_slice` are in the view space.
+  fn draw_slice_to_layer(
+    &mut self, slice: TextureSlice, view_to_slice: &Transform, color_filter: &ColorMatrix,
+    view_rect: &DeviceRect, points: [Point; 4],
+  ) -> Impl::Texture {
+    let offset = view_rect.origin.to_f32().to_vector().cast_unit();
+    let layer_points = points.map(|p| p - offset);
+    let layer_to_slice = view_to_slice.pre_translate(offset);
+    let mut layer = self
+      .gpu_impl
+      .new_texture(view_rect.size, ColorFormat::Rgba8);
+    self.draw_img_slice(
+      slice,
+      &layer_to_slice,
+      -1,
+      color_filter,
+      view_rect.size,
+      layer_points,
+      true,
+    );
+    let surface_color = self.surface_color.replace(Color::TRANSPARENT);
+    self.new_draw_phase(&mut layer);
+    self.surface_color = surface_color;
+    layer
+  }
+
+  /// Composites the `layer` of the `view_rect` to the output, the `backdrop`
+  /// is the content to blend with or the mask of the layer.
+  fn composite_layer(
+    &mut self, layer: &Impl::Texture, backdrop: &Impl::Texture, view_rect: &DeviceRect,
+    prim: TexturePrimitive, mask_offset: u32, output: &mut Impl::Texture,
+  ) {
+    self.texture_vertices_buffer.clear();
+    let rect = view_rect.to_f32().cast_unit();
+    add_rect_vertices(
+      rect_corners(&rect),
+      output.size(),
+      TexturePrimIndex(0),
+      &mut self.texture_vertices_buffer,
+    );
+    let prims_offset = self
+      .gpu_impl
+      .load_texture_data(&[prim], &self.texture_vertices_buffer);
+    let indices = 0..self.texture_vertices_buffer.indices.len() as u32;
+    self.gpu_impl.draw_texture_triangles(
+      output,
+      indices,
+      None,
+      layer,
+      Some(backdrop),
+      mask_offset,
+      prims_offset,
+    );
+  }
+
   fn draw_filter(&mut self, filter: &FilterPhase, output: &mut Impl::Texture, mask_offset: u32) {
     let mask_origin: DevicePoint = filter.view_rect.origin.cast().cast_unit();
     let size = filter.view_rect.size;
@@ -1211,7 +1533,9 @@ where
           mask_head: filter.mask_head,
           opacity: 1.0,
           is_premultiplied: 1,
-          _padding: [0; 3],
+          blend_mode: 0,
+          mask_mode: 0,
+          _padding: 0,
         };
 
         self.texture_vertices_buffer.clear();
@@ -1232,6 +1556,7 @@ where
           indices,
           None,
           src_tex,
+          None,
           mask_offset,
           prims_offset,
         );
@@ -1240,6 +1565,35 @@ where
         std::mem::swap(&mut p_src, &mut p_dst);
       }
 
+      if layer.blend_mode != BlendMode::Normal {
+        // Blend the result with the content under the filter.
+        let backdrop = unsafe { &mut *p_dst };
+        self.gpu_impl.copy_texture_from_texture(
+          backdrop,
+          DevicePoint::zero(),
+          output,
+          &filter.view_rect,
+        );
+        let prim = layer_primitive(&filter.view_rect, filter.mask_head, layer.blend_mode as u32, 0);
+        self.composite_layer(
+          unsafe { &*p_src },
+          backdrop,
+          &filter.view_rect,
+          prim,
+          mask_offset,
+          output,
+        );
+        // The blended output is the source of the next layer.
+        self.gpu_impl.flush_draw_commands();
+        self.gpu_impl.copy_texture_from_texture(
+          unsafe { &mut *p_src },
+          DevicePoint::zero(),
+          output,
+          &filter.view_rect,
+        );
+        continue;
+      }
+
       // Update Output (Source for next layer)
       self.gpu_impl.copy_texture_from_texture(
         output,
@@ -1270,6 +1624,22 @@ impl TextureIdxMap {
   fn len(&self) -> usize { self.textures.len() }
 }
 
+/// The primitive to composite a layer of the `view_rect` to the output.
+fn layer_primitive(
+  view_rect: &DeviceRect, mask_head: i32, blend_mode: u32, mask_mode: u32,
+) -> TexturePrimitive {
+  let origin = view_rect.origin.to_f32();
+  TexturePrimitive {
+    transform: Transform::translation(-origin.x, -origin.y).to_array(),
+    mask_head,
+    opacity: 1.0,
+    is_premultiplied: 1,
+    blend_mode,
+    mask_mode,
+    _padding: 0,
+  }
+}
+
 pub fn vertices_coord(pos: Point, tex_size: DeviceSize) -> [f32; 2] {
   [pos.x / tex_size.width as f32, pos.y / tex_size.height as f32]
 }
@@ -1316,7 +1686,9 @@ mod tests {
   use ribir_algo::Resource;
   use ribir_core::prelude::AppCtx;
   use ribir_dev_helper::*;
-  use ribir_painter::{Brush, LineCap, LineJoin, Painter, Path, Radius, StrokeOptions, Svg};
+  use ribir_painter::{
+    Brush, Filter, LineCap, LineJoin, Painter, Path, Radius, StrokeOptions, Svg,
+  };
   use ribir_types::*;
 
   use super::*;
@@ -1429,6 +1801,7 @@ mod tests {
       miter_limit: 4.0,
       line_cap: LineCap::Butt,
       line_join: LineJoin::Miter,
+      dash: None,
     };
     assert!(GPUBackend::<crate::WgpuImpl>::support_sdf_stroke(&base));
 
@@ -1438,6 +1811,9 @@ mod tests {
     let round_join = StrokeOptions { line_join: LineJoin::Round, ..base.clone() };
     assert!(!GPUBackend::<crate::WgpuImpl>::support_sdf_stroke(&round_join));
 
+    let dashed = StrokeOptions { dash: Some(([4., 2.][..].into(), 0.)), ..base.clone() };
+    assert!(!GPUBackend::<crate::WgpuImpl>::support_sdf_stroke(&dashed));
+
     let zero_width = StrokeOptions { width: 0.0, ..base };
     assert!(!GPUBackend::<crate::WgpuImpl>::support_sdf_stroke(&zero_width));
   }
@@ -1457,6 +1833,121 @@ mod tests {
     painter
   }
 
+  painter_backend_eq_image_test!(dashed_strokes, comparison = 0.001);
+  fn dashed_strokes() -> Painter {
+    let mut painter = painter(Size::new(200., 100.));
+    let line = |painter: &mut Painter, y: f32| {
+      painter
+        .begin_path(Point::new(10., y))
+        .line_to(Point::new(190., y))
+        .end_path(false);
+    };
+    painter
+      .set_stroke_brush(Color::RED)
+      .set_line_width(6.);
+
+    line(&mut painter, 20.);
+    painter
+      .set_line_dash(Some(([12., 6.][..].into(), 0.)))
+      .stroke();
+
+    // Zero-length dashes with round caps draw dots.
+    line(&mut painter, 50.);
+    painter
+      .set_line_cap(LineCap::Round)
+      .set_line_dash(Some(([0., 12.][..].into(), 0.)))
+      .stroke();
+
+    painter
+      .begin_path(Point::new(10., 80.))
+      .bezier_curve_to(Point::new(70., 60.), Point::new(130., 100.), Point::new(190., 80.))
+      .end_path(false);
+    painter
+      .set_line_dash(Some(([16., 8., 2., 8.][..].into(), 4.)))
+      .stroke();
+
+    painter
+  }
+
+  painter_backend_eq_image_test!(blend_modes, comparison = 0.002);
+  fn blend_modes() -> Painter {
+    let mut painter = painter(Size::new(240., 140.));
+    let leaves = Resource::new(webp_image(include_bytes!("../imgs/leaves.webp")));
+    painter
+      .set_fill_brush(leaves)
+      .rect(&Rect::from_size(Size::new(240., 140.)), true)
+      .fill();
+
+    let modes = [BlendMode::Multiply, BlendMode::Screen, BlendMode::Overlay, BlendMode::Difference];
+    for (i, mode) in modes.into_iter().enumerate() {
+      let rect = Rect::new(Point::new(10. + 57. * i as f32, 10.), Size::new(50., 50.));
+      painter
+        .save()
+        .set_blend_mode(mode)
+        .set_fill_brush(Color::from_rgb(255, 128, 0))
+        .rect(&rect, true)
+        .fill()
+        .restore();
+    }
+
+    // A filter layer blends its result with the content under it.
+    let area = Path::rect(&Rect::new(Point::new(10., 80.), Size::new(220., 50.)));
+    painter.filter_path(area.into(), Filter::grayscale(1.).blend_mode(BlendMode::Multiply));
+
+    painter
+  }
+
+  painter_backend_eq_image_test!(sweep_gradients, comparison = 0.002);
+  fn sweep_gradients() -> Painter {
+    let mut painter = painter(Size::new(220., 110.));
+    let stops = vec![
+      GradientStop::new(Color::RED, 0.),
+      GradientStop::new(Color::YELLOW, 0.5),
+      GradientStop::new(Color::BLUE, 1.),
+    ];
+    let full_turn = SweepGradient::new(Point::new(55., 55.), stops.clone());
+    painter
+      .set_fill_brush(full_turn)
+      .circle(Point::new(55., 55.), 50., true)
+      .fill();
+
+    // A partial sweep that repeats, stroked with a wide line.
+    let mut partial = SweepGradient::new(Point::new(165., 55.), stops);
+    partial.start_angle = Angle::degrees(45.);
+    partial.end_angle = Angle::degrees(135.);
+    partial.spread_method = SpreadMethod::Repeat;
+    painter
+      .set_stroke_brush(partial)
+      .set_line_width(16.)
+      .circle(Point::new(165., 55.), 40., true)
+      .stroke();
+
+    painter
+  }
+
+  painter_backend_eq_image_test!(image_patterns, comparison = 0.002);
+  fn image_patterns() -> Painter {
+    let mut painter = painter(Size::new(300., 200.));
+    let leaves = Resource::new(webp_image(include_bytes!("../imgs/leaves.webp")));
+    let scale = Transform::scale(0.25, 0.25);
+    let patterns = [
+      ImagePattern::new(leaves.clone()),
+      ImagePattern::mirror(leaves.clone()),
+      ImagePattern::repeat_x(leaves.clone()),
+      ImagePattern::repeat_y(leaves).with_tile(TileMode::NoRepeat, TileMode::Mirror),
+    ];
+    for (i, pattern) in patterns.into_iter().enumerate() {
+      let origin = Point::new(10. + 145. * (i % 2) as f32, 10. + 95. * (i / 2) as f32);
+      let pattern = pattern.with_transform(scale.then_translate(origin.to_vector()));
+      painter
+        .set_fill_brush(pattern)
+        .rect(&Rect::new(origin, Size::new(135., 85.)), true)
+        .fill();
+    }
+
+    painter
+  }
+
   painter_backend_eq_image_test!(two_img_brush, comparison = 0.006);
   fn two_img_brush() -> Painter {
     let mut painter = painter(Size::new(200., 100.));
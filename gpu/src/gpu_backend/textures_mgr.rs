@@ -551,11 +551,17 @@ impl Hash for PathKey {
       PathKey::Fill(path) => path.hash(state),
       PathKey::Stroke { resource: path, options } => {
         path.hash(state);
-        let StrokeOptions { width, miter_limit, line_cap, line_join } = options;
+        let StrokeOptions { width, miter_limit, line_cap, line_join, dash } = options;
         width.to_bits().hash(state);
         miter_limit.to_bits().hash(state);
         line_cap.hash(state);
         line_join.hash(state);
+        if let Some((pattern, offset)) = dash {
+          pattern
+            .iter()
+            .for_each(|len| len.to_bits().hash(state));
+          offset.to_bits().hash(state);
+        }
       }
     }
   }
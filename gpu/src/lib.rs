@@ -59,8 +59,21 @@ pub use wgpu_impl::*;
 ///   |     |  +------------------------------------+    |
 ///   |     |  | load_linear_gradient_primitives()  |    |
 ///   |     +->| load_linear_gradient_stops()       |    |
-///   |        | load_linear_gradient_vertices()    |    |
-///   |        | draw_linear_gradient_triangles()   |    |
+///   |     |  | load_linear_gradient_vertices()    |    |
+///   |     |  | draw_linear_gradient_triangles()   |    |
+///   |     |  +------------------------------------+    |
+///   |     |                                            |
+///   |     |  +------------------------------------+    |
+///   |     |  | load_sweep_gradient_primitives()   |    |
+///   |     +->| load_sweep_gradient_stops()        |    |
+///   |     |  | load_sweep_gradient_vertices()     |    |
+///   |     |  | draw_sweep_gradient_triangles()    |    |
+///   |     |  +------------------------------------+    |
+///   |     |                                            |
+///   |     |  +------------------------------------+    |
+///   |     |  | load_pattern_primitives()          |    |
+///   |     +->| load_pattern_vertices()            |    |
+///   |        | draw_pattern_triangles()           |    |
 ///   |        +------------------------------------+    |
 ///   +---<----------------------------------------------+
 ///
@@ -133,6 +146,18 @@ pub trait GPUBackendImpl {
     buffers: &VertexBuffers<LinearGradientPrimIndex>,
   ) -> (u32, u32);
 
+  /// Load the primitives and gradient color stops that
+  /// `draw_sweep_gradient_triangles` will use.
+  fn load_sweep_gradient_data(
+    &mut self, primitives: &[SweepGradientPrimitive], stops: &[GradientStopPrimitive],
+    buffers: &VertexBuffers<SweepGradientPrimIndex>,
+  ) -> (u32, u32);
+
+  /// Load the primitives and vertices that `draw_pattern_triangles` will use.
+  fn load_pattern_data(
+    &mut self, primitives: &[PatternPrimitive], buffers: &VertexBuffers<PatternPrimIndex>,
+  ) -> u32;
+
   /// load the filter
   fn load_filter_data(
     &mut self, primitives: &FilterPrimitive, kernel_matrix: &[f32], buffers: &VertexBuffers<()>,
@@ -170,6 +195,20 @@ pub trait GPUBackendImpl {
     mask_offset: u32, prims_offset: u32, stops_offset: u32,
   );
 
+  /// Draw triangles fill with color sweep gradient. And use the clear color
+  /// clear the texture first if it's a Some-Value
+  fn draw_sweep_gradient_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+    mask_offset: u32, prims_offset: u32, stops_offset: u32,
+  );
+
+  /// Draw triangles fill with a tiled image pattern. And use the clear color
+  /// clear the texture first if it's a Some-Value
+  fn draw_pattern_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+    mask_offset: u32, prims_offset: u32,
+  );
+
   /// Flush any pending draw commands to the queue.
   fn flush_draw_commands(&mut self);
 
@@ -184,10 +223,14 @@ pub trait GPUBackendImpl {
   ) -> u32;
 
   /// Draw triangles fill with texture. And use the clear color clear the
-  /// texture first if it's a Some-Value
+  /// texture first if it's a Some-Value. The `backdrop` is the copy of the
+  /// content behind, the primitives with a blend mode blend with it, or the
+  /// mask of the primitives with a mask mode.
+  #[allow(clippy::too_many_arguments)]
   fn draw_texture_triangles(
     &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
-    from_texture: &Self::Texture, mask_offset: u32, prims_offset: u32,
+    from_texture: &Self::Texture, backdrop: Option<&Self::Texture>, mask_offset: u32,
+    prims_offset: u32,
   );
 
   /// A frame end, call once per frame
@@ -209,6 +252,12 @@ pub struct DrawPhaseLimits {
   /// The maximum number of linear gradient primitives that the backend can load
   /// in a single draw
   pub max_linear_gradient_primitives: usize,
+  /// The maximum number of sweep gradient primitives that the backend can load
+  /// in a single draw
+  pub max_sweep_gradient_primitives: usize,
+  /// The maximum number of pattern primitives that the backend can load in a
+  /// single draw
+  pub max_pattern_primitives: usize,
   /// The maximum number of gradient stops that the backend can load in a single
   /// draw phase
   pub max_gradient_stop_primitives: usize,
@@ -244,6 +293,14 @@ pub struct RadialGradientPrimIndex(u32);
 #[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
 pub struct LinearGradientPrimIndex(u32);
 
+#[repr(C, packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct SweepGradientPrimIndex(u32);
+
+#[repr(C, packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct PatternPrimIndex(u32);
+
 #[repr(C, packed)]
 #[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
 pub struct GradientStopPrimitive {
@@ -303,6 +360,29 @@ pub struct LinearGradientPrimitive {
   pub mask_head_and_spread: i32,
 }
 
+#[repr(C, packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct SweepGradientPrimitive {
+  /// A 2x3 column-major matrix, transform a vertex position to the texture
+  /// position
+  pub transform: [f32; 6],
+  /// position of the center
+  pub center: [f32; 2],
+  /// the angle in radians where the gradient starts.
+  pub start_angle: f32,
+  /// the angle in radians where the gradient ends.
+  pub end_angle: f32,
+  /// The color stop information, there are two parts:
+  /// - The high 16-bit index represents the start index of the color stop.
+  /// - The low 16-bit index represents the size of the color stop.
+  pub stop: u32,
+  /// A mix of two 16-bit values:
+  /// - The high 16-bit index represents the head mask layer.
+  /// - The low 16-bit represents the spread method of the gradient. 0 for pad,
+  ///   1 for reflect and 2 for repeat
+  pub mask_head_and_spread: i32,
+}
+
 #[repr(C, packed)]
 #[derive(AsBytes, PartialEq, Clone, Copy)]
 pub struct ImgPrimitive {
@@ -329,6 +409,33 @@ pub struct ImgPrimitive {
   pub color_matrix: [f32; 4 * 4],
 }
 
+#[repr(C, packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy)]
+pub struct PatternPrimitive {
+  /// A 2x3 column-major matrix, transform a vertex position to the image
+  /// space of the pattern.
+  pub transform: [f32; 6],
+  /// The origin of the image placed in texture.
+  pub img_start: [f32; 2],
+  /// The size of the image.
+  pub img_size: [f32; 2],
+
+  /// This represents a mix of two 16-bit indices:
+  /// - The high 16-bit index represents the head mask layer. It is an i16.
+  /// - The low 16-bit index represents the texture. It is a u16.
+  pub mask_head_and_tex_idx: i32,
+
+  /// The tile mode of the x-axis in the low 16 bits and the y-axis in the high
+  /// 16 bits, 0 for repeat, 1 for mirror and 2 for no-repeat.
+  pub tile: u32,
+
+  /// the final pix color will be color * color_matrix + base_color
+  pub base_color: [f32; 4],
+
+  /// the final pix color will be color * color_matrix + base_color
+  pub color_matrix: [f32; 4 * 4],
+}
+
 /// The mask layer describes an alpha channel layer that is used in the fragment
 /// shader to sample the alpha channel and apply it to the color.
 #[derive(AsBytes, Clone)]
@@ -431,6 +538,13 @@ pub struct TexturePrimitive {
   /// The index of the texture.
   /// 1 for premultiplied alpha, 0 for non-premultiplied alpha
   pub is_premultiplied: u32,
+  /// The `BlendMode` used to composite with the backdrop texture, 0 for
+  /// `BlendMode::Normal`.
+  pub blend_mode: u32,
+  /// How the backdrop texture masks the texture, 0 for no mask, 1 for the
+  /// `MaskMode::Luminance` and 2 for the `MaskMode::Alpha`. A mask is not used
+  /// with a blend mode.
+  pub mask_mode: u32,
   /// dummy for align
-  pub _padding: [u32; 3],
+  pub _padding: u32,
 }
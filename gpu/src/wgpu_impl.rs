@@ -17,7 +17,9 @@ use self::{
   draw_color_triangles_pass::DrawColorTrianglesPass,
   draw_img_triangles_pass::DrawImgTrianglesPass,
   draw_linear_gradient_pass::DrawLinearGradientTrianglesPass,
+  draw_pattern_pass::DrawPatternTrianglesPass,
   draw_radial_gradient_pass::DrawRadialGradientTrianglesPass,
+  draw_sweep_gradient_pass::DrawSweepGradientTrianglesPass,
   primitive_pool::{PrimitivePool, PrimitivePoolMode},
   texture_pass::{ClearTexturePass, CopyTexturePass},
   uniform::Uniform,
@@ -25,7 +27,8 @@ use self::{
 use crate::{
   ColorAttr, DrawPhaseLimits, FilterPrimitive, GPUBackendImpl, GradientStopPrimitive,
   ImagePrimIndex, ImgPrimitive, LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer,
-  RadialGradientPrimIndex, RadialGradientPrimitive, TexturePrimIndex, TexturePrimitive,
+  PatternPrimIndex, PatternPrimitive, RadialGradientPrimIndex, RadialGradientPrimitive,
+  SweepGradientPrimIndex, SweepGradientPrimitive, TexturePrimIndex, TexturePrimitive,
   gpu_backend::Texture,
   wgpu_impl::{draw_filter_pass::DrawFilterPass, draw_texture_pass::DrawTexturePass},
 };
@@ -39,7 +42,9 @@ mod draw_color_triangles_pass;
 mod draw_filter_pass;
 mod draw_img_triangles_pass;
 mod draw_linear_gradient_pass;
+mod draw_pattern_pass;
 mod draw_radial_gradient_pass;
+mod draw_sweep_gradient_pass;
 mod draw_texture_pass;
 mod texture_pass;
 
@@ -60,6 +65,8 @@ pub struct WgpuImpl {
   img_triangles_pass: Option<DrawImgTrianglesPass>,
   radial_gradient_pass: Option<DrawRadialGradientTrianglesPass>,
   linear_gradient_pass: Option<DrawLinearGradientTrianglesPass>,
+  sweep_gradient_pass: Option<DrawSweepGradientTrianglesPass>,
+  pattern_pass: Option<DrawPatternTrianglesPass>,
   filter_pass: Option<DrawFilterPass>,
   draw_texture_pass: Option<DrawTexturePass>,
   texs_layout: wgpu::BindGroupLayout,
@@ -68,9 +75,9 @@ pub struct WgpuImpl {
   slot0_pool: PrimitivePool,
   slot1_pool: PrimitivePool,
   /// Mode for draw passes that use both slot0 (primitives) and slot1 (stops):
-  /// color, radial-gradient, linear-gradient.
+  /// color, radial-gradient, linear-gradient, sweep-gradient.
   dual_slot_mode: PrimitivePoolMode,
-  /// Mode for draw passes that use only slot0: img, filter, texture.
+  /// Mode for draw passes that use only slot0: img, pattern, filter, texture.
   slot0_only_mode: PrimitivePoolMode,
   limits: DrawPhaseLimits,
   surface_format: Option<wgpu::TextureFormat>,
@@ -167,6 +174,45 @@ macro_rules! linear_gradient_pass {
   }};
 }
 
+macro_rules! sweep_gradient_pass {
+  ($backend:ident) => {{
+    let slot0_layout = $backend.slot0_pool.layout();
+    let slot1_layout = $backend.slot1_pool.layout();
+
+    $backend
+      .sweep_gradient_pass
+      .get_or_insert_with(|| {
+        DrawSweepGradientTrianglesPass::new(
+          &$backend.device,
+          $backend.mask_layers_uniform.layout(),
+          &$backend.texs_layout,
+          slot0_layout,
+          slot1_layout,
+          $backend.dual_slot_mode,
+          &$backend.limits,
+        )
+      })
+  }};
+}
+
+macro_rules! pattern_pass {
+  ($backend:ident) => {{
+    let slot0_layout = $backend.slot0_pool.layout();
+
+    $backend.pattern_pass.get_or_insert_with(|| {
+      DrawPatternTrianglesPass::new(
+        &$backend.device,
+        $backend.mask_layers_uniform.layout(),
+        &$backend.texs_layout,
+        slot0_layout,
+        $backend.slot1_pool.layout(),
+        $backend.slot0_only_mode,
+        &$backend.limits,
+      )
+    })
+  }};
+}
+
 macro_rules! filter_pass {
   ($backend:ident) => {{
     let slot0_layout = $backend.slot0_pool.layout();
@@ -281,6 +327,12 @@ impl WgpuImpl {
     if let Some(p) = self.linear_gradient_pass.as_mut() {
       p.reset();
     }
+    if let Some(p) = self.sweep_gradient_pass.as_mut() {
+      p.reset();
+    }
+    if let Some(p) = self.pattern_pass.as_mut() {
+      p.reset();
+    }
     if let Some(p) = self.filter_pass.as_mut() {
       p.reset();
     }
@@ -407,6 +459,48 @@ impl WgpuImpl {
     linear_gradient_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue)
   }
 
+  fn try_load_sweep_gradient_primitives(
+    &mut self, primitives: &[SweepGradientPrimitive],
+  ) -> Option<u32> {
+    self
+      .slot0_pool
+      .write_typed_slice(&self.queue, primitives)
+      .map(|slice| {
+        let _ = self.slot0_pool.index_base(slice);
+        self.slot0_pool.resolve_load_offset(slice)
+      })
+  }
+
+  fn try_load_sweep_gradient_stops(&mut self, stops: &[GradientStopPrimitive]) -> Option<u32> {
+    self
+      .slot1_pool
+      .write_typed_slice(&self.queue, stops)
+      .map(|slice| {
+        let _ = self.slot1_pool.index_base(slice);
+        self.slot1_pool.resolve_load_offset(slice)
+      })
+  }
+
+  fn try_load_sweep_gradient_vertices(
+    &mut self, buffers: &VertexBuffers<SweepGradientPrimIndex>,
+  ) -> Option<()> {
+    sweep_gradient_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue)
+  }
+
+  fn try_load_pattern_primitives(&mut self, primitives: &[PatternPrimitive]) -> Option<u32> {
+    self
+      .slot0_pool
+      .write_typed_slice(&self.queue, primitives)
+      .map(|slice| {
+        let _ = self.slot0_pool.index_base(slice);
+        self.slot0_pool.resolve_load_offset(slice)
+      })
+  }
+
+  fn try_load_pattern_vertices(&mut self, buffers: &VertexBuffers<PatternPrimIndex>) -> Option<()> {
+    pattern_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue)
+  }
+
   fn try_load_filter_primitive(
     &mut self, primitive: &FilterPrimitive, kernel_matrix: &[f32],
   ) -> Option<u32> {
@@ -551,6 +645,38 @@ impl GPUBackendImpl for WgpuImpl {
     load(self).unwrap()
   }
 
+  fn load_sweep_gradient_data(
+    &mut self, primitives: &[SweepGradientPrimitive], stops: &[GradientStopPrimitive],
+    buffers: &VertexBuffers<SweepGradientPrimIndex>,
+  ) -> (u32, u32) {
+    let load = |b: &mut Self| {
+      let p_offset = b.try_load_sweep_gradient_primitives(primitives)?;
+      let s_offset = b.try_load_sweep_gradient_stops(stops)?;
+      b.try_load_sweep_gradient_vertices(buffers)?;
+      Some((p_offset, s_offset))
+    };
+    if let Some(offsets) = load(self) {
+      return offsets;
+    }
+    self.atomic_flush();
+    load(self).unwrap()
+  }
+
+  fn load_pattern_data(
+    &mut self, primitives: &[PatternPrimitive], buffers: &VertexBuffers<PatternPrimIndex>,
+  ) -> u32 {
+    let load = |b: &mut Self| {
+      let offset = b.try_load_pattern_primitives(primitives)?;
+      b.try_load_pattern_vertices(buffers)?;
+      Some(offset)
+    };
+    if let Some(offset) = load(self) {
+      return offset;
+    }
+    self.atomic_flush();
+    load(self).unwrap()
+  }
+
   fn load_filter_data(
     &mut self, primitive: &FilterPrimitive, kernel_matrix: &[f32], buffers: &VertexBuffers<()>,
   ) -> u32 {
@@ -639,6 +765,52 @@ impl GPUBackendImpl for WgpuImpl {
     );
   }
 
+  fn draw_sweep_gradient_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+    mask_offset: u32, prims_offset: u32, stops_offset: u32,
+  ) {
+    let slot0_bind = self.slot0_pool.bind_group();
+    let slot1_bind = self.slot1_pool.bind_group();
+    let encoder = command_encoder!(self);
+
+    sweep_gradient_pass!(self).draw_triangles(
+      texture,
+      indices,
+      clear,
+      &self.device,
+      encoder,
+      self.textures_bind.as_ref().unwrap(),
+      &self.mask_layers_uniform,
+      slot0_bind,
+      slot1_bind,
+      mask_offset,
+      self.slot0_pool.bind_offset(prims_offset),
+      self.slot1_pool.bind_offset(stops_offset),
+    );
+  }
+
+  fn draw_pattern_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+    mask_offset: u32, prims_offset: u32,
+  ) {
+    let slot0_bind = self.slot0_pool.bind_group();
+    let encoder = command_encoder!(self);
+
+    pattern_pass!(self).draw_triangles(
+      texture,
+      indices,
+      clear,
+      &self.device,
+      encoder,
+      self.textures_bind.as_ref().unwrap(),
+      &self.mask_layers_uniform,
+      slot0_bind,
+      self.slot1_pool.bind_group(),
+      mask_offset,
+      self.slot0_pool.bind_offset(prims_offset),
+    );
+  }
+
   fn draw_alpha_triangles_with_scissor(
     &mut self, indices: &Range<u32>, texture: &mut Self::Texture, scissor: DeviceRect,
     size_offset: u32,
@@ -755,7 +927,8 @@ impl GPUBackendImpl for WgpuImpl {
 
   fn draw_texture_triangles(
     &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
-    from_texture: &Self::Texture, mask_offset: u32, prims_offset: u32,
+    from_texture: &Self::Texture, backdrop: Option<&Self::Texture>, mask_offset: u32,
+    prims_offset: u32,
   ) {
     let slot0_bind = self.slot0_pool.bind_group();
     let encoder = command_encoder!(self);
@@ -772,6 +945,7 @@ impl GPUBackendImpl for WgpuImpl {
       mask_offset,
       self.slot0_pool.bind_offset(prims_offset),
       from_texture,
+      backdrop.unwrap_or(from_texture),
     );
   }
 
@@ -1199,6 +1373,8 @@ impl WgpuImpl {
       max_image_primitives: uniform_bytes / size_of::<ImgPrimitive>(),
       max_radial_gradient_primitives: uniform_bytes / size_of::<RadialGradientPrimitive>(),
       max_linear_gradient_primitives: uniform_bytes / size_of::<LinearGradientPrimitive>(),
+      max_sweep_gradient_primitives: uniform_bytes / size_of::<SweepGradientPrimitive>(),
+      max_pattern_primitives: uniform_bytes / size_of::<PatternPrimitive>(),
       max_gradient_stop_primitives: uniform_bytes / size_of::<GradientStopPrimitive>(),
       max_mask_layers: uniform_bytes / size_of::<MaskLayer>(),
       max_filter_matrix_len,
@@ -1271,6 +1447,8 @@ impl WgpuImpl {
       img_triangles_pass: None,
       radial_gradient_pass: None,
       linear_gradient_pass: None,
+      sweep_gradient_pass: None,
+      pattern_pass: None,
       filter_pass: None,
       draw_texture_pass: None,
       texs_layout,
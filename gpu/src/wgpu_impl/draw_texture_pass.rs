@@ -25,17 +25,8 @@ impl DrawTexturePass {
     pool_mode: PrimitivePoolMode, limits: &DrawPhaseLimits,
   ) -> Self {
     let input_tex_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-      entries: &[wgpu::BindGroupLayoutEntry {
-        binding: 0,
-        visibility: wgpu::ShaderStages::FRAGMENT,
-        ty: wgpu::BindingType::Texture {
-          sample_type: wgpu::TextureSampleType::Float { filterable: true },
-          view_dimension: wgpu::TextureViewDimension::D2,
-          multisampled: false,
-        },
-        count: None,
-      }],
-      label: Some("Single texture layout"),
+      entries: &[input_tex_entry(0), input_tex_entry(1)],
+      label: Some("Input and backdrop textures layout"),
     });
 
     let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -83,7 +74,7 @@ impl DrawTexturePass {
     &mut self, texture: &WgpuTexture, indices: Range<u32>, clear: Option<Color>,
     device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder, textures_bind: &wgpu::BindGroup,
     mask_layer_storage: &Uniform<MaskLayer>, slot0_bind: &wgpu::BindGroup, mask_layer_offset: u32,
-    prims_offset: u32, from_texture: &WgpuTexture,
+    prims_offset: u32, from_texture: &WgpuTexture, backdrop: &WgpuTexture,
   ) {
     self.update(texture.format(), device);
     let pipeline = self.pipeline.as_ref().unwrap();
@@ -99,10 +90,16 @@ impl DrawTexturePass {
 
     let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
       layout: &self.input_tex_layout,
-      entries: &[wgpu::BindGroupEntry {
-        binding: 0,
-        resource: wgpu::BindingResource::TextureView(from_texture.view()),
-      }],
+      entries: &[
+        wgpu::BindGroupEntry {
+          binding: 0,
+          resource: wgpu::BindingResource::TextureView(from_texture.view()),
+        },
+        wgpu::BindGroupEntry {
+          binding: 1,
+          resource: wgpu::BindingResource::TextureView(backdrop.view()),
+        },
+      ],
       label: Some("Draw texture bind group"),
     });
 
@@ -197,3 +194,16 @@ impl DrawTexturePass {
     }
   }
 }
+
+fn input_tex_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+  wgpu::BindGroupLayoutEntry {
+    binding,
+    visibility: wgpu::ShaderStages::FRAGMENT,
+    ty: wgpu::BindingType::Texture {
+      sample_type: wgpu::TextureSampleType::Float { filterable: true },
+      view_dimension: wgpu::TextureViewDimension::D2,
+      multisampled: false,
+    },
+    count: None,
+  }
+}
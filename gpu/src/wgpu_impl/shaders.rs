@@ -353,6 +353,156 @@ fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
 "#
 }
 
+pub fn sweep_gradient_shader(limits: &DrawPhaseLimits, pool_mode: PrimitivePoolMode) -> String {
+  let primitive_and_stops_decl = match pool_mode {
+    PrimitivePoolMode::Uniform => {
+      format!(
+        r#"
+@group(2) @binding(0)
+var<uniform> prims: array<Primitive, {}>;
+
+@group(3) @binding(0)
+var<uniform> stops: array<StopPair, {}>;"#,
+        limits.max_sweep_gradient_primitives,
+        limits.max_gradient_stop_primitives / 2,
+      )
+    }
+    PrimitivePoolMode::Storage => r#"
+@group(2) @binding(0)
+var<storage, read> prims: array<Primitive>;
+
+@group(3) @binding(0)
+var<storage, read> stops: array<StopPair>;"#
+      .to_string(),
+  };
+
+  basic_template(limits.max_mask_layers)
+    + &primitive_and_stops_decl
+    + r#"
+struct Vertex {
+  @location(0) pos: vec2<f32>,
+  @location(1) @interpolate(flat) prim_idx: u32,
+};
+
+struct FragInput {
+  @builtin(position) pos: vec4<f32>,
+  @location(0) @interpolate(flat) prim_idx: u32,
+}
+
+@vertex
+fn vs_main(v: Vertex) -> FragInput {
+    var input: FragInput;
+    // convert from gpu-backend coords(0..1) to wgpu corrds(-1..1)
+    let pos = v.pos * vec2(2., -2.) + vec2(-1., 1.);
+    input.pos = vec4<f32>(pos, 0.0, 1.0);
+    input.prim_idx = v.prim_idx;
+    return input;
+}
+
+// A pair of stops. This arrangement aligns the stops with 16 bytes, minimizing excessive padding.
+struct StopPair {
+    color1: u32,
+    offset1: f32,
+    color2: u32,
+    offset2: f32,
+}
+
+struct Stop {
+    color: vec4<f32>,
+    offset: f32,
+}
+
+// Since a the different alignment between WebGPU and WebGL, we not use 
+// mat3x2<f32> in the struct, but use vec2<f32> instead. Then, we compose it.
+struct Primitive {
+  t0: vec2<f32>,
+  t1: vec2<f32>,
+  t2: vec2<f32>,
+  center: vec2<f32>,
+  start_angle: f32,
+  end_angle: f32,
+  // A value mixed stop_start(u16) and stop_cnt(u16)
+  stop: u32,
+  // A value mixed mask_head(i16) and spread(u16)
+  mask_head_and_spread: i32
+}
+
+
+const TAU: f32 = 6.283185307179586;
+
+fn calc_offset(pos: vec2<f32>, prim: Primitive) -> f32 {
+    let span = prim.end_angle - prim.start_angle;
+    let v = pos - prim.center;
+    let angle = (atan2(v.y, v.x) - prim.start_angle) * sign(span);
+    return (angle - TAU * floor(angle / TAU)) / abs(span);
+}
+
+fn unpackUnorm4x8(packed: u32) -> vec4<f32> {
+    return vec4<f32>(
+        f32((packed & 0xff000000) >> 24) / 255.0,
+        f32((packed & 0x00ff0000) >> 16) / 255.0,
+        f32((packed & 0x0000ff00) >> 8) / 255.0,
+        f32((packed & 0x000000ff) >> 0) / 255.0
+    );
+}
+
+fn get_stop(idx: u32) -> Stop {
+    let pair = stops[idx / 2];
+    if idx % 2 == 0 {
+        return Stop(unpackUnorm4x8(pair.color1), pair.offset1);
+    } else {
+        return Stop(unpackUnorm4x8(pair.color2), pair.offset2);
+    }
+}
+
+@fragment
+fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
+    let prim = prims[input.prim_idx];
+    let pos = mat3x2(prim.t0, prim.t1, prim.t2) * vec3(input.pos.xy, 1.);
+
+    var alpha = 1.;
+    var mask_idx = prim.mask_head_and_spread >> 16;
+    loop {
+        if mask_idx < 0 { break; }
+
+        let mask = mask_layers[u32(mask_idx)];
+        alpha *= mask_sample(mask, input.pos.xy);
+        mask_idx = mask.prev_mask_idx;
+    }
+
+    var offset = 0.;
+    if prim.start_angle != prim.end_angle {
+        offset = calc_offset(pos, prim);
+    }
+    let spread = abs(prim.mask_head_and_spread & 0x0000ffff);
+    if spread == 0 {
+        // pad
+        offset = min(1., max(0., offset));
+    } else if spread == 1 {
+        //reflect
+        offset = 1. - abs(fract(offset / 2.) - 0.5) * 2.;
+    } else {
+        //repeat
+        offset = fract(offset);
+    }
+
+    let stop_start = prim.stop >> 16;
+    let stop_cnt = prim.stop & 0x0000ffff;
+    var prev = get_stop(stop_start);
+    var next = get_stop(stop_start + 1);
+    for (var i = 2u; i < stop_cnt && next.offset < offset; i++) {
+        prev = next;
+        next = get_stop(stop_start + i);
+    }
+
+    offset = max(prev.offset, min(next.offset, offset));
+    let weight1 = (next.offset - offset) / (next.offset - prev.offset);
+    let weight2 = 1. - weight1;
+    return (prev.color * weight1 + next.color * weight2) * vec4<f32>(1., 1., 1., alpha);
+}
+"#
+}
+
 pub fn color_triangles_shader(max_mask_layers: usize) -> String {
   basic_template(max_mask_layers)
     + r#"
@@ -513,6 +663,138 @@ pub fn img_triangles_shader(limits: &DrawPhaseLimits, pool_mode: PrimitivePoolMo
   "#
 }
 
+pub fn pattern_shader(limits: &DrawPhaseLimits, pool_mode: PrimitivePoolMode) -> String {
+  let primitives_decl = match pool_mode {
+    PrimitivePoolMode::Uniform => {
+      format!(
+        "
+      @group(2) @binding(0) 
+      var<uniform> primtives: array<PatternPrimitive, {}>;",
+        limits.max_pattern_primitives
+      )
+    }
+    PrimitivePoolMode::Storage => "
+      @group(2) @binding(0)
+      var<storage, read> primtives: array<PatternPrimitive>;"
+      .to_string(),
+  };
+
+  basic_template(limits.max_mask_layers)
+    + &primitives_decl
+    + r#"
+  struct VertexInput {
+    @location(0) pos: vec2<f32>,
+    @location(1) prim_idx: u32,
+  }
+  
+  // Since a the different alignment between WebGPU and WebGL, we not use 
+  // mat3x2<f32> in the struct, but use vec2<f32> instead. Then, we compose it.
+  struct PatternPrimitive {
+    /// Transform a vertex position to a image texture position.
+    t0: vec2<f32>,
+    t1: vec2<f32>,
+    t2: vec2<f32>,
+    /// The origin of the image placed in texture.
+    img_start: vec2<f32>,
+    /// The size of the image image.
+    img_size: vec2<f32>,
+    
+    /// This is a mix field,
+    /// - the high 16 bits is the index of head mask layer, as a i16 type.
+    /// - the low 16 bits is the index of texture, as a u16 type.
+    mask_head_and_tex_idx: i32,
+
+    /// The tile mode of the x-axis in the low 16 bits and the y-axis in the high
+    /// 16 bits, 0 for repeat, 1 for mirror and 2 for no-repeat.
+    tile: u32,
+
+    /// base color
+    base_color: vec4<f32>,
+
+    /// color matrix of [f32; 4 * 4]
+    color_matrix: mat4x4<f32>,
+  }
+  
+  struct VertexOutput {
+    @builtin(position) pos: vec4<f32>,
+    @location(0) @interpolate(flat) prim_idx: u32
+  }
+  
+  @vertex
+  fn vs_main(v: VertexInput) -> VertexOutput {
+      var o: VertexOutput;
+      // convert from gpu-backend coords(0..1) to wgpu corrds(-1..1)
+      let pos = v.pos * vec2(2., -2.) + vec2(-1., 1.);
+      o.pos = vec4<f32>(pos, 1., 1.);
+      o.prim_idx = v.prim_idx;
+  
+      return o;
+  }
+  
+  
+  @fragment
+  fn fs_main(f: VertexOutput) -> @location(0) vec4<f32> {
+      let prim = primtives[f.prim_idx];
+      let pos = mat3x2(prim.t0, prim.t1, prim.t2) * f.pos.xyz;
+      let x = tile(prim.tile & 0x0000FFFF, pos.x, prim.img_size.x);
+      let y = tile(prim.tile >> 16, pos.y, prim.img_size.y);
+      if x < 0. || y < 0. {
+        return vec4<f32>(0.);
+      }
+      // Keep the sample inside the image, not bleed to its neighbors in the texture.
+      let img_pos = clamp(vec2(x, y), vec2(0.5), prim.img_size - 0.5) + prim.img_start;
+      var color = img_sample(prim, img_pos);
+  
+      var mask_idx = prim.mask_head_and_tex_idx >> 16 ;
+      var alpha = 1.0;
+      loop {
+          if mask_idx < 0 { break; }
+  
+          let mask = mask_layers[u32(mask_idx)];
+          alpha *= mask_sample(mask, f.pos.xy);
+          mask_idx = mask.prev_mask_idx;
+      }
+      color.a = color.a * alpha;
+      return color * prim.color_matrix + prim.base_color;
+  }
+  
+  // Maps `v` into the image of length `len` by the tile mode, a negative value
+  // means out of the image.
+  fn tile(mode: u32, v: f32, len: f32) -> f32 {
+      let t = v / len;
+      switch mode {
+        case 0u: { return fract(t) * len; }
+        case 1u: { return (1. - abs(fract(t * 0.5) * 2. - 1.)) * len; }
+        default: {
+          if t < 0. || t > 1. { return -1.; }
+          return v;
+        }
+      }
+  }
+
+  fn img_sample(prim: PatternPrimitive, pos: vec2<f32>) -> vec4<f32> {
+      switch abs(prim.mask_head_and_tex_idx & 0x0000FFFF) {
+        case 0: { return img_tex_smaple(tex_0, prim, pos); }
+        case 1: { return img_tex_smaple(tex_1, prim, pos); }
+        case 2: { return img_tex_smaple(tex_2, prim, pos); }
+        case 3: { return img_tex_smaple(tex_3, prim, pos); }
+        case 4: { return img_tex_smaple(tex_4, prim, pos); }
+        case 5: { return img_tex_smaple(tex_5, prim, pos); }
+        case 6: { return img_tex_smaple(tex_6, prim, pos); }
+        case 7: { return img_tex_smaple(tex_7, prim, pos); }
+        // should not happen, use a red color to indicate error
+        default: { return vec4<f32>(1., 0., 0., 1.); }
+    };
+  }
+  
+  fn img_tex_smaple(tex: texture_2d<f32>, prim: PatternPrimitive, pos: vec2<f32>) -> vec4<f32> {
+      let img_tex_size = textureDimensions(tex);
+      let sample_pos = pos / vec2<f32>(f32(img_tex_size.x), f32(img_tex_size.y));
+      return textureSampleLevel(tex, s_sampler, sample_pos, 0.);
+  }
+  "#
+}
+
 pub fn filter_triangles_shader(limits: &DrawPhaseLimits, pool_mode: PrimitivePoolMode) -> String {
   let primitive_decl = match pool_mode {
     PrimitivePoolMode::Uniform => "
@@ -855,8 +1137,9 @@ pub fn texture_triangles_shader(limits: &DrawPhaseLimits, pool_mode: PrimitivePo
         mask_head: i32,
         opacity: f32,
         is_premultiplied: u32,
-        pad1: u32,
-        pad2: vec2<u32>,
+        blend_mode: u32,
+        mask_mode: u32,
+        pad: u32,
     }
 
     @vertex
@@ -870,6 +1153,64 @@ pub fn texture_triangles_shader(limits: &DrawPhaseLimits, pool_mode: PrimitivePo
 
     @group(3) @binding(0)
     var input_tex: texture_2d<f32>;
+    @group(3) @binding(1)
+    var backdrop_tex: texture_2d<f32>;
+
+    fn color_dodge(cb: f32, cs: f32) -> f32 {
+        if (cb == 0.0) { return 0.0; }
+        if (cs >= 1.0) { return 1.0; }
+        return min(1.0, cb / (1.0 - cs));
+    }
+
+    fn color_burn(cb: f32, cs: f32) -> f32 {
+        if (cb >= 1.0) { return 1.0; }
+        if (cs <= 0.0) { return 0.0; }
+        return 1.0 - min(1.0, (1.0 - cb) / cs);
+    }
+
+    fn soft_light(cb: f32, cs: f32) -> f32 {
+        if (cs <= 0.5) {
+            return cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb);
+        }
+        var d = sqrt(cb);
+        if (cb <= 0.25) {
+            d = ((16.0 * cb - 12.0) * cb + 4.0) * cb;
+        }
+        return cb + (2.0 * cs - 1.0) * (d - cb);
+    }
+
+    fn hard_light(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+        let multiply = cb * 2.0 * cs;
+        let screen = 2.0 * cs - 1.0 + cb - cb * (2.0 * cs - 1.0);
+        return select(screen, multiply, cs <= vec3(0.5));
+    }
+
+    // The separable blend functions of the W3C Compositing and Blending spec, the
+    // mode index follows the `BlendMode` of the painter.
+    fn blend(mode: u32, cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+        var mixed = cs;
+        switch mode {
+            case 1u: { mixed = cb * cs; }
+            case 2u: { mixed = cb + cs - cb * cs; }
+            case 3u: { mixed = hard_light(cs, cb); }
+            case 4u: { mixed = min(cb, cs); }
+            case 5u: { mixed = max(cb, cs); }
+            case 6u: {
+                mixed = vec3(color_dodge(cb.r, cs.r), color_dodge(cb.g, cs.g), color_dodge(cb.b, cs.b));
+            }
+            case 7u: {
+                mixed = vec3(color_burn(cb.r, cs.r), color_burn(cb.g, cs.g), color_burn(cb.b, cs.b));
+            }
+            case 8u: { mixed = hard_light(cb, cs); }
+            case 9u: {
+                mixed = vec3(soft_light(cb.r, cs.r), soft_light(cb.g, cs.g), soft_light(cb.b, cs.b));
+            }
+            case 10u: { mixed = abs(cb - cs); }
+            case 11u: { mixed = cb + cs - 2.0 * cb * cs; }
+            default: {}
+        }
+        return mixed;
+    }
 
     @fragment
     fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
@@ -895,6 +1236,27 @@ pub fn texture_triangles_shader(limits: &DrawPhaseLimits, pool_mode: PrimitivePo
         if (prim.is_premultiplied == 1u && color.a > 0.0) {
             color = vec4<f32>(color.rgb / color.a, color.a);
         }
+
+        if (prim.mask_mode != 0u) {
+            // The backdrop is the premultiplied mask of the layer.
+            let mask = textureSampleLevel(backdrop_tex, s_sampler, sample_pos, 0.);
+            var coverage = mask.a;
+            if (prim.mask_mode == 1u) {
+                coverage = dot(mask.rgb, vec3(0.2125, 0.7154, 0.0721));
+            }
+            return vec4<f32>(color.rgb, color.a * prim.opacity * alpha * coverage);
+        }
+
+        if (prim.blend_mode != 0u) {
+            // The backdrop is a premultiplied copy of the output under the layer.
+            var backdrop = textureSampleLevel(backdrop_tex, s_sampler, sample_pos, 0.);
+            if (backdrop.a > 0.0) {
+                backdrop = vec4<f32>(backdrop.rgb / backdrop.a, backdrop.a);
+            }
+            let mixed = blend(prim.blend_mode, backdrop.rgb, color.rgb);
+            let rgb = mix(color.rgb, clamp(mixed, vec3(0.0), vec3(1.0)), backdrop.a);
+            return vec4<f32>(rgb, color.a * prim.opacity * alpha);
+        }
         
         return vec4<f32>(color.rgb, color.a * prim.opacity);
     }
@@ -21,6 +21,7 @@
 //! | Text | `asset!("file.txt", "text")` | `String` | UTF-8 text |
 //! | SVG | `asset!("file.svg", "svg")` | `Svg` | Compressed at compile-time |
 //! | Image | `asset!("file.png", "image")` | `Image` | Converted to WebP |
+//! | FTL | `asset!("file.ftl", "ftl")` | `String` | Syntax checked |
 //!
 //! # Adding New Asset Types
 //!
@@ -61,7 +62,7 @@ mod basic;
 mod image;
 mod svg;
 
-use basic::{BinaryAsset, TextAsset};
+use basic::{BinaryAsset, FtlAsset, TextAsset};
 use image::ImageAsset;
 use svg::SvgAsset;
 
@@ -310,6 +311,7 @@ impl Parse for AssetArgs {
           Box::new(SvgAsset { inherit_fill, inherit_stroke })
         }
         "image" => Box::new(ImageAsset),
+        "ftl" => Box::new(FtlAsset),
         _ => Box::new(BinaryAsset),
       }
     } else {
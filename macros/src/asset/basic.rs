@@ -28,3 +28,43 @@ impl Asset for TextAsset {
     quote! { String::from_utf8(#data_expr.into_owned()).expect("Invalid UTF-8") }
   }
 }
+
+/// A Fluent translation source, its syntax is checked at compile time.
+pub(crate) struct FtlAsset;
+
+impl Asset for FtlAsset {
+  fn process(&self, ctx: &AssetContext) -> syn::Result<Option<Vec<u8>>> {
+    let source = std::fs::read_to_string(&ctx.abs_input)
+      .map_err(|e| ctx.error(format!("Read failed: {e}")))?;
+    if let Err((_, errors)) = fluent_syntax::parser::parse(source.as_str()) {
+      let errors = errors
+        .iter()
+        .map(|e| format!("line {}: {e}", line_of(&source, e.pos.start)))
+        .collect::<Vec<_>>();
+      return Err(ctx.error(format!("Invalid FTL ({})", errors.join("; "))));
+    }
+    // No processing needed, use original data
+    Ok(None)
+  }
+
+  fn load_expr(&self, data_expr: TokenStream) -> TokenStream {
+    quote! { String::from_utf8(#data_expr.into_owned()).expect("Invalid UTF-8") }
+  }
+}
+
+/// The 1-based line of the byte offset `pos` in `source`.
+fn line_of(source: &str, pos: usize) -> usize { source[..pos].matches('\n').count() + 1 }
+
+#[cfg(test)]
+mod tests {
+  use super::line_of;
+
+  #[test]
+  fn ftl_error_line() {
+    let source = "a = A\nb\n";
+    assert_eq!(line_of(source, 0), 1);
+    // The error at the start of the second line.
+    assert_eq!(line_of(source, 6), 2);
+    assert_eq!(line_of(source, source.len()), 3);
+  }
+}
@@ -0,0 +1,99 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+  Expr, ExprLit, Lit, LitStr, Token,
+  parse::{Parse, ParseStream},
+  punctuated::Punctuated,
+  spanned::Spanned,
+};
+
+use crate::{pipe_macro, symbol_process::symbol_to_macro};
+
+/// The arguments of `t!` and `tr!`: `"id", name = expr, "var-name" = expr`.
+struct Translation {
+  id: Expr,
+  args: Vec<(LitStr, Expr)>,
+}
+
+impl Parse for Translation {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let mut exprs = Punctuated::<Expr, Token![,]>::parse_terminated(input)?.into_iter();
+    let id = exprs
+      .next()
+      .ok_or_else(|| input.error("Expected the message id."))?;
+    let args = exprs
+      .map(|expr| match expr {
+        Expr::Assign(assign) => {
+          let name = match &*assign.left {
+            Expr::Path(p) if p.path.get_ident().is_some() => {
+              let ident = p.path.get_ident().unwrap();
+              LitStr::new(&ident.to_string(), ident.span())
+            }
+            Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => s.clone(),
+            other => {
+              return Err(syn::Error::new(
+                other.span(),
+                "Expected a variable name or a string literal.",
+              ));
+            }
+          };
+          Ok((name, *assign.right))
+        }
+        other => Err(syn::Error::new(other.span(), "Expected an argument as `name = value`.")),
+      })
+      .collect::<syn::Result<Vec<_>>>()?;
+    Ok(Self { id, args })
+  }
+}
+
+impl Translation {
+  /// The code formats the message by `locale`.
+  fn format_tokens(&self, locale: TokenStream) -> TokenStream {
+    let Self { id, args } = self;
+    if args.is_empty() {
+      quote! { #locale.tr(#id, None) }
+    } else {
+      let names = args.iter().map(|(name, _)| name);
+      let values = args.iter().map(|(_, value)| value);
+      quote! {{
+        let mut _ribir_args = FluentArgs::new();
+        #(_ribir_args.set(#names, #values);)*
+        #locale.tr(#id, Some(&_ribir_args))
+      }}
+    }
+  }
+}
+
+/// `t!("id", name = value)` creates a `TextValue` that follows the locale and
+/// the states read by `$` in the values.
+pub fn gen_t(input: TokenStream) -> TokenStream {
+  let span = input.span();
+  let res = symbol_to_macro(input)
+    .map_err(|e| e.to_compile_error())
+    .and_then(|input| syn::parse2::<Translation>(input).map_err(|e| e.to_compile_error()));
+  match res {
+    Ok(t) => {
+      let locale = quote_spanned! { Span::call_site() => $read(_ribir_locale) };
+      let pipe = pipe_macro::gen_code(t.format_tokens(locale), None);
+      quote_spanned! { span => {
+        let _ribir_locale = Locale::watcher(BuildCtx::get());
+        TextValue::Pipe { init_value: <_>::default(), pipe: #pipe }
+      }}
+    }
+    Err(err) => err,
+  }
+}
+
+/// `tr!(ctx, "id", name = value)` formats the message once by the locale of
+/// `ctx`, resolved the same way as `t!`.
+pub fn gen_tr(input: TokenStream) -> TokenStream {
+  let parser = |input: ParseStream| {
+    let ctx = input.parse::<Expr>()?;
+    input.parse::<Token![,]>()?;
+    Ok((ctx, input.parse::<Translation>()?))
+  };
+  match syn::parse::Parser::parse2(parser, input) {
+    Ok((ctx, t)) => t.format_tokens(quote! { Locale::watcher(&#ctx).read() }),
+    Err(err) => err.to_compile_error(),
+  }
+}
@@ -13,6 +13,7 @@ use syn::{DeriveInput, parse_macro_input};
 mod asset;
 mod child_template;
 mod fn_widget_macro;
+mod i18n_macro;
 mod pipe_macro;
 mod rdl_macro;
 mod watch_macro;
@@ -205,6 +206,33 @@ pub fn distinct_pipe(input: TokenStream) -> TokenStream {
 #[proc_macro]
 pub fn watch(input: TokenStream) -> TokenStream { watch_macro::gen_code(input.into(), None).into() }
 
+/// Declares a `TextValue` of a localized message, the text is updated when the
+/// language of the `Locale` changes. It must be used when building a widget.
+///
+/// The first argument is the message id, use `message.attr` for an attribute
+/// of the message. The rest are the variables of the message, as `name =
+/// value` or `"var-name" = value`, the value can read states by `$` to update
+/// the text when they change.
+///
+/// ```ignore
+/// @Text { text: t!("unread-emails", count = $read(inbox).unread()) }
+/// ```
+///
+/// This macro requires the `i18n` feature.
+#[proc_macro]
+pub fn t(input: TokenStream) -> TokenStream { i18n_macro::gen_t(input.into()).into() }
+
+/// Formats a localized message once by the `Locale` of the context, the
+/// arguments after the context are the same as the `t!` macro.
+///
+/// ```ignore
+/// on_tap: move |e| println!("{}", tr!(e, "saved", file = name.as_str())),
+/// ```
+///
+/// This macro requires the `i18n` feature.
+#[proc_macro]
+pub fn tr(input: TokenStream) -> TokenStream { i18n_macro::gen_tr(input.into()).into() }
+
 /// The `part_writer` macro creates a partial writer from a mutable reference of
 /// a writer.
 ///
@@ -270,6 +298,7 @@ pub fn part_reader(input: TokenStream) -> TokenStream {
 /// | Text | `asset!("file.txt", "text")` | `String` | None (raw copy) |
 /// | SVG | `asset!("file.svg", "svg")` | `Svg` | Serialized/compressed |
 /// | Image | `asset!("file.png", "image")` | `Image` | Converted to WebP |
+/// | FTL | `asset!("file.ftl", "ftl")` | `String` | Fluent syntax checked |
 ///
 /// # Syntax
 ///
@@ -288,6 +317,9 @@ pub fn part_reader(input: TokenStream) -> TokenStream {
 /// // Image (PNG, JPEG, GIF, BMP → WebP)
 /// asset!("path/to/photo.png", "image")
 /// asset!("path/to/animation.gif", "image")  // Animated GIF → Animated WebP
+///
+/// // Fluent translations, see `Locale::add_ftl`
+/// asset!("locales/en-US.ftl", "ftl")
 /// ```
 ///
 /// # Path Resolution
@@ -1,4 +1,4 @@
-use ribir_types::Point;
+use ribir_types::{Angle, Point};
 pub use ribir_types::{Color, LightnessTone};
 use serde::{Deserialize, Serialize};
 
@@ -137,6 +137,29 @@ pub struct GradientStop {
 impl GradientStop {
   #[inline]
   pub fn new(color: Color, offset: f32) -> Self { Self { color, offset } }
+
+  /// The color of the gradient at `offset`, the stops are sorted by offset.
+  pub fn color_at(stops: &[GradientStop], offset: f32) -> Color {
+    let Some(first) = stops.first() else { return Color::TRANSPARENT };
+    if offset <= first.offset {
+      return first.color;
+    }
+    for w in stops.windows(2) {
+      let (from, to) = (&w[0], &w[1]);
+      if offset <= to.offset {
+        let t = (offset - from.offset) / (to.offset - from.offset).max(f32::EPSILON);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        let (a, b) = (from.color, to.color);
+        return Color::new(
+          mix(a.red, b.red),
+          mix(a.green, b.green),
+          mix(a.blue, b.blue),
+          mix(a.alpha, b.alpha),
+        );
+      }
+    }
+    stops[stops.len() - 1].color
+  }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -156,3 +179,91 @@ pub struct LinearGradient {
   pub stops: Vec<GradientStop>,
   pub spread_method: SpreadMethod,
 }
+
+/// A conic gradient, the colors sweep around the `center` from the
+/// `start_angle` to the `end_angle`, clockwise in the painter coordinate.
+///
+/// The `spread_method` decides the colors outside the swept angles.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SweepGradient {
+  pub center: Point,
+  pub start_angle: Angle,
+  pub end_angle: Angle,
+  pub stops: Vec<GradientStop>,
+  pub spread_method: SpreadMethod,
+}
+
+impl RadialGradient {
+  /// The gradient offset of the point `p`, before the spread method applies,
+  /// or `None` if no circle of the gradient passes `p`.
+  ///
+  /// It follows the two-point conical gradient of the HTML canvas: the circle
+  /// of offset `ω` interpolates both the centers and the radii, and the
+  /// largest `ω` whose circle has a non-negative radius wins.
+  pub fn offset_at(&self, p: Point) -> Option<f32> {
+    let r0 = self.start_radius;
+    let d0 = p - self.start_center;
+    let d10 = self.end_center - self.start_center;
+    let dr = self.end_radius - r0;
+    // Solves `|p - center(ω)| = radius(ω)`, a quadratic equation of `ω`.
+    let a = d10.square_length() - dr * dr;
+    let b = -2. * (d10.dot(d0) + dr * r0);
+    let c = d0.square_length() - r0 * r0;
+    let valid = |w: f32| r0 + dr * w >= 0.;
+    if a.abs() <= f32::EPSILON {
+      if b.abs() <= f32::EPSILON {
+        return None;
+      }
+      let w = -c / b;
+      return valid(w).then_some(w);
+    }
+    let delta = b * b - 4. * a * c;
+    if delta < 0. {
+      return None;
+    }
+    let sqrt_delta = delta.sqrt();
+    let w1 = (-b + sqrt_delta) / (2. * a);
+    let w2 = (-b - sqrt_delta) / (2. * a);
+    [w1.max(w2), w1.min(w2)]
+      .into_iter()
+      .find(|w| valid(*w))
+  }
+
+  /// The color of the gradient at the point `p`, or `None` if no circle of
+  /// the gradient passes `p`.
+  pub fn color_at(&self, p: Point) -> Option<Color> {
+    let offset = self.spread_method.apply(self.offset_at(p)?);
+    Some(GradientStop::color_at(&self.stops, offset))
+  }
+}
+
+impl SweepGradient {
+  /// Creates a gradient that sweeps a full turn around `center` from the
+  /// positive x-axis.
+  pub fn new(center: Point, stops: Vec<GradientStop>) -> Self {
+    Self {
+      center,
+      start_angle: Angle::zero(),
+      end_angle: Angle::two_pi(),
+      stops,
+      spread_method: SpreadMethod::Pad,
+    }
+  }
+
+  /// The gradient offset of the point `p`, before the spread method applies.
+  pub fn offset_at(&self, p: Point) -> f32 {
+    let span = self.end_angle.radians - self.start_angle.radians;
+    if span == 0. {
+      return 0.;
+    }
+    let v = p - self.center;
+    let angle = v.y.atan2(v.x);
+    let turn = std::f32::consts::TAU;
+    ((angle - self.start_angle.radians) * span.signum()).rem_euclid(turn) / span.abs()
+  }
+
+  /// The color of the gradient at the point `p`.
+  pub fn color_at(&self, p: Point) -> Color {
+    GradientStop::color_at(&self.stops, self.spread_method.apply(self.offset_at(p)))
+  }
+}
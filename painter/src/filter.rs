@@ -3,7 +3,10 @@
 use serde::{Deserialize, Serialize};
 use smallvec::{SmallVec, smallvec};
 
-use crate::color::{Color, ColorFilterMatrix};
+use crate::{
+  BlendMode,
+  color::{Color, ColorFilterMatrix},
+};
 
 /// Represents a 2D convolution matrix used for image filtering operations.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -20,8 +23,8 @@ pub enum FilterOp {
   Convolution(FlattenMatrix),
 }
 
-/// A filter layer that contains a list of operations, a composite mode, and an
-/// offset.
+/// A filter layer that contains a list of operations, a composite mode, an
+/// offset and a blend mode.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilterLayer {
   pub ops: SmallVec<[FilterOp; 1]>,
@@ -31,6 +34,11 @@ pub struct FilterLayer {
   /// shadows.
   #[serde(default)]
   pub offset: [f32; 2],
+  /// How the result of the layer mixes with the content under the filter.
+  /// `BlendMode::Normal` replaces the content with the result, the other modes
+  /// blend the result with the content.
+  #[serde(default)]
+  pub blend_mode: BlendMode,
 }
 
 /// The composite type for the filter.
@@ -93,8 +101,10 @@ impl Filter {
       let first = filter.layers.first().unwrap();
       last.composite == FilterComposite::Replace
         && last.offset == [0., 0.]
+        && last.blend_mode == BlendMode::Normal
         && first.composite == FilterComposite::Replace
         && first.offset == [0., 0.]
+        && first.blend_mode == BlendMode::Normal
     };
 
     if can_merge {
@@ -132,6 +142,15 @@ impl Filter {
     self
   }
 
+  /// Sets the blend mode of the last filter stage, the result of the stage
+  /// blends with the content under the filter instead of replacing it.
+  pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+    if let Some(layer) = self.layers.last_mut() {
+      layer.blend_mode = mode;
+    }
+    self
+  }
+
   /// Creates a grayscale filter with the specified amount.
   /// Amount should be between 0.0 and 1.0, where 1.0 is fully grayscale.
   #[rustfmt::skip]
@@ -309,7 +328,8 @@ impl Filter {
       layers: smallvec![FilterLayer {
         ops,
         composite: FilterComposite::default(),
-        offset: [0., 0.]
+        offset: [0., 0.],
+        blend_mode: BlendMode::Normal,
       }],
     }
   }
@@ -325,6 +345,7 @@ impl Filter {
         ops: smallvec![FilterOp::Color(matrix)],
         composite: FilterComposite::default(),
         offset: [0., 0.],
+        blend_mode: BlendMode::Normal,
       }],
     }
   }
@@ -336,6 +357,7 @@ impl Filter {
         ops: smallvec![FilterOp::Convolution(matrix)],
         composite: FilterComposite::default(),
         offset: [0., 0.],
+        blend_mode: BlendMode::Normal,
       }],
     }
   }
@@ -371,6 +393,7 @@ impl Filter {
         ops,
         composite: FilterComposite::ExcludeSource,
         offset: [offset.0, offset.1],
+        blend_mode: BlendMode::Normal,
       }],
     }
   }
@@ -404,6 +427,7 @@ impl Filter {
       if !optimization_broken
         && layer.offset == [0., 0.]
         && layer.composite == FilterComposite::Replace
+        && layer.blend_mode == BlendMode::Normal
       {
         let mut new_ops = SmallVec::new();
         for op in layer.ops {
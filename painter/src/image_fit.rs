@@ -0,0 +1,199 @@
+use ribir_types::{Point, Rect, Size};
+use serde::{Deserialize, Serialize};
+
+/// How an image is resized to fit its box, the same as the CSS `object-fit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ImageFit {
+  /// Stretches the image to fill the box, ignoring the aspect ratio.
+  #[default]
+  Fill,
+  /// Scales the image uniformly to fit within the box, potentially leaving
+  /// empty spaces.
+  Contain,
+  /// Scales the image uniformly to cover the box, the overflowed part is
+  /// cropped.
+  Cover,
+  /// Keeps the original size of the image, the overflowed part is cropped.
+  None,
+  /// The smaller one of `None` and `Contain`.
+  ScaleDown,
+}
+
+/// Where an image is placed in its box, the same as the CSS
+/// `object-position` in fractions.
+///
+/// The `x` and `y` are in `0.0..=1.0`, `0.0` aligns the start edges of the
+/// image and the box, `1.0` aligns the end edges.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImageAlign {
+  pub x: f32,
+  pub y: f32,
+}
+
+/// How the edges and the center of a nine-patch image fill their space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NinePatchMode {
+  /// Stretches the slices to fill the space.
+  #[default]
+  Stretch,
+  /// Repeats the slices in the original size, the last one is cropped.
+  Repeat,
+}
+
+impl ImageFit {
+  /// Returns the rect the image of `img_size` is painted to in the `dst`
+  /// rect, it may exceed the `dst` rect.
+  pub fn place(self, img_size: Size, dst: &Rect, align: ImageAlign) -> Rect {
+    let sx = dst.width() / img_size.width;
+    let sy = dst.height() / img_size.height;
+    let (sx, sy) = match self {
+      ImageFit::Fill => return *dst,
+      ImageFit::Contain => (sx.min(sy), sx.min(sy)),
+      ImageFit::Cover => (sx.max(sy), sx.max(sy)),
+      ImageFit::None => (1., 1.),
+      ImageFit::ScaleDown => {
+        let s = sx.min(sy).min(1.);
+        (s, s)
+      }
+    };
+    let size = Size::new(img_size.width * sx, img_size.height * sy);
+    let origin = Point::new(
+      dst.min_x() + (dst.width() - size.width) * align.x,
+      dst.min_y() + (dst.height() - size.height) * align.y,
+    );
+    Rect::new(origin, size)
+  }
+}
+
+impl ImageAlign {
+  pub const TOP_LEFT: Self = Self::new(0., 0.);
+  pub const TOP_CENTER: Self = Self::new(0.5, 0.);
+  pub const TOP_RIGHT: Self = Self::new(1., 0.);
+  pub const CENTER_LEFT: Self = Self::new(0., 0.5);
+  pub const CENTER: Self = Self::new(0.5, 0.5);
+  pub const CENTER_RIGHT: Self = Self::new(1., 0.5);
+  pub const BOTTOM_LEFT: Self = Self::new(0., 1.);
+  pub const BOTTOM_CENTER: Self = Self::new(0.5, 1.);
+  pub const BOTTOM_RIGHT: Self = Self::new(1., 1.);
+
+  pub const fn new(x: f32, y: f32) -> Self { Self { x, y } }
+}
+
+impl Default for ImageAlign {
+  fn default() -> Self { Self::CENTER }
+}
+
+/// A slice of a nine-patch image, maps the `src` rect of the image to the
+/// `dst` rect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NinePatchSlice {
+  pub src: Rect,
+  pub dst: Rect,
+  /// The size of a tile in the `dst` rect if the slice is repeated, the
+  /// corners and the stretched axes are one tile.
+  pub tile: Size,
+}
+
+/// Splits the image of `img_size` into nine slices by the `center` rect, and
+/// maps them to the `dst` rect.
+///
+/// The corners keep their size, unless the `dst` rect is too small to hold
+/// them, then they are shrunk proportionally. The empty slices are skipped.
+pub fn nine_patch_slices(
+  img_size: Size, center: &Rect, dst: &Rect, mode: NinePatchMode,
+) -> impl Iterator<Item = NinePatchSlice> {
+  fn axis(
+    len: f32, start: f32, end: f32, dst_start: f32, dst_len: f32,
+  ) -> ([f32; 4], [f32; 4], f32) {
+    let (head, tail) = (start, len - end);
+    let shrink = if head + tail > dst_len { dst_len / (head + tail) } else { 1. };
+    let dst_end = dst_start + dst_len;
+    let src = [0., start, end, len];
+    let dst = [dst_start, dst_start + head * shrink, dst_end - tail * shrink, dst_end];
+    (src, dst, shrink)
+  }
+
+  let center = center
+    .intersection(&Rect::from_size(img_size))
+    .unwrap_or_else(Rect::zero);
+  let (src_x, dst_x, shrink_x) =
+    axis(img_size.width, center.min_x(), center.max_x(), dst.min_x(), dst.width());
+  let (src_y, dst_y, shrink_y) =
+    axis(img_size.height, center.min_y(), center.max_y(), dst.min_y(), dst.height());
+
+  (0..9).filter_map(move |i| {
+    let (col, row) = (i % 3, i / 3);
+    let src = Rect::new(
+      Point::new(src_x[col], src_y[row]),
+      Size::new(src_x[col + 1] - src_x[col], src_y[row + 1] - src_y[row]),
+    );
+    let dst = Rect::new(
+      Point::new(dst_x[col], dst_y[row]),
+      Size::new(dst_x[col + 1] - dst_x[col], dst_y[row + 1] - dst_y[row]),
+    );
+    if src.is_empty() || dst.is_empty() {
+      return None;
+    }
+
+    let repeat = mode == NinePatchMode::Repeat;
+    let tile = Size::new(
+      if repeat && col == 1 { src.width() * shrink_y.min(shrink_x) } else { dst.width() },
+      if repeat && row == 1 { src.height() * shrink_y.min(shrink_x) } else { dst.height() },
+    );
+    Some(NinePatchSlice { src, dst, tile })
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_types::rect;
+
+  use super::*;
+
+  #[test]
+  fn fit_place() {
+    let img = Size::new(100., 50.);
+    let dst = rect(0., 0., 100., 100.);
+    let place = |fit: ImageFit| fit.place(img, &dst, ImageAlign::CENTER);
+
+    assert_eq!(place(ImageFit::Fill), dst);
+    assert_eq!(place(ImageFit::Contain), rect(0., 25., 100., 50.));
+    assert_eq!(place(ImageFit::Cover), rect(-50., 0., 200., 100.));
+    assert_eq!(place(ImageFit::None), rect(0., 25., 100., 50.));
+    assert_eq!(place(ImageFit::ScaleDown), rect(0., 25., 100., 50.));
+
+    let small = rect(0., 0., 50., 50.);
+    assert_eq!(
+      ImageFit::ScaleDown.place(img, &small, ImageAlign::TOP_LEFT),
+      rect(0., 0., 50., 25.)
+    );
+    assert_eq!(
+      ImageFit::None.place(img, &small, ImageAlign::BOTTOM_RIGHT),
+      rect(-50., 0., 100., 50.)
+    );
+  }
+
+  #[test]
+  fn nine_slices() {
+    let img = Size::new(30., 30.);
+    let center = rect(10., 10., 10., 10.);
+    let slices: Vec<_> =
+      nine_patch_slices(img, &center, &rect(0., 0., 100., 60.), NinePatchMode::Repeat).collect();
+    assert_eq!(slices.len(), 9);
+
+    // The corners keep their size.
+    assert_eq!(slices[0].dst, rect(0., 0., 10., 10.));
+    assert_eq!(slices[8].dst, rect(90., 50., 10., 10.));
+    // The top edge repeats horizontally.
+    assert_eq!(slices[1].dst, rect(10., 0., 80., 10.));
+    assert_eq!(slices[1].tile, Size::new(10., 10.));
+    // The center repeats on both axes.
+    assert_eq!(slices[4].tile, Size::new(10., 10.));
+
+    // Too small to hold the corners.
+    let slices: Vec<_> =
+      nine_patch_slices(img, &center, &rect(0., 0., 10., 10.), NinePatchMode::Stretch).collect();
+    assert_eq!(slices.len(), 4);
+    assert_eq!(slices[0].dst, rect(0., 0., 5., 5.));
+  }
+}
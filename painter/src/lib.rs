@@ -3,15 +3,19 @@
 //! A 2d logic painter, generate the paint command
 pub mod color;
 pub mod filter;
+mod image_fit;
+pub use image_fit::*;
 mod painter;
 pub mod path;
 pub mod path_builder;
+mod path_ops;
 pub use path::*;
+pub use path_ops::{FlattenedPath, PathOp};
 mod text;
 pub use text::*;
 
 pub use crate::{
-  color::{Color, GradientStop, LightnessTone},
+  color::{Color, GradientStop, LightnessTone, SweepGradient},
   filter::*,
   painter::*,
 };
@@ -21,4 +25,6 @@ pub use style::*;
 
 pub use crate::pixel_image::{ColorFormat, PixelImage};
 mod svg;
-pub use svg::Svg;
+pub use svg::{Svg, SvgFonts};
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
@@ -7,9 +7,11 @@ use smallvec::SmallVec;
 
 pub use crate::FontFaceId as FaceId;
 use crate::{
-  Brush, Color, GlyphRasterSource, PixelImage, Svg, TextDrawPayload,
-  color::{ColorFilterMatrix, LinearGradient, RadialGradient},
+  Brush, Color, GlyphRasterSource, ImageAlign, ImageFit, ImagePattern, NinePatchMode,
+  NinePatchSlice, PixelImage, Svg, TextDrawPayload, TileMode,
+  color::{ColorFilterMatrix, LinearGradient, RadialGradient, SweepGradient},
   filter::{Filter, FilterLayer, FilterOp},
+  image_fit::nine_patch_slices,
   path::*,
   path_builder::PathBuilder,
 };
@@ -109,6 +111,8 @@ pub enum CommandBrush {
   Image { img: Resource<PixelImage>, color_filter: ColorMatrix },
   Radial(Resource<RadialGradient>),
   Linear(Resource<LinearGradient>),
+  Sweep(Resource<SweepGradient>),
+  Pattern { pattern: Resource<ImagePattern>, color_filter: ColorMatrix },
 }
 
 #[repr(u32)]
@@ -120,6 +124,17 @@ pub enum SpreadMethod {
   Repeat,
 }
 
+impl SpreadMethod {
+  /// Maps a gradient offset outside `[0, 1]` back into it.
+  pub fn apply(self, offset: f32) -> f32 {
+    match self {
+      SpreadMethod::Pad => offset.clamp(0., 1.),
+      SpreadMethod::Repeat => offset.rem_euclid(1.),
+      SpreadMethod::Reflect => 1. - ((offset * 0.5).rem_euclid(1.) * 2. - 1.).abs(),
+    }
+  }
+}
+
 /// A path and its geometry information are friendly to paint and cache.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PathCommand {
@@ -162,6 +177,13 @@ pub enum PaintCommand {
   Bundle {
     transform: Transform,
     color_filter: ColorMatrix,
+    /// How the bundle composites with the content drawn before it.
+    #[serde(default)]
+    blend_mode: BlendMode,
+    /// The mask covers the bundle, the bundle is only visible where the mask
+    /// is painted.
+    #[serde(default)]
+    mask: Option<BundleMask>,
     /// the bounds of the bundle commands. This is the union of all paint
     /// command
     bounds: Rect,
@@ -191,6 +213,47 @@ pub enum PaintCommand {
   Text(TextCommand),
 }
 
+/// The blend mode of a layer, describes how the colors of the layer mix with
+/// the colors behind it. The formulas follow the [W3C Compositing and Blending]
+/// specification.
+///
+/// [W3C Compositing and Blending]: https://www.w3.org/TR/compositing-1/#blending
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum BlendMode {
+  /// The layer is drawn over the backdrop, the default.
+  #[default]
+  Normal,
+  Multiply,
+  Screen,
+  Overlay,
+  Darken,
+  Lighten,
+  ColorDodge,
+  ColorBurn,
+  HardLight,
+  SoftLight,
+  Difference,
+  Exclusion,
+}
+
+/// How the painted mask covers the content under it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum MaskMode {
+  /// The coverage is the luminance of the mask multiplied by its alpha.
+  #[default]
+  Luminance,
+  /// The coverage is the alpha of the mask.
+  Alpha,
+}
+
+/// The mask of a bundle, its commands are in the same space as the commands of
+/// the bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleMask {
+  pub mode: MaskMode,
+  pub cmds: Resource<Box<[PaintCommand]>>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum ColorMatrix {
   Opacity(f32),
@@ -278,6 +341,11 @@ struct PainterState {
   bounds: Rect,
 
   filters: SmallVec<[FilterState; 0]>,
+  /// The blend mode set in this state and the index of the first command it
+  /// applies to.
+  blend: Option<(BlendMode, usize)>,
+  /// The mask set in this state and the index of the first command it covers.
+  mask: Option<(BundleMask, usize)>,
 }
 
 #[derive(Clone)]
@@ -300,6 +368,8 @@ impl PainterState {
       color_filter: ColorMatrix::Opacity(1.),
       style: PathStyle::Fill,
       filters: SmallVec::new(),
+      blend: None,
+      mask: None,
     }
   }
 }
@@ -350,7 +420,9 @@ impl Painter {
   /// painter.merge(&mut overlay);
   /// ```
   pub fn fork(&self) -> Self {
-    let init_state = self.current_state().clone();
+    let mut init_state = self.current_state().clone();
+    init_state.blend = None;
+    init_state.mask = None;
     Painter {
       state_stack: vec![init_state.clone()],
       init_state,
@@ -423,6 +495,8 @@ impl Painter {
     let mut new_state = self.current_state().clone();
 
     new_state.filters = SmallVec::new();
+    new_state.blend = None;
+    new_state.mask = None;
 
     self.state_stack.push(new_state);
     self
@@ -452,6 +526,14 @@ impl Painter {
         filter.filter.into_layers(),
       );
     }
+
+    if let Some((mask, start_idx)) = state.mask {
+      self.generate_mask_bundle(mask, start_idx);
+    }
+
+    if let Some((blend_mode, start_idx)) = state.blend {
+      self.generate_blend_bundle(blend_mode, start_idx);
+    }
   }
 
   pub fn reset(&mut self) {
@@ -500,6 +582,40 @@ impl Painter {
     self
   }
 
+  /// Return the blend mode of the current state.
+  pub fn blend_mode(&self) -> BlendMode {
+    self
+      .current_state()
+      .blend
+      .map_or(BlendMode::Normal, |(mode, _)| mode)
+  }
+
+  /// Set the blend mode of the content drawn in the current state. When the
+  /// state is restored, the content drawn since the first call in this state is
+  /// bundled to a layer that blends with the content behind it.
+  pub fn set_blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+    let start_idx = self.commands.len();
+    let blend = &mut self.current_state_mut().blend;
+    let start_idx = blend.map_or(start_idx, |(_, idx)| idx);
+    *blend = Some((mode, start_idx));
+    self
+  }
+
+  /// Set the mask of the content drawn in the current state, the `cmds` of the
+  /// mask are painted in the space of the painter. When the state is restored,
+  /// the content drawn since the first call in this state is bundled to a layer
+  /// that is only visible where the mask covers.
+  ///
+  /// The clips should be applied before saving the state or after setting the
+  /// mask, so that they are bundled with the content or not at all.
+  pub fn set_mask(&mut self, mode: MaskMode, cmds: Resource<Box<[PaintCommand]>>) -> &mut Self {
+    let start_idx = self.commands.len();
+    let mask = &mut self.current_state_mut().mask;
+    let start_idx = mask.as_ref().map_or(start_idx, |(_, idx)| *idx);
+    *mask = Some((BundleMask { mode, cmds }, start_idx));
+    self
+  }
+
   pub fn is_transparent(&self) -> bool { self.current_state().color_filter.is_transparent() }
 
   pub fn current_color_filter(&self) -> &ColorMatrix { &self.current_state().color_filter }
@@ -551,6 +667,20 @@ impl Painter {
     self
   }
 
+  /// Return the dash pattern and the dash offset of the stroke pen.
+  #[inline]
+  pub fn line_dash(&self) -> Option<&(SmallVec<[f32; 4]>, f32)> {
+    self.stroke_options().dash.as_ref()
+  }
+
+  /// Set the dash pattern and the dash offset of the stroke pen, `None` strokes
+  /// a solid line. See [`StrokeOptions::dash`].
+  #[inline]
+  pub fn set_line_dash(&mut self, dash: Option<(SmallVec<[f32; 4]>, f32)>) -> &mut Self {
+    self.current_state_mut().stroke_options.dash = dash;
+    self
+  }
+
   /// Return the current transformation matrix being applied to the layer.
   #[inline]
   pub fn transform(&self) -> &Transform { &self.current_state().transform }
@@ -811,7 +941,9 @@ impl Painter {
 
     let transform = *self.transform();
     let color_filter = *self.color_filter();
-    let cmd = PaintCommand::Bundle { transform, color_filter, bounds, cmds };
+    let blend_mode = BlendMode::Normal;
+    let cmd =
+      PaintCommand::Bundle { transform, color_filter, blend_mode, mask: None, bounds, cmds };
     self.commands.push(cmd);
     self
   }
@@ -844,9 +976,17 @@ impl Painter {
             PaintCommand::Path(path)
           }
           PaintCommand::PopClip => PaintCommand::PopClip,
-          PaintCommand::Bundle { transform: b_ts, mut color_filter, bounds, cmds } => {
+          PaintCommand::Bundle {
+            transform: b_ts,
+            mut color_filter,
+            blend_mode,
+            mask,
+            bounds,
+            cmds,
+          } => {
             color_filter.chains(self.color_filter());
-            PaintCommand::Bundle { transform: transform.then(&b_ts), color_filter, bounds, cmds }
+            let transform = transform.then(&b_ts);
+            PaintCommand::Bundle { transform, color_filter, blend_mode, mask, bounds, cmds }
           }
           PaintCommand::Filter { .. } => cmd.clone(),
           PaintCommand::Text(mut text_cmd) => {
@@ -901,6 +1041,66 @@ impl Painter {
     self
   }
 
+  /// Draw the image into the `dst_rect` resized by the `fit` and placed by the
+  /// `align`, the part outside the `dst_rect` is cropped.
+  pub fn draw_img_fit(
+    &mut self, img: Resource<PixelImage>, dst_rect: &Rect, fit: ImageFit, align: ImageAlign,
+  ) -> &mut Self {
+    let img_rect = Rect::from_size(Size::new(img.width() as f32, img.height() as f32));
+    if img_rect.is_empty() {
+      return self;
+    }
+    let placed = fit.place(img_rect.size, dst_rect, align);
+    let Some(visible) = placed.intersection(dst_rect) else { return self };
+
+    let sx = placed.width() / img_rect.width();
+    let sy = placed.height() / img_rect.height();
+    let src = Rect::new(
+      Point::new((visible.min_x() - placed.min_x()) / sx, (visible.min_y() - placed.min_y()) / sy),
+      Size::new(visible.width() / sx, visible.height() / sy),
+    );
+    // Avoid the float error exceeding the image.
+    let src = src.intersection(&img_rect).unwrap_or(img_rect);
+    self.draw_img(img, &visible, &Some(src))
+  }
+
+  /// Draw the image as a nine-patch into the `dst_rect`.
+  ///
+  /// The `center` rect splits the image into nine slices, the corners are
+  /// painted in their original size, the edges and the center fill the rest
+  /// space by the `mode`.
+  ///
+  /// A repeated slice is painted by one fill of a repeating [`ImagePattern`],
+  /// the pixels of the slice are copied once and kept in `slices`, the caller
+  /// keeps it as long as it paints the image.
+  pub fn draw_img_nine(
+    &mut self, img: Resource<PixelImage>, center: &Rect, dst_rect: &Rect, mode: NinePatchMode,
+    slices: &mut NineSlices,
+  ) -> &mut Self {
+    let img_size = Size::new(img.width() as f32, img.height() as f32);
+    for NinePatchSlice { src, dst, tile } in nine_patch_slices(img_size, center, dst_rect, mode) {
+      if tile == dst.size {
+        self.draw_img(img.clone(), &dst, &Some(src));
+        continue;
+      }
+
+      let Some(slice) = slices.slice(&img, &src) else { continue };
+      let tile_mode = |repeat: bool| if repeat { TileMode::Repeat } else { TileMode::NoRepeat };
+      let transform =
+        Transform::scale(tile.width / slice.width() as f32, tile.height / slice.height() as f32)
+          .then_translate(dst.origin.to_vector());
+      let pattern = ImagePattern::new(slice)
+        .with_tile(tile_mode(tile.width < dst.width()), tile_mode(tile.height < dst.height()))
+        .with_transform(transform);
+      let mut painter = self.save_guard();
+      painter
+        .rect(&dst, true)
+        .set_fill_brush(pattern)
+        .fill();
+    }
+    self
+  }
+
   pub fn draw_text_payload(
     &mut self, payload: Resource<TextDrawPayload>, paint_bounds: Rect,
   ) -> &mut Self {
@@ -1118,6 +1318,8 @@ impl Painter {
       let bundle = PaintCommand::Bundle {
         transform: Transform::identity(),
         color_filter,
+        blend_mode: BlendMode::Normal,
+        mask: None,
         bounds: new_bounds,
         cmds: Resource::new(cmds.into_boxed_slice()),
       };
@@ -1128,6 +1330,48 @@ impl Painter {
     self.commands.extend(cmds);
   }
 
+  /// Bundle the commands drawn since `cmd_start_idx` to a layer that blends
+  /// with the content behind it by `blend_mode`.
+  fn generate_blend_bundle(&mut self, blend_mode: BlendMode, cmd_start_idx: usize) {
+    if blend_mode == BlendMode::Normal || cmd_start_idx >= self.commands.len() {
+      return;
+    }
+
+    let Some(bounds) =
+      Self::compute_commands_bounds(&self.commands[cmd_start_idx..]).filter(locatable_bounds)
+    else {
+      return;
+    };
+    let cmds: Box<[PaintCommand]> = self.commands.drain(cmd_start_idx..).collect();
+    self.commands.push(PaintCommand::Bundle {
+      transform: Transform::identity(),
+      color_filter: ColorMatrix::default(),
+      blend_mode,
+      mask: None,
+      bounds,
+      cmds: Resource::new(cmds),
+    });
+  }
+
+  /// Bundle the commands drawn since `cmd_start_idx` to a layer covered by
+  /// `mask`.
+  fn generate_mask_bundle(&mut self, mask: BundleMask, cmd_start_idx: usize) {
+    let Some(bounds) =
+      Self::compute_commands_bounds(&self.commands[cmd_start_idx..]).filter(locatable_bounds)
+    else {
+      return;
+    };
+    let cmds: Box<[PaintCommand]> = self.commands.drain(cmd_start_idx..).collect();
+    self.commands.push(PaintCommand::Bundle {
+      transform: Transform::identity(),
+      color_filter: ColorMatrix::default(),
+      blend_mode: BlendMode::Normal,
+      mask: Some(mask),
+      bounds,
+      cmds: Resource::new(cmds),
+    });
+  }
+
   /// Compute the union bounds of a list of paint commands.
   fn compute_commands_bounds(cmds: &[PaintCommand]) -> Option<Rect> {
     let mut bounds: Option<Rect> = None;
@@ -1154,6 +1398,10 @@ impl From<Brush> for CommandBrush {
       Brush::Image(img) => CommandBrush::Image { img, color_filter: ColorMatrix::default() },
       Brush::RadialGradient(radial_gradient) => CommandBrush::Radial(radial_gradient),
       Brush::LinearGradient(linear_gradient) => CommandBrush::Linear(linear_gradient),
+      Brush::SweepGradient(sweep_gradient) => CommandBrush::Sweep(sweep_gradient),
+      Brush::Pattern(pattern) => {
+        CommandBrush::Pattern { pattern, color_filter: ColorMatrix::default() }
+      }
     }
   }
 }
@@ -1267,7 +1515,9 @@ impl CommandBrush {
   pub fn apply_color_filter(&mut self, filter: &ColorMatrix) -> &mut Self {
     match self {
       CommandBrush::Color(color) => *color = filter.apply_to(color),
-      CommandBrush::Image { color_filter, .. } => color_filter.chains(filter),
+      CommandBrush::Image { color_filter, .. } | CommandBrush::Pattern { color_filter, .. } => {
+        color_filter.chains(filter)
+      }
       CommandBrush::Radial(gradient) => {
         let mut gradient = (**gradient).clone();
         gradient
@@ -1284,6 +1534,14 @@ impl CommandBrush {
           .for_each(|s| s.color = filter.apply_to(&s.color));
         *self = CommandBrush::Linear(Resource::new(gradient));
       }
+      CommandBrush::Sweep(gradient) => {
+        let mut gradient = (**gradient).clone();
+        gradient
+          .stops
+          .iter_mut()
+          .for_each(|s| s.color = filter.apply_to(&s.color));
+        *self = CommandBrush::Sweep(Resource::new(gradient));
+      }
     }
     self
   }
@@ -1349,11 +1607,46 @@ macro_rules! invisible_return {
 }
 use invisible_return;
 
+/// The slices of a nine-patch image repeated by [`Painter::draw_img_nine`], so
+/// they are not copied every time the image is painted.
+///
+/// It only keeps the slices of the last painted image.
+#[derive(Default)]
+pub struct NineSlices {
+  img: Option<Resource<PixelImage>>,
+  slices: Vec<(DeviceRect, Resource<PixelImage>)>,
+}
+
+impl NineSlices {
+  /// Returns the pixels of the `src` slice of the image, so the slice can be
+  /// repeated by a pattern. Returns `None` if the slice is less than a pixel.
+  fn slice(&mut self, img: &Resource<PixelImage>, src: &Rect) -> Option<Resource<PixelImage>> {
+    let rect: DeviceRect = src.round().to_i32().cast_unit();
+    if rect.is_empty() {
+      return None;
+    }
+    if self.img.as_ref() != Some(img) {
+      self.img = Some(img.clone());
+      self.slices.clear();
+    }
+    let slice = match self.slices.iter().find(|(r, _)| *r == rect) {
+      Some((_, slice)) => slice.clone(),
+      None => {
+        let slice = Resource::new(img.crop(&rect));
+        self.slices.push((rect, slice.clone()));
+        slice
+      }
+    };
+    Some(slice)
+  }
+}
+
 #[cfg(test)]
 mod test {
-  use ribir_types::rect;
+  use ribir_types::{DeviceSize, rect};
 
   use super::*;
+  use crate::test_utils::white_image;
 
   fn painter() -> Painter { Painter::new(Rect::from_size(Size::new(512., 512.))) }
 
@@ -1376,6 +1669,201 @@ mod test {
     assert_eq!(&Transform::new(1., 0., 0., 1., 0., 0.), painter.transform());
   }
 
+  #[test]
+  fn dashed_path() {
+    let mut builder = Path::builder();
+    builder
+      .begin_path(Point::new(0., 0.))
+      .line_to(Point::new(10., 0.))
+      .end_path(false);
+    let line = builder.build();
+    let dashes_len = |pattern: &[f32], offset: f32| {
+      let dashed = line.dashed(pattern, offset);
+      let count = dashed
+        .segments()
+        .filter(|s| matches!(s, PathSegment::MoveTo(_)))
+        .count();
+      (count, dashed.sampler().length())
+    };
+
+    let eq = |(c1, l1): (usize, f32), (c2, l2): (usize, f32)| c1 == c2 && (l1 - l2).abs() < 1e-3;
+    assert!(eq(dashes_len(&[2., 3.], 0.), (2, 4.)));
+    assert!(eq(dashes_len(&[2., 3.], 1.), (3, 4.)));
+    // An odd pattern repeats to alternate the dashes and gaps.
+    assert!(eq(dashes_len(&[3.], 0.), (2, 6.)));
+    // An invalid pattern keeps the path solid.
+    assert!(eq(dashes_len(&[0., 0.], 0.), (1, 10.)));
+    // The zero-length dashes are kept for the caps to draw dots.
+    assert!(eq(dashes_len(&[0., 5.], 0.), (2, 0.)));
+    // Every cycle starts from its index, the offset doesn't drift.
+    assert!(eq(dashes_len(&[0.1, 0.1], 0.05), (51, 5.)));
+    // A tiny pattern would split into too many dashes, it keeps solid.
+    assert!(eq(dashes_len(&[1e-8, 1e-8], 0.), (1, 10.)));
+    assert!(eq(dashes_len(&[1e-5, 1e-5], 0.), (1, 10.)));
+  }
+
+  #[test]
+  fn blend_mode_bundle() {
+    let mut painter = painter();
+    painter
+      .rect(&rect(0., 0., 10., 10.), true)
+      .fill()
+      .save()
+      .set_blend_mode(BlendMode::Multiply)
+      .rect(&rect(5., 5., 10., 10.), true)
+      .fill()
+      .rect(&rect(20., 20., 10., 10.), true)
+      .fill();
+    assert_eq!(painter.blend_mode(), BlendMode::Multiply);
+    painter.restore();
+    assert_eq!(painter.blend_mode(), BlendMode::Normal);
+
+    let commands = painter.finish();
+    assert_eq!(commands.len(), 2);
+    let PaintCommand::Bundle { blend_mode, bounds, cmds, .. } = &commands[1] else {
+      panic!("the blended content should be bundled");
+    };
+    assert_eq!(*blend_mode, BlendMode::Multiply);
+    assert_eq!(*bounds, rect(5., 5., 25., 25.));
+    assert_eq!(cmds.len(), 2);
+  }
+
+  #[test]
+  fn mask_bundle() {
+    let mut mask = painter();
+    mask
+      .set_fill_brush(Color::WHITE)
+      .circle(Point::new(10., 10.), 5., true)
+      .fill();
+    let mask_cmds: Box<[PaintCommand]> = mask.finish().to_vec().into_boxed_slice();
+
+    let mut painter = painter();
+    painter
+      .save()
+      .rect(&rect(0., 0., 10., 10.), true)
+      .fill()
+      .set_mask(MaskMode::Alpha, Resource::new(mask_cmds))
+      .rect(&rect(5., 5., 10., 10.), true)
+      .fill()
+      .restore();
+
+    let commands = painter.finish();
+    assert_eq!(commands.len(), 2);
+    let PaintCommand::Bundle { mask: Some(mask), bounds, cmds, .. } = &commands[1] else {
+      panic!("the masked content should be bundled");
+    };
+    assert_eq!(mask.mode, MaskMode::Alpha);
+    assert_eq!(mask.cmds.len(), 1);
+    assert_eq!(*bounds, rect(5., 5., 10., 10.));
+    assert_eq!(cmds.len(), 1);
+  }
+
+  fn paint_count(commands: &[PaintCommand]) -> usize {
+    commands
+      .iter()
+      .filter(|c| matches!(c, PaintCommand::Path(p) if !matches!(p.action, PaintPathAction::Clip)))
+      .count()
+  }
+
+  /// The bounds of the clips and the fills of the commands.
+  fn clips_and_fills(commands: &[PaintCommand]) -> (Vec<Rect>, Vec<Rect>) {
+    let paths = commands.iter().filter_map(|c| match c {
+      PaintCommand::Path(p) => Some(p),
+      _ => None,
+    });
+    let (clips, fills): (Vec<_>, Vec<_>) =
+      paths.partition(|p| matches!(p.action, PaintPathAction::Clip));
+    let bounds = |paths: Vec<&PathCommand>| paths.iter().map(|p| p.paint_bounds).collect();
+    (bounds(clips), bounds(fills))
+  }
+
+  #[test]
+  fn draw_img_fit_crop() {
+    let mut painter = painter();
+    let dst = rect(0., 0., 100., 100.);
+    painter.draw_img_fit(white_image(100, 50), &dst, ImageFit::Cover, ImageAlign::CENTER);
+    // Scaled to cover the box, the overflowed left and right parts are cropped.
+    let (clips, fills) = clips_and_fills(&painter.finish());
+    assert_eq!(clips, vec![dst]);
+    assert_eq!(fills, vec![rect(-50., 0., 200., 100.)]);
+
+    let dst = rect(0., 0., 50., 50.);
+    painter.draw_img_fit(white_image(100, 50), &dst, ImageFit::Contain, ImageAlign::BOTTOM_RIGHT);
+    // Scaled to fit the box and placed at the bottom, nothing is cropped.
+    let (clips, fills) = clips_and_fills(&painter.finish());
+    assert!(clips.is_empty());
+    assert_eq!(fills, vec![rect(0., 25., 50., 25.)]);
+
+    let dst = rect(10., 10., 20., 20.);
+    painter.draw_img_fit(white_image(100, 50), &dst, ImageFit::None, ImageAlign::TOP_LEFT);
+    // The original size, only the top left part is visible.
+    let (clips, fills) = clips_and_fills(&painter.finish());
+    assert_eq!(clips, vec![dst]);
+    assert_eq!(fills, vec![rect(10., 10., 100., 50.)]);
+  }
+
+  #[test]
+  fn draw_img_nine_patch() {
+    let center = rect(10., 10., 10., 10.);
+    let dst = rect(0., 0., 100., 60.);
+
+    let mut painter = painter();
+    let mut slices = NineSlices::default();
+    painter.draw_img_nine(white_image(30, 30), &center, &dst, NinePatchMode::Stretch, &mut slices);
+    assert_eq!(paint_count(&painter.finish()), 9);
+
+    // The corners are drawn as images, the edges and the center are filled by a
+    // repeating pattern of their slice.
+    let img = white_image(30, 30);
+    let mut repeat = |painter: &mut Painter, img: &Resource<PixelImage>| {
+      painter.draw_img_nine(img.clone(), &center, &dst, NinePatchMode::Repeat, &mut slices);
+      let commands = painter.finish();
+      assert_eq!(paint_count(&commands), 9);
+      commands
+        .iter()
+        .filter_map(|c| match c {
+          PaintCommand::Path(PathCommand {
+            paint_bounds,
+            action: PaintPathAction::Paint { brush: CommandBrush::Pattern { pattern, .. }, .. },
+            ..
+          }) => Some((*paint_bounds, pattern.clone())),
+          _ => None,
+        })
+        .collect::<Vec<_>>()
+    };
+    let patterns = repeat(&mut painter, &img);
+    assert_eq!(patterns.len(), 5);
+
+    let (bounds, center) = &patterns[2];
+    assert_eq!(*bounds, rect(10., 10., 80., 40.));
+    assert_eq!(center.image.size(), DeviceSize::new(10, 10));
+    assert_eq!(center.transform, Transform::translation(10., 10.));
+    assert_eq!((center.tile_x, center.tile_y), (TileMode::Repeat, TileMode::Repeat));
+    // The top edge is stretched vertically and repeated horizontally.
+    let (bounds, top) = &patterns[0];
+    assert_eq!(*bounds, rect(10., 0., 80., 10.));
+    assert_eq!((top.tile_x, top.tile_y), (TileMode::Repeat, TileMode::NoRepeat));
+
+    // The slices are cropped once and reused.
+    let again = repeat(&mut painter, &img);
+    assert!(
+      patterns
+        .iter()
+        .zip(again.iter())
+        .all(|((_, a), (_, b))| a.image == b.image)
+    );
+
+    // Only the slices of the last painted image are kept.
+    let others = repeat(&mut painter, &white_image(30, 30));
+    assert!(
+      patterns
+        .iter()
+        .zip(others.iter())
+        .all(|((_, a), (_, b))| a.image != b.image)
+    );
+    assert_eq!(slices.slices.len(), 5);
+  }
+
   #[test]
   fn fix_clip_pop_without_restore() {
     let mut painter = painter();
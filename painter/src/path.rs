@@ -7,9 +7,13 @@ use lyon_algorithms::{
 };
 use ribir_types::{Point, Rect, Transform, Vector};
 use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 
 use crate::path_builder::PathBuilder;
 
+/// The most dashes that [`Path::dashed`] splits a sub path into.
+pub const MAX_DASHES: usize = 100_000;
+
 /// Path widget describe a shape, build the shape from [`Builder`]!
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Path {
@@ -63,6 +67,17 @@ pub struct StrokeOptions {
   ///
   /// Default: Miter
   pub line_join: LineJoin,
+
+  /// The dash pattern and the dash offset of the stroke.
+  ///
+  /// The pattern is the lengths of the alternating dashes and gaps, a pattern
+  /// with an odd count is repeated to make it even. The offset is the distance
+  /// into the pattern at which every sub-path starts, so moving it makes the
+  /// dashes "march" along the path.
+  ///
+  /// Default: None, a solid line.
+  #[serde(default)]
+  pub dash: Option<(SmallVec<[f32; 4]>, f32)>,
 }
 
 /// Draws at the beginning and end of an open path contour.
@@ -224,6 +239,52 @@ impl Path {
     })
   }
 
+  /// Returns the dashes of this path. See [`StrokeOptions::dash`] for the
+  /// meaning of `pattern` and `offset`.
+  ///
+  /// Returns the path itself if the pattern is invalid, that is empty, with a
+  /// negative length, or the sum of it is zero. A sub path that would split
+  /// into more than [`MAX_DASHES`] dashes is kept solid.
+  pub fn dashed(&self, pattern: &[f32], offset: f32) -> Path {
+    let invalid = pattern
+      .iter()
+      .any(|len| !len.is_finite() || *len < 0.);
+    let period: f32 = pattern.iter().sum();
+    if invalid || period <= 0. || !offset.is_finite() {
+      return self.clone();
+    }
+
+    let mut builder = LyonPath::builder();
+    let mut sub_path = LyonPath::builder();
+    for e in self.lyon_path.iter() {
+      match e {
+        Event::Begin { at } => {
+          sub_path.begin(at);
+        }
+        Event::Line { to, .. } => {
+          sub_path.line_to(to);
+        }
+        Event::Quadratic { ctrl, to, .. } => {
+          sub_path.quadratic_bezier_to(ctrl, to);
+        }
+        Event::Cubic { ctrl1, ctrl2, to, .. } => {
+          sub_path.cubic_bezier_to(ctrl1, ctrl2, to);
+        }
+        Event::End { close, .. } => {
+          sub_path.end(close);
+          let path: Path = std::mem::replace(&mut sub_path, LyonPath::builder())
+            .build()
+            .into();
+          path
+            .sampler()
+            .dash_into(pattern, offset, &mut builder);
+        }
+      }
+    }
+
+    Path::from(builder.build())
+  }
+
   #[cfg(feature = "tessellation")]
   pub fn fill_tessellate<Attr>(
     &self, tolerance: f32, buffer: &mut VertexBuffers<Attr>,
@@ -253,7 +314,9 @@ impl Path {
     };
 
     let mut stroke_tess = StrokeTessellator::default();
-    let StrokeOptions { width, miter_limit, line_cap, line_join } = options;
+    let StrokeOptions { width, miter_limit, line_cap, line_join, dash } = options;
+    let dashed = dash.map(|(pattern, offset)| self.dashed(&pattern, offset));
+    let path = dashed.as_ref().unwrap_or(self);
     let cap = match line_cap {
       LineCap::Butt => lyon_tessellation::LineCap::Butt,
       LineCap::Round => lyon_tessellation::LineCap::Round,
@@ -274,7 +337,7 @@ impl Path {
 
     stroke_tess
       .tessellate_path(
-        &self.lyon_path,
+        &path.lyon_path,
         &options,
         &mut BuffersBuilder::new(buffer, move |v: StrokeVertex| {
           vertex_ctor(v.position().cast_unit())
@@ -293,6 +356,16 @@ impl PathSampler {
   #[inline]
   pub fn distance_sample(&self, dist: f32) -> Point { self.sample(dist, SampleType::Distance) }
 
+  /// Sample the unit tangent at a given rate along the path.
+  #[inline]
+  pub fn normalized_tangent(&self, rate: f32) -> Vector {
+    self.tangent(rate, SampleType::Normalized)
+  }
+
+  /// Sample the unit tangent at a given distance along the path.
+  #[inline]
+  pub fn distance_tangent(&self, dist: f32) -> Vector { self.tangent(dist, SampleType::Distance) }
+
   /// Construct a path for a specific rate range of the measured path.
   #[inline]
   pub fn normalized_sub_path(&self, rate_range: Range<f32>) -> Path {
@@ -314,6 +387,63 @@ impl PathSampler {
     sampler.sample(dist).position().cast_unit()
   }
 
+  fn tangent(&self, dist: f32, t: SampleType) -> Vector {
+    let mut sampler = self.measurements.create_sampler(&self.path, t);
+    sampler
+      .sample(dist)
+      .tangent()
+      .try_normalize()
+      .map_or(Vector::zero(), |v| v.cast_unit())
+  }
+
+  /// Appends the dashes of the measured path to `builder`, the pattern must be
+  /// valid.
+  ///
+  /// The path is appended solid if it would split into more than
+  /// [`MAX_DASHES`] dashes, which happens when the period of the pattern is
+  /// tiny relative to the length.
+  fn dash_into(
+    &self, pattern: &[f32], offset: f32,
+    builder: &mut dyn lyon_algorithms::path::builder::PathBuilder,
+  ) {
+    // A pattern with an odd count repeats twice to alternate dashes and gaps.
+    let count = if pattern.len().is_multiple_of(2) { pattern.len() } else { pattern.len() * 2 };
+    let period: f32 = pattern.iter().cycle().take(count).sum();
+    let length = self.length();
+    let mut sampler = self
+      .measurements
+      .create_sampler(&self.path, SampleType::Distance);
+
+    let phase = -offset.rem_euclid(period);
+    let cycles = ((length - phase) / period).ceil();
+    if !cycles.is_finite() || cycles * count as f32 > MAX_DASHES as f32 {
+      sampler.split_range(0.0..length, builder);
+      return;
+    }
+
+    // Every cycle starts from its index, so the float errors don't accumulate.
+    for cycle in 0..cycles as usize {
+      let mut start = phase + cycle as f32 * period;
+      for (idx, len) in pattern.iter().cycle().take(count).enumerate() {
+        if start >= length {
+          return;
+        }
+        let end = start + len;
+        if idx.is_multiple_of(2) && end > start && end > 0. {
+          sampler.split_range(start.max(0.)..end.min(length), builder);
+        } else if idx.is_multiple_of(2) && end == start && start >= 0. {
+          // Keep a zero-length dash as a degenerate sub path, so the round and
+          // square caps draw a dot for it.
+          let at = sampler.sample(start).position();
+          builder.begin(at, &[]);
+          builder.line_to(at, &[]);
+          builder.end(false);
+        }
+        start = end;
+      }
+    }
+  }
+
   fn sub_path(&self, range: Range<f32>, t: SampleType) -> Path {
     let mut sampler = self.measurements.create_sampler(&self.path, t);
     let mut builder = LyonPath::builder();
@@ -403,6 +533,7 @@ impl Default for StrokeOptions {
       miter_limit: 4.0,
       line_cap: LineCap::default(),
       line_join: LineJoin::default(),
+      dash: None,
     }
   }
 }
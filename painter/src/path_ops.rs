@@ -0,0 +1,327 @@
+//! The boolean operations, stroke outlines, offsets and hit-testing of
+//! [`Path`].
+//!
+//! The curves are flattened to polygons before the operations, so the result
+//! paths only contain lines.
+use i_overlay::{
+  core::{fill_rule::FillRule, overlay_rule::OverlayRule},
+  float::single::SingleFloatOverlay,
+  mesh::{
+    outline::offset::OutlineOffset,
+    stroke::offset::StrokeOffset,
+    style::{self, OutlineStyle, StrokeStyle},
+  },
+};
+use lyon_algorithms::path::{Event, Path as LyonPath, iterator::PathIterator};
+use ribir_types::{Point, Rect};
+
+use crate::{LineCap, LineJoin, Path, StrokeOptions};
+
+/// The tolerance to flatten the curves of the paths.
+const FLATTEN_TOLERANCE: f32 = 0.05;
+/// The ratio of the round joins and caps, the smaller the smoother.
+const ROUND_RATIO: f32 = 0.1;
+
+type Contour = Vec<[f32; 2]>;
+
+/// The boolean operation to combine two paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathOp {
+  /// The area covered by either path.
+  Union,
+  /// The area covered by both paths.
+  Intersection,
+  /// The area of the first path that is not covered by the second path.
+  Difference,
+  /// The area covered by only one of the paths.
+  Xor,
+}
+
+/// A path with its curves flattened to polygons, to hit-test the same path
+/// many times without flattening it again.
+#[derive(Debug, Clone)]
+pub struct FlattenedPath {
+  bounds: Rect,
+  contours: Vec<Contour>,
+}
+
+impl FlattenedPath {
+  /// Returns whether the point is in the filled area of the path by the
+  /// non-zero rule.
+  pub fn contains(&self, pt: Point) -> bool {
+    if !self.bounds.contains(pt) {
+      return false;
+    }
+    let winding: i32 = self
+      .contours
+      .iter()
+      .map(|c| winding_number(c, pt))
+      .sum();
+    winding != 0
+  }
+}
+
+impl Path {
+  /// Combines this path with `other` by the boolean `op`, both paths are
+  /// filled by the non-zero rule.
+  pub fn op(&self, other: &Path, op: PathOp) -> Path {
+    let rule = match op {
+      PathOp::Union => OverlayRule::Union,
+      PathOp::Intersection => OverlayRule::Intersect,
+      PathOp::Difference => OverlayRule::Difference,
+      PathOp::Xor => OverlayRule::Xor,
+    };
+    let subj = self.contours();
+    let clip = other.contours();
+    shapes_to_path(subj.overlay(&clip, rule, FillRule::NonZero))
+  }
+
+  #[inline]
+  pub fn union(&self, other: &Path) -> Path { self.op(other, PathOp::Union) }
+
+  #[inline]
+  pub fn intersection(&self, other: &Path) -> Path { self.op(other, PathOp::Intersection) }
+
+  #[inline]
+  pub fn difference(&self, other: &Path) -> Path { self.op(other, PathOp::Difference) }
+
+  #[inline]
+  pub fn xor(&self, other: &Path) -> Path { self.op(other, PathOp::Xor) }
+
+  /// Returns the outline of the stroke of this path as a filled path.
+  pub fn stroke_outline(&self, options: &StrokeOptions) -> Path {
+    let StrokeOptions { width, miter_limit, line_cap, line_join, dash } = options;
+    let dashed = dash
+      .as_ref()
+      .map(|(pattern, offset)| self.dashed(pattern, *offset));
+    let path = dashed.as_ref().unwrap_or(self);
+
+    let cap = match line_cap {
+      LineCap::Butt => style::LineCap::Butt,
+      LineCap::Round => style::LineCap::Round(ROUND_RATIO),
+      LineCap::Square => style::LineCap::Square,
+    };
+    let stroke = StrokeStyle::new(*width)
+      .line_join(line_join_style(*line_join, *miter_limit))
+      .start_cap(cap.clone())
+      .end_cap(cap);
+
+    let (closed, open): (Vec<_>, Vec<_>) = path
+      .contours_with_close()
+      .into_iter()
+      .partition(|(_, close)| *close);
+    let closed: Vec<Contour> = closed.into_iter().map(|(c, _)| c).collect();
+    let open: Vec<Contour> = open.into_iter().map(|(c, _)| c).collect();
+
+    let mut shapes = closed.stroke(stroke.clone(), true);
+    let open = open.stroke(stroke, false);
+    if shapes.is_empty() {
+      shapes = open;
+    } else if !open.is_empty() {
+      shapes = shapes.overlay(&open, OverlayRule::Union, FillRule::NonZero);
+    }
+    shapes_to_path(shapes)
+  }
+
+  /// Returns the path offset by `distance`, a positive distance grows the
+  /// filled area, and a negative one shrinks it.
+  pub fn offset(&self, distance: f32, join: LineJoin) -> Path {
+    // Resolve the self-intersections and the orientations of the contours.
+    let shapes =
+      self
+        .contours()
+        .overlay(&Vec::<Contour>::new(), OverlayRule::Subject, FillRule::NonZero);
+    if distance == 0. {
+      return shapes_to_path(shapes);
+    }
+    let style = OutlineStyle::new(distance).line_join(line_join_style(join, 4.));
+    shapes_to_path(shapes.outline(&style))
+  }
+
+  /// Returns whether the point is in the filled area of this path by the
+  /// non-zero rule.
+  ///
+  /// The path is flattened on every call, use [`Path::flatten`] to hit-test
+  /// the same path many times.
+  pub fn contains(&self, pt: Point) -> bool {
+    self.bounds(None).contains(pt) && self.flatten().contains(pt)
+  }
+
+  /// Flattens the curves of this path to hit-test it.
+  pub fn flatten(&self) -> FlattenedPath {
+    FlattenedPath { bounds: self.bounds(None), contours: self.contours() }
+  }
+
+  /// Returns whether the point is in the stroke of this path.
+  pub fn stroke_contains(&self, pt: Point, options: &StrokeOptions) -> bool {
+    let bounds = self.bounds(Some(options.width * options.miter_limit.max(1.)));
+    bounds.contains(pt) && self.stroke_outline(options).contains(pt)
+  }
+
+  /// Returns the area of the filled region of this path by the non-zero rule.
+  pub fn area(&self) -> f32 {
+    self
+      .contours()
+      .overlay(&Vec::<Contour>::new(), OverlayRule::Subject, FillRule::NonZero)
+      .iter()
+      .flatten()
+      .map(|c| signed_area(c))
+      .sum::<f32>()
+      .abs()
+  }
+
+  fn contours(&self) -> Vec<Contour> {
+    self
+      .contours_with_close()
+      .into_iter()
+      .map(|(c, _)| c)
+      .collect()
+  }
+
+  fn contours_with_close(&self) -> Vec<(Contour, bool)> {
+    let mut contours = vec![];
+    let mut current = vec![];
+    for e in self.lyon_path.iter().flattened(FLATTEN_TOLERANCE) {
+      match e {
+        Event::Begin { at } => current.push([at.x, at.y]),
+        Event::Line { to, .. } => current.push([to.x, to.y]),
+        Event::End { close, .. } => {
+          let contour = std::mem::take(&mut current);
+          if contour.len() > 1 {
+            contours.push((contour, close));
+          }
+        }
+        Event::Quadratic { .. } | Event::Cubic { .. } => unreachable!("the path is flattened"),
+      }
+    }
+    contours
+  }
+}
+
+fn line_join_style(join: LineJoin, miter_limit: f32) -> style::LineJoin<f32> {
+  match join {
+    LineJoin::Round => style::LineJoin::Round(ROUND_RATIO),
+    LineJoin::Bevel => style::LineJoin::Bevel,
+    // The miter is used until the angle of the corner is less than the one of
+    // the miter limit.
+    LineJoin::Miter | LineJoin::MiterClip => {
+      style::LineJoin::Miter(2. * (1. / miter_limit.max(1.)).asin())
+    }
+  }
+}
+
+fn shapes_to_path(shapes: Vec<Vec<Contour>>) -> Path {
+  let mut builder = LyonPath::builder();
+  for contour in shapes.iter().flatten() {
+    let mut points = contour
+      .iter()
+      .map(|[x, y]| lyon_algorithms::geom::point(*x, *y));
+    let Some(first) = points.next() else { continue };
+    builder.begin(first);
+    for p in points {
+      builder.line_to(p);
+    }
+    builder.end(true);
+  }
+  builder.build().into()
+}
+
+fn winding_number(contour: &[[f32; 2]], pt: Point) -> i32 {
+  let mut winding = 0;
+  let n = contour.len();
+  for i in 0..n {
+    let [x0, y0] = contour[i];
+    let [x1, y1] = contour[(i + 1) % n];
+    let cross = (x1 - x0) * (pt.y - y0) - (pt.x - x0) * (y1 - y0);
+    if y0 <= pt.y {
+      if y1 > pt.y && cross > 0. {
+        winding += 1;
+      }
+    } else if y1 <= pt.y && cross < 0. {
+      winding -= 1;
+    }
+  }
+  winding
+}
+
+fn signed_area(contour: &[[f32; 2]]) -> f32 {
+  let n = contour.len();
+  (0..n)
+    .map(|i| {
+      let [x0, y0] = contour[i];
+      let [x1, y1] = contour[(i + 1) % n];
+      x0 * y1 - x1 * y0
+    })
+    .sum::<f32>()
+    / 2.
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_types::{Rect, Size, Vector, rect};
+
+  use super::*;
+
+  fn square(x: f32, y: f32, size: f32) -> Path { Path::rect(&rect(x, y, size, size)) }
+
+  fn near(a: f32, b: f32) -> bool { (a - b).abs() < 0.5 }
+
+  #[test]
+  fn boolean_ops() {
+    let a = square(0., 0., 10.);
+    let b = square(5., 5., 10.);
+
+    assert!(near(a.union(&b).area(), 175.));
+    assert!(near(a.intersection(&b).area(), 25.));
+    assert!(near(a.difference(&b).area(), 75.));
+    assert!(near(a.xor(&b).area(), 150.));
+
+    let hole = square(0., 0., 20.).difference(&square(5., 5., 10.));
+    assert!(hole.contains(Point::new(2., 2.)));
+    assert!(!hole.contains(Point::new(10., 10.)));
+
+    let flattened = hole.flatten();
+    assert!(flattened.contains(Point::new(2., 2.)));
+    assert!(!flattened.contains(Point::new(10., 10.)));
+    assert!(!flattened.contains(Point::new(30., 30.)));
+  }
+
+  fn line() -> Path {
+    let mut builder = Path::builder();
+    builder
+      .begin_path(Point::new(0., 0.))
+      .line_to(Point::new(10., 0.))
+      .end_path(false);
+    builder.build()
+  }
+
+  #[test]
+  fn stroke_and_offset() {
+    let line = line();
+    let options = StrokeOptions { width: 2., ..Default::default() };
+    let outline = line.stroke_outline(&options);
+    assert!(near(outline.area(), 20.));
+    assert!(line.stroke_contains(Point::new(5., 0.5), &options));
+    assert!(!line.stroke_contains(Point::new(5., 2.), &options));
+
+    let grown = square(0., 0., 10.).offset(1., LineJoin::Miter);
+    assert_eq!(grown.bounds(None), Rect::new(Point::new(-1., -1.), Size::new(12., 12.)));
+    let shrunk = square(0., 0., 10.).offset(-1., LineJoin::Miter);
+    assert!(near(shrunk.area(), 64.));
+  }
+
+  #[test]
+  fn sample_tangent() {
+    let sampler = line().sampler();
+    assert_eq!(sampler.distance_tangent(5.), Vector::new(1., 0.));
+    assert_eq!(sampler.normalized_tangent(1.), Vector::new(1., 0.));
+  }
+
+  #[test]
+  fn contains_curve() {
+    let circle = Path::circle(Point::new(10., 10.), 5.);
+    assert!(circle.contains(Point::new(10., 10.)));
+    assert!(circle.contains(Point::new(14., 10.)));
+    assert!(!circle.contains(Point::new(14., 14.)));
+  }
+}
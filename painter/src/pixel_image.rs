@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 
 use image_webp::{ColorType as WebPColorType, WebPDecoder, WebPEncoder};
-use ribir_types::DeviceSize;
+use ribir_types::{DeviceRect, DeviceSize};
 use serde::{Deserialize, Serialize};
 
 type BoxError = Box<dyn std::error::Error>;
@@ -45,6 +45,23 @@ impl PixelImage {
   #[inline]
   pub fn pixel_bytes(&self) -> &[u8] { &self.data }
 
+  /// Copies the pixels in the `rect` to a new image, the part of the `rect`
+  /// outside the image is ignored.
+  pub fn crop(&self, rect: &DeviceRect) -> PixelImage {
+    let Some(rect) = rect.intersection(&DeviceRect::from_size(self.size())) else {
+      return PixelImage::new(Cow::Borrowed(&[]), 0, 0, self.format);
+    };
+    let bpp = self.format.bytes_per_pixel() as usize;
+    let stride = self.width as usize * bpp;
+    let (x, row) = (rect.min_x() as usize * bpp, rect.width() as usize * bpp);
+    let mut data = Vec::with_capacity(row * rect.height() as usize);
+    for y in rect.y_range() {
+      let start = y as usize * stride + x;
+      data.extend_from_slice(&self.data[start..start + row]);
+    }
+    PixelImage::new(data.into(), rect.width() as u32, rect.height() as u32, self.format)
+  }
+
   fn expand_rgb(bytes: Vec<u8>) -> Vec<u8> {
     bytes
       .chunks_exact(3)
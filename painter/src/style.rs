@@ -1,9 +1,10 @@
 use ribir_algo::Resource;
+use ribir_types::Transform;
 use serde::{Deserialize, Serialize};
 
 use crate::{
   Color, PixelImage,
-  color::{LinearGradient, RadialGradient},
+  color::{LinearGradient, RadialGradient, SweepGradient},
 };
 
 /// The brush is used to fill or stroke shapes with color, image, or gradient.
@@ -14,6 +15,86 @@ pub enum Brush {
   Image(Resource<PixelImage>),
   RadialGradient(Resource<RadialGradient>),
   LinearGradient(Resource<LinearGradient>),
+  SweepGradient(Resource<SweepGradient>),
+  /// An image tiled by its own transform and tile modes.
+  Pattern(Resource<ImagePattern>),
+}
+
+/// How a pattern fills the space outside its image along an axis.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TileMode {
+  /// Repeat the image.
+  #[default]
+  Repeat,
+  /// Repeat the image, every other tile is mirrored.
+  Mirror,
+  /// Only paint the image once, the space outside it is transparent.
+  NoRepeat,
+}
+
+/// An image brush that tiles the image in its own coordinate space.
+///
+/// Unlike the [`Brush::Image`], the pattern is placed by the `transform`, which
+/// maps the image space to the space of the painted path, and every axis has
+/// its own [`TileMode`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImagePattern {
+  pub image: Resource<PixelImage>,
+  pub transform: Transform,
+  pub tile_x: TileMode,
+  pub tile_y: TileMode,
+}
+
+impl ImagePattern {
+  /// Creates a pattern that repeats the image on both axes.
+  pub fn new(image: Resource<PixelImage>) -> Self {
+    Self {
+      image,
+      transform: Transform::identity(),
+      tile_x: TileMode::Repeat,
+      tile_y: TileMode::Repeat,
+    }
+  }
+
+  /// Creates a pattern that only repeats the image horizontally.
+  pub fn repeat_x(image: Resource<PixelImage>) -> Self {
+    Self::new(image).with_tile(TileMode::Repeat, TileMode::NoRepeat)
+  }
+
+  /// Creates a pattern that only repeats the image vertically.
+  pub fn repeat_y(image: Resource<PixelImage>) -> Self {
+    Self::new(image).with_tile(TileMode::NoRepeat, TileMode::Repeat)
+  }
+
+  /// Creates a pattern that mirrors the image on both axes.
+  pub fn mirror(image: Resource<PixelImage>) -> Self {
+    Self::new(image).with_tile(TileMode::Mirror, TileMode::Mirror)
+  }
+
+  pub fn with_tile(mut self, tile_x: TileMode, tile_y: TileMode) -> Self {
+    self.tile_x = tile_x;
+    self.tile_y = tile_y;
+    self
+  }
+
+  pub fn with_transform(mut self, transform: Transform) -> Self {
+    self.transform = transform;
+    self
+  }
+
+  /// Maps the position `v` of an image axis of the length `len` into the
+  /// image, returns `None` if the position is outside a `NoRepeat` axis.
+  pub fn tile(mode: TileMode, v: f32, len: f32) -> Option<f32> {
+    let t = v / len;
+    let t = match mode {
+      TileMode::Repeat => t.rem_euclid(1.),
+      TileMode::Mirror => 1. - ((t * 0.5).rem_euclid(1.) * 2. - 1.).abs(),
+      TileMode::NoRepeat if (0. ..=1.).contains(&t) => t,
+      TileMode::NoRepeat => return None,
+    };
+    Some(t * len)
+  }
 }
 
 impl Brush {
@@ -31,6 +112,8 @@ impl Brush {
       Brush::Image(_) => true,
       Brush::RadialGradient(gradient) => gradient.stops.iter().any(|s| s.color.alpha > 0),
       Brush::LinearGradient(gradient) => gradient.stops.iter().any(|s| s.color.alpha > 0),
+      Brush::SweepGradient(gradient) => gradient.stops.iter().any(|s| s.color.alpha > 0),
+      Brush::Pattern(_) => true,
     }
   }
 }
@@ -70,6 +153,26 @@ impl From<LinearGradient> for Brush {
   fn from(gradient: LinearGradient) -> Self { Resource::new(gradient).into() }
 }
 
+impl From<Resource<SweepGradient>> for Brush {
+  #[inline]
+  fn from(gradient: Resource<SweepGradient>) -> Self { Brush::SweepGradient(gradient) }
+}
+
+impl From<SweepGradient> for Brush {
+  #[inline]
+  fn from(gradient: SweepGradient) -> Self { Resource::new(gradient).into() }
+}
+
+impl From<Resource<ImagePattern>> for Brush {
+  #[inline]
+  fn from(pattern: Resource<ImagePattern>) -> Self { Brush::Pattern(pattern) }
+}
+
+impl From<ImagePattern> for Brush {
+  #[inline]
+  fn from(pattern: ImagePattern) -> Self { Resource::new(pattern).into() }
+}
+
 impl Default for Brush {
   #[inline]
   fn default() -> Self { Color::BLACK.into() }
@@ -1,19 +1,45 @@
-use std::{cell::RefCell, error::Error, io::Read, vec};
+use std::{
+  borrow::Cow,
+  cell::RefCell,
+  error::Error,
+  io::Read,
+  sync::{Arc, Mutex},
+  vec,
+};
 
+use lyon_algorithms::path::builder::SvgPathBuilder;
 use ribir_algo::Resource;
 use ribir_types::{Point, Rect, Size, Transform};
 use serde::{Deserialize, Serialize};
-use usvg::{Options, Stop, Tree};
+use usvg::{Options, Stop, Tree, fontdb};
 
 use crate::{
-  Brush, Color, CommandBrush, GradientStop, LineCap, LineJoin, PaintCommand, PaintPathAction, Path,
-  StrokeOptions,
+  BlendMode, Brush, BundleMask, Color, ColorFilterMatrix, ColorFormat, CommandBrush, Filter,
+  GradientStop, ImagePattern, LineCap, LineJoin, MaskMode, PaintCommand, PaintPathAction, Path,
+  PixelImage, StrokeOptions,
   color::{LinearGradient, RadialGradient},
 };
 
-/// This is a basic SVG support designed for rendering to Ribir painter. It is
-/// currently quite simple and primarily used for Ribir icons. More features
-/// will be added as needed.
+/// The fonts to shape the `<text>` of SVGs, see [`Svg::parse_with_fonts`].
+pub type SvgFonts = fontdb::Database;
+
+/// The SVG support designed for rendering to Ribir painter. The SVG is
+/// flattened to paint commands when parsed.
+///
+/// Besides the paths and the gradients, these features are mapped to the
+/// painter:
+/// - `<clipPath>` is a clip of the union of its shapes.
+/// - `<mask>` is a luminance or alpha mask of the content, painted by the
+///   content of the mask and clipped by the mask region.
+/// - the filters `feGaussianBlur`, `feColorMatrix`, `feDropShadow` and
+///   `feOffset` are chained as a [`Filter`], other primitives are ignored.
+/// - `<pattern>` fills are an [`ImagePattern`] of its tile, the content of the
+///   tile is rasterized when parsed.
+/// - WebP and nested SVG images are drawn as images.
+/// - `<text>` is painted by the outlines of its glyphs, so the parsed SVG keeps
+///   serializable and not depends on the fonts of Ribir. The fonts are looked
+///   up in the [`SvgFonts`] passed to [`Svg::parse_with_fonts`], or in the
+///   shared fonts, see [`Svg::shared_fonts`].
 
 #[derive(Serialize, Deserialize)]
 pub struct Svg {
@@ -33,14 +59,35 @@ struct StaticSvg {
   commands: Resource<Box<[PaintCommand]>>,
 }
 
-// todo: share fontdb
+/// The pixels of a pattern tile per unit of the SVG, keeps the tile sharp when
+/// the SVG is scaled up a little.
+const PATTERN_RASTER_SCALE: f32 = 2.;
+/// The maximum pixels of a side of a rasterized pattern tile.
+const MAX_PATTERN_TILE_SIZE: f32 = 1024.;
+
+/// The fonts shared by the SVGs, `None` until the first SVG is parsed.
+static SHARED_FONTS: Mutex<Option<Arc<SvgFonts>>> = Mutex::new(None);
+
+fn with_shared_fonts<R>(f: impl FnOnce(&mut Arc<SvgFonts>) -> R) -> R {
+  let mut fonts = SHARED_FONTS.lock().unwrap();
+  let fonts = fonts.get_or_insert_with(|| {
+    #[allow(unused_mut)]
+    let mut db = SvgFonts::new();
+    #[cfg(not(target_arch = "wasm32"))]
+    db.load_system_fonts();
+    Arc::new(db)
+  });
+  f(fonts)
+}
+
 impl Svg {
   // FIXME: This is a temporary workaround. Utilize the magic color for the SVG,
   // and replace it with the actual color when rendering.
   const DYNAMIC_COLOR: Color = Color::from_u32(0x191B1901);
   const DYNAMIC_COLOR_STR: &'static str = "#191B1901";
 
-  /// Parse SVG from bytes.
+  /// Parse SVG from bytes, its `<text>` is shaped with the
+  /// [shared fonts](Svg::shared_fonts).
   ///
   /// - **inherit_fill**: Indicates whether this SVG will inherit the fill color
   ///   from the environment.
@@ -48,6 +95,27 @@ impl Svg {
   ///   color from the environment.
   pub fn parse_from_bytes(
     svg_data: &[u8], inherit_fill: bool, inherit_stroke: bool,
+  ) -> Result<Self, Box<dyn Error>> {
+    Self::parse_with_fonts(svg_data, inherit_fill, inherit_stroke, Self::shared_fonts())
+  }
+
+  /// The fonts shared by the SVGs parsed without their own fonts. They're the
+  /// system fonts, loaded on the first use, and the fonts added by
+  /// [`Svg::load_font_data`].
+  pub fn shared_fonts() -> Arc<SvgFonts> { with_shared_fonts(|fonts| fonts.clone()) }
+
+  /// Adds the fonts in `data` to the [shared fonts](Svg::shared_fonts), the
+  /// SVGs parsed after it can use them.
+  pub fn load_font_data(data: Vec<u8>) {
+    with_shared_fonts(|fonts| Arc::make_mut(fonts).load_font_data(data));
+  }
+
+  /// Parse SVG from bytes, and shape its `<text>` with `fonts`.
+  ///
+  /// Loading the system fonts is slow, prefer to load them once and share the
+  /// fonts between the SVGs.
+  pub fn parse_with_fonts(
+    svg_data: &[u8], inherit_fill: bool, inherit_stroke: bool, fonts: Arc<SvgFonts>,
   ) -> Result<Self, Box<dyn Error>> {
     let magic = Self::DYNAMIC_COLOR_STR;
     let style_sheet = match (inherit_fill, inherit_stroke) {
@@ -57,14 +125,14 @@ impl Svg {
       _ => None,
     };
 
-    let opt = Options { style_sheet, ..<_>::default() };
+    let opt = Options { style_sheet, fontdb: fonts, ..<_>::default() };
     let tree = Tree::from_data(svg_data, &opt).unwrap();
 
     let size = tree.size();
 
     let bound_rect = Rect::from_size(Size::new(f32::MAX, f32::MAX));
     let mut painter = crate::Painter::new(bound_rect);
-    paint_group(tree.root(), &mut painter);
+    paint_group(tree.root(), &Transform::identity(), &mut painter);
 
     let paint_commands = painter.finish().to_owned().into_boxed_slice();
     let (used_fill_fallback, used_stroke_fallback) = fallback_color_check(&paint_commands);
@@ -130,87 +198,335 @@ impl Svg {
   pub fn deserialize(str: &str) -> Result<Self, Box<dyn Error>> { Ok(serde_json::from_str(str)?) }
 }
 
-fn paint_group(g: &usvg::Group, painter: &mut crate::Painter) {
-  let mut painter = painter.save_guard();
+fn paint_group(g: &usvg::Group, ts: &Transform, painter: &mut crate::Painter) {
   for child in g.children() {
     match child {
-      usvg::Node::Group(g) => {
-        // todo;
-        painter.apply_alpha(g.opacity().get());
+      usvg::Node::Group(g) => paint_sub_group(g, ts, painter),
+      usvg::Node::Path(p) if p.is_visible() => paint_path(p, ts, painter),
+      usvg::Node::Path(_) => {}
+      usvg::Node::Image(img) if img.is_visible() => paint_image(img, ts, painter),
+      usvg::Node::Image(_) => {}
+      usvg::Node::Text(t) => paint_group(t.flattened(), ts, painter),
+    }
+  }
+}
 
-        if g.clip_path().is_some() {
-          tracing::warn!("[painter]: not support `clip path` in svg, ignored!");
-        }
-        if g.mask().is_some() {
-          tracing::warn!("[painter]: not support `mask` in svg, ignored!");
-        }
-        if !g.filters().is_empty() {
-          tracing::warn!("[painter]: not support `filters` in svg, ignored!");
+fn paint_sub_group(g: &usvg::Group, parent_ts: &Transform, painter: &mut crate::Painter) {
+  let ts = matrix_convert(g.transform()).then(parent_ts);
+  let mut layer = painter.save_guard();
+  layer
+    .apply_alpha(g.opacity().get())
+    .set_blend_mode(blend_mode_from_usvg(g.blend_mode()));
+  if let Some(clip) = g.clip_path() {
+    apply_clip_path(clip, &ts, &mut layer);
+  }
+  // The mask bundles the content drawn in its state, so it's set in a new state
+  // after the clips.
+  let mut layer = layer.save_guard();
+  if let Some(mask) = g.mask() {
+    apply_mask(mask, &ts, &mut layer);
+  }
+  if g.filters().is_empty() {
+    paint_group(g, &ts, &mut layer);
+  } else {
+    paint_filtered_group(g, &ts, &mut layer);
+  }
+}
+
+fn paint_path(p: &usvg::Path, ts: &Transform, painter: &mut crate::Painter) {
+  let path = usvg_path_to_path(p);
+  if let Some(fill) = p.fill() {
+    if let usvg::Paint::Pattern(pattern) = fill.paint() {
+      fill_with_pattern(&path, pattern, fill.opacity(), ts, painter);
+    } else {
+      let (brush, transform) = brush_from_usvg_paint(fill.paint(), fill.opacity());
+
+      let inverse_ts = transform.inverse().unwrap();
+      let path = Resource::new(path.clone().transform(&inverse_ts));
+      painter
+        .set_transform(*ts)
+        .set_fill_brush(brush.clone())
+        .apply_transform(&transform)
+        .fill_path(path.into());
+    }
+  }
+
+  if let Some(stroke) = p.stroke() {
+    let options = StrokeOptions {
+      width: stroke.width().get(),
+      line_cap: stroke.linecap().into(),
+      line_join: stroke.linejoin().into(),
+      miter_limit: stroke.miterlimit().get(),
+      dash: stroke
+        .dasharray()
+        .map(|dash| (dash.iter().copied().collect(), stroke.dashoffset())),
+    };
+
+    let (brush, transform) = brush_from_usvg_paint(stroke.paint(), stroke.opacity());
+    painter
+      .set_transform(*ts)
+      .set_stroke_brush(brush.clone())
+      .set_strokes(options)
+      .apply_transform(&transform)
+      .stroke_path(path.into());
+  };
+}
+
+/// Clips the painter by the union of the shapes of the clip path, the
+/// `clip-rule` of the shapes is ignored.
+fn apply_clip_path(clip: &usvg::ClipPath, ts: &Transform, painter: &mut crate::Painter) {
+  if let Some(clip) = clip.clip_path() {
+    apply_clip_path(clip, ts, painter);
+  }
+
+  let ts = matrix_convert(clip.transform()).then(ts);
+  let mut builder = lyon_algorithms::path::Path::svg_builder();
+  collect_shapes(clip.root(), &Transform::identity(), &mut |p| p.is_visible(), &mut builder);
+  clip_by_shapes(builder.build().into(), &ts, painter);
+}
+
+/// Masks the content drawn after it in the state of the painter by the
+/// content of the mask.
+fn apply_mask(mask: &usvg::Mask, ts: &Transform, painter: &mut crate::Painter) {
+  let mode = match mask.kind() {
+    usvg::MaskType::Luminance => MaskMode::Luminance,
+    usvg::MaskType::Alpha => MaskMode::Alpha,
+  };
+
+  let mut mask_painter = crate::Painter::new(Rect::from_size(Size::new(f32::MAX, f32::MAX)));
+  let region = mask.rect();
+  let region =
+    Rect::new(Point::new(region.x(), region.y()), Size::new(region.width(), region.height()));
+  mask_painter
+    .set_transform(*ts)
+    .clip(Path::rect(&region).into());
+  {
+    let mut layer = mask_painter.save_guard();
+    // The mask of a mask covers the content of the mask.
+    if let Some(mask) = mask.mask() {
+      apply_mask(mask, ts, &mut layer);
+    }
+    paint_group(mask.root(), ts, &mut layer);
+  }
+  let cmds = mask_painter
+    .finish()
+    .to_owned()
+    .into_boxed_slice();
+  painter.set_mask(mode, Resource::new(cmds));
+}
+
+/// Collects the geometry of the shapes in `g` accepted by `filter` into
+/// `builder`, in the space of `g` transformed by `ts`.
+fn collect_shapes(
+  g: &usvg::Group, ts: &Transform, filter: &mut dyn FnMut(&usvg::Path) -> bool,
+  builder: &mut impl SvgPathBuilder,
+) {
+  for child in g.children() {
+    match child {
+      usvg::Node::Group(g) => {
+        let ts = matrix_convert(g.transform()).then(ts);
+        if g.clip_path().is_some() || g.mask().is_some() || !g.filters().is_empty() {
+          tracing::warn!("[painter]: not support effects inside a clip path, ignored!");
         }
-        paint_group(g, &mut painter);
+        collect_shapes(g, &ts, filter, builder);
       }
       usvg::Node::Path(p) => {
-        painter.set_transform(matrix_convert(p.abs_transform()));
-        let path = usvg_path_to_path(p);
-        if let Some(fill) = p.fill() {
-          let (brush, transform) = brush_from_usvg_paint(fill.paint(), fill.opacity());
-
-          let inverse_ts = transform.inverse().unwrap();
-          let path = Resource::new(path.clone().transform(&inverse_ts));
-          painter
-            .set_fill_brush(brush.clone())
-            .apply_transform(&transform)
-            .fill_path(path.into());
-          //&o_ts.then(&n_ts.inverse().unwrap())));
+        if filter(p) {
+          append_usvg_path(p, ts, builder);
         }
-
-        if let Some(stroke) = p.stroke() {
-          let options = StrokeOptions {
-            width: stroke.width().get(),
-            line_cap: stroke.linecap().into(),
-            line_join: stroke.linejoin().into(),
-            miter_limit: stroke.miterlimit().get(),
-          };
-
-          let (brush, transform) = brush_from_usvg_paint(stroke.paint(), stroke.opacity());
-          painter
-            .set_stroke_brush(brush.clone())
-            .set_strokes(options)
-            .apply_transform(&transform)
-            .stroke_path(path.into());
-        };
       }
-      usvg::Node::Image(_) => {
-        // todo;
-        tracing::warn!("[painter]: not support draw embed image in svg, ignored!");
+      usvg::Node::Text(t) => collect_shapes(t.flattened(), ts, filter, builder),
+      usvg::Node::Image(_) => {}
+    }
+  }
+}
+
+fn clip_by_shapes(shapes: Path, ts: &Transform, painter: &mut crate::Painter) {
+  // An empty clip path or mask hides everything.
+  let shapes = if shapes.bounds(None).is_empty() { Path::rect(&Rect::zero()) } else { shapes };
+  painter.set_transform(*ts).clip(shapes.into());
+}
+
+/// Paints the group with its filters, the filter primitives are chained one
+/// after another, and the result is clipped by the filter regions.
+fn paint_filtered_group(g: &usvg::Group, ts: &Transform, painter: &mut crate::Painter) {
+  let mut filter = Filter::new();
+  for f in g.filters() {
+    let region = f.rect();
+    let region =
+      Rect::new(Point::new(region.x(), region.y()), Size::new(region.width(), region.height()));
+    painter
+      .set_transform(*ts)
+      .clip(Path::rect(&region).into());
+    for primitive in f.primitives() {
+      filter = filter.then(filter_from_usvg(primitive.kind()));
+    }
+  }
+
+  let mut layer = painter.save_guard();
+  layer.set_transform(*ts).filter(filter);
+  paint_group(g, ts, &mut layer);
+}
+
+fn filter_from_usvg(kind: &usvg::filter::Kind) -> Filter {
+  use usvg::filter::{ColorMatrixKind, Input, Kind};
+
+  let input = match kind {
+    Kind::GaussianBlur(blur) => Some(blur.input()),
+    Kind::ColorMatrix(matrix) => Some(matrix.input()),
+    Kind::DropShadow(shadow) => Some(shadow.input()),
+    Kind::Offset(offset) => Some(offset.input()),
+    _ => None,
+  };
+  if let Some(input) = input.filter(|input| !matches!(input, Input::SourceGraphic)) {
+    tracing::warn!(
+      "[painter]: not support filter input `{input:?}` in svg, use the source instead!"
+    );
+  }
+
+  match kind {
+    // The sigma of the blur filter is the half of its radius.
+    Kind::GaussianBlur(blur) => {
+      Filter::blur(2. * blur.std_dev_x().get().max(blur.std_dev_y().get()))
+    }
+    Kind::ColorMatrix(matrix) => match matrix.kind() {
+      ColorMatrixKind::Matrix(m) => {
+        #[rustfmt::skip]
+        let matrix = ColorFilterMatrix::new([
+          m[0], m[1], m[2], m[3],
+          m[5], m[6], m[7], m[8],
+          m[10], m[11], m[12], m[13],
+          m[15], m[16], m[17], m[18],
+        ]);
+        let offsets = [m[4], m[9], m[14], m[19]];
+        if offsets.iter().any(|v| *v != 0.) {
+          let [r, g, b, a] = offsets.map(|v| v.clamp(0., 1.));
+          Filter::color(matrix.with_base(Color::from_f32_rgba(r, g, b, a)))
+        } else {
+          Filter::color(matrix)
+        }
       }
-      usvg::Node::Text(t) => paint_group(t.flattened(), &mut painter),
+      ColorMatrixKind::Saturate(level) => Filter::saturate(level.get()),
+      ColorMatrixKind::HueRotate(deg) => Filter::hue_rotate(deg.to_radians()),
+      ColorMatrixKind::LuminanceToAlpha => Filter::luminance_to_alpha(),
+    },
+    Kind::DropShadow(shadow) => {
+      let usvg::Color { red, green, blue } = shadow.color();
+      let color = Color::from_rgb(red, green, blue).with_alpha(shadow.opacity().get());
+      let std_dev = shadow
+        .std_dev_x()
+        .get()
+        .max(shadow.std_dev_y().get());
+      Filter::drop_shadow((shadow.dx(), shadow.dy()), 2. * std_dev, color)
     }
+    Kind::Offset(offset) => {
+      Filter::color(ColorFilterMatrix::only_alpha(1.)).offset(offset.dx(), offset.dy())
+    }
+    kind => {
+      tracing::warn!("[painter]: not support filter `{kind:?}` in svg, ignored!");
+      Filter::new()
+    }
+  }
+}
+
+/// Fills the path by an [`ImagePattern`] of the rasterized pattern tile.
+fn fill_with_pattern(
+  path: &Path, pattern: &usvg::Pattern, opacity: usvg::Opacity, ts: &Transform,
+  painter: &mut crate::Painter,
+) {
+  let rect = pattern.rect();
+  let (x, y, w, h) = (rect.x(), rect.y(), rect.width(), rect.height());
+  let scale = PATTERN_RASTER_SCALE.min(MAX_PATTERN_TILE_SIZE / w.max(h));
+  let (width, height) = ((w * scale).ceil().max(1.), (h * scale).ceil().max(1.));
+  let Some(image) = rasterize_tile(pattern.root(), width as u32, height as u32, w, h) else {
+    return;
+  };
+
+  // Maps the pixels of the tile to the space of the path.
+  let transform = Transform::scale(w / width, h / height)
+    .then_translate((x, y).into())
+    .then(&matrix_convert(pattern.transform()));
+  let brush = ImagePattern::new(Resource::new(image)).with_transform(transform);
+  let mut layer = painter.save_guard();
+  layer
+    .set_transform(*ts)
+    .set_fill_brush(brush)
+    .apply_alpha(opacity.get())
+    .fill_path(path.clone().into());
+}
+
+/// Rasterizes the content of a pattern tile of the size `w` x `h` to an image
+/// of `width` x `height` pixels.
+fn rasterize_tile(
+  root: &usvg::Group, width: u32, height: u32, w: f32, h: f32,
+) -> Option<PixelImage> {
+  use resvg::tiny_skia;
+
+  let mut pixmap = tiny_skia::Pixmap::new(width, height)?;
+  let node = usvg::Node::Group(Box::new(root.clone()));
+  // `render_node` places the bounding box of the node at the origin, moves it
+  // back to keep the tile space.
+  if let Some(bounds) = node.abs_layer_bounding_box() {
+    let ts = tiny_skia::Transform::from_scale(width as f32 / w, height as f32 / h)
+      .pre_translate(bounds.x(), bounds.y());
+    resvg::render_node(&node, ts, &mut pixmap.as_mut());
   }
+
+  let data = pixmap
+    .pixels()
+    .iter()
+    .flat_map(|px| {
+      let px = px.demultiply();
+      [px.red(), px.green(), px.blue(), px.alpha()]
+    })
+    .collect::<Vec<_>>();
+  Some(PixelImage::new(Cow::Owned(data), width, height, ColorFormat::Rgba8))
 }
+
+fn paint_image(img: &usvg::Image, ts: &Transform, painter: &mut crate::Painter) {
+  match img.kind() {
+    usvg::ImageKind::SVG(tree) => paint_group(tree.root(), ts, painter),
+    usvg::ImageKind::WEBP(data) => match PixelImage::from_webp(data) {
+      Ok(pixel) => {
+        let rect = Rect::from_size(Size::new(pixel.width() as f32, pixel.height() as f32));
+        painter
+          .set_transform(*ts)
+          .draw_img(Resource::new(pixel), &rect, &None);
+      }
+      Err(err) => tracing::warn!("[painter]: failed to decode the image in svg: {err}"),
+    },
+    _ => tracing::warn!("[painter]: only support WebP and SVG images in svg, ignored!"),
+  }
+}
+
 fn usvg_path_to_path(path: &usvg::Path) -> Path {
   let mut builder = lyon_algorithms::path::Path::svg_builder();
+  append_usvg_path(path, &Transform::identity(), &mut builder);
+  builder.build().into()
+}
+
+fn append_usvg_path(path: &usvg::Path, ts: &Transform, builder: &mut impl SvgPathBuilder) {
+  let point = |pt: usvg::tiny_skia_path::Point| {
+    ts.transform_point(Point::new(pt.x, pt.y))
+      .to_untyped()
+  };
   path.data().segments().for_each(|seg| match seg {
     usvg::tiny_skia_path::PathSegment::MoveTo(pt) => {
-      builder.move_to(point(pt.x, pt.y));
+      builder.move_to(point(pt));
     }
     usvg::tiny_skia_path::PathSegment::LineTo(pt) => {
-      builder.line_to(point(pt.x, pt.y));
+      builder.line_to(point(pt));
     }
     usvg::tiny_skia_path::PathSegment::CubicTo(pt1, pt2, pt3) => {
-      builder.cubic_bezier_to(point(pt1.x, pt1.y), point(pt2.x, pt2.y), point(pt3.x, pt3.y));
+      builder.cubic_bezier_to(point(pt1), point(pt2), point(pt3));
     }
     usvg::tiny_skia_path::PathSegment::QuadTo(pt1, pt2) => {
-      builder.quadratic_bezier_to(point(pt1.x, pt1.y), point(pt2.x, pt2.y));
+      builder.quadratic_bezier_to(point(pt1), point(pt2));
     }
     usvg::tiny_skia_path::PathSegment::Close => builder.close(),
   });
-
-  builder.build().into()
 }
 
-fn point(x: f32, y: f32) -> lyon_algorithms::math::Point { Point::new(x, y).to_untyped() }
-
 fn matrix_convert(t: usvg::Transform) -> Transform {
   let usvg::Transform { sx, kx, ky, sy, tx, ty } = t;
   Transform::new(sx, ky, kx, sy, tx, ty)
@@ -275,10 +591,13 @@ fn fallback_color_check(cmds: &[PaintCommand]) -> (bool, bool) {
         }
       }
       PaintCommand::PopClip => {}
-      PaintCommand::Bundle { cmds, .. } => {
-        let (f, s) = fallback_color_check(cmds);
-        fill_fallback = f;
-        stroke_fallback |= s;
+      PaintCommand::Bundle { cmds, mask, .. } => {
+        let mask_cmds = mask.as_ref().map(|mask| &mask.cmds);
+        for cmds in std::iter::once(cmds).chain(mask_cmds) {
+          let (f, s) = fallback_color_check(cmds);
+          fill_fallback |= f;
+          stroke_fallback |= s;
+        }
       }
       PaintCommand::Filter { .. } => {}
       PaintCommand::Text(_) => {}
@@ -304,13 +623,18 @@ fn brush_replace(cmds: &[PaintCommand], fill: &Brush, stroke: &Brush) -> Box<[Pa
         PaintCommand::Path(p)
       }
       PaintCommand::PopClip => PaintCommand::PopClip,
-      PaintCommand::Bundle { transform, color_filter, bounds, cmds } => {
+      PaintCommand::Bundle { transform, color_filter, blend_mode, mask, bounds, cmds } => {
         let cmds = brush_replace(cmds, fill, stroke);
         let cmds = Resource::new(cmds);
 
         PaintCommand::Bundle {
           transform: *transform,
           color_filter: *color_filter,
+          blend_mode: *blend_mode,
+          mask: mask.as_ref().map(|mask| BundleMask {
+            mode: mask.mode,
+            cmds: Resource::new(brush_replace(&mask.cmds, fill, stroke)),
+          }),
           bounds: *bounds,
           cmds,
         }
@@ -321,6 +645,27 @@ fn brush_replace(cmds: &[PaintCommand], fill: &Brush, stroke: &Brush) -> Box<[Pa
     .collect()
 }
 
+fn blend_mode_from_usvg(mode: usvg::BlendMode) -> BlendMode {
+  match mode {
+    usvg::BlendMode::Normal => BlendMode::Normal,
+    usvg::BlendMode::Multiply => BlendMode::Multiply,
+    usvg::BlendMode::Screen => BlendMode::Screen,
+    usvg::BlendMode::Overlay => BlendMode::Overlay,
+    usvg::BlendMode::Darken => BlendMode::Darken,
+    usvg::BlendMode::Lighten => BlendMode::Lighten,
+    usvg::BlendMode::ColorDodge => BlendMode::ColorDodge,
+    usvg::BlendMode::ColorBurn => BlendMode::ColorBurn,
+    usvg::BlendMode::HardLight => BlendMode::HardLight,
+    usvg::BlendMode::SoftLight => BlendMode::SoftLight,
+    usvg::BlendMode::Difference => BlendMode::Difference,
+    usvg::BlendMode::Exclusion => BlendMode::Exclusion,
+    mode => {
+      tracing::warn!("[painter]: not support blend mode `{mode:?}` in svg, ignored!");
+      BlendMode::Normal
+    }
+  }
+}
+
 fn convert_to_gradient_stops(stops: &[Stop]) -> Vec<GradientStop> {
   assert!(!stops.is_empty());
 
@@ -0,0 +1,11 @@
+//! The fixtures shared by the tests of the painter and the crates built on it.
+
+use ribir_algo::Resource;
+
+use crate::{ColorFormat, PixelImage};
+
+/// An opaque white image.
+pub fn white_image(width: u32, height: u32) -> Resource<PixelImage> {
+  let data = vec![255; (width * height * 4) as usize];
+  Resource::new(PixelImage::new(data.into(), width, height, ColorFormat::Rgba8))
+}
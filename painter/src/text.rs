@@ -6,13 +6,17 @@
 
 pub use ribir_algo::Substr;
 pub use ribir_text::{
-  FontSystem, GlyphRasterSource, RasterBitmap, RasterBitmapFormat,
+  FontSystem, GlyphOrientation, GlyphRasterSource, OutlineSegment, RasterBitmap,
+  RasterBitmapFormat,
   font::*,
   paragraph::{
     Caret, CaretAffinity, CaretMotion, ClusterIndex, LineIndex, TextByteIndex, TextHitResult,
     TextRange, VisualPosition,
   },
-  style::{GlyphUnit, ParagraphStyle, TextAlign, TextOverflow, TextStyle, TextWrap},
+  style::{
+    BaselineShift, FontFeature, FontTag, FontVariation, GlyphUnit, ParagraphStyle, TextAlign,
+    TextDirection, TextOverflow, TextStyle, TextWrap, WritingMode,
+  },
 };
 
 use crate::Brush;
@@ -23,6 +27,8 @@ pub type SpanStyle = ribir_text::SpanStyle<Brush>;
 pub type TextSpan = ribir_text::TextSpan<Brush>;
 pub type DrawGlyph = ribir_text::DrawGlyph;
 pub type DrawTextDecoration = ribir_text::DrawTextDecoration<Brush>;
+pub type DrawTextBackground = ribir_text::DrawTextBackground<Brush>;
+pub type TextBackground = ribir_text::TextBackground<Brush>;
 pub type GlyphId = ribir_text::GlyphId;
 pub type DrawGlyphRun = ribir_text::DrawGlyphRun<Brush>;
 pub type TextDrawPayload = ribir_text::TextDrawPayload<Brush>;
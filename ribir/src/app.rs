@@ -16,7 +16,9 @@ use ribir_core::{
 use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
 use winit::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy};
 
-use crate::winit_shell_wnd::{RibirShell, ShellCmd, ShellWndHandle, WinitShellWnd, new_id};
+use crate::winit_shell_wnd::{
+  A11yAdapter, RibirShell, ShellCmd, ShellWndHandle, WinitShellWnd, new_id,
+};
 
 mod app_event_handler;
 mod ui_executor;
@@ -48,6 +50,14 @@ pub(crate) enum RibirAppEvent {
   FuturesWake,
   App(AppEvent),
   Cmd(ShellCmd),
+  /// The requests of the assistive technologies.
+  #[cfg(not(target_arch = "wasm32"))]
+  A11y(accesskit_winit::Event),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<accesskit_winit::Event> for RibirAppEvent {
+  fn from(e: accesskit_winit::Event) -> Self { RibirAppEvent::A11y(e) }
 }
 
 /// A sender to send event to the application event loop from which the
@@ -148,6 +158,11 @@ impl App {
 
   pub(crate) fn cmd_sender() -> CmdSender { CmdSender(App::shared().event_loop_proxy.clone()) }
 
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) fn event_loop_proxy() -> EventLoopProxy<RibirAppEvent> {
+    (*App::shared().event_loop_proxy).clone()
+  }
+
   /// Creates a new window containing the specified root widget.
   ///
   /// # Platform-specific Behavior: Web
@@ -184,9 +199,9 @@ impl App {
   /// created synchronously (in event loop callback) and backend is initialized
   /// asynchronously.
   pub(crate) async fn new_window_from_winit(
-    winit_wnd: std::sync::Arc<winit::window::Window>,
+    winit_wnd: std::sync::Arc<winit::window::Window>, a11y: A11yAdapter,
   ) -> BoxShellWindow {
-    let shell_wnd = WinitShellWnd::from_winit_window(winit_wnd).await;
+    let shell_wnd = WinitShellWnd::from_winit_window(winit_wnd, a11y).await;
 
     let proxy = ShellWndHandle {
       winit_wnd: shell_wnd.winit_wnd.clone(),
@@ -20,9 +20,14 @@ impl ApplicationHandler<RibirAppEvent> for AppHandler {
     &mut self, event_loop: &ActiveEventLoop, window_id: winit::window::WindowId, event: WindowEvent,
   ) {
     let wnd_id = new_id(window_id);
-    if App::shell_window(wnd_id).is_none() {
+    let Some(shell_wnd) = App::shell_window(wnd_id) else {
       return;
     };
+    {
+      let mut shell_wnd = shell_wnd.borrow_mut();
+      let winit_wnd = shell_wnd.winit_wnd.clone();
+      shell_wnd.a11y.process_event(&winit_wnd, &event);
+    }
 
     let _guard = active_event_guard(event_loop);
 
@@ -123,6 +128,20 @@ impl ApplicationHandler<RibirAppEvent> for AppHandler {
       RibirAppEvent::App(mut e) => {
         App::shared().events_stream.clone().next(&mut e);
       }
+      #[cfg(not(target_arch = "wasm32"))]
+      RibirAppEvent::A11y(accesskit_winit::Event { window_id, window_event }) => {
+        let wnd_id = new_id(window_id);
+        let event = match window_event {
+          accesskit_winit::WindowEvent::InitialTreeRequested => UiEvent::A11yActivated { wnd_id },
+          accesskit_winit::WindowEvent::ActionRequested(request) => {
+            UiEvent::A11yAction { wnd_id, request }
+          }
+          accesskit_winit::WindowEvent::AccessibilityDeactivated => {
+            UiEvent::A11yDeactivated { wnd_id }
+          }
+        };
+        App::send_event(event);
+      }
       RibirAppEvent::Cmd(cmd) => match cmd {
         ShellCmd::RunAsync { fut } => {
           App::spawn_local(fut);
@@ -130,11 +149,11 @@ impl ApplicationHandler<RibirAppEvent> for AppHandler {
         ShellCmd::NewWindow { attrs, sender } => {
           // Create winit window synchronously - we're in the event loop callback
           // where active_event_loop is available
-          let winit_wnd = WinitShellWnd::create_winit_window(*attrs);
+          let (winit_wnd, a11y) = WinitShellWnd::create_winit_window(*attrs);
 
           // Initialize backend asynchronously - this doesn't need active_event_loop
           App::spawn_local(async move {
-            let shell_wnd = App::new_window_from_winit(winit_wnd).await;
+            let shell_wnd = App::new_window_from_winit(winit_wnd, a11y).await;
             let _ = sender.send(shell_wnd);
           });
         }
@@ -3,7 +3,12 @@ mod wgpu_backend;
 #[cfg(feature = "wgpu")]
 pub(crate) use wgpu_backend::WgpuBackend as Backend;
 
-#[cfg(not(any(feature = "wgpu")))]
+#[cfg(all(feature = "cpu", not(feature = "wgpu")))]
+mod cpu_backend;
+#[cfg(all(feature = "cpu", not(feature = "wgpu")))]
+pub(crate) use cpu_backend::CpuBackend as Backend;
+
+#[cfg(not(any(feature = "wgpu", feature = "cpu")))]
 mod mock_backend;
-#[cfg(not(any(feature = "wgpu")))]
+#[cfg(not(any(feature = "wgpu", feature = "cpu")))]
 pub(crate) use mock_backend::MockBackend as Backend;
@@ -0,0 +1,85 @@
+use std::num::NonZeroU32;
+
+#[cfg(feature = "debug")]
+use ribir_core::prelude::{BoxFuture, PixelImage};
+use ribir_core::prelude::{
+  Color, DeviceRect, DeviceSize, GlyphRasterSource, PaintCommand, PainterBackend, Transform,
+  tracing,
+};
+use ribir_cpu::{CpuBackend as CpuPainter, CpuTexture};
+use winit::window::Window;
+
+use crate::winit_shell_wnd::WinitBackend;
+
+/// A window backend that rasterizes the frame by the CPU and presents it by
+/// `softbuffer`, for the machines without a usable GPU.
+pub struct CpuBackend<'a> {
+  surface: softbuffer::Surface<&'a Window, &'a Window>,
+  backend: CpuPainter,
+  texture: CpuTexture,
+}
+
+impl<'a> WinitBackend<'a> for CpuBackend<'a> {
+  async fn new(window: &'a Window) -> CpuBackend<'a> {
+    let context = softbuffer::Context::new(window).expect("Failed to create a softbuffer context.");
+    let surface =
+      softbuffer::Surface::new(&context, window).expect("Failed to create a softbuffer surface.");
+    let size = window.inner_size();
+    let size = DeviceSize::new(size.width as i32, size.height as i32);
+
+    let mut cpu =
+      CpuBackend { surface, backend: CpuPainter::default(), texture: CpuTexture::new(size) };
+    cpu.on_resize(size);
+    cpu
+  }
+
+  fn on_resize(&mut self, size: DeviceSize) {
+    let width = NonZeroU32::new(size.width.max(1) as u32).unwrap();
+    let height = NonZeroU32::new(size.height.max(1) as u32).unwrap();
+    if let Err(err) = self.surface.resize(width, height) {
+      tracing::warn!("Failed to resize the softbuffer surface: {err}");
+    }
+    if size != self.texture.size() {
+      self.texture = CpuTexture::new(size);
+    }
+  }
+
+  fn begin_frame(&mut self, surface_color: Color) { self.backend.begin_frame(surface_color); }
+
+  fn draw_commands(
+    &mut self, viewport: DeviceRect, global_matrix: &Transform, commands: &[PaintCommand],
+    glyph_provider: &dyn GlyphRasterSource,
+  ) {
+    self.backend.draw_commands(
+      viewport,
+      commands,
+      global_matrix,
+      &mut self.texture,
+      glyph_provider,
+    );
+  }
+
+  fn end_frame(&mut self) {
+    self.backend.end_frame();
+    let Ok(mut buffer) = self.surface.buffer_mut() else { return };
+    let pixels = self.texture.premultiplied_bytes().chunks_exact(4);
+    for (dst, px) in buffer.iter_mut().zip(pixels) {
+      *dst = ((px[0] as u32) << 16) | ((px[1] as u32) << 8) | px[2] as u32;
+    }
+    let _ = buffer.present();
+  }
+
+  #[cfg(feature = "debug")]
+  fn capture_screenshot(
+    &mut self, _: DeviceRect, _: &Transform, _: &[PaintCommand], _: &dyn GlyphRasterSource,
+  ) -> Option<BoxFuture<'static, Option<PixelImage>>> {
+    let size = self.texture.size();
+    if size.is_empty() {
+      return None;
+    }
+    let img = self
+      .texture
+      .copy_as_image(&DeviceRect::from_size(size));
+    Some(Box::pin(async move { Some(img) }))
+  }
+}
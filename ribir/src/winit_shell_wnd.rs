@@ -4,7 +4,7 @@ use ribir_core::{
   prelude::*,
   window::{
     BoxShellWindow, RedrawDemand, Shell, ShellWindow, UiEvent, WindowAttributes, WindowId,
-    WindowLevel,
+    WindowKind, WindowLevel,
   },
 };
 use winit::dpi::{LogicalPosition, LogicalSize};
@@ -39,6 +39,10 @@ pub enum ShellCmd {
   Close {
     id: WindowId,
   },
+  UpdateA11y {
+    id: WindowId,
+    update: accesskit::TreeUpdate,
+  },
   RunAsync {
     fut: BoxFuture<'static, ()>,
   },
@@ -54,9 +58,10 @@ pub enum ShellCmd {
 impl ShellCmd {
   pub fn wnd_id(&self) -> Option<WindowId> {
     match self {
-      ShellCmd::RequestDraw { id, .. } | ShellCmd::Draw { id, .. } | ShellCmd::Close { id } => {
-        Some(*id)
-      }
+      ShellCmd::RequestDraw { id, .. }
+      | ShellCmd::Draw { id, .. }
+      | ShellCmd::Close { id }
+      | ShellCmd::UpdateA11y { id, .. } => Some(*id),
       ShellCmd::RunAsync { .. } | ShellCmd::Exit | ShellCmd::NewWindow { .. } => None,
     }
   }
@@ -119,9 +124,74 @@ pub trait WinitBackend<'a>: Sized {
 
 pub(crate) struct WinitShellWnd {
   pub(crate) winit_wnd: Arc<winit::window::Window>,
+  pub(crate) a11y: A11yAdapter,
   backend: Backend<'static>,
 }
 
+/// Bridges the accessibility tree of a window to the platform, the requests
+/// of the assistive technologies are sent back as the events of the app.
+pub(crate) struct A11yAdapter {
+  #[cfg(not(target_arch = "wasm32"))]
+  adapter: accesskit_winit::Adapter,
+}
+
+impl A11yAdapter {
+  /// Creates the window with its adapter, the adapter must be created before
+  /// the window is shown.
+  fn create_window(mut attrs: WindowAttributes) -> (Arc<winit::window::Window>, Self) {
+    #[cfg(not(target_arch = "wasm32"))]
+    apply_owner(&mut attrs);
+    #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+    let (owner, attrs_kind) = (attrs.owner(), attrs.kind());
+    let visible = attrs.0.visible;
+    attrs.0.visible = false;
+    let event_loop = App::active_event_loop();
+    attrs.fit_monitors(&monitor_rects(event_loop));
+    let winit_wnd = Arc::new(event_loop.create_window(attrs.0).unwrap());
+    #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+    if let Some(owner) = owner.and_then(App::shell_window) {
+      set_transient_for(&winit_wnd, &owner.borrow().winit_wnd, attrs_kind);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let this = A11yAdapter {
+      adapter: accesskit_winit::Adapter::with_event_loop_proxy(&winit_wnd, App::event_loop_proxy()),
+    };
+    #[cfg(target_arch = "wasm32")]
+    let this = A11yAdapter {};
+
+    winit_wnd.set_visible(visible);
+    (winit_wnd, this)
+  }
+
+  #[allow(unused_variables)]
+  pub(crate) fn process_event(
+    &mut self, winit_wnd: &winit::window::Window, event: &winit::event::WindowEvent,
+  ) {
+    #[cfg(not(target_arch = "wasm32"))]
+    self.adapter.process_event(winit_wnd, event);
+  }
+
+  #[allow(unused_variables)]
+  fn update(&mut self, update: accesskit::TreeUpdate) {
+    #[cfg(not(target_arch = "wasm32"))]
+    self.adapter.update_if_active(|| update);
+  }
+}
+
+/// The logical rects of the monitors.
+fn monitor_rects(event_loop: &winit::event_loop::ActiveEventLoop) -> Vec<Rect> {
+  event_loop
+    .available_monitors()
+    .map(|m| {
+      let scale = m.scale_factor();
+      let pos = m.position().to_logical::<f32>(scale);
+      let size = m.size().to_logical::<f32>(scale);
+      Rect::new(Point::new(pos.x, pos.y), Size::new(size.width, size.height))
+    })
+    .collect()
+}
+
 fn window_size(winit_wnd: &winit::window::Window) -> Size {
   let size = winit_wnd
     .inner_size()
@@ -175,6 +245,7 @@ impl WinitShellWnd {
       ShellCmd::Close { id } => {
         App::remove_shell_window(id);
       }
+      ShellCmd::UpdateA11y { update, .. } => self.a11y.update(update),
       _ => (),
     }
   }
@@ -234,6 +305,8 @@ impl ShellWindow for ShellWndHandle {
 
   fn is_minimized(&self) -> bool { self.winit_wnd.is_minimized().unwrap_or_default() }
 
+  fn is_maximized(&self) -> bool { self.winit_wnd.is_maximized() }
+
   fn set_min_size(&mut self, size: Size) {
     self
       .winit_wnd
@@ -298,6 +371,12 @@ impl ShellWindow for ShellWndHandle {
       .send(ShellCmd::RequestDraw { id: self.id(), demand });
   }
 
+  fn update_a11y_tree(&mut self, update: accesskit::TreeUpdate) {
+    self
+      .sender
+      .send(ShellCmd::UpdateA11y { id: self.id(), update });
+  }
+
   fn position(&self) -> Point {
     let scale_factor = self.winit_wnd.scale_factor() as f32;
     self
@@ -307,6 +386,15 @@ impl ShellWindow for ShellWndHandle {
       .unwrap_or_default()
   }
 
+  fn client_position(&self) -> Point {
+    let scale_factor = self.winit_wnd.scale_factor() as f32;
+    self
+      .winit_wnd
+      .inner_position()
+      .map(|pos| Point::new(pos.x as f32 / scale_factor, pos.y as f32 / scale_factor))
+      .unwrap_or_else(|_| self.position())
+  }
+
   fn set_position(&mut self, point: Point) {
     let pos = self.position();
     if pos != point {
@@ -326,7 +414,9 @@ impl WinitShellWnd {
   /// Synchronously create a winit window. This must be called from within the
   /// event loop callback where `active_event_loop` is available.
   #[cfg(target_arch = "wasm32")]
-  pub(crate) fn create_winit_window(mut attrs: WindowAttributes) -> Arc<winit::window::Window> {
+  pub(crate) fn create_winit_window(
+    mut attrs: WindowAttributes,
+  ) -> (Arc<winit::window::Window>, A11yAdapter) {
     use web_sys::wasm_bindgen::JsCast;
     use winit::platform::web::WindowAttributesExtWebSys;
 
@@ -353,32 +443,28 @@ impl WinitShellWnd {
 
     attrs.0 = attrs.0.with_canvas(Some(canvas));
 
-    Arc::new(
-      App::active_event_loop()
-        .create_window(attrs.0)
-        .unwrap(),
-    )
+    A11yAdapter::create_window(attrs)
   }
 
   /// Synchronously create a winit window. This must be called from within the
   /// event loop callback where `active_event_loop` is available.
   #[cfg(not(target_arch = "wasm32"))]
-  pub(crate) fn create_winit_window(attrs: WindowAttributes) -> Arc<winit::window::Window> {
-    Arc::new(
-      App::active_event_loop()
-        .create_window(attrs.0)
-        .unwrap(),
-    )
+  pub(crate) fn create_winit_window(
+    attrs: WindowAttributes,
+  ) -> (Arc<winit::window::Window>, A11yAdapter) {
+    A11yAdapter::create_window(attrs)
   }
 
   /// Asynchronously initialize the backend for an existing winit window.
   /// This doesn't require `active_event_loop` to be available.
-  pub(crate) async fn from_winit_window(winit_wnd: Arc<winit::window::Window>) -> Self {
+  pub(crate) async fn from_winit_window(
+    winit_wnd: Arc<winit::window::Window>, a11y: A11yAdapter,
+  ) -> Self {
     let ptr = winit_wnd.as_ref() as *const winit::window::Window;
     // Safety: a reference to winit_wnd is valid as long as the WinitShellWnd is
     // alive.
     let backend = Backend::new(unsafe { &*ptr }).await;
-    WinitShellWnd { backend, winit_wnd }
+    WinitShellWnd { backend, winit_wnd, a11y }
   }
 
   #[cfg(target_arch = "wasm32")]
@@ -416,19 +502,105 @@ impl WinitShellWnd {
   pub(crate) async fn new(attrs: WindowAttributes) -> Self { Self::inner_new(attrs).await }
 
   async fn inner_new(attrs: WindowAttributes) -> Self {
-    let winit_wnd = Arc::new(
-      App::active_event_loop()
-        .create_window(attrs.0)
-        .unwrap(),
-    );
-    let ptr = winit_wnd.as_ref() as *const winit::window::Window;
-    // Safety: a reference to winit_wnd is valid as long as the WinitShellWnd is
-    // alive.
-    let backend = Backend::new(unsafe { &*ptr }).await;
-    WinitShellWnd { backend, winit_wnd }
+    let (winit_wnd, a11y) = A11yAdapter::create_window(attrs);
+    Self::from_winit_window(winit_wnd, a11y).await
   }
 }
 
+/// Makes the window owned by its owner's native window, an owned window stays
+/// above its owner without being clipped by it. Popups also have no
+/// decorations and don't take the focus.
+///
+/// It's the owner window on Windows and the child window on macOS. On X11 the
+/// owner is set as the transient window after the window is created, see
+/// `set_transient_for`. Wayland has no way to set it by winit yet.
+#[cfg(not(target_arch = "wasm32"))]
+fn apply_owner(attrs: &mut WindowAttributes) {
+  #[cfg(any(target_os = "windows", target_os = "macos"))]
+  if let Some(owner) = attrs.owner().and_then(App::shell_window) {
+    use winit::raw_window_handle::HasWindowHandle;
+
+    let owner = owner.borrow();
+    if let Ok(handle) = owner.winit_wnd.window_handle() {
+      #[cfg(target_os = "windows")]
+      if let winit::raw_window_handle::RawWindowHandle::Win32(handle) = handle.as_raw() {
+        use winit::platform::windows::WindowAttributesExtWindows;
+
+        let winit_attrs = std::mem::take(&mut attrs.0);
+        attrs.0 = winit_attrs.with_owner_window(handle.hwnd.get());
+      }
+      #[cfg(target_os = "macos")]
+      {
+        let winit_attrs = std::mem::take(&mut attrs.0);
+        // Safety: the owned windows are closed before their owner is destroyed.
+        attrs.0 = unsafe { winit_attrs.with_parent_window(Some(handle.as_raw())) };
+      }
+    }
+  }
+
+  if attrs.kind() == WindowKind::Popup {
+    attrs.0.decorations = false;
+    attrs.0.active = false;
+  }
+}
+
+/// Sets `owner` as the transient window of `wnd` on X11, so the window manager
+/// keeps `wnd` above it. The parent window of winit embeds the window into its
+/// parent instead. A modal window is also hinted as modal, the window manager
+/// may use it to decorate the window, the input of the owner is blocked by the
+/// event loop anyway.
+///
+/// All the windows share one connection to the X server, it's only created
+/// when the first owned window is opened on X11.
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+fn set_transient_for(wnd: &winit::window::Window, owner: &winit::window::Window, kind: WindowKind) {
+  use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+  use x11rb::{
+    connection::Connection,
+    protocol::xproto::{AtomEnum, ConnectionExt as _, PropMode},
+    rust_connection::RustConnection,
+    wrapper::ConnectionExt as _,
+  };
+
+  thread_local! {
+    static X11_CONN: Option<RustConnection> = x11rb::connect(None)
+      .inspect_err(|err| tracing::warn!("Failed to connect to the X server: {err}"))
+      .ok()
+      .map(|(conn, _)| conn);
+  }
+
+  let (Ok(wnd), Ok(owner)) = (wnd.window_handle(), owner.window_handle()) else { return };
+  let (RawWindowHandle::Xlib(wnd), RawWindowHandle::Xlib(owner)) = (wnd.as_raw(), owner.as_raw())
+  else {
+    return;
+  };
+  X11_CONN.with(|conn| {
+    let Some(conn) = conn else { return };
+    let wnd = wnd.window as u32;
+    let _ = conn.change_property32(
+      PropMode::REPLACE,
+      wnd,
+      AtomEnum::WM_TRANSIENT_FOR,
+      AtomEnum::WINDOW,
+      &[owner.window as u32],
+    );
+    if kind == WindowKind::Modal {
+      let atom = |name: &[u8]| {
+        conn
+          .intern_atom(false, name)
+          .ok()
+          .and_then(|cookie| cookie.reply().ok())
+          .map(|reply| reply.atom)
+      };
+      // The window is not mapped yet, so the state is set as a property.
+      if let (Some(state), Some(modal)) = (atom(b"_NET_WM_STATE"), atom(b"_NET_WM_STATE_MODAL")) {
+        let _ = conn.change_property32(PropMode::APPEND, wnd, state, AtomEnum::ATOM, &[modal]);
+      }
+    }
+    let _ = conn.flush();
+  });
+}
+
 fn img_to_winit_icon(icon: &PixelImage) -> winit::window::Icon {
   assert!(icon.color_format() == ColorFormat::Rgba8);
   winit::window::Icon::from_rgba(icon.pixel_bytes().to_vec(), icon.width(), icon.height()).unwrap()
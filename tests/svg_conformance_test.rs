@@ -0,0 +1,172 @@
+//! The conformance tests of the SVG features, the test cases follow the
+//! layout of the resvg test suite.
+
+use std::sync::Arc;
+
+use ribir::prelude::*;
+use ribir_dev_helper::*;
+
+fn parse_svg(svg: &[u8]) -> Svg {
+  let mut fonts = SvgFonts::new();
+  fonts.load_font_data(include_bytes!("../fonts/DejaVuSans.ttf").to_vec());
+  Svg::parse_with_fonts(svg, false, false, Arc::new(fonts)).unwrap()
+}
+
+fn svg_painter(svg: &[u8]) -> Painter {
+  let svg = parse_svg(svg);
+  let mut painter = Painter::new(Rect::from_size(svg.size()));
+  painter.draw_svg(&svg);
+  painter
+}
+
+/// All the commands of the SVG, the commands of the bundles are flattened.
+fn svg_commands(svg: &[u8]) -> Vec<PaintCommand> {
+  fn flatten(cmds: &[PaintCommand], out: &mut Vec<PaintCommand>) {
+    for cmd in cmds {
+      out.push(cmd.clone());
+      if let PaintCommand::Bundle { cmds, .. } = cmd {
+        flatten(cmds, out);
+      }
+    }
+  }
+
+  let svg = parse_svg(svg);
+  let mut out = vec![];
+  flatten(&svg.commands(&Color::BLACK.into(), &Color::BLACK.into()), &mut out);
+  out
+}
+
+fn clip_count(cmds: &[PaintCommand]) -> usize {
+  cmds
+    .iter()
+    .filter(|cmd| matches!(cmd, PaintCommand::Path(p) if matches!(p.action, PaintPathAction::Clip)))
+    .count()
+}
+
+const CLIP_PATH: &[u8] =
+  include_bytes!("./assets/svg_conformance/masking-clipPath-simple-case.svg");
+const MASK: &[u8] = include_bytes!("./assets/svg_conformance/masking-mask-simple-case.svg");
+const BLUR: &[u8] =
+  include_bytes!("./assets/svg_conformance/filters-feGaussianBlur-simple-case.svg");
+const DROP_SHADOW: &[u8] =
+  include_bytes!("./assets/svg_conformance/filters-feDropShadow-simple-case.svg");
+const PATTERN: &[u8] =
+  include_bytes!("./assets/svg_conformance/paint-servers-pattern-simple-case.svg");
+const EMBEDDED_SVG: &[u8] =
+  include_bytes!("./assets/svg_conformance/structure-image-embedded-svg.svg");
+const TEXT: &[u8] = include_bytes!("./assets/svg_conformance/text-text-simple-case.svg");
+
+fn masking_clip_path_simple_case() -> Painter { svg_painter(CLIP_PATH) }
+painter_backend_eq_image_test!(masking_clip_path_simple_case, comparison = 0.005);
+
+fn masking_mask_simple_case() -> Painter { svg_painter(MASK) }
+painter_backend_eq_image_test!(masking_mask_simple_case, comparison = 0.005);
+
+fn filters_fe_gaussian_blur_simple_case() -> Painter { svg_painter(BLUR) }
+painter_backend_eq_image_test!(filters_fe_gaussian_blur_simple_case, comparison = 0.005);
+
+fn filters_fe_drop_shadow_simple_case() -> Painter { svg_painter(DROP_SHADOW) }
+painter_backend_eq_image_test!(filters_fe_drop_shadow_simple_case, comparison = 0.005);
+
+fn paint_servers_pattern_simple_case() -> Painter { svg_painter(PATTERN) }
+painter_backend_eq_image_test!(paint_servers_pattern_simple_case, comparison = 0.005);
+
+fn structure_image_embedded_svg() -> Painter { svg_painter(EMBEDDED_SVG) }
+painter_backend_eq_image_test!(structure_image_embedded_svg, comparison = 0.005);
+
+fn text_text_simple_case() -> Painter { svg_painter(TEXT) }
+painter_backend_eq_image_test!(text_text_simple_case, comparison = 0.005);
+
+#[test]
+fn clip_path_to_clip() {
+  let cmds = svg_commands(CLIP_PATH);
+  assert_eq!(clip_count(&cmds), 1);
+  assert!(
+    cmds
+      .iter()
+      .any(|cmd| matches!(cmd, PaintCommand::PopClip))
+  );
+}
+
+#[test]
+fn mask_to_layer() {
+  let cmds = svg_commands(MASK);
+  let mask = cmds.iter().find_map(|cmd| match cmd {
+    PaintCommand::Bundle { mask: Some(mask), .. } => Some(mask),
+    _ => None,
+  });
+  let mask = mask.expect("the masked content should be bundled with the mask");
+  assert_eq!(mask.mode, MaskMode::Luminance);
+  // The mask region and the half opacity white circle.
+  assert_eq!(clip_count(&mask.cmds), 1);
+  let fill = mask.cmds.iter().find_map(|cmd| match cmd {
+    PaintCommand::Path(PathCommand {
+      action: PaintPathAction::Paint { brush: CommandBrush::Color(c), .. },
+      ..
+    }) => Some(*c),
+    _ => None,
+  });
+  assert!(fill.is_some_and(|c| c.alpha.abs_diff(128) <= 1));
+}
+
+#[test]
+fn blur_to_filter() {
+  let cmds = svg_commands(BLUR);
+  assert!(
+    cmds
+      .iter()
+      .any(|cmd| matches!(cmd, PaintCommand::Filter { .. }))
+  );
+  // The filter region.
+  assert_eq!(clip_count(&cmds), 1);
+}
+
+#[test]
+fn pattern_to_image_brush() {
+  let cmds = svg_commands(PATTERN);
+  let pattern = cmds.iter().find_map(|cmd| match cmd {
+    PaintCommand::Path(PathCommand {
+      action: PaintPathAction::Paint { brush: CommandBrush::Pattern { pattern, .. }, .. },
+      ..
+    }) => Some(pattern),
+    _ => None,
+  });
+  let pattern = pattern.expect("the pattern should fill by an image pattern");
+  // The 20x20 tile is rasterized at twice of its size.
+  assert_eq!(pattern.image.size(), DeviceSize::new(40, 40));
+  assert_eq!(clip_count(&cmds), 0);
+}
+
+#[test]
+fn embedded_svg_image() {
+  let cmds = svg_commands(EMBEDDED_SVG);
+  assert!(
+    cmds
+      .iter()
+      .any(|cmd| matches!(cmd, PaintCommand::Path(_)))
+  );
+}
+
+#[test]
+fn text_to_outlines() {
+  let cmds = svg_commands(TEXT);
+  assert!(!cmds.is_empty());
+  assert!(
+    cmds
+      .iter()
+      .all(|cmd| matches!(cmd, PaintCommand::Path(_)))
+  );
+}
+
+#[test]
+fn text_with_shared_fonts() {
+  Svg::load_font_data(include_bytes!("../fonts/DejaVuSans.ttf").to_vec());
+  let svg = Svg::parse_from_bytes(TEXT, false, false).unwrap();
+  let cmds = svg.commands(&Color::BLACK.into(), &Color::BLACK.into());
+  assert!(!cmds.is_empty());
+  assert!(
+    cmds
+      .iter()
+      .all(|cmd| matches!(cmd, PaintCommand::Path(_)))
+  );
+}
@@ -8,6 +8,7 @@ use crate::GlyphRasterSourceRef;
 pub type FontStretch = FontWidth;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontFamily {
   Name(Cow<'static, str>),
   Serif,
@@ -18,10 +19,15 @@ pub enum FontFamily {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct FontFace {
   pub families: Box<[FontFamily]>,
+  #[cfg_attr(feature = "serde", serde(with = "serde_face::stretch"))]
   pub stretch: FontStretch,
+  #[cfg_attr(feature = "serde", serde(with = "serde_face::style"))]
   pub style: FontStyle,
+  #[cfg_attr(feature = "serde", serde(with = "serde_face::weight"))]
   pub weight: FontWeight,
 }
 
@@ -62,6 +68,70 @@ impl<T: Into<Cow<'static, str>>> From<T> for FontFamily {
   fn from(value: T) -> Self { FontFamily::Name(value.into()) }
 }
 
+/// The font attributes come from `fontique`, which has no serde support, so
+/// they are written as their CSS values: the stretch ratio, the weight number
+/// and the style keyword.
+#[cfg(feature = "serde")]
+mod serde_face {
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  pub mod stretch {
+    use super::*;
+    use crate::FontStretch;
+
+    pub fn serialize<S: Serializer>(stretch: &FontStretch, s: S) -> Result<S::Ok, S::Error> {
+      stretch.ratio().serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<FontStretch, D::Error> {
+      f32::deserialize(d).map(FontStretch::from_ratio)
+    }
+  }
+
+  pub mod weight {
+    use super::*;
+    use crate::FontWeight;
+
+    pub fn serialize<S: Serializer>(weight: &FontWeight, s: S) -> Result<S::Ok, S::Error> {
+      weight.value().serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<FontWeight, D::Error> {
+      f32::deserialize(d).map(FontWeight::new)
+    }
+  }
+
+  pub mod style {
+    use super::*;
+    use crate::FontStyle;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Style {
+      Normal,
+      Italic,
+      Oblique(Option<f32>),
+    }
+
+    pub fn serialize<S: Serializer>(style: &FontStyle, s: S) -> Result<S::Ok, S::Error> {
+      match *style {
+        FontStyle::Normal => Style::Normal,
+        FontStyle::Italic => Style::Italic,
+        FontStyle::Oblique(angle) => Style::Oblique(angle),
+      }
+      .serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<FontStyle, D::Error> {
+      Ok(match Style::deserialize(d)? {
+        Style::Normal => FontStyle::Normal,
+        Style::Italic => FontStyle::Italic,
+        Style::Oblique(angle) => FontStyle::Oblique(angle),
+      })
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct FontRequest {
   pub face: FontFace,
@@ -71,10 +141,15 @@ pub struct FontRequest {
 pub struct FontFaceId {
   pub blob_id: u64,
   pub index: u32,
+  /// Identifies a variable-font instance or a synthesized (bold/oblique)
+  /// rendition of the face, `0` for the face as stored in the font file.
+  pub instance: u64,
 }
 
 impl FontFaceId {
-  pub fn new(blob_id: u64, index: u32) -> Self { Self { blob_id, index } }
+  pub fn new(blob_id: u64, index: u32) -> Self { Self { blob_id, index, instance: 0 } }
+
+  pub fn with_instance(self, instance: u64) -> Self { Self { instance, ..self } }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -100,12 +175,46 @@ pub struct FontFaceMetrics {
   pub stroke_size: f32,
 }
 
+/// A style a font family is available in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontFaceStyle {
+  pub stretch: FontStretch,
+  pub style: FontStyle,
+  pub weight: FontWeight,
+}
+
+/// A font family known to the font system, either registered by the
+/// application or installed in the system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontFamilyInfo {
+  pub name: CowArc<str>,
+  pub styles: Box<[FontFaceStyle]>,
+}
+
 pub trait FontSystem {
-  fn register_font_bytes(&mut self, data: Vec<u8>) -> Result<(), FontLoadError>;
+  fn register_font_bytes(&mut self, data: Vec<u8>) -> Result<(), FontLoadError> {
+    self.load_font_bytes(data).map(|_| ())
+  }
 
-  fn register_font_file(&mut self, path: &Path) -> Result<(), FontLoadError>;
+  fn register_font_file(&mut self, path: &Path) -> Result<(), FontLoadError> {
+    self.load_font_file(path).map(|_| ())
+  }
+
+  /// Registers the fonts in `data` and returns the names of their families.
+  fn load_font_bytes(&mut self, data: Vec<u8>) -> Result<Box<[CowArc<str>]>, FontLoadError>;
+
+  /// Registers the fonts of the file at `path` and returns the names of their
+  /// families.
+  fn load_font_file(&mut self, path: &Path) -> Result<Box<[CowArc<str>]>, FontLoadError>;
 
   fn face_metrics(&self, face: FontFaceId) -> Option<FontFaceMetrics>;
 
+  /// Lists the registered and system font families, sorted by name.
+  fn font_families(&mut self) -> Vec<FontFamilyInfo>;
+
+  /// Returns `true` if the font resolved for `face` has a glyph for every
+  /// character of `text`, without falling back to another font.
+  fn covers(&mut self, face: &FontFace, text: &str) -> bool;
+
   fn raster_source(&self) -> GlyphRasterSourceRef;
 }
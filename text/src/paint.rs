@@ -1,4 +1,4 @@
-use ribir_types::{Point, Rect, Vector};
+use ribir_types::{Point, Rect, Transform, Vector};
 
 use crate::{font::FontFaceId, paragraph::ClusterIndex, style::TextDecoration};
 
@@ -7,8 +7,14 @@ pub struct GlyphId(pub u16);
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct TextDrawPayload<Brush> {
+  /// The paint bounds in paragraph space.
   pub bounds: Rect,
   pub origin_offset: Vector,
+  /// Maps glyph and decoration coordinates into paragraph space. It is the
+  /// identity for horizontal writing modes.
+  pub transform: Transform,
+  /// The span backgrounds, painted below the glyphs.
+  pub backgrounds: Box<[DrawTextBackground<Brush>]>,
   pub runs: Box<[DrawGlyphRun<Brush>]>,
   pub decorations: Box<[DrawTextDecoration<Brush>]>,
 }
@@ -18,9 +24,51 @@ pub struct DrawGlyphRun<Brush> {
   pub face_id: FontFaceId,
   pub logical_font_size: f32,
   pub brush: Option<Brush>,
+  pub orientation: GlyphOrientation,
   pub glyphs: Box<[DrawGlyph]>,
 }
 
+/// How the glyphs of a run are set in a vertical line.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GlyphOrientation {
+  /// The glyph is set along the line, so it's turned sideways with a vertical
+  /// line.
+  #[default]
+  Sideways,
+  /// The glyph stands upright in a vertical line, it's turned back about the
+  /// center of its em box. `em_center` is the offset from the baseline to the
+  /// center of the em box.
+  Upright { em_center: f32 },
+}
+
+impl<Brush> DrawGlyphRun<Brush> {
+  /// The transform that sets the `glyph` of the run in its orientation, see
+  /// [`GlyphOrientation::glyph_transform`].
+  pub fn glyph_transform(&self, glyph: &DrawGlyph, origin_offset: Vector) -> Transform {
+    self
+      .orientation
+      .glyph_transform(glyph, origin_offset)
+  }
+}
+
+impl GlyphOrientation {
+  /// The transform that sets the `glyph` in this orientation, it's applied to
+  /// the glyph placed at its baseline origin shifted by `origin_offset`, and
+  /// before the transform of the payload.
+  pub fn glyph_transform(self, glyph: &DrawGlyph, origin_offset: Vector) -> Transform {
+    match self {
+      GlyphOrientation::Sideways => Transform::identity(),
+      GlyphOrientation::Upright { em_center } => {
+        let c =
+          glyph.baseline_origin + origin_offset + Vector::new(glyph.advance.x / 2., em_center);
+        // Rotates 90° counterclockwise about the center, the payload rotates
+        // it back when the line is turned vertical.
+        Transform::new(0., -1., 1., 0., c.x - c.y, c.y + c.x)
+      }
+    }
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DrawGlyph {
   pub glyph_id: GlyphId,
@@ -35,3 +83,10 @@ pub struct DrawTextDecoration<Brush> {
   pub brush: Option<Brush>,
   pub rect: Rect,
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawTextBackground<Brush> {
+  pub brush: Brush,
+  pub radius: f32,
+  pub rect: Rect,
+}
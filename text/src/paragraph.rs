@@ -3,7 +3,7 @@ use ribir_types::{BoxClamp, Point, Rect, Size};
 
 use crate::{
   paint::TextDrawPayload,
-  style::{ParagraphStyle, SpanStyle, TextAlign, TextStyle},
+  style::{ParagraphStyle, SpanStyle, TextAlign, TextStyle, WritingMode},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
@@ -98,6 +98,15 @@ pub trait ParagraphLayout<Brush> {
 
   fn draw_payload(&self) -> &TextDrawPayload<Brush>;
 
+  /// The writing mode the paragraph is laid out in. The caret motions are in
+  /// the direction of the lines, so `Prev` and `Next` walk down a vertical
+  /// line, `Up` and `Down` cross its lines.
+  fn writing_mode(&self) -> WritingMode;
+
+  /// The distance from the top of the paragraph to the baseline of its first
+  /// line, `None` if there is no line or the lines are laid out vertically.
+  fn first_baseline(&self) -> Option<f32>;
+
   fn hit_test_point(&self, point: Point) -> TextHitResult;
 
   fn caret_rect(&self, caret: Caret) -> Rect;
@@ -1,4 +1,6 @@
 use std::{
+  borrow::Cow,
+  hash::{Hash, Hasher},
   rc::Rc,
   sync::{OnceLock, RwLock},
 };
@@ -11,25 +13,38 @@ use parley::{
   layout::{Affinity as ParleyAffinity, BreakReason, PositionedLayoutItem},
   style::{
     FontFamily as ParleyFontFamily, FontFamilyName as ParleyFontFamilyName,
-    FontStyle as ParleyFontStyle, FontWeight as ParleyFontWeight, FontWidth as ParleyFontWidth,
-    GenericFamily, LineHeight as ParleyLineHeight, OverflowWrap as ParleyOverflowWrap,
-    StyleProperty, TextWrapMode,
+    FontFeature as ParleyFontFeature, FontSettings as ParleyFontSettings,
+    FontStyle as ParleyFontStyle, FontVariation as ParleyFontVariation,
+    FontWeight as ParleyFontWeight, FontWidth as ParleyFontWidth, GenericFamily,
+    LineHeight as ParleyLineHeight, OverflowWrap as ParleyOverflowWrap, StyleProperty,
+    TextWrapMode,
   },
 };
-use ribir_algo::Arc;
-use ribir_types::{BoxClamp, Point, Rect, Size, Vector};
+use ribir_algo::{Arc, CowArc};
+use ribir_types::{BoxClamp, Point, Rect, Size, Transform, Vector};
 use swash::FontRef;
 
 use crate::{
   AttributedText, FontSystem,
-  font::{FontFaceId, FontFaceMetrics, FontFamily, FontLoadError, FontStretch, FontStyle},
-  paint::{DrawGlyph, DrawGlyphRun, DrawTextDecoration, GlyphId, TextDrawPayload},
+  font::{
+    FontFaceId, FontFaceMetrics, FontFaceStyle, FontFamily, FontFamilyInfo, FontLoadError,
+    FontStretch, FontStyle,
+  },
+  paint::{
+    DrawGlyph, DrawGlyphRun, DrawTextBackground, DrawTextDecoration, GlyphId, GlyphOrientation,
+    TextDrawPayload,
+  },
   paragraph::{
     Caret, CaretAffinity, CaretMotion, ClusterIndex, LineIndex, Paragraph, ParagraphLayout,
     ParagraphLayoutRef, TextByteIndex, TextHitResult, TextRange, TextSpan, VisualPosition,
   },
-  raster::{GlyphRasterSource, GlyphRasterSourceRef, RasterBitmap, RasterBitmapFormat},
-  style::{Color, LineHeight, ParagraphStyle, TextAlign, TextDecoration, TextStyle, TextWrap},
+  raster::{
+    GlyphRasterSource, GlyphRasterSourceRef, OutlineSegment, RasterBitmap, RasterBitmapFormat,
+  },
+  style::{
+    BaselineShift, Color, FontFeature, FontVariation, LineHeight, ParagraphStyle, TextAlign,
+    TextBackground, TextDecoration, TextDirection, TextStyle, TextWrap, WritingMode,
+  },
 };
 
 fn build_text_paragraph<Brush>(
@@ -51,12 +66,30 @@ struct ParleyParagraph<Brush> {
   faces: ParleyFaces,
 }
 
+/// A paragraph layout. The parley layout, `logical_size`, `payload` and the
+/// visual lines are always kept in horizontal space, vertical writing modes are
+/// mapped into paragraph space only at the public API boundary.
 struct ParleyParagraphLayout<Brush> {
   layout: Arc<ParleyLayout<usize>>,
   logical_size: Size,
   payload: TextDrawPayload<Brush>,
   line_offsets: Box<[f32]>,
   line_positions: OnceLock<Box<[VisualLine]>>,
+  /// The byte length of the directional mark prefixed to the source text.
+  text_offset: usize,
+  writing_mode: WritingMode,
+  /// `payload` mapped into paragraph space for vertical writing modes.
+  vertical_payload: Option<TextDrawPayload<Brush>>,
+  /// The span backgrounds, painted along the selection geometry of their
+  /// ranges once the lines are aligned.
+  backgrounds: Box<[(TextRange, TextBackground<Brush>)]>,
+}
+
+struct ShapedLayout<Brush> {
+  layout: ParleyLayout<usize>,
+  brushes: Vec<Option<Brush>>,
+  baseline_shifts: BaselineShifts,
+  text_offset: usize,
 }
 
 use crate::svg_glyph::extract_svg_glyph;
@@ -72,8 +105,17 @@ struct ParleyGlyphRasterSource {
 struct ParleyFace {
   font: ParleyFontData,
   metrics: FontFaceMetrics,
+  /// Normalized variation coordinates the run was shaped with.
+  coords: Box<[i16]>,
+  /// Whether the matcher asked for synthetic bold.
+  embolden: bool,
+  /// The synthetic oblique angle in degrees, if any.
+  skew: Option<f32>,
 }
 
+/// Baseline shifts keyed by the parley brush index of the styled range.
+type BaselineShifts = Vec<(usize, BaselineShift)>;
+
 struct ParleyEngine {
   font_ctx: ParleyFontContext,
   layout_ctx: ParleyLayoutContext<usize>,
@@ -123,7 +165,7 @@ impl GlyphRasterSource for ParleyGlyphRasterSource {
       .unwrap()
       .get(&face_id)
       .cloned()?;
-    raster_bitmap_from_font(&face.font, glyph_id, font_ppem)
+    raster_bitmap_from_face(&face, glyph_id, font_ppem)
   }
 
   fn raster_svg(&self, face_id: FontFaceId, glyph_id: GlyphId) -> Option<String> {
@@ -132,6 +174,18 @@ impl GlyphRasterSource for ParleyGlyphRasterSource {
     let font_ref = swash::FontRef::from_index(face.font.data.data(), face.font.index as usize)?;
     extract_svg_glyph(glyph_id, &font_ref, face.font.index)
   }
+
+  fn glyph_outline(
+    &self, face_id: FontFaceId, glyph_id: GlyphId, font_size: f32,
+  ) -> Option<Vec<OutlineSegment>> {
+    let face = self
+      .faces
+      .read()
+      .unwrap()
+      .get(&face_id)
+      .cloned()?;
+    outline_from_face(&face, glyph_id, font_size)
+  }
 }
 
 impl ParleyEngine {
@@ -145,44 +199,149 @@ impl ParleyEngine {
     Self { font_ctx, layout_ctx: ParleyLayoutContext::new() }
   }
 
-  pub fn register_font_bytes(&mut self, data: Vec<u8>) {
+  pub fn register_font_bytes(&mut self, data: Vec<u8>) -> Box<[CowArc<str>]> {
     let blob = Blob::from(data);
-    self
-      .font_ctx
-      .collection
-      .register_fonts(blob, None);
+    let collection = &mut self.font_ctx.collection;
+    let families = collection.register_fonts(blob, None);
+    families
+      .into_iter()
+      .filter_map(|(id, _)| Some(collection.family(id)?.name().to_owned().into()))
+      .collect()
   }
 
-  fn register_face(font: &ParleyFontData, faces: &ParleyFaces) -> (FontFaceId, FontFaceMetrics) {
-    let face_id = FontFaceId::new(font.data.id(), font.index);
-    let metrics = font_metrics(font);
-    let mut faces = faces.write().unwrap();
-    if let std::collections::hash_map::Entry::Vacant(entry) = faces.entry(face_id) {
-      entry.insert(ParleyFace { metrics, font: font.clone() });
+  fn font_families(&mut self) -> Vec<FontFamilyInfo> {
+    let collection = &mut self.font_ctx.collection;
+    let names = collection
+      .family_names()
+      .map(str::to_owned)
+      .collect::<Vec<_>>();
+    let mut families = names
+      .into_iter()
+      .filter_map(|name| {
+        let family = collection.family(collection.family_id(&name)?)?;
+        let styles = family
+          .fonts()
+          .iter()
+          .map(|font| FontFaceStyle {
+            stretch: font.width(),
+            style: font.style(),
+            weight: font.weight(),
+          })
+          .collect();
+        Some(FontFamilyInfo { name: name.into(), styles })
+      })
+      .collect::<Vec<_>>();
+    families.sort_by(|a, b| (*a.name).cmp(&*b.name));
+    families
+  }
+
+  /// Loads the font the first available family of `face` resolves to, the same
+  /// way a paragraph picks its primary font.
+  fn match_font(&mut self, face: &crate::FontFace) -> Option<(Blob<u8>, u32)> {
+    let ParleyFontContext { collection, source_cache, .. } = &mut self.font_ctx;
+    for family in face.families.iter() {
+      let ids = match generic_or_named(family) {
+        Ok(generic) => collection
+          .generic_families(generic)
+          .collect::<Vec<_>>(),
+        Err(name) => collection.family_id(name).into_iter().collect(),
+      };
+      for id in ids {
+        let Some(info) = collection.family(id) else { continue };
+        let Some(font) = info.match_font(face.stretch, face.style, face.weight, true) else {
+          continue;
+        };
+        if let Some(blob) = font.load(Some(source_cache)) {
+          return Some((blob, font.index()));
+        }
+      }
     }
-    (face_id, metrics)
+    None
   }
 
-  fn intrinsic_widths<Brush>(
+  fn covers(&mut self, face: &crate::FontFace, text: &str) -> bool {
+    let Some((blob, index)) = self.match_font(face) else {
+      return false;
+    };
+    let Some(font) = FontRef::from_index(blob.data(), index as usize) else {
+      return false;
+    };
+    let charmap = font.charmap();
+    text
+      .chars()
+      .filter(|c| !c.is_control())
+      .all(|c| charmap.map(c) != 0)
+  }
+
+  fn register_face(
+    font: &ParleyFontData, coords: &[i16], synthesis: parley::fontique::Synthesis,
+    faces: &ParleyFaces,
+  ) -> (FontFaceId, ParleyFace) {
+    let embolden = synthesis.embolden();
+    let skew = synthesis.skew();
+    let face_id = FontFaceId::new(font.data.id(), font.index)
+      .with_instance(face_instance(coords, embolden, skew));
+    let mut faces = faces.write().unwrap();
+    let face = faces
+      .entry(face_id)
+      .or_insert_with(|| ParleyFace {
+        metrics: font_metrics(font, coords),
+        font: font.clone(),
+        coords: coords.into(),
+        embolden,
+        skew,
+      });
+    (face_id, face.clone())
+  }
+
+  fn shape<Brush>(
     &mut self, source: &AttributedText<Brush>, text_style: &TextStyle,
-    paragraph_style: &ParagraphStyle,
-  ) -> (f32, f32)
+    paragraph_style: &ParagraphStyle, include_wrap: bool,
+  ) -> ShapedLayout<Brush>
   where
     Brush: Clone + From<Color>,
   {
-    let text = source.text.as_ref();
+    let (text, text_offset) = directed_text(source.text.as_ref(), paragraph_style.direction);
     let mut builder = self
       .layout_ctx
-      .ranged_builder(&mut self.font_ctx, text, 1.0, true);
-    push_layout_defaults(&mut builder, text_style, paragraph_style, false);
+      .ranged_builder(&mut self.font_ctx, &text, 1.0, true);
+    push_layout_defaults(&mut builder, text_style, paragraph_style, include_wrap);
 
     let mut brushes = vec![None];
+    let mut baseline_shifts = BaselineShifts::new();
 
     for span in source.spans.iter() {
-      push_span_styles(&mut builder, span, text_style, paragraph_style, &mut brushes);
+      push_span_styles(
+        &mut builder,
+        span,
+        text_style,
+        paragraph_style,
+        &mut brushes,
+        &mut baseline_shifts,
+        text_offset,
+      );
     }
 
-    let layout = builder.build(text);
+    let layout = builder.build(&text);
+    ShapedLayout { layout, brushes, baseline_shifts, text_offset }
+  }
+
+  fn intrinsic_widths<Brush>(
+    &mut self, source: &AttributedText<Brush>, text_style: &TextStyle,
+    paragraph_style: &ParagraphStyle,
+  ) -> (f32, f32)
+  where
+    Brush: Clone + From<Color>,
+  {
+    let mut layout = self
+      .shape(source, text_style, paragraph_style, false)
+      .layout;
+    if paragraph_style.writing_mode.is_vertical() {
+      // Vertical lines only wrap against the height, so the width is the
+      // block extent of the unwrapped columns.
+      layout.break_all_lines(None);
+      return (layout.height(), layout.height());
+    }
     let widths = layout.calculate_content_widths();
     (widths.min, widths.max)
   }
@@ -194,19 +353,10 @@ impl ParleyEngine {
   where
     Brush: Clone + From<Color> + PartialEq + 'static,
   {
-    let text = source.text.as_ref();
-    let mut builder = self
-      .layout_ctx
-      .ranged_builder(&mut self.font_ctx, text, 1.0, true);
-    push_layout_defaults(&mut builder, text_style, paragraph_style, true);
-
-    let mut brushes = vec![None];
-
-    for span in source.spans.iter() {
-      push_span_styles(&mut builder, span, text_style, paragraph_style, &mut brushes);
-    }
-
-    let mut layout = builder.build(text);
+    let ShapedLayout { mut layout, brushes, baseline_shifts, text_offset } =
+      self.shape(source, text_style, paragraph_style, true);
+    let writing_mode = paragraph_style.writing_mode;
+    let clamp = horizontal_clamp(clamp, writing_mode);
     let wrap_width = match paragraph_style.wrap {
       TextWrap::Wrap if clamp.max.width.is_finite() => Some(clamp.max.width),
       _ => None,
@@ -214,7 +364,20 @@ impl ParleyEngine {
     layout.break_all_lines(wrap_width);
 
     let layout = Arc::new(layout);
-    let payload = Self::build_payload(layout.as_ref(), faces, &brushes);
+    let payload = Self::build_payload(
+      layout.as_ref(),
+      faces,
+      &brushes,
+      &baseline_shifts,
+      source.text.as_ref(),
+      text_offset,
+      writing_mode,
+    );
+    let backgrounds = source
+      .spans
+      .iter()
+      .filter_map(|span| Some((span.range, span.style.background.clone()?)))
+      .collect();
     let logical_size = payload.bounds.size;
     let line_offsets = vec![0.; layout.len()].into_boxed_slice();
 
@@ -224,12 +387,18 @@ impl ParleyEngine {
       payload,
       line_offsets,
       line_positions: OnceLock::new(),
+      text_offset,
+      writing_mode,
+      vertical_payload: None,
+      backgrounds,
     }
     .with_alignment(paragraph_style.text_align, clamp.clamp(logical_size))
   }
 
   fn build_payload<Brush>(
     layout: &ParleyLayout<usize>, faces: &ParleyFaces, brushes: &[Option<Brush>],
+    baseline_shifts: &[(usize, BaselineShift)], text: &str, text_offset: usize,
+    writing_mode: WritingMode,
   ) -> TextDrawPayload<Brush>
   where
     Brush: Clone + PartialEq + 'static,
@@ -246,50 +415,77 @@ impl ParleyEngine {
         let run = glyph_run.run();
         let font = run.font();
         let font_size = run.font_size();
-        let (face_id, metrics) = Self::register_face(font, faces);
+        let (face_id, face) =
+          Self::register_face(font, run.normalized_coords(), run.synthesis(), faces);
+        let metrics = face.metrics;
         let baseline_shift = baseline_shift(Some(metrics), font_size);
+        let span_shift = baseline_shifts
+          .iter()
+          .find(|(brush, _)| *brush == glyph_run.style().brush)
+          .map_or(0., |(_, shift)| shift.resolve_for_font_size(font_size));
         let scale = font_metrics_scale(metrics, font_size);
         let ascender = metrics.ascender * scale;
         let descender = metrics.descender * scale;
 
+        // In a vertical line, the glyphs of the upright scripts stand upright,
+        // the others are set sideways.
+        let upright = GlyphOrientation::Upright { em_center: (descender.abs() - ascender) / 2. };
         let mut pen_x = glyph_run.offset();
-        let baseline_y = glyph_run.baseline() + baseline_shift;
+        let baseline_y = glyph_run.baseline() + baseline_shift - span_shift;
         let mut run_bounds: Option<Rect> = None;
-        let glyphs = glyph_run
-          .glyphs()
-          .map(|glyph| {
+        let mut glyphs = Vec::new();
+        for cluster in run.visual_clusters() {
+          let cluster_idx = cluster
+            .text_range()
+            .start
+            .saturating_sub(text_offset);
+          let orientation = if writing_mode.is_vertical()
+            && text
+              .get(cluster_idx..)
+              .and_then(|s| s.chars().next())
+              .is_some_and(is_upright_char)
+          {
+            upright
+          } else {
+            GlyphOrientation::Sideways
+          };
+          for glyph in cluster.glyphs() {
             let draw = DrawGlyph {
               glyph_id: GlyphId(glyph.id as u16),
-              cluster: ClusterIndex(run.text_range().start),
+              cluster: ClusterIndex(cluster_idx),
               baseline_origin: Point::new(pen_x + glyph.x, baseline_y + glyph.y),
               advance: Vector::new(glyph.advance, 0.),
             };
-            run_bounds = Some(union_optional_rect(
-              run_bounds,
-              glyph_metrics_rect(draw.baseline_origin, glyph.advance, ascender, descender),
-            ));
+            let rect = orientation
+              .glyph_transform(&draw, Vector::zero())
+              .outer_transformed_rect(&glyph_metrics_rect(
+                draw.baseline_origin,
+                glyph.advance,
+                ascender,
+                descender,
+              ));
+            run_bounds = Some(union_optional_rect(run_bounds, rect));
             pen_x += glyph.advance;
-            draw
-          })
-          .collect::<Vec<_>>()
-          .into_boxed_slice();
+            glyphs.push((draw, orientation));
+          }
+        }
 
         if let Some(run_bounds) = run_bounds {
           bounds = bounds.union(&run_bounds);
         }
-        if let Some(first) = glyphs.first() {
+        if let Some((first, _)) = glyphs.first() {
           union_edge_glyph_rect(
             &mut bounds,
-            font,
+            &face,
             first.glyph_id,
             font_size,
             first.baseline_origin,
           );
         }
         if glyphs.len() > 1
-          && let Some(last) = glyphs.last()
+          && let Some((last, _)) = glyphs.last()
         {
-          union_edge_glyph_rect(&mut bounds, font, last.glyph_id, font_size, last.baseline_origin);
+          union_edge_glyph_rect(&mut bounds, &face, last.glyph_id, font_size, last.baseline_origin);
         }
 
         let underline = glyph_run.style().underline.as_ref();
@@ -351,15 +547,22 @@ impl ParleyEngine {
           }
         }
 
-        runs.push(DrawGlyphRun {
-          face_id,
-          logical_font_size: font_size,
-          brush: brushes
-            .get(glyph_run.style().brush)
-            .cloned()
-            .flatten(),
-          glyphs,
-        });
+        let brush = brushes
+          .get(glyph_run.style().brush)
+          .cloned()
+          .flatten();
+        for part in glyphs.chunk_by(|a, b| a.1 == b.1) {
+          runs.push(DrawGlyphRun {
+            face_id,
+            logical_font_size: font_size,
+            brush: brush.clone(),
+            orientation: part[0].1,
+            glyphs: part
+              .iter()
+              .map(|(glyph, _)| glyph.clone())
+              .collect(),
+          });
+        }
       }
     }
 
@@ -374,6 +577,8 @@ impl ParleyEngine {
     TextDrawPayload {
       bounds,
       origin_offset: shift,
+      transform: Transform::identity(),
+      backgrounds: Box::new([]),
       runs: runs.into_boxed_slice(),
       decorations: decorations.into_boxed_slice(),
     }
@@ -430,6 +635,14 @@ fn push_layout_defaults(
   builder.push_default(StyleProperty::FontWidth(font_width(text_style.font_face.stretch)));
   builder.push_default(StyleProperty::FontStyle(font_style(text_style.font_face.style)));
   builder.push_default(StyleProperty::LetterSpacing(text_style.letter_space));
+  builder.push_default(StyleProperty::WordSpacing(text_style.word_space));
+  if !text_style.font_features.is_empty() {
+    builder.push_default(StyleProperty::FontFeatures(font_features(&text_style.font_features)));
+  }
+  if !text_style.font_variations.is_empty() {
+    builder
+      .push_default(StyleProperty::FontVariations(font_variations(&text_style.font_variations)));
+  }
   if include_wrap {
     builder.push_default(StyleProperty::TextWrapMode(text_wrap_mode(paragraph_style.wrap)));
     builder.push_default(StyleProperty::OverflowWrap(match paragraph_style.wrap {
@@ -479,19 +692,35 @@ impl<Brush> ParagraphLayout<Brush> for ParleyParagraphLayout<Brush>
 where
   Brush: Clone + PartialEq + 'static,
 {
-  fn size(&self) -> Size { self.logical_size }
+  fn size(&self) -> Size { flip_size(self.logical_size, self.writing_mode) }
 
   fn aligned(&self, text_align: TextAlign, size: Size) -> ParagraphLayoutRef<Brush> {
     Arc::new(Box::new(
       self
         .clone_for_alignment()
-        .with_alignment(text_align, size),
+        .with_alignment(text_align, flip_size(size, self.writing_mode)),
     ))
   }
 
-  fn draw_payload(&self) -> &TextDrawPayload<Brush> { &self.payload }
+  fn draw_payload(&self) -> &TextDrawPayload<Brush> {
+    self
+      .vertical_payload
+      .as_ref()
+      .unwrap_or(&self.payload)
+  }
+
+  fn writing_mode(&self) -> WritingMode { self.writing_mode }
+
+  fn first_baseline(&self) -> Option<f32> {
+    if self.writing_mode.is_vertical() {
+      return None;
+    }
+    let line = self.layout.get(0)?;
+    Some(line.metrics().baseline + self.payload.origin_offset.y)
+  }
 
   fn hit_test_point(&self, point: Point) -> TextHitResult {
+    let point = self.to_horizontal_point(point);
     let line = self.line_index_for_y(point.y);
     let layout_point = Point::new(
       point.x - self.payload.origin_offset.x - self.line_x_offset(line),
@@ -505,28 +734,22 @@ where
   fn caret_rect(&self, caret: Caret) -> Rect {
     let cursor = self.caret_to_cursor(caret);
     let caret = self.cursor_to_caret(cursor);
-    if let Some(VisualPosition { line, slot }) = caret.visual {
+    let rect = if let Some(VisualPosition { line, slot }) = caret.visual {
       let (line, slot) = self.clamp_visual_position(line.0, slot);
       self.visual_line(line).caret_rect(slot)
     } else {
       let rect = rect_from_box(cursor.geometry(&self.layout, CARET_WIDTH))
         .translate(self.payload.origin_offset);
       rect.translate(Vector::new(self.line_x_offset(self.line_index_for_y(rect.center().y)), 0.))
-    }
+    };
+    self.to_paragraph_rect(rect)
   }
 
   fn selection_rects(&self, selection: TextRange) -> Box<[Rect]> {
-    let start =
-      ParleyCursor::from_byte_index(&self.layout, selection.start.0, ParleyAffinity::Downstream);
-    let end =
-      ParleyCursor::from_byte_index(&self.layout, selection.end.0, ParleyAffinity::Upstream);
-    ParleySelection::new(start, end)
-      .geometry(&self.layout)
+    self
+      .horizontal_selection_rects(selection)
       .into_iter()
-      .map(|(rect, _)| {
-        let rect = rect_from_box(rect).translate(self.payload.origin_offset);
-        rect.translate(Vector::new(self.line_x_offset(self.line_index_for_y(rect.center().y)), 0.))
-      })
+      .map(|rect| self.to_paragraph_rect(rect))
       .collect::<Vec<_>>()
       .into_boxed_slice()
   }
@@ -623,9 +846,14 @@ where
       payload: self.payload.clone(),
       line_offsets: self.line_offsets.clone(),
       line_positions: OnceLock::new(),
+      text_offset: self.text_offset,
+      writing_mode: self.writing_mode,
+      vertical_payload: None,
+      backgrounds: self.backgrounds.clone(),
     }
   }
 
+  /// Aligns the lines within `size`, which is given in horizontal space.
   fn with_alignment(self, text_align: TextAlign, size: Size) -> Self {
     let line_offsets = line_alignment_offsets(self.layout.as_ref(), size.width, text_align);
     if size == self.logical_size
@@ -633,7 +861,7 @@ where
         .iter()
         .all(|offset| offset.abs() <= f32::EPSILON)
     {
-      return self;
+      return self.with_backgrounds().with_vertical_payload();
     }
 
     let payload = shift_payload_by_line_offsets(&self.payload, self.layout.as_ref(), &line_offsets);
@@ -643,7 +871,94 @@ where
       payload,
       line_offsets,
       line_positions: OnceLock::new(),
+      text_offset: self.text_offset,
+      writing_mode: self.writing_mode,
+      vertical_payload: None,
+      backgrounds: self.backgrounds,
     }
+    .with_backgrounds()
+    .with_vertical_payload()
+  }
+
+  fn with_backgrounds(mut self) -> Self {
+    if self.backgrounds.is_empty() {
+      return self;
+    }
+    let backgrounds = self
+      .backgrounds
+      .iter()
+      .flat_map(|(range, background)| {
+        self
+          .horizontal_selection_rects(*range)
+          .into_iter()
+          .map(|rect| DrawTextBackground {
+            brush: background.brush.clone(),
+            radius: background.radius,
+            rect,
+          })
+      })
+      .collect::<Vec<_>>();
+    self.payload.bounds = backgrounds
+      .iter()
+      .fold(self.payload.bounds, |bounds, background| bounds.union(&background.rect));
+    self.payload.backgrounds = backgrounds.into_boxed_slice();
+    self
+  }
+
+  /// The selection rectangles in the horizontal space of `payload`.
+  fn horizontal_selection_rects(&self, selection: TextRange) -> Vec<Rect> {
+    let start = ParleyCursor::from_byte_index(
+      &self.layout,
+      selection.start.0 + self.text_offset,
+      ParleyAffinity::Downstream,
+    );
+    let end = ParleyCursor::from_byte_index(
+      &self.layout,
+      selection.end.0 + self.text_offset,
+      ParleyAffinity::Upstream,
+    );
+    ParleySelection::new(start, end)
+      .geometry(&self.layout)
+      .into_iter()
+      .map(|(rect, _)| {
+        let rect = rect_from_box(rect).translate(self.payload.origin_offset);
+        rect.translate(Vector::new(self.line_x_offset(self.line_index_for_y(rect.center().y)), 0.))
+      })
+      .collect()
+  }
+
+  fn with_vertical_payload(mut self) -> Self {
+    self.vertical_payload = self.vertical_transform().map(|transform| {
+      let mut payload = self.payload.clone();
+      payload.bounds = transform.outer_transformed_rect(&payload.bounds);
+      payload.transform = transform;
+      payload
+    });
+    self
+  }
+
+  /// Rotates the horizontal layout 90° clockwise, so its first line becomes
+  /// the rightmost column.
+  fn vertical_transform(&self) -> Option<Transform> {
+    match self.writing_mode {
+      WritingMode::HorizontalTb => None,
+      WritingMode::VerticalRl => {
+        Some(Transform::new(0., 1., -1., 0., self.logical_size.height, 0.))
+      }
+    }
+  }
+
+  fn to_horizontal_point(&self, point: Point) -> Point {
+    match self.writing_mode {
+      WritingMode::HorizontalTb => point,
+      WritingMode::VerticalRl => Point::new(point.y, self.logical_size.height - point.x),
+    }
+  }
+
+  fn to_paragraph_rect(&self, rect: Rect) -> Rect {
+    self
+      .vertical_transform()
+      .map_or(rect, |transform| transform.outer_transformed_rect(&rect))
   }
 
   fn line_index_for_y(&self, y: f32) -> usize {
@@ -709,7 +1024,7 @@ where
     let (line, slot) = self.clamp_visual_position(line, slot);
     let key = self.visual_line(line).slots[slot].key;
     Caret {
-      byte: TextByteIndex(key.index),
+      byte: TextByteIndex(key.index.saturating_sub(self.text_offset)),
       affinity: match key.affinity {
         ParleyAffinity::Upstream => CaretAffinity::Upstream,
         ParleyAffinity::Downstream => CaretAffinity::Downstream,
@@ -719,7 +1034,11 @@ where
   }
 
   fn caret_to_cursor(&self, caret: Caret) -> ParleyCursor {
-    ParleyCursor::from_byte_index(&self.layout, caret.byte.0, parley_affinity(caret.affinity))
+    ParleyCursor::from_byte_index(
+      &self.layout,
+      caret.byte.0 + self.text_offset,
+      parley_affinity(caret.affinity),
+    )
   }
 
   fn find_visual(&self, key: CursorKey) -> Option<VisualPosition> {
@@ -741,7 +1060,7 @@ where
     let visual = self.find_visual(key);
 
     Caret {
-      byte: TextByteIndex(cursor.index()),
+      byte: TextByteIndex(cursor.index().saturating_sub(self.text_offset)),
       affinity: match cursor.affinity() {
         ParleyAffinity::Upstream => CaretAffinity::Upstream,
         ParleyAffinity::Downstream => CaretAffinity::Downstream,
@@ -752,7 +1071,12 @@ where
 
   fn line_positions(&self) -> &[VisualLine] {
     self.line_positions.get_or_init(|| {
-      build_line_positions(&self.layout, self.payload.origin_offset, &self.line_offsets)
+      build_line_positions(
+        &self.layout,
+        self.payload.origin_offset,
+        &self.line_offsets,
+        self.text_offset,
+      )
     })
   }
 
@@ -777,10 +1101,11 @@ where
 fn push_span_styles<Brush>(
   builder: &mut parley::RangedBuilder<'_, usize>, span: &TextSpan<Brush>, text_style: &TextStyle,
   paragraph_style: &ParagraphStyle, brushes: &mut Vec<Option<Brush>>,
+  baseline_shifts: &mut BaselineShifts, text_offset: usize,
 ) where
   Brush: Clone + From<Color>,
 {
-  let range = span.range.start.0..span.range.end.0;
+  let range = span.range.start.0 + text_offset..span.range.end.0 + text_offset;
   if let Some(font) = span.style.font.as_ref() {
     builder.push(StyleProperty::FontFamily(font_family_for_face(&font.face)), range.clone());
     builder.push(
@@ -796,12 +1121,30 @@ fn push_span_styles<Brush>(
   if let Some(letter_spacing) = span.style.letter_spacing {
     builder.push(StyleProperty::LetterSpacing(letter_spacing), range.clone());
   }
-  let brush_idx = if let Some(brush) = span.style.brush.as_ref() {
-    brushes.push(Some(brush.clone()));
+  if let Some(word_spacing) = span.style.word_spacing {
+    builder.push(StyleProperty::WordSpacing(word_spacing), range.clone());
+  }
+  if let Some(features) = span.style.font_features.as_ref() {
+    builder.push(StyleProperty::FontFeatures(font_features(features)), range.clone());
+  }
+  if let Some(variations) = span.style.font_variations.as_ref() {
+    builder.push(StyleProperty::FontVariations(font_variations(variations)), range.clone());
+  }
+  // A shifted span always gets its own brush slot, so parley keeps it in a
+  // separate glyph run and the payload builder can look the shift up.
+  let baseline_shift = span
+    .style
+    .baseline_shift
+    .filter(|shift| *shift != BaselineShift::Baseline);
+  let brush_idx = if span.style.brush.is_some() || baseline_shift.is_some() {
+    brushes.push(span.style.brush.clone());
     brushes.len() - 1
   } else {
     0
   };
+  if let Some(shift) = baseline_shift {
+    baseline_shifts.push((brush_idx, shift));
+  }
   builder.push(StyleProperty::Brush(brush_idx), range.clone());
   if let Some(decoration) = span.style.decoration.as_ref() {
     let decoration_brush_idx = decoration.decoration_color.map(|color| {
@@ -851,6 +1194,52 @@ fn parley_line_height(line_height: LineHeight) -> ParleyLineHeight {
   }
 }
 
+fn font_features(features: &[FontFeature]) -> ParleyFontSettings<'static, ParleyFontFeature> {
+  ParleyFontSettings::List(Cow::Owned(
+    features
+      .iter()
+      .map(|feature| ParleyFontFeature { tag: feature.tag.to_u32(), value: feature.value })
+      .collect(),
+  ))
+}
+
+fn font_variations(
+  variations: &[FontVariation],
+) -> ParleyFontSettings<'static, ParleyFontVariation> {
+  ParleyFontSettings::List(Cow::Owned(
+    variations
+      .iter()
+      .map(|variation| ParleyFontVariation { tag: variation.tag.to_u32(), value: variation.value })
+      .collect(),
+  ))
+}
+
+/// Parley resolves the base direction from the first strong character, so an
+/// explicit direction is forced by prefixing an invisible directional mark.
+/// Returns the text to shape and the byte length of the prefix.
+fn directed_text(text: &str, direction: TextDirection) -> (Cow<'_, str>, usize) {
+  let mark = match direction {
+    TextDirection::Auto => return (Cow::Borrowed(text), 0),
+    TextDirection::Ltr => '\u{200E}',
+    TextDirection::Rtl => '\u{200F}',
+  };
+  let mut directed = String::with_capacity(mark.len_utf8() + text.len());
+  directed.push(mark);
+  directed.push_str(text);
+  (Cow::Owned(directed), mark.len_utf8())
+}
+
+fn flip_size(size: Size, writing_mode: WritingMode) -> Size {
+  match writing_mode {
+    WritingMode::HorizontalTb => size,
+    WritingMode::VerticalRl => Size::new(size.height, size.width),
+  }
+}
+
+fn horizontal_clamp(clamp: BoxClamp, writing_mode: WritingMode) -> BoxClamp {
+  BoxClamp { min: flip_size(clamp.min, writing_mode), max: flip_size(clamp.max, writing_mode) }
+}
+
 fn text_wrap_mode(wrap: TextWrap) -> TextWrapMode {
   match wrap {
     TextWrap::NoWrap => TextWrapMode::NoWrap,
@@ -861,7 +1250,7 @@ fn text_wrap_mode(wrap: TextWrap) -> TextWrapMode {
 const CARET_WIDTH: f32 = 1.0;
 
 fn build_line_positions(
-  layout: &ParleyLayout<usize>, origin_offset: Vector, line_offsets: &[f32],
+  layout: &ParleyLayout<usize>, origin_offset: Vector, line_offsets: &[f32], text_offset: usize,
 ) -> Box<[VisualLine]> {
   let mut lines = Vec::new();
 
@@ -871,8 +1260,12 @@ fn build_line_positions(
     };
     let metrics = line.metrics();
     let break_reason = line.break_reason();
-    let start =
-      ParleyCursor::from_byte_index(layout, line.text_range().start, ParleyAffinity::Downstream);
+    // Skip the directional mark, so it never becomes a caret slot.
+    let start = ParleyCursor::from_byte_index(
+      layout,
+      line.text_range().start.max(text_offset),
+      ParleyAffinity::Downstream,
+    );
     let line_shift = line_offsets
       .get(line_index)
       .copied()
@@ -942,7 +1335,7 @@ fn build_line_positions(
   }
 
   if lines.is_empty() {
-    let cursor = ParleyCursor::from_byte_index(layout, 0, ParleyAffinity::Downstream);
+    let cursor = ParleyCursor::from_byte_index(layout, text_offset, ParleyAffinity::Downstream);
     let rect = rect_from_box(cursor.geometry(layout, 1.0));
     let height = rect.height();
     let metrics = parley::layout::LineMetrics {
@@ -1138,33 +1531,37 @@ fn parley_affinity(affinity: CaretAffinity) -> ParleyAffinity {
 }
 
 fn font_family_for_face(face: &crate::FontFace) -> ParleyFontFamily<'static> {
-  let mut families = Vec::new();
-
-  for family in face.families.iter() {
-    match family {
-      FontFamily::Name(name) => families.push(ParleyFontFamilyName::Named(name.to_string().into())),
-      FontFamily::Serif => families.push(ParleyFontFamilyName::Generic(GenericFamily::Serif)),
-      FontFamily::SansSerif => {
-        families.push(ParleyFontFamilyName::Generic(GenericFamily::SansSerif))
-      }
-      FontFamily::Cursive => families.push(ParleyFontFamilyName::Generic(GenericFamily::Cursive)),
-      FontFamily::Fantasy => families.push(ParleyFontFamilyName::Generic(GenericFamily::Fantasy)),
-      FontFamily::Monospace => {
-        families.push(ParleyFontFamilyName::Generic(GenericFamily::Monospace))
-      }
-    }
-  }
+  let families = face
+    .families
+    .iter()
+    .map(|family| match generic_or_named(family) {
+      Ok(generic) => ParleyFontFamilyName::Generic(generic),
+      Err(name) => ParleyFontFamilyName::Named(name.to_string().into()),
+    })
+    .collect::<Vec<_>>();
 
   ParleyFontFamily::List(families.into())
 }
 
+/// Returns the generic family of `family`, or its name if it is a named one.
+fn generic_or_named(family: &FontFamily) -> Result<GenericFamily, &str> {
+  match family {
+    FontFamily::Name(name) => Err(name),
+    FontFamily::Serif => Ok(GenericFamily::Serif),
+    FontFamily::SansSerif => Ok(GenericFamily::SansSerif),
+    FontFamily::Cursive => Ok(GenericFamily::Cursive),
+    FontFamily::Fantasy => Ok(GenericFamily::Fantasy),
+    FontFamily::Monospace => Ok(GenericFamily::Monospace),
+  }
+}
+
 fn font_style(style: FontStyle) -> ParleyFontStyle { style }
 
 fn font_width(stretch: FontStretch) -> ParleyFontWidth { stretch }
 
-fn font_metrics(font: &ParleyFontData) -> FontFaceMetrics {
+fn font_metrics(font: &ParleyFontData, coords: &[i16]) -> FontFaceMetrics {
   let font_ref = FontRef::from_index(font.data.data(), font.index as usize).unwrap();
-  let metrics = font_ref.metrics(&[]);
+  let metrics = font_ref.metrics(coords);
   FontFaceMetrics {
     units_per_em: metrics.units_per_em,
     vertical_height: Some(metrics.ascent - metrics.descent),
@@ -1187,6 +1584,27 @@ fn union_optional_rect(bounds: Option<Rect>, rect: Rect) -> Rect {
   bounds.map_or(rect, |bounds| bounds.union(&rect))
 }
 
+/// Whether the char stands upright in a vertical line, a subset of the
+/// `U` and `Tu` classes of UAX #50 covering the CJK scripts and symbols.
+fn is_upright_char(c: char) -> bool {
+  matches!(c as u32,
+    0x1100..=0x11FF // Hangul Jamo
+    | 0x2E80..=0x2FDF // CJK Radicals, Kangxi Radicals
+    | 0x2FF0..=0x303F // Ideographic Description, CJK Symbols and Punctuation
+    | 0x3040..=0x31FF // Hiragana, Katakana, Bopomofo, Hangul Compatibility Jamo, ...
+    | 0x3200..=0x9FFF // Enclosed CJK, CJK Compatibility, CJK Unified Ideographs
+    | 0xA960..=0xA97F // Hangul Jamo Extended-A
+    | 0xAC00..=0xD7FF // Hangul Syllables, Hangul Jamo Extended-B
+    | 0xF900..=0xFAFF // CJK Compatibility Ideographs
+    | 0xFE10..=0xFE1F // Vertical Forms
+    | 0xFE30..=0xFE4F // CJK Compatibility Forms
+    | 0xFF01..=0xFF60 // Fullwidth Forms
+    | 0xFFE0..=0xFFE6 // Fullwidth Signs
+    | 0x1F000..=0x1FAFF // Mahjong, Domino, Playing Cards, Emoji, ...
+    | 0x20000..=0x3FFFF // CJK Unified Ideographs Extension B and later
+  )
+}
+
 fn glyph_metrics_rect(baseline_origin: Point, advance: f32, ascender: f32, descender: f32) -> Rect {
   Rect::new(
     Point::new(baseline_origin.x, baseline_origin.y - ascender),
@@ -1199,12 +1617,24 @@ fn baseline_shift(metrics: Option<FontFaceMetrics>, font_size: f32) -> f32 {
   0.
 }
 
+fn face_instance(coords: &[i16], embolden: bool, skew: Option<f32>) -> u64 {
+  if coords.iter().all(|coord| *coord == 0) && !embolden && skew.is_none() {
+    return 0;
+  }
+
+  let mut hasher = ahash::AHasher::default();
+  coords.hash(&mut hasher);
+  embolden.hash(&mut hasher);
+  skew.map(f32::to_bits).hash(&mut hasher);
+  hasher.finish()
+}
+
 fn glyph_raster_rect(
-  font: &ParleyFontData, glyph_id: GlyphId, font_size: f32, baseline_origin: Point,
+  face: &ParleyFace, glyph_id: GlyphId, font_size: f32, baseline_origin: Point,
 ) -> Option<Rect> {
   let img_size = font_size.ceil().max(1.) as u16;
   let scale = font_size / img_size as f32;
-  let bitmap = raster_bitmap_from_font(font, glyph_id, img_size)?;
+  let bitmap = raster_bitmap_from_face(face, glyph_id, img_size)?;
 
   Some(Rect::new(
     Point::new(
@@ -1216,10 +1646,9 @@ fn glyph_raster_rect(
 }
 
 fn union_edge_glyph_rect(
-  bounds: &mut Rect, font: &ParleyFontData, glyph_id: GlyphId, font_size: f32,
-  baseline_origin: Point,
+  bounds: &mut Rect, face: &ParleyFace, glyph_id: GlyphId, font_size: f32, baseline_origin: Point,
 ) {
-  if let Some(rect) = glyph_raster_rect(font, glyph_id, font_size, baseline_origin) {
+  if let Some(rect) = glyph_raster_rect(face, glyph_id, font_size, baseline_origin) {
     *bounds = bounds.union(&rect);
   }
 }
@@ -1240,21 +1669,32 @@ fn subpixel_mask_to_alpha(data: Vec<u8>) -> Vec<u8> {
   alpha
 }
 
-fn raster_bitmap_from_font(
-  font: &ParleyFontData, glyph_id: GlyphId, img_size: u16,
+fn raster_bitmap_from_face(
+  face: &ParleyFace, glyph_id: GlyphId, img_size: u16,
 ) -> Option<RasterBitmap> {
+  let font = &face.font;
   let font_ref = FontRef::from_index(font.data.data(), font.index as usize)?;
   let mut scale_ctx = swash::scale::ScaleContext::new();
   let mut scaler = scale_ctx
     .builder(font_ref)
     .size(img_size as f32)
+    .normalized_coords(face.coords.iter())
     .hint(true)
     .build();
+  let embolden = if face.embolden { img_size as f32 / 32. } else { 0. };
+  let skew = face.skew.map(|angle| {
+    swash::zeno::Transform::skew(
+      swash::zeno::Angle::from_degrees(angle),
+      swash::zeno::Angle::from_degrees(0.),
+    )
+  });
   let image = swash::scale::Render::new(&[
     swash::scale::Source::ColorBitmap(swash::scale::StrikeWith::BestFit),
     swash::scale::Source::Bitmap(swash::scale::StrikeWith::BestFit),
     swash::scale::Source::Outline,
   ])
+  .embolden(embolden)
+  .transform(skew)
   .render(&mut scaler, glyph_id.0);
 
   image.map(|img| {
@@ -1276,6 +1716,52 @@ fn raster_bitmap_from_font(
   })
 }
 
+fn outline_from_face(
+  face: &ParleyFace, glyph_id: GlyphId, font_size: f32,
+) -> Option<Vec<OutlineSegment>> {
+  use swash::zeno::Verb;
+
+  let font = &face.font;
+  let font_ref = FontRef::from_index(font.data.data(), font.index as usize)?;
+  let mut scale_ctx = swash::scale::ScaleContext::new();
+  let mut scaler = scale_ctx
+    .builder(font_ref)
+    .size(font_size)
+    .normalized_coords(face.coords.iter())
+    .build();
+  let mut outline = scaler.scale_outline(glyph_id.0)?;
+  if face.embolden {
+    let strength = font_size / 32.;
+    outline.embolden(strength, strength);
+  }
+  if let Some(angle) = face.skew {
+    outline.transform(&swash::zeno::Transform::skew(
+      swash::zeno::Angle::from_degrees(angle),
+      swash::zeno::Angle::from_degrees(0.),
+    ));
+  }
+
+  // The font space is y-up, flip it to the painter space.
+  let mut points = outline
+    .points()
+    .iter()
+    .map(|p| Point::new(p.x, -p.y));
+  let mut segments = Vec::with_capacity(outline.verbs().len());
+  for verb in outline.verbs() {
+    let seg = match verb {
+      Verb::MoveTo => OutlineSegment::MoveTo(points.next()?),
+      Verb::LineTo => OutlineSegment::LineTo(points.next()?),
+      Verb::QuadTo => OutlineSegment::QuadTo { ctrl: points.next()?, to: points.next()? },
+      Verb::CurveTo => {
+        OutlineSegment::CubicTo { ctrl1: points.next()?, ctrl2: points.next()?, to: points.next()? }
+      }
+      Verb::Close => OutlineSegment::Close,
+    };
+    segments.push(seg);
+  }
+  Some(segments)
+}
+
 pub(crate) struct ParleyFontSystem {
   engine: Rc<std::cell::RefCell<ParleyEngine>>,
   faces: ParleyFaces,
@@ -1303,15 +1789,19 @@ impl ParleyFontSystem {
 }
 
 impl FontSystem for ParleyFontSystem {
-  fn register_font_bytes(&mut self, data: Vec<u8>) -> Result<(), FontLoadError> {
-    self.engine.borrow_mut().register_font_bytes(data);
-
-    Ok(())
+  fn load_font_bytes(&mut self, data: Vec<u8>) -> Result<Box<[CowArc<str>]>, FontLoadError> {
+    let families = self.engine.borrow_mut().register_font_bytes(data);
+    if families.is_empty() {
+      return Err(FontLoadError::new("no font found in the data"));
+    }
+    Ok(families)
   }
 
-  fn register_font_file(&mut self, path: &std::path::Path) -> Result<(), FontLoadError> {
+  fn load_font_file(
+    &mut self, path: &std::path::Path,
+  ) -> Result<Box<[CowArc<str>]>, FontLoadError> {
     let data = std::fs::read(path).map_err(|e| FontLoadError::new(e.to_string()))?;
-    self.register_font_bytes(data)
+    self.load_font_bytes(data)
   }
 
   fn face_metrics(&self, face: FontFaceId) -> Option<FontFaceMetrics> {
@@ -1323,6 +1813,12 @@ impl FontSystem for ParleyFontSystem {
       .map(|face| face.metrics)
   }
 
+  fn font_families(&mut self) -> Vec<FontFamilyInfo> { self.engine.borrow_mut().font_families() }
+
+  fn covers(&mut self, face: &crate::FontFace, text: &str) -> bool {
+    self.engine.borrow_mut().covers(face, text)
+  }
+
   fn raster_source(&self) -> GlyphRasterSourceRef { build_text_raster_source(self.faces.clone()) }
 }
 
@@ -1402,11 +1898,12 @@ mod tests {
         line_height: None,
         brush: None,
         decoration: None,
+        ..Default::default()
       },
     );
     let paragraph = services.paragraph(source);
     let paragraph_style =
-      crate::ParagraphStyle { text_align: crate::TextAlign::Start, wrap: crate::TextWrap::Wrap };
+      crate::ParagraphStyle { wrap: crate::TextWrap::Wrap, ..Default::default() };
 
     let compact = crate::TextStyle {
       font_size: 16.,
@@ -1414,6 +1911,7 @@ mod tests {
       letter_space: 0.,
       line_height: crate::LineHeight::Px(16.),
       overflow: crate::TextOverflow::AutoWrap,
+      ..<_>::default()
     };
     let spacious = crate::TextStyle { line_height: crate::LineHeight::Px(40.), ..compact.clone() };
 
@@ -1425,6 +1923,40 @@ mod tests {
     assert!(spacious_layout.size().height > compact_layout.size().height);
   }
 
+  #[test]
+  fn first_baseline_matches_first_line_glyphs() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    let source = crate::AttributedText::styled(
+      "A\nB",
+      crate::SpanStyle {
+        font: Some(crate::FontRequest { face: dejavu_face() }),
+        font_size: Some(16.),
+        letter_spacing: Some(0.),
+        ..Default::default()
+      },
+    );
+    let paragraph = services.paragraph(source);
+    let style = crate::TextStyle {
+      font_size: 16.,
+      font_face: dejavu_face(),
+      letter_space: 0.,
+      line_height: crate::LineHeight::Px(40.),
+      ..<_>::default()
+    };
+    let layout = paragraph.layout(
+      &style,
+      &no_wrap_paragraph_style(),
+      BoxClamp::max_size(Size::new(200., 200.)),
+    );
+
+    let baseline = layout.first_baseline().unwrap();
+    let first_glyph = &layout.draw_payload().runs[0].glyphs[0];
+    assert!((baseline - first_glyph.baseline_origin.y).abs() < 0.01);
+    assert!(baseline > 12. && baseline < 40.);
+  }
+
   #[test]
   fn draw_payload_preserves_run_brush_overrides() {
     let services = crate::new_text_services::<TestBrush>();
@@ -1442,6 +1974,7 @@ mod tests {
             line_height: None,
             brush: None,
             decoration: None,
+            ..Default::default()
           },
         },
         crate::TextSpan {
@@ -1453,6 +1986,7 @@ mod tests {
             line_height: None,
             brush: Some(TestBrush(7)),
             decoration: None,
+            ..Default::default()
           },
         },
       ]
@@ -1466,9 +2000,10 @@ mod tests {
       letter_space: 0.,
       line_height: crate::LineHeight::Px(16.),
       overflow: crate::TextOverflow::Overflow,
+      ..<_>::default()
     };
     let paragraph_style =
-      crate::ParagraphStyle { text_align: crate::TextAlign::Start, wrap: crate::TextWrap::NoWrap };
+      crate::ParagraphStyle { wrap: crate::TextWrap::NoWrap, ..Default::default() };
     let layout =
       paragraph.layout(&text_style, &paragraph_style, BoxClamp::max_size(Size::new(200., 200.)));
     let payload = layout.draw_payload();
@@ -1496,6 +2031,7 @@ mod tests {
         line_height: None,
         brush: None,
         decoration: None,
+        ..Default::default()
       },
     );
     let paragraph = services.paragraph(source);
@@ -1505,9 +2041,10 @@ mod tests {
       letter_space: 0.,
       line_height: crate::LineHeight::Px(64.),
       overflow: crate::TextOverflow::Overflow,
+      ..<_>::default()
     };
     let paragraph_style =
-      crate::ParagraphStyle { text_align: crate::TextAlign::Start, wrap: crate::TextWrap::NoWrap };
+      crate::ParagraphStyle { wrap: crate::TextWrap::NoWrap, ..Default::default() };
     let layout =
       paragraph.layout(&text_style, &paragraph_style, BoxClamp::max_size(Size::new(500., 200.)));
     let payload = layout.draw_payload();
@@ -1535,6 +2072,7 @@ mod tests {
         line_height: None,
         brush: None,
         decoration: None,
+        ..Default::default()
       },
     );
     let paragraph = services.paragraph(source);
@@ -1544,9 +2082,10 @@ mod tests {
       letter_space: 0.,
       line_height: crate::LineHeight::Px(64.),
       overflow: crate::TextOverflow::Overflow,
+      ..<_>::default()
     };
     let paragraph_style =
-      crate::ParagraphStyle { text_align: crate::TextAlign::Start, wrap: crate::TextWrap::NoWrap };
+      crate::ParagraphStyle { wrap: crate::TextWrap::NoWrap, ..Default::default() };
     let layout =
       paragraph.layout(&text_style, &paragraph_style, BoxClamp::max_size(Size::new(500., 200.)));
 
@@ -1578,11 +2117,12 @@ mod tests {
         line_height: None,
         brush: None,
         decoration: None,
+        ..Default::default()
       },
     );
     let paragraph = services.paragraph(source);
     let paragraph_style =
-      crate::ParagraphStyle { text_align: crate::TextAlign::Start, wrap: crate::TextWrap::Wrap };
+      crate::ParagraphStyle { wrap: crate::TextWrap::Wrap, ..Default::default() };
 
     let number = crate::TextStyle {
       font_size: 20.,
@@ -1590,6 +2130,7 @@ mod tests {
       letter_space: 0.,
       line_height: crate::LineHeight::Scale(1.5),
       overflow: crate::TextOverflow::AutoWrap,
+      ..<_>::default()
     };
     let absolute = crate::TextStyle { line_height: crate::LineHeight::Px(30.), ..number.clone() };
 
@@ -1618,11 +2159,12 @@ mod tests {
         line_height: None,
         brush: None,
         decoration: None,
+        ..Default::default()
       },
     );
     let paragraph = services.paragraph(source);
     let paragraph_style =
-      crate::ParagraphStyle { text_align: crate::TextAlign::Start, wrap: crate::TextWrap::Wrap };
+      crate::ParagraphStyle { wrap: crate::TextWrap::Wrap, ..Default::default() };
 
     let relative = crate::TextStyle {
       font_size: 20.,
@@ -1630,6 +2172,7 @@ mod tests {
       letter_space: 0.,
       line_height: crate::LineHeight::default(),
       overflow: crate::TextOverflow::AutoWrap,
+      ..<_>::default()
     };
     let absolute = crate::TextStyle { line_height: crate::LineHeight::Px(20.), ..relative.clone() };
 
@@ -1640,4 +2183,282 @@ mod tests {
 
     assert!((relative_layout.size().height - absolute_layout.size().height).abs() > 0.1);
   }
+
+  fn dejavu_text_style() -> crate::TextStyle {
+    crate::TextStyle {
+      font_size: 16.,
+      font_face: dejavu_face(),
+      line_height: crate::LineHeight::Px(16.),
+      ..<_>::default()
+    }
+  }
+
+  fn no_wrap_paragraph_style() -> crate::ParagraphStyle {
+    crate::ParagraphStyle { wrap: crate::TextWrap::NoWrap, ..Default::default() }
+  }
+
+  #[test]
+  fn word_spacing_widens_paragraph() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    let paragraph = services.paragraph(crate::AttributedText::plain("a b c"));
+    let clamp = BoxClamp::max_size(Size::new(500., 200.));
+    let normal = paragraph.layout(&dejavu_text_style(), &no_wrap_paragraph_style(), clamp);
+    let spaced = paragraph.layout(
+      &dejavu_text_style().with_word_space(10.),
+      &no_wrap_paragraph_style(),
+      clamp,
+    );
+
+    assert!(spaced.size().width >= normal.size().width + 19.);
+  }
+
+  #[test]
+  fn baseline_shift_raises_superscript_glyphs() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    let source = crate::AttributedText::builder()
+      .push_text("x")
+      .push_styled_text(
+        "2",
+        crate::SpanStyle {
+          baseline_shift: Some(crate::BaselineShift::Super),
+          ..Default::default()
+        },
+      )
+      .build();
+    let paragraph = services.paragraph(source);
+    let layout = paragraph.layout(
+      &dejavu_text_style(),
+      &no_wrap_paragraph_style(),
+      BoxClamp::max_size(Size::new(200., 200.)),
+    );
+    let runs = &layout.draw_payload().runs;
+
+    assert_eq!(runs.len(), 2);
+    let base_y = runs[0].glyphs[0].baseline_origin.y;
+    let super_y = runs[1].glyphs[0].baseline_origin.y;
+    assert!((base_y - super_y - 16. * 0.33).abs() < 0.01);
+  }
+
+  #[test]
+  fn font_variations_render_as_distinct_face_instance() {
+    let services = crate::new_text_services::<TestBrush>();
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/Nunito-VariableFont_wght.ttf";
+    let _ = services.register_font_file(std::path::Path::new(&path));
+
+    let nunito = crate::FontFace {
+      families: Box::new([crate::FontFamily::Name("Nunito".into())]),
+      ..Default::default()
+    };
+    let text_style = crate::TextStyle { font_face: nunito, ..dejavu_text_style() };
+    let paragraph = services.paragraph(crate::AttributedText::plain("Ribir"));
+    let clamp = BoxClamp::max_size(Size::new(500., 200.));
+    let regular = paragraph.layout(&text_style, &no_wrap_paragraph_style(), clamp);
+    let black = paragraph.layout(
+      &text_style.with_font_variations([crate::FontVariation::new(b"wght", 900.)]),
+      &no_wrap_paragraph_style(),
+      clamp,
+    );
+
+    let regular_face = regular.draw_payload().runs[0].face_id;
+    let black_face = black.draw_payload().runs[0].face_id;
+    assert_eq!(regular_face.blob_id, black_face.blob_id);
+    assert_ne!(regular_face.instance, black_face.instance);
+    assert!(black.size().width > regular.size().width);
+  }
+
+  #[test]
+  fn forced_direction_keeps_source_indices() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    let paragraph = services.paragraph(crate::AttributedText::plain("abc"));
+    let clamp = BoxClamp::max_size(Size::new(200., 200.));
+    let auto = paragraph.layout(&dejavu_text_style(), &no_wrap_paragraph_style(), clamp);
+    let rtl_style =
+      crate::ParagraphStyle { direction: TextDirection::Rtl, ..no_wrap_paragraph_style() };
+    let rtl = paragraph.layout(&dejavu_text_style(), &rtl_style, clamp);
+
+    let width = |rects: Box<[Rect]>| rects.iter().map(|r| r.width()).sum::<f32>();
+    let all = crate::TextRange::new(0, 3);
+    assert!((width(auto.selection_rects(all)) - width(rtl.selection_rects(all))).abs() < 0.01);
+
+    let hit = rtl.hit_test_point(Point::new(0.5, 8.));
+    assert_eq!(hit.caret.byte, crate::TextByteIndex(0));
+  }
+
+  #[test]
+  fn vertical_rl_swaps_size_and_rotates_payload() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    let paragraph = services.paragraph(crate::AttributedText::plain("abc"));
+    let clamp = BoxClamp::max_size(Size::new(200., 200.));
+    let horizontal = paragraph.layout(&dejavu_text_style(), &no_wrap_paragraph_style(), clamp);
+    let vertical_style =
+      crate::ParagraphStyle { writing_mode: WritingMode::VerticalRl, ..no_wrap_paragraph_style() };
+    let vertical = paragraph.layout(&dejavu_text_style(), &vertical_style, clamp);
+
+    let size = horizontal.size();
+    assert_eq!(vertical.size(), Size::new(size.height, size.width));
+    assert_ne!(vertical.draw_payload().transform, Transform::identity());
+
+    let caret = vertical.caret_rect(Caret::default());
+    assert!(caret.width() > caret.height());
+    assert!(caret.min_y() < 1.);
+  }
+
+  #[test]
+  fn vertical_rl_sets_cjk_upright() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    let paragraph = services.paragraph(crate::AttributedText::plain("ab\u{6F22}\u{5B57}"));
+    let clamp = BoxClamp::max_size(Size::new(200., 200.));
+    let vertical_style =
+      crate::ParagraphStyle { writing_mode: WritingMode::VerticalRl, ..no_wrap_paragraph_style() };
+    let vertical = paragraph.layout(&dejavu_text_style(), &vertical_style, clamp);
+
+    let runs = &vertical.draw_payload().runs;
+    let upright =
+      |run: &&DrawGlyphRun<TestBrush>| matches!(run.orientation, GlyphOrientation::Upright { .. });
+    assert!(
+      runs
+        .iter()
+        .filter(|run| !upright(run))
+        .flat_map(|run| run.glyphs.iter())
+        .all(|glyph| glyph.cluster.0 < 2)
+    );
+    assert!(
+      runs
+        .iter()
+        .filter(upright)
+        .flat_map(|run| run.glyphs.iter())
+        .all(|glyph| glyph.cluster.0 >= 2)
+    );
+    assert!(runs.iter().any(|run| upright(&run)));
+
+    let horizontal = paragraph.layout(&dejavu_text_style(), &no_wrap_paragraph_style(), clamp);
+    assert!(
+      horizontal
+        .draw_payload()
+        .runs
+        .iter()
+        .all(|run| run.orientation == GlyphOrientation::Sideways)
+    );
+
+    // The caret motions are in the direction of the line, `Next` walks down.
+    let caret = vertical.move_caret(Caret::default(), CaretMotion::Next);
+    assert_eq!(caret.byte, crate::TextByteIndex(1));
+    assert!(vertical.caret_rect(caret).min_y() > vertical.caret_rect(Caret::default()).min_y());
+  }
+
+  #[test]
+  fn mixed_bidi_orders_runs_visually() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    // "abc" followed by three hebrew letters at the bytes 4, 6 and 8.
+    let paragraph = services.paragraph(crate::AttributedText::plain("abc \u{5D0}\u{5D1}\u{5D2}"));
+    let clamp = BoxClamp::max_size(Size::new(200., 200.));
+    let layout = |direction| {
+      let style = crate::ParagraphStyle { direction, ..no_wrap_paragraph_style() };
+      paragraph.layout(&dejavu_text_style(), &style, clamp)
+    };
+    let glyph_x = |layout: &ParagraphLayoutRef<TestBrush>, byte: usize| {
+      layout
+        .draw_payload()
+        .runs
+        .iter()
+        .flat_map(|run| run.glyphs.iter())
+        .find(|glyph| glyph.cluster.0 == byte)
+        .map(|glyph| glyph.baseline_origin.x)
+        .unwrap()
+    };
+    let caret_x = |layout: &ParagraphLayoutRef<TestBrush>, byte: usize| {
+      layout
+        .caret_rect(Caret { byte: crate::TextByteIndex(byte), ..Default::default() })
+        .min_x()
+    };
+
+    let ltr = layout(TextDirection::Ltr);
+    assert!(glyph_x(&ltr, 0) < glyph_x(&ltr, 4));
+    assert!(glyph_x(&ltr, 4) > glyph_x(&ltr, 6));
+    assert!(glyph_x(&ltr, 6) > glyph_x(&ltr, 8));
+    assert!(caret_x(&ltr, 0) < caret_x(&ltr, 4));
+
+    let rtl = layout(TextDirection::Rtl);
+    assert!(glyph_x(&rtl, 0) > glyph_x(&rtl, 4));
+    assert!(glyph_x(&rtl, 0) < glyph_x(&rtl, 1));
+    assert!(glyph_x(&rtl, 4) > glyph_x(&rtl, 6));
+    assert!(caret_x(&rtl, 0) > caret_x(&rtl, 4));
+  }
+  #[test]
+  fn span_background_follows_line_breaks() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    let source = crate::AttributedText::builder()
+      .push_styled_text(
+        "highlight across lines",
+        crate::SpanStyle {
+          background: Some(crate::TextBackground::new(TestBrush(3)).with_radius(2.)),
+          ..Default::default()
+        },
+      )
+      .build();
+    let paragraph = services.paragraph(source);
+    let layout = paragraph.layout(
+      &dejavu_text_style(),
+      &crate::ParagraphStyle::default(),
+      BoxClamp::max_size(Size::new(80., 200.)),
+    );
+
+    let backgrounds = &layout.draw_payload().backgrounds;
+    let selection = layout.selection_rects(crate::TextRange::new(0, 22));
+    assert!(backgrounds.len() > 1);
+    assert_eq!(backgrounds.len(), selection.len());
+    assert!(
+      backgrounds
+        .iter()
+        .zip(selection.iter())
+        .all(|(bg, rect)| bg.rect == *rect && bg.brush == TestBrush(3) && bg.radius == 2.)
+    );
+  }
+
+  #[test]
+  fn registered_fonts_are_enumerated() {
+    let services = crate::new_text_services::<TestBrush>();
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf";
+    let families = services
+      .load_font_file(std::path::Path::new(&path))
+      .unwrap();
+    assert_eq!(&*families, &[CowArc::from("DejaVu Sans")]);
+
+    let all = services.font_families();
+    let dejavu = all
+      .iter()
+      .find(|family| &*family.name == "DejaVu Sans")
+      .unwrap();
+    assert!(!dejavu.styles.is_empty());
+    assert!(all.windows(2).all(|w| *w[0].name <= *w[1].name));
+  }
+
+  #[test]
+  fn covers_reports_missing_glyphs() {
+    let services = crate::new_text_services::<TestBrush>();
+    register_test_font(services.as_ref());
+
+    assert!(services.covers(&dejavu_face(), "Ribir\n"));
+    assert!(!services.covers(&dejavu_face(), "漢字"));
+  }
+
+  #[test]
+  fn invalid_font_data_is_rejected() {
+    let services = crate::new_text_services::<TestBrush>();
+    assert!(services.register_font_bytes(vec![0; 16]).is_err());
+  }
 }
@@ -22,6 +22,17 @@ pub struct RasterBitmap {
   pub placement: Point,
 }
 
+/// A segment of a glyph outline, the points are relative to the baseline
+/// origin of the glyph with the y-axis pointing down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutlineSegment {
+  MoveTo(Point),
+  LineTo(Point),
+  QuadTo { ctrl: Point, to: Point },
+  CubicTo { ctrl1: Point, ctrl2: Point, to: Point },
+  Close,
+}
+
 pub trait GlyphRasterSource {
   fn face_metrics(&self, face_id: FontFaceId) -> Option<FontFaceMetrics>;
 
@@ -30,6 +41,15 @@ pub trait GlyphRasterSource {
   ) -> Option<RasterBitmap>;
 
   fn raster_svg(&self, face_id: FontFaceId, glyph_id: GlyphId) -> Option<String>;
+
+  /// The vector outline of the glyph scaled to `font_size`, used by the
+  /// consumers that keep text as vectors, e.g. exporting to SVG or PDF. Returns
+  /// `None` if the glyph has no outline, such as a bitmap emoji.
+  fn glyph_outline(
+    &self, _face_id: FontFaceId, _glyph_id: GlyphId, _font_size: f32,
+  ) -> Option<Vec<OutlineSegment>> {
+    None
+  }
 }
 
 pub type GlyphRasterSourceRef = Arc<Box<dyn GlyphRasterSource + Send + Sync>>;
@@ -1,10 +1,11 @@
 use std::{cell::RefCell, path::Path, rc::Rc};
 
+use ribir_algo::CowArc;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 use crate::{
   AttributedText, FontSystem, GlyphRasterSourceRef,
-  font::{FontFaceId, FontFaceMetrics, FontLoadError},
+  font::{FontFace, FontFaceId, FontFaceMetrics, FontFamilyInfo, FontLoadError},
   paragraph::Paragraph,
   parley_backend::ParleyFontSystem,
   style::Color,
@@ -18,12 +19,30 @@ where
 }
 
 pub trait TextServices<Brush> {
-  fn register_font_bytes(&self, data: Vec<u8>) -> Result<(), FontLoadError>;
+  fn register_font_bytes(&self, data: Vec<u8>) -> Result<(), FontLoadError> {
+    self.load_font_bytes(data).map(|_| ())
+  }
+
+  fn register_font_file(&self, path: &Path) -> Result<(), FontLoadError> {
+    self.load_font_file(path).map(|_| ())
+  }
+
+  /// Registers the fonts in `data` and returns the names of their families.
+  fn load_font_bytes(&self, data: Vec<u8>) -> Result<Box<[CowArc<str>]>, FontLoadError>;
 
-  fn register_font_file(&self, path: &Path) -> Result<(), FontLoadError>;
+  /// Registers the fonts of the file at `path` and returns the names of their
+  /// families.
+  fn load_font_file(&self, path: &Path) -> Result<Box<[CowArc<str>]>, FontLoadError>;
 
   fn face_metrics(&self, face: FontFaceId) -> Option<FontFaceMetrics>;
 
+  /// Lists the registered and system font families, sorted by name.
+  fn font_families(&self) -> Vec<FontFamilyInfo>;
+
+  /// Returns `true` if the font resolved for `face` has a glyph for every
+  /// character of `text`.
+  fn covers(&self, face: &FontFace, text: &str) -> bool;
+
   fn paragraph(&self, source: AttributedText<Brush>) -> Rc<dyn Paragraph<Brush>>;
 
   fn raster_source(&self) -> GlyphRasterSourceRef;
@@ -131,24 +150,27 @@ impl<Brush> TextServices<Brush> for ParleyTextServices
 where
   Brush: Clone + From<Color> + PartialEq + 'static,
 {
-  fn register_font_bytes(&self, data: Vec<u8>) -> Result<(), FontLoadError> {
+  fn load_font_bytes(&self, data: Vec<u8>) -> Result<Box<[CowArc<str>]>, FontLoadError> {
     self
       .font_system
       .borrow_mut()
-      .register_font_bytes(data)
+      .load_font_bytes(data)
   }
 
-  fn register_font_file(&self, path: &Path) -> Result<(), FontLoadError> {
-    self
-      .font_system
-      .borrow_mut()
-      .register_font_file(path)
+  fn load_font_file(&self, path: &Path) -> Result<Box<[CowArc<str>]>, FontLoadError> {
+    self.font_system.borrow_mut().load_font_file(path)
   }
 
   fn face_metrics(&self, face: FontFaceId) -> Option<FontFaceMetrics> {
     self.font_system.borrow().face_metrics(face)
   }
 
+  fn font_families(&self) -> Vec<FontFamilyInfo> { self.font_system.borrow_mut().font_families() }
+
+  fn covers(&self, face: &FontFace, text: &str) -> bool {
+    self.font_system.borrow_mut().covers(face, text)
+  }
+
   fn paragraph(&self, source: AttributedText<Brush>) -> Rc<dyn Paragraph<Brush>> {
     self.font_system.borrow().paragraph(source)
   }
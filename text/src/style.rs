@@ -5,6 +5,7 @@ use crate::{FontFace, FontRequest};
 
 bitflags::bitflags! {
   #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+  #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
   pub struct TextDecoration: u8 {
     const NONE = 0;
     /// Draw a line underneath each line of text.
@@ -17,6 +18,8 @@ bitflags::bitflags! {
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TextDecorationStyle {
   pub decoration: TextDecoration,
   pub decoration_color: Option<Color>,
@@ -58,7 +61,28 @@ where
   }
 }
 
+/// A background painted behind a span. It follows the selection geometry of
+/// the span, so a span broken across lines gets one rectangle per line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextBackground<Brush> {
+  pub brush: Brush,
+  /// The corner radius of each background rectangle.
+  pub radius: f32,
+}
+
+impl<Brush> TextBackground<Brush> {
+  #[inline]
+  pub fn new(brush: impl Into<Brush>) -> Self { Self { brush: brush.into(), radius: 0. } }
+
+  #[inline]
+  pub fn with_radius(mut self, radius: f32) -> Self {
+    self.radius = radius;
+    self
+  }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineHeight {
   Scale(f32),
   Px(f32),
@@ -91,36 +115,222 @@ pub enum TextWrap {
   Wrap,
 }
 
+/// The base direction of a paragraph, which decides how bidirectional runs
+/// are ordered and which side `TextAlign::Start` refers to.
+///
+/// It also serves as a provider value, so layout widgets such as `Row` and
+/// `Flex` can mirror their children for right-to-left locales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextDirection {
+  /// Resolve the direction from the first strong character of the text.
+  #[default]
+  Auto,
+  /// Left to right.
+  Ltr,
+  /// Right to left.
+  Rtl,
+}
+
+impl TextDirection {
+  #[inline]
+  pub fn is_rtl(self) -> bool { matches!(self, TextDirection::Rtl) }
+}
+
+/// How the lines of a paragraph are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WritingMode {
+  /// Lines run horizontally and stack from top to bottom.
+  #[default]
+  HorizontalTb,
+  /// Lines run vertically and stack from right to left. The glyphs of the CJK
+  /// scripts stand upright, the others are set sideways, rotated 90°
+  /// clockwise.
+  VerticalRl,
+}
+
+impl WritingMode {
+  #[inline]
+  pub fn is_vertical(self) -> bool { matches!(self, WritingMode::VerticalRl) }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParagraphStyle {
   pub text_align: TextAlign,
   pub wrap: TextWrap,
+  pub direction: TextDirection,
+  pub writing_mode: WritingMode,
 }
 
 impl Default for ParagraphStyle {
-  fn default() -> Self { Self { text_align: TextAlign::Start, wrap: TextWrap::default() } }
+  fn default() -> Self {
+    Self {
+      text_align: TextAlign::Start,
+      wrap: TextWrap::default(),
+      direction: TextDirection::default(),
+      writing_mode: WritingMode::default(),
+    }
+  }
 }
 
-#[derive(Debug, Clone, PartialEq, Default)]
+/// A four-byte OpenType tag, such as `tnum` for a feature or `wght` for a
+/// variation axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FontTag(pub [u8; 4]);
+
+impl FontTag {
+  #[inline]
+  pub const fn new(tag: &[u8; 4]) -> Self { Self(*tag) }
+
+  /// The tag packed into a big-endian `u32`, as stored in font tables.
+  #[inline]
+  pub const fn to_u32(self) -> u32 { u32::from_be_bytes(self.0) }
+}
+
+impl From<&[u8; 4]> for FontTag {
+  #[inline]
+  fn from(tag: &[u8; 4]) -> Self { Self::new(tag) }
+}
+
+/// A tag is written as its four ASCII characters, e.g. `"tnum"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FontTag {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&String::from_utf8_lossy(&self.0))
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FontTag {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let tag = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+    let bytes: [u8; 4] = tag.as_bytes().try_into().map_err(|_| {
+      serde::de::Error::invalid_value(serde::de::Unexpected::Str(&tag), &"a four-byte tag")
+    })?;
+    Ok(Self(bytes))
+  }
+}
+
+/// An OpenType feature setting, e.g. `tnum` for tabular numbers, `liga` for
+/// standard ligatures or `smcp` for small capitals.
+///
+/// A value of `0` disables the feature, `1` enables it, and larger values
+/// select an alternate for features such as `salt` or `cvXX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontFeature {
+  pub tag: FontTag,
+  pub value: u16,
+}
+
+impl FontFeature {
+  #[inline]
+  pub const fn new(tag: &[u8; 4], value: u16) -> Self { Self { tag: FontTag::new(tag), value } }
+
+  /// Turn the feature on.
+  #[inline]
+  pub const fn on(tag: &[u8; 4]) -> Self { Self::new(tag, 1) }
+
+  /// Turn the feature off.
+  #[inline]
+  pub const fn off(tag: &[u8; 4]) -> Self { Self::new(tag, 0) }
+}
+
+/// A value for one axis of a variable font, e.g. `wght` = `650.`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FontVariation {
+  pub tag: FontTag,
+  pub value: f32,
+}
+
+impl FontVariation {
+  #[inline]
+  pub const fn new(tag: &[u8; 4], value: f32) -> Self { Self { tag: FontTag::new(tag), value } }
+}
+
+/// Moves the glyphs of a span up or down relative to the line baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BaselineShift {
+  #[default]
+  Baseline,
+  /// Raise the glyphs to the superscript position.
+  Super,
+  /// Lower the glyphs to the subscript position.
+  Sub,
+  /// Raise the glyphs by a logical pixel length, negative values lower them.
+  Px(f32),
+}
+
+impl BaselineShift {
+  /// The upward offset in logical pixels for glyphs of `font_size`.
+  #[inline]
+  pub fn resolve_for_font_size(self, font_size: f32) -> f32 {
+    match self {
+      BaselineShift::Baseline => 0.,
+      BaselineShift::Super => font_size * 0.33,
+      BaselineShift::Sub => -font_size * 0.2,
+      BaselineShift::Px(value) => value,
+    }
+  }
+}
+
+impl From<f32> for BaselineShift {
+  #[inline]
+  fn from(value: f32) -> Self { Self::Px(value) }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct SpanStyle<Brush> {
   pub font: Option<FontRequest>,
   pub font_size: Option<f32>,
   pub letter_spacing: Option<f32>,
+  pub word_spacing: Option<f32>,
   pub line_height: Option<LineHeight>,
+  pub font_features: Option<Box<[FontFeature]>>,
+  pub font_variations: Option<Box<[FontVariation]>>,
+  pub baseline_shift: Option<BaselineShift>,
   pub brush: Option<Brush>,
+  pub background: Option<TextBackground<Brush>>,
   pub decoration: Option<TextDecorationStyle>,
 }
 
+impl<Brush> Default for SpanStyle<Brush> {
+  fn default() -> Self {
+    Self {
+      font: None,
+      font_size: None,
+      letter_spacing: None,
+      word_spacing: None,
+      line_height: None,
+      font_features: None,
+      font_variations: None,
+      baseline_shift: None,
+      brush: None,
+      background: None,
+      decoration: None,
+    }
+  }
+}
+
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TextStyle {
   pub font_size: f32,
   pub font_face: FontFace,
   pub letter_space: f32,
+  pub word_space: f32,
   pub line_height: LineHeight,
   pub overflow: TextOverflow,
+  /// OpenType features applied to the whole text, spans may override them.
+  pub font_features: Box<[FontFeature]>,
+  /// Variable font axis values applied to the whole text, spans may override
+  /// them.
+  pub font_variations: Box<[FontVariation]>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextOverflow {
   #[default]
   Overflow,
@@ -179,6 +389,19 @@ impl TextStyle {
   #[inline]
   pub fn with_letter_space(self, letter_space: f32) -> Self { Self { letter_space, ..self } }
 
+  #[inline]
+  pub fn with_word_space(self, word_space: f32) -> Self { Self { word_space, ..self } }
+
+  #[inline]
+  pub fn with_font_features(self, font_features: impl Into<Box<[FontFeature]>>) -> Self {
+    Self { font_features: font_features.into(), ..self }
+  }
+
+  #[inline]
+  pub fn with_font_variations(self, font_variations: impl Into<Box<[FontVariation]>>) -> Self {
+    Self { font_variations: font_variations.into(), ..self }
+  }
+
   #[inline]
   pub fn with_line_height(self, line_height: impl Into<LineHeight>) -> Self {
     Self { line_height: line_height.into(), ..self }
@@ -197,6 +420,7 @@ pub fn single_style_paragraph_style(
       TextOverflow::Overflow | TextOverflow::Ellipsis => TextWrap::NoWrap,
       TextOverflow::AutoWrap => TextWrap::Wrap,
     },
+    ..Default::default()
   }
 }
 
@@ -207,9 +431,8 @@ pub fn single_style_span_style<Brush>(
     font: Some(crate::FontRequest { face: text_style.font_face.clone() }),
     font_size: Some(text_style.font_size),
     letter_spacing: Some(text_style.letter_space),
-    line_height: None,
-    brush: None,
     decoration,
+    ..Default::default()
   }
 }
 
@@ -226,8 +449,11 @@ impl Default for TextStyle {
       font_size: 14.,
       font_face: Default::default(),
       letter_space: 0.,
+      word_space: 0.,
       line_height: LineHeight::default(),
       overflow: <_>::default(),
+      font_features: Box::new([]),
+      font_variations: Box::new([]),
     }
   }
 }
@@ -256,6 +482,21 @@ mod tests {
     assert_eq!(paragraph.wrap, TextWrap::NoWrap);
   }
 
+  #[test]
+  fn font_tag_packs_big_endian() {
+    assert_eq!(FontTag::new(b"wght").to_u32(), 0x7767_6874);
+    assert_eq!(FontFeature::on(b"tnum").value, 1);
+    assert_eq!(FontFeature::off(b"liga").value, 0);
+  }
+
+  #[test]
+  fn baseline_shift_resolves_against_font_size() {
+    assert_eq!(BaselineShift::Baseline.resolve_for_font_size(20.), 0.);
+    assert!(BaselineShift::Super.resolve_for_font_size(20.) > 0.);
+    assert!(BaselineShift::Sub.resolve_for_font_size(20.) < 0.);
+    assert_eq!(BaselineShift::from(3.).resolve_for_font_size(20.), 3.);
+  }
+
   #[test]
   fn text_decoration_style_converts_from_decoration() {
     let style = TextDecorationStyle::from(TextDecoration::UNDERLINE);
@@ -81,7 +81,7 @@ fn button_init(classes: &mut Classes) {
   fn btn_interactive(w: Widget) -> Widget {
     let outline = Palette::of(BuildCtx::get()).outline();
     let mut w = FatObj::new(w);
-    w.with_border(Border::all(BorderSide { color: outline.into(), width: 1. }));
+    w.with_border(Border::all(BorderSide::new(1., outline.into())));
 
     let mut w = base_interactive(w.into_widget());
     w.with_foreground(BuildCtx::color())
@@ -1,5 +1,8 @@
 use ribir_core::prelude::*;
-use ribir_widgets::input::{INPUT, TEXT_CARET, TEXT_SELECTION, TEXTAREA};
+use ribir_widgets::{
+  form::{FORM_FIELD_ERROR_TEXT, FORM_FIELD_SUPPORTING_TEXT, FieldStatus},
+  input::{INPUT, INPUT_PASSWORD_TOGGLE, TEXT_CARET, TEXT_SELECTION, TEXTAREA},
+};
 
 use crate::md;
 
@@ -34,16 +37,52 @@ pub(super) fn init(classes: &mut Classes) {
   fn input_border(w: Widget) -> Widget {
     let mut w = FatObj::new(w);
     let blur = Palette::of(BuildCtx::get()).on_surface_variant();
+    let error = Palette::of(BuildCtx::get()).error();
 
     let focus_watcher = w.is_focused();
-    let border = BuildCtx::color().combine_with(focus_watcher, move |(c, focus)| {
-      let color = if *focus { *c } else { blur };
-      Border::all(BorderSide::new(1., color.into()))
-    });
+    // The status of the form field the input is in, if any.
+    let field = Variant::<FieldStatus>::new_or_default(BuildCtx::get());
+    let border = BuildCtx::color()
+      .combine(focus_watcher)
+      .combine_with(field, move |((c, focus), field)| {
+        let color = if field.shown_error().is_some() {
+          error
+        } else if *focus {
+          *c
+        } else {
+          blur
+        };
+        Border::all(BorderSide::new(1., color.into()))
+      });
 
     w.with_border(border).with_radius(md::RADIUS_2);
     w.into_widget()
   }
   classes.insert(INPUT, input_border);
   classes.insert(TEXTAREA, input_border);
+
+  classes.insert(
+    INPUT_PASSWORD_TOGGLE,
+    style_class! {
+      margin: md::EDGES_LEFT_4,
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+    },
+  );
+
+  classes.insert(
+    FORM_FIELD_SUPPORTING_TEXT,
+    style_class! {
+      margin: md::EDGES_TOP_4,
+      foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_small.text.clone(),
+    },
+  );
+  classes.insert(
+    FORM_FIELD_ERROR_TEXT,
+    style_class! {
+      margin: md::EDGES_TOP_4,
+      foreground: Palette::of(BuildCtx::get()).error(),
+      text_style: TypographyTheme::of(BuildCtx::get()).body_small.text.clone(),
+    },
+  );
 }
@@ -8,10 +8,7 @@ pub(super) fn init(classes: &mut Classes) {
     SWITCH_UNCHECKED,
     style_class! {
       background: Palette::of(BuildCtx::get()).surface_variant(),
-      border: Border::all(BorderSide {
-        color: Palette::of(BuildCtx::get()).outline().into(),
-        width: 2.,
-      }),
+      border: Border::all(BorderSide::new(2., Palette::of(BuildCtx::get()).outline().into())),
     },
   );
 
@@ -19,10 +16,7 @@ pub(super) fn init(classes: &mut Classes) {
     SWITCH_CHECKED,
     style_class! {
       background: Palette::of(BuildCtx::get()).primary(),
-      border: Border::all(BorderSide {
-        color: Color::TRANSPARENT.into(),
-        width: 2.,
-      }),
+      border: Border::all(BorderSide::new(2., Color::TRANSPARENT.into())),
     },
   );
 
@@ -60,6 +60,8 @@ fn new(palette: Palette) -> Theme {
   register_svg!("sms", "../icons/sms_FILL0_wght400_GRAD0_opsz48.svg");
   register_svg!("account_circle", "../icons/account_circle_FILL0_wght400_GRAD0_opsz48.svg");
   register_svg!("info", "../icons/info_FILL0_wght400_GRAD0_opsz48.svg");
+  register_svg!("visibility", "../icons/visibility_FILL0_wght400_GRAD0_opsz48.svg");
+  register_svg!("visibility_off", "../icons/visibility_off_FILL0_wght400_GRAD0_opsz48.svg");
 
   let classes = classes::initd_classes();
   Theme {
@@ -74,6 +76,15 @@ fn new(palette: Palette) -> Theme {
   }
 }
 
+/// Creates a purple light material theme with the theme file at `path`
+/// applied. See [`ThemeFile`] for the file format.
+#[cfg(feature = "theme-file")]
+pub fn from_file(path: impl Into<std::path::PathBuf>) -> Result<Theme, ThemeFileError> {
+  let mut theme = purple::light();
+  theme.load_file(path)?;
+  Ok(theme)
+}
+
 pub mod purple {
   use super::*;
 
@@ -131,6 +142,7 @@ pub fn typography_theme() -> TypographyTheme {
         letter_space,
         font_face,
         overflow: TextOverflow::Overflow,
+        ..<_>::default()
       },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
@@ -60,6 +60,7 @@ pub mod motion {
   /// Expressive is the default scheme and should be used for most products.
   /// Standard is a more restrained alternative for utilitarian surfaces.
   #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+  #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
   pub enum Scheme {
     #[default]
     Expressive,
@@ -68,6 +69,7 @@ pub mod motion {
 
   /// Spring speed buckets used by Material motion tokens.
   #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+  #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
   pub enum Speed {
     Fast,
     Default,
@@ -191,18 +193,21 @@ pub mod motion {
     }
 
     #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SpringPairToken {
       pub damping: f32,
       pub stiffness: f32,
     }
 
     #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SpringToken {
       pub effects: SpringPairToken,
       pub spatial: SpringPairToken,
     }
 
     #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SpringSchemeToken {
       pub fast: SpringToken,
       pub default: SpringToken,
@@ -210,6 +215,7 @@ pub mod motion {
     }
 
     #[derive(Clone, Copy, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SpringTransition {
       pub duration: Duration,
       pub damping: f32,
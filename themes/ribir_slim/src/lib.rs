@@ -53,6 +53,7 @@ fn typography_theme() -> TypographyTheme {
         letter_space,
         font_face,
         overflow: TextOverflow::Overflow,
+        ..<_>::default()
       },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
@@ -11,7 +11,8 @@ pub struct Color {
 
 /// Describe the light tone of a color, should between [0, 1.0], 0.0 gives
 /// absolute black and 1.0 give the brightest white.
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct LightnessTone(f32);
 
 impl LightnessTone {
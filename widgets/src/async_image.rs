@@ -0,0 +1,586 @@
+//! Load images asynchronously with the placeholder, error and fade-in states.
+//!
+//! [`AsyncImage`] reads the encoded bytes from a file, a URL or a future, and
+//! decodes them on a small pool of worker threads, so the UI thread is never
+//! blocked. The
+//! URLs are loaded by the [`ImageFetcher`] provided in the context, this crate
+//! does not bind any HTTP client.
+//!
+//! The loaded images are kept in a shared memory-bounded cache, see
+//! [`ImageCache`].
+//!
+//! # Example
+//!
+//! ```rust ignore
+//! use ribir::prelude::*;
+//!
+//! struct HttpFetcher;
+//!
+//! impl ImageFetcher for HttpFetcher {
+//!   fn fetch(&self, url: &str) -> LocalBoxFuture<'static, Result<Vec<u8>, ImageLoadError>> {
+//!     todo!("load the bytes of the url by your http client")
+//!   }
+//! }
+//!
+//! fn_widget! {
+//!   @Providers {
+//!     providers: [Provider::new(Box::new(HttpFetcher) as Box<dyn ImageFetcher>)],
+//!     @AsyncImage {
+//!       source: ImageSource::url("https://ribir.org/logo.webp"),
+//!       placeholder: GenWidget::new(|| text! { text: "Loading..." }),
+//!       error: GenWidget::new(|| text! { text: "Failed" }),
+//!     }
+//!   }
+//! };
+//! ```
+use std::{cell::RefCell, path::PathBuf, rc::Rc, sync::Arc};
+
+pub use futures::future::LocalBoxFuture;
+use futures::{channel::oneshot, future::abortable};
+use ribir_core::prelude::*;
+
+/// A widget that loads an image asynchronously.
+///
+/// It shows the `placeholder` while loading, the `error` widget if it failed,
+/// and fades in the image once it is ready. The loading is canceled when the
+/// widget is disposed.
+///
+/// The images loaded from a path or a URL are cached, so the same source is
+/// shown immediately the next time.
+#[declare(stateless)]
+pub struct AsyncImage {
+  /// Where to load the image from.
+  source: ImageSource,
+  /// The widget shown while the image is loading.
+  #[declare(default)]
+  placeholder: Option<GenWidget>,
+  /// The widget shown if the image failed to load.
+  #[declare(default)]
+  error: Option<GenWidget>,
+  /// The transition of the opacity when the image is ready.
+  #[declare(custom, default = default_fade())]
+  fade: Rc<Box<dyn Transition>>,
+}
+
+/// The source of an [`AsyncImage`].
+pub enum ImageSource {
+  /// A file on the local file system.
+  Path(PathBuf),
+  /// A URL loaded by the [`ImageFetcher`] provided in the context.
+  Url(String),
+  /// The encoded bytes resolved by a future, it is not cached.
+  Bytes(LocalBoxFuture<'static, Result<Vec<u8>, ImageLoadError>>),
+}
+
+/// Loads the encoded bytes of an image URL for [`AsyncImage`].
+///
+/// Provide it as a `Box<dyn ImageFetcher>`, so an application can plug its own
+/// HTTP client in, and the tests can use an in-memory stand-in.
+pub trait ImageFetcher {
+  fn fetch(&self, url: &str) -> LocalBoxFuture<'static, Result<Vec<u8>, ImageLoadError>>;
+}
+
+/// The error of loading an [`AsyncImage`].
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ImageLoadError {
+  #[error("Failed to load the image: {0}")]
+  Load(String),
+  #[error("No `ImageFetcher` is provided to load: {0}")]
+  NoFetcher(String),
+  #[error("Failed to decode the image: {0:?}")]
+  Decode(Arc<ImageError>),
+}
+
+/// The shared cache of the images loaded by [`AsyncImage`] from a path or a
+/// URL.
+///
+/// The cache is bounded by the memory of the decoded pixels, the least
+/// recently used images are evicted first. The default capacity is 64MB.
+pub struct ImageCache {
+  images: FrameCache<String, Image>,
+  used: usize,
+  capacity: usize,
+}
+
+enum LoadState {
+  Loading,
+  Loaded(Image),
+  Failed,
+}
+
+const DEFAULT_CACHE_CAPACITY: usize = 64 * 1024 * 1024;
+
+thread_local! {
+  static IMAGE_CACHE: RefCell<ImageCache> =
+    RefCell::new(ImageCache::new(DEFAULT_CACHE_CAPACITY));
+}
+
+impl AsyncImageDeclarer {
+  /// Set the transition used to fade in the image.
+  pub fn with_fade(&mut self, t: impl Transition + 'static) -> &mut Self {
+    self.fade = Some(Rc::new(Box::new(t)));
+    self
+  }
+}
+
+impl Compose for AsyncImage {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let f = move || {
+      let Ok(AsyncImage { source, placeholder, error, fade }) = this.try_into_value() else {
+        panic!("AsyncImage should be a stateless widget");
+      };
+
+      let key = source.cache_key();
+      if let Some(image) = key.as_ref().and_then(|k| ImageCache::get(k)) {
+        return image.into_widget();
+      }
+
+      let (task, handle) = abortable(load_image(source));
+      let state = Stateful::new(LoadState::Loading);
+      let writer = state.clone_writer();
+      AppCtx::spawn_local(async move {
+        let Ok(res) = task.await else { return };
+        *writer.write() = match res {
+          Ok(image) => {
+            if let Some(key) = key {
+              ImageCache::put(key, image.clone());
+            }
+            LoadState::Loaded(image)
+          }
+          Err(_) => LoadState::Failed,
+        };
+      });
+
+      let mut w = FatObj::new(
+        pipe!(match &*$read(state) {
+          LoadState::Loading => gen_or_void(&placeholder),
+          LoadState::Failed => gen_or_void(&error),
+          LoadState::Loaded(image) => fade_in(image.clone(), &fade),
+        })
+        .into_widget(),
+      );
+      w.on_disposed(move |_| handle.abort());
+      w.into_widget()
+    };
+
+    FnWidget::new(f).into_widget()
+  }
+}
+
+impl ImageSource {
+  pub fn path(path: impl Into<PathBuf>) -> Self { Self::Path(path.into()) }
+
+  pub fn url(url: impl Into<String>) -> Self { Self::Url(url.into()) }
+
+  pub fn bytes(bytes: impl Future<Output = Result<Vec<u8>, ImageLoadError>> + 'static) -> Self {
+    Self::Bytes(Box::pin(bytes))
+  }
+
+  fn cache_key(&self) -> Option<String> {
+    match self {
+      ImageSource::Path(path) => Some(format!("file:{}", path.display())),
+      ImageSource::Url(url) => Some(url.clone()),
+      ImageSource::Bytes(_) => None,
+    }
+  }
+}
+
+impl From<PathBuf> for ImageSource {
+  fn from(path: PathBuf) -> Self { Self::Path(path) }
+}
+
+impl ImageCache {
+  /// Set the capacity of the cache in bytes, the images are evicted until
+  /// the cache fits it.
+  pub fn set_capacity(bytes: usize) {
+    IMAGE_CACHE.with_borrow_mut(|cache| {
+      cache.capacity = bytes;
+      cache.shrink();
+    });
+  }
+
+  /// The capacity of the cache in bytes.
+  pub fn capacity() -> usize { IMAGE_CACHE.with_borrow(|cache| cache.capacity) }
+
+  /// The memory in bytes used by the cached images.
+  pub fn used() -> usize { IMAGE_CACHE.with_borrow(|cache| cache.used) }
+
+  /// Remove all the cached images.
+  pub fn clear() {
+    IMAGE_CACHE.with_borrow_mut(|cache| {
+      cache.images.clear();
+      cache.used = 0;
+    });
+  }
+
+  fn get(key: &str) -> Option<Image> {
+    IMAGE_CACHE.with_borrow_mut(|cache| cache.images.get(key).cloned())
+  }
+
+  fn put(key: String, image: Image) {
+    IMAGE_CACHE.with_borrow_mut(|cache| cache.insert(key, image))
+  }
+
+  fn new(capacity: usize) -> Self { Self { images: FrameCache::new(), used: 0, capacity } }
+
+  fn insert(&mut self, key: String, image: Image) {
+    let bytes = image_bytes(&image);
+    if bytes > self.capacity {
+      return;
+    }
+    if let Some(old) = self.images.put(key, image) {
+      self.used -= image_bytes(&old);
+    }
+    self.used += bytes;
+    self.shrink();
+  }
+
+  fn shrink(&mut self) {
+    while self.used > self.capacity {
+      let Some((_, image)) = self.images.pop_lru() else { break };
+      self.used -= image_bytes(&image);
+    }
+  }
+}
+
+/// The memory of the decoded pixels of the image.
+fn image_bytes(image: &Image) -> usize {
+  image.width() as usize * image.height() as usize * 4 * image.frame_count().max(1) as usize
+}
+
+fn default_fade() -> Rc<Box<dyn Transition>> {
+  Rc::new(Box::new(EasingTransition {
+    easing: easing::LinearEasing,
+    duration: Duration::from_millis(200),
+  }))
+}
+
+fn gen_or_void(w: &Option<GenWidget>) -> Widget<'static> {
+  w.as_ref()
+    .map_or_else(|| Void::default().into_widget(), GenWidget::gen_widget)
+}
+
+fn fade_in(image: Image, fade: &Rc<Box<dyn Transition>>) -> Widget<'static> {
+  let mut w = FatObj::new(image.into_widget());
+  w.with_opacity(0.);
+  let opacity = w.opacity();
+  opacity
+    .clone_writer()
+    .transition(fade.dyn_clone());
+  w.on_mounted(move |_| *opacity.write() = 1.);
+  w.into_widget()
+}
+
+fn load_image(source: ImageSource) -> LocalBoxFuture<'static, Result<Image, ImageLoadError>> {
+  let bytes = match source {
+    ImageSource::Path(path) => {
+      // Read the file on the worker thread too.
+      return Box::pin(off_ui_thread(move || {
+        let data = std::fs::read(&path)
+          .map_err(|e| ImageLoadError::Load(format!("{}: {e}", path.display())))?;
+        decode(data)
+      }));
+    }
+    ImageSource::Url(url) => {
+      let fetcher = Provider::of::<Box<dyn ImageFetcher>>(BuildCtx::get());
+      match fetcher {
+        Some(fetcher) => fetcher.fetch(&url),
+        None => return Box::pin(async move { Err(ImageLoadError::NoFetcher(url)) }),
+      }
+    }
+    ImageSource::Bytes(bytes) => bytes,
+  };
+
+  Box::pin(async move {
+    let data = bytes.await?;
+    off_ui_thread(move || decode(data)).await
+  })
+}
+
+/// Decodes the image and its first frame, it runs on the worker thread.
+fn decode(data: Vec<u8>) -> Result<Image, ImageLoadError> {
+  let image = Image::new(data).map_err(|e| ImageLoadError::Decode(Arc::new(e)))?;
+  if image.frame(0).is_none() {
+    let err = ImageError::Decode("The first frame of the image is broken".into());
+    return Err(ImageLoadError::Decode(Arc::new(err)));
+  }
+  Ok(image)
+}
+
+/// Runs the job on the decoding pool, the job is skipped if the returned
+/// future is dropped before the job starts.
+#[cfg(not(target_arch = "wasm32"))]
+async fn off_ui_thread(
+  job: impl FnOnce() -> Result<Image, ImageLoadError> + Send + 'static,
+) -> Result<Image, ImageLoadError> {
+  let (tx, rx) = oneshot::channel();
+  DECODE_POOL.execute(Box::new(move || {
+    if !tx.is_canceled() {
+      let _ = tx.send(job());
+    }
+  }));
+  rx.await
+    .unwrap_or_else(|_| Err(ImageLoadError::Load("The decoding thread is panicked".into())))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+static DECODE_POOL: WorkerPool = WorkerPool::new();
+
+/// A pool of at most [`max_workers`] threads, the threads are started when
+/// the jobs are more than the idle threads, and the extra jobs are queued.
+#[cfg(not(target_arch = "wasm32"))]
+struct WorkerPool {
+  state: std::sync::Mutex<PoolState>,
+  job_ready: std::sync::Condvar,
+  #[cfg(test)]
+  idle: std::sync::Condvar,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct PoolState {
+  jobs: std::collections::VecDeque<Box<dyn FnOnce() + Send>>,
+  workers: usize,
+  busy: usize,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl WorkerPool {
+  const fn new() -> Self {
+    Self {
+      state: std::sync::Mutex::new(PoolState {
+        jobs: std::collections::VecDeque::new(),
+        workers: 0,
+        busy: 0,
+      }),
+      job_ready: std::sync::Condvar::new(),
+      #[cfg(test)]
+      idle: std::sync::Condvar::new(),
+    }
+  }
+
+  fn execute(&'static self, job: Box<dyn FnOnce() + Send>) {
+    let mut state = self.state.lock().unwrap();
+    state.jobs.push_back(job);
+    if state.jobs.len() > state.workers - state.busy && state.workers < max_workers() {
+      let spawned = std::thread::Builder::new()
+        .name("ribir-image-decoder".into())
+        .spawn(move || self.work());
+      match spawned {
+        Ok(_) => state.workers += 1,
+        Err(err) => tracing::warn!("Failed to start the image decoding thread: {err}"),
+      }
+    }
+    self.job_ready.notify_one();
+  }
+
+  fn work(&self) {
+    let mut state = self.state.lock().unwrap();
+    loop {
+      let Some(job) = state.jobs.pop_front() else {
+        state = self.job_ready.wait(state).unwrap();
+        continue;
+      };
+      state.busy += 1;
+      drop(state);
+      // A panicked job drops its sender, the waiting future reports the error.
+      let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+      state = self.state.lock().unwrap();
+      state.busy -= 1;
+      #[cfg(test)]
+      if state.busy == 0 && state.jobs.is_empty() {
+        self.idle.notify_all();
+      }
+    }
+  }
+
+  /// Blocks until all the queued jobs are finished.
+  #[cfg(test)]
+  fn wait_idle(&self) {
+    let mut state = self.state.lock().unwrap();
+    while state.busy > 0 || !state.jobs.is_empty() {
+      state = self.idle.wait(state).unwrap();
+    }
+  }
+}
+
+/// The number of the decoding threads, decoding is CPU bound so more threads
+/// than the cores do not help.
+#[cfg(not(target_arch = "wasm32"))]
+fn max_workers() -> usize { std::thread::available_parallelism().map_or(2, |n| n.get().min(4)) }
+
+/// There are no threads in the wasm, the job runs in place.
+#[cfg(target_arch = "wasm32")]
+async fn off_ui_thread(
+  job: impl FnOnce() -> Result<Image, ImageLoadError> + 'static,
+) -> Result<Image, ImageLoadError> {
+  job()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::Cell, collections::HashMap};
+
+  use futures::future::pending;
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  const LEAVES: &[u8] = include_bytes!("../../gpu/imgs/leaves.webp");
+  const PLACEHOLDER_SIZE: Size = Size::new(10., 10.);
+  const ERROR_SIZE: Size = Size::new(20., 20.);
+
+  struct MemoryFetcher(HashMap<&'static str, &'static [u8]>);
+
+  impl ImageFetcher for MemoryFetcher {
+    fn fetch(&self, url: &str) -> LocalBoxFuture<'static, Result<Vec<u8>, ImageLoadError>> {
+      let res = self
+        .0
+        .get(url)
+        .map(|data| data.to_vec())
+        .ok_or_else(|| ImageLoadError::Load(format!("404: {url}")));
+      Box::pin(async move { res })
+    }
+  }
+
+  fn fetcher_provider() -> Provider {
+    let fetcher = MemoryFetcher(HashMap::from([
+      ("mem://leaves.webp", LEAVES),
+      ("mem://broken.webp", b"RIFF1234WEBPbroken".as_slice()),
+    ]));
+    Provider::new(Box::new(fetcher) as Box<dyn ImageFetcher>)
+  }
+
+  fn async_image(url: &'static str) -> GenWidget {
+    GenWidget::new(move || {
+      fn_widget! {
+        @Providers {
+          providers: [fetcher_provider()],
+          @AsyncImage {
+            source: ImageSource::url(url),
+            placeholder: GenWidget::new(|| mock_box! { size: PLACEHOLDER_SIZE }),
+            error: GenWidget::new(|| mock_box! { size: ERROR_SIZE }),
+          }
+        }
+      }
+    })
+  }
+
+  /// Drives the loading task until it submits the decoding job, waits for the
+  /// decoding pool and then lets the task apply the result.
+  fn wait_for_loaded(wnd: &TestWindow) {
+    AppCtx::run_until_stalled();
+    DECODE_POOL.wait_idle();
+    AppCtx::run_until_stalled();
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn placeholder_then_image() {
+    reset_test_env!();
+    ImageCache::clear();
+
+    let leaves = Image::new(LEAVES).unwrap();
+    let image_size = Size::new(leaves.width() as f32, leaves.height() as f32);
+
+    let wnd = TestWindow::from_widget(async_image("mem://leaves.webp"));
+    wnd.draw_frame();
+    wnd.assert_root_size(PLACEHOLDER_SIZE);
+    wait_for_loaded(&wnd);
+    wnd.assert_root_size(image_size);
+    assert_eq!(ImageCache::used(), image_bytes(&leaves));
+
+    // The cached image is shown without loading.
+    let wnd = TestWindow::from_widget(async_image("mem://leaves.webp"));
+    wnd.draw_frame();
+    wnd.assert_root_size(image_size);
+  }
+
+  #[test]
+  fn error_widget() {
+    reset_test_env!();
+
+    let wnd = TestWindow::from_widget(async_image("mem://broken.webp"));
+    wnd.draw_frame();
+    wnd.assert_root_size(PLACEHOLDER_SIZE);
+    wait_for_loaded(&wnd);
+    wnd.assert_root_size(ERROR_SIZE);
+
+    let wnd = TestWindow::from_widget(async_image("mem://not-found.webp"));
+    wnd.draw_frame();
+    wait_for_loaded(&wnd);
+    wnd.assert_root_size(ERROR_SIZE);
+  }
+
+  #[test]
+  fn pool_bounds_threads() {
+    let done = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    for _ in 0..32 {
+      let done = done.clone();
+      DECODE_POOL.execute(Box::new(move || {
+        std::thread::sleep(Duration::from_millis(1));
+        done.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+      }));
+    }
+    DECODE_POOL.wait_idle();
+
+    assert_eq!(done.load(std::sync::atomic::Ordering::Relaxed), 32);
+    assert!(DECODE_POOL.state.lock().unwrap().workers <= max_workers());
+  }
+
+  #[test]
+  fn cancel_on_disposed() {
+    reset_test_env!();
+
+    struct DropFlag(Rc<Cell<bool>>);
+    impl Drop for DropFlag {
+      fn drop(&mut self) { self.0.set(true); }
+    }
+
+    let dropped = Rc::new(Cell::new(false));
+    let c_dropped = dropped.clone();
+    let show = Stateful::new(true);
+    let c_show = show.clone_writer();
+    let wnd = TestWindow::from_widget(fn_widget! {
+      let dropped = c_dropped.clone();
+      @pipe! {
+        (*$read(c_show)).then(|| {
+          let flag = DropFlag(dropped.clone());
+          @AsyncImage {
+            source: ImageSource::bytes(async move {
+              let _flag = flag;
+              pending().await
+            }),
+          }
+        })
+      }
+    });
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    assert!(!dropped.get());
+
+    *show.write() = false;
+    wnd.draw_frame();
+    AppCtx::run_until_stalled();
+    assert!(dropped.get());
+  }
+
+  #[test]
+  fn cache_evict_lru() {
+    let img = |size| Image::from_parts(vec![], size, size, LoopCount::Finite(1), vec![]);
+    let mut cache = ImageCache::new(1000);
+
+    cache.insert("a".into(), img(10));
+    cache.insert("b".into(), img(10));
+    assert_eq!(cache.used, 800);
+
+    // Too large to be cached.
+    cache.insert("c".into(), img(20));
+    assert_eq!(cache.images.len(), 2);
+
+    cache.images.get("a");
+    cache.insert("d".into(), img(10));
+    assert!(cache.images.peek("b").is_none());
+    assert!(cache.images.peek("a").is_some());
+    assert_eq!(cache.used, 800);
+  }
+}
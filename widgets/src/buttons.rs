@@ -365,7 +365,7 @@ impl<'c> ButtonChild<'c> {
   ) -> Widget<'c> {
     let Self { label, icon } = self;
 
-    match (label, icon) {
+    let widget = match (label, icon) {
       // Case 1: Neither - render empty
       (None, None) => void!(class: btn).into_widget(),
 
@@ -412,7 +412,11 @@ impl<'c> ButtonChild<'c> {
         }
         .into_widget()
       }
-    }
+    };
+
+    let mut widget = FatObj::new(widget);
+    widget.with_a11y(A11y::new(Role::Button).with_action(Action::Click));
+    widget.into_widget()
   }
 }
 
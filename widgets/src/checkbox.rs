@@ -91,6 +91,17 @@ impl Checkbox {
       .bubble_custom_event(e.target(), new_state);
   }
 
+  fn a11y(&self) -> A11y {
+    let toggled = match (self.indeterminate, self.checked) {
+      (true, _) => Toggled::Mixed,
+      (_, true) => Toggled::True,
+      _ => Toggled::False,
+    };
+    A11y::new(Role::CheckBox)
+      .with_toggled(toggled)
+      .with_action(Action::Click)
+  }
+
   /// Manually toggle the checkbox state.
   /// This is an imperative API (Path C) and should not be used for default UI
   /// interactions.
@@ -118,6 +129,7 @@ impl ComposeChild<'static> for Checkbox {
       };
       let icon_with_label = icon_with_label(icon.into_widget(), child);
       @FatObj {
+        a11y: pipe!($read(this).a11y()),
         on_action: move |e| $read(this).request_toggle(e),
         @{ icon_with_label }
       }
@@ -128,7 +140,7 @@ impl ComposeChild<'static> for Checkbox {
 
 #[cfg(test)]
 mod tests {
-  use ribir_core::test_helper::*;
+  use ribir_core::{reset_test_env, test_helper::*};
   use ribir_dev_helper::*;
 
   use super::*;
@@ -142,4 +154,28 @@ mod tests {
     })
     .with_wnd_size(Size::new(240., 160.)),
   );
+
+  #[test]
+  fn checkbox_a11y() {
+    reset_test_env!();
+
+    let checked = Stateful::new(false);
+    let c_checked = checked.clone_writer();
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @Checkbox { checked: TwoWay::new(c_checked.clone_writer()), @ { "check me" } }
+    });
+    wnd.enable_a11y();
+    wnd.draw_frame();
+
+    let check = wnd
+      .a11y_tree()
+      .find_by_role(Role::CheckBox)
+      .unwrap();
+    assert_eq!(wnd.a11y_tree().node(check).unwrap().toggled(), Some(Toggled::False));
+
+    wnd.a11y_action(check, Action::Click);
+    wnd.draw_frame();
+    assert!(*checked.read());
+    assert_eq!(wnd.a11y_tree().node(check).unwrap().toggled(), Some(Toggled::True));
+  }
 }
@@ -13,6 +13,10 @@ pub struct Trailing<T>(T);
 ///
 /// It is useful for assisting your widget in
 /// gathering a child that is wrapped by `Leading`, `Trailing`, or neither.
+///
+/// Leading and trailing are the start and end sides of the line. The widgets
+/// place them in a `Row`, which follows the provided `TextDirection`, so a
+/// leading child is placed on the right in a right-to-left context.
 #[derive(Template)]
 pub enum PositionChild<T> {
   Default(T),
@@ -66,6 +70,8 @@ pub fn icon_with_label(icon: Widget, label: Option<PositionChild<TextValue>>) ->
 
 #[cfg(test)]
 mod tests {
+  use ribir_core::test_helper::*;
+
   use super::*;
 
   #[test]
@@ -82,4 +88,26 @@ mod tests {
     }
     .r_into();
   }
+
+  #[test]
+  fn leading_follows_text_direction() {
+    reset_test_env!();
+
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @Providers {
+        providers: [Provider::new(TextDirection::Rtl)],
+        @ {
+          icon_with_label(
+            @Container { size: Size::new(20., 20.) }.into_widget(),
+            Some(PositionChild::Leading(Leading::new("label"))),
+          )
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let label = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    let icon = wnd.layout_info_by_path(&[0, 1]).unwrap();
+    assert!(label.pos.x > icon.pos.x);
+  }
 }
@@ -0,0 +1,137 @@
+use ribir_core::prelude::*;
+
+/// A widget paints an image resized by the `fit` and placed by the `align` in
+/// its box, like the CSS `object-fit` and `object-position`.
+///
+/// The widget takes the size of the image unless it is constrained, the part
+/// of the image outside the box is cropped. Only the first frame of an animated
+/// image is painted.
+///
+/// # Example
+///
+/// ```rust ignore
+/// use ribir::prelude::*;
+///
+/// fn_widget! {
+///   @FitImage {
+///     size: Size::new(100., 100.),
+///     image: asset!("./cover.png", "image"),
+///     fit: ImageFit::Cover,
+///     align: ImageAlign::TOP_CENTER,
+///   }
+/// };
+/// ```
+#[declare]
+pub struct FitImage {
+  pub image: Image,
+  #[declare(default)]
+  pub fit: ImageFit,
+  #[declare(default)]
+  pub align: ImageAlign,
+}
+
+impl Render for FitImage {
+  fn size_affected_by_child(&self) -> bool { false }
+
+  fn measure(&self, clamp: BoxClamp, _: &mut MeasureCtx) -> Size {
+    clamp.clamp(Size::new(self.image.width() as f32, self.image.height() as f32))
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let size = ctx.box_size().unwrap();
+    let Some(frame) = self.image.frame(0) else { return };
+    ctx
+      .painter()
+      .draw_img_fit(frame.image, &Rect::from_size(size), self.fit, self.align);
+  }
+
+  #[cfg(feature = "debug")]
+  fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("fit_image") }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use ribir_dev_helper::*;
+
+  use super::*;
+
+  /// The bounds of the clips and the image fills of the painted frame.
+  fn paint_bounds(wnd: &mut TestWindow) -> (Vec<Rect>, Vec<Rect>) {
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().expect("expected a frame");
+    let (mut clips, mut fills) = (vec![], vec![]);
+    for cmd in frame.commands {
+      let PaintCommand::Path(path) = cmd else { continue };
+      match path.action {
+        PaintPathAction::Clip => clips.push(path.paint_bounds),
+        PaintPathAction::Paint { brush: CommandBrush::Image { .. }, .. } => {
+          fills.push(path.paint_bounds)
+        }
+        _ => {}
+      }
+    }
+    (clips, fills)
+  }
+
+  widget_layout_test!(
+    fit_image_size,
+    WidgetTester::new(fn_widget! {
+      @FitImage { image: white_image(100, 50), fit: ImageFit::Cover }
+    }),
+    LayoutCase::default().with_size(Size::new(100., 50.))
+  );
+
+  widget_layout_test!(
+    fit_image_constrained,
+    WidgetTester::new(fn_widget! {
+      @FitImage {
+        size: Size::new(40., 40.),
+        image: white_image(100, 50),
+        fit: ImageFit::Contain,
+        align: ImageAlign::BOTTOM_RIGHT,
+      }
+    }),
+    LayoutCase::default().with_size(Size::new(40., 40.))
+  );
+
+  #[test]
+  fn fit_image_cover_crop() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @FitImage {
+          size: Size::new(100., 100.),
+          image: white_image(100, 50),
+          fit: ImageFit::Cover,
+        }
+      },
+      Size::new(100., 100.),
+    );
+    // Scaled to cover the box, the overflowed left and right parts are cropped.
+    let (clips, fills) = paint_bounds(&mut wnd);
+    assert!(clips.contains(&Rect::from_size(Size::new(100., 100.))));
+    assert_eq!(fills, vec![Rect::new(Point::new(-50., 0.), Size::new(200., 100.))]);
+  }
+
+  #[test]
+  fn fit_image_contain_align() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @FitImage {
+          size: Size::new(50., 50.),
+          image: white_image(100, 50),
+          fit: ImageFit::Contain,
+          align: ImageAlign::BOTTOM_RIGHT,
+        }
+      },
+      Size::new(100., 100.),
+    );
+    // Scaled to fit the box and placed at its bottom.
+    let (_, fills) = paint_bounds(&mut wnd);
+    assert_eq!(fills, vec![Rect::new(Point::new(0., 25.), Size::new(50., 25.))]);
+  }
+}
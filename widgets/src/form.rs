@@ -0,0 +1,839 @@
+//! A form model that collects the values of the input widgets and validates
+//! them.
+//!
+//! The [`Form`] provides its state to the descendants, and every
+//! [`FormField`] registers the control it wraps to the form by a name. The
+//! field listens to the change events bubbled by the control, so it works with
+//! any control that emits them, such as [`Input`], [`TextArea`],
+//! [`NumberInput`], [`Checkbox`], [`Slider`] and [`Switch`].
+//!
+//! Each field tracks whether it is dirty, touched and valid. The error of a
+//! field is shown after it is touched or the form is submitted, and the
+//! submission is blocked until all fields are valid.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ribir::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   let form = @Form {};
+//!   let submit = @FilledButton {
+//!     disabled: pipe!(!$read(form).is_valid()),
+//!     on_tap: move |_| {
+//!       if let Some(value) = $write(form).submit() {
+//!         println!("{:?}", value);
+//!       }
+//!     },
+//!     @ { "Submit" }
+//!   };
+//!   @(form) {
+//!     @Column {
+//!       @FormField {
+//!         name: "email",
+//!         supporting_text: "We never share your email.",
+//!         validators: vec![
+//!           Validator::required("Email is required."),
+//!           Validator::new(|v| match v.as_text() {
+//!             Some(s) if !s.contains('@') => Err("Not a valid email.".into()),
+//!             _ => Ok(()),
+//!           }),
+//!         ],
+//!         @Input {}
+//!       }
+//!       @FormField {
+//!         name: "subscribe",
+//!         initial_value: false,
+//!         @Checkbox { @ { "Subscribe to the newsletter" } }
+//!       }
+//!       @ { submit }
+//!     }
+//!   }
+//! };
+//! ```
+
+use std::{collections::BTreeMap, rc::Rc};
+
+use futures::future::LocalBoxFuture;
+use ribir_core::{prelude::*, window::WindowId};
+
+use crate::prelude::*;
+
+class_names! {
+  /// Class name for the supporting text of a form field.
+  FORM_FIELD_SUPPORTING_TEXT,
+  /// Class name for the error text of a form field.
+  FORM_FIELD_ERROR_TEXT,
+}
+
+/// The value of a form field.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum FieldValue {
+  /// The field has no value yet.
+  #[default]
+  Empty,
+  Bool(bool),
+  Number(f32),
+  Text(String),
+}
+
+impl FieldValue {
+  pub fn as_text(&self) -> Option<&str> { if let Self::Text(s) = self { Some(s) } else { None } }
+
+  pub fn as_bool(&self) -> Option<bool> { if let Self::Bool(b) = self { Some(*b) } else { None } }
+
+  pub fn as_number(&self) -> Option<f32> {
+    if let Self::Number(n) = self { Some(*n) } else { None }
+  }
+
+  /// Whether the value is missing for a required field, that is an empty
+  /// value, an empty text or an unchecked state.
+  pub fn is_blank(&self) -> bool {
+    match self {
+      Self::Empty | Self::Bool(false) => true,
+      Self::Text(s) => s.is_empty(),
+      _ => false,
+    }
+  }
+
+  /// An empty value or an empty text, an `Input` cleared by the user is the
+  /// same as it has no value.
+  fn is_empty(&self) -> bool { matches!(self, Self::Empty) || self.as_text() == Some("") }
+
+  fn same_as(&self, other: &Self) -> bool { self == other || (self.is_empty() && other.is_empty()) }
+}
+
+impl From<bool> for FieldValue {
+  fn from(v: bool) -> Self { Self::Bool(v) }
+}
+
+impl From<f32> for FieldValue {
+  fn from(v: f32) -> Self { Self::Number(v) }
+}
+
+impl From<&str> for FieldValue {
+  fn from(v: &str) -> Self { Self::Text(v.to_string()) }
+}
+
+impl From<String> for FieldValue {
+  fn from(v: String) -> Self { Self::Text(v) }
+}
+
+impl From<CowArc<str>> for FieldValue {
+  fn from(v: CowArc<str>) -> Self { Self::Text(v.to_string()) }
+}
+
+/// The aggregated value of a form, the values of its fields keyed by their
+/// names.
+///
+/// It serializes as a map with the `serde` feature, so you can deserialize it
+/// into your own struct by [`FormValue::deserialize_into`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct FormValue(BTreeMap<String, FieldValue>);
+
+impl FormValue {
+  pub fn get(&self, name: &str) -> Option<&FieldValue> { self.0.get(name) }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &FieldValue)> {
+    self.0.iter().map(|(k, v)| (k.as_str(), v))
+  }
+
+  pub fn len(&self) -> usize { self.0.len() }
+
+  pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+  /// Convert the value to the struct `T` whose fields are named after the
+  /// form fields.
+  #[cfg(feature = "serde")]
+  pub fn deserialize_into<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+    serde_json::to_value(self).and_then(serde_json::from_value)
+  }
+}
+
+/// The result of a validator, the error is the message shown to the user.
+pub type ValidationResult = Result<(), CowArc<str>>;
+
+/// A validator of a form field.
+///
+/// The validators of a field run in order every time its value changes, and
+/// stop at the first error. The asynchronous validators only run after all the
+/// synchronous ones pass, and the result of an outdated value is discarded.
+///
+/// The validators run without borrowing the form, a validator that depends on
+/// other fields reads them from the [`FormValue`] passed to it, see
+/// [`Validator::with_form`].
+#[derive(Clone)]
+pub enum Validator {
+  Sync(Rc<dyn Fn(&FieldValue) -> ValidationResult>),
+  /// A synchronous validator that reads the values of the other fields, it
+  /// also runs when any other field changes.
+  Form(Rc<dyn Fn(&FieldValue, &FormValue) -> ValidationResult>),
+  Async(Rc<dyn Fn(FieldValue) -> LocalBoxFuture<'static, ValidationResult>>),
+}
+
+impl Validator {
+  /// Create a synchronous validator.
+  pub fn new(f: impl Fn(&FieldValue) -> ValidationResult + 'static) -> Self {
+    Self::Sync(Rc::new(f))
+  }
+
+  /// Create a synchronous validator that checks the value with the values of
+  /// the other fields, such as confirming a password.
+  ///
+  /// ```
+  /// use ribir::prelude::*;
+  ///
+  /// let _confirm = Validator::with_form(|v, form| {
+  ///   if Some(v) == form.get("password") { Ok(()) } else { Err("Not the same.".into()) }
+  /// });
+  /// ```
+  pub fn with_form(f: impl Fn(&FieldValue, &FormValue) -> ValidationResult + 'static) -> Self {
+    Self::Form(Rc::new(f))
+  }
+
+  /// Create an asynchronous validator, such as checking the value on a server.
+  pub fn new_async<F>(f: impl Fn(FieldValue) -> F + 'static) -> Self
+  where
+    F: Future<Output = ValidationResult> + 'static,
+  {
+    Self::Async(Rc::new(move |v| Box::pin(f(v))))
+  }
+
+  /// A validator that fails with `msg` if the value is blank, see
+  /// [`FieldValue::is_blank`].
+  pub fn required(msg: impl Into<CowArc<str>>) -> Self {
+    let msg = msg.into();
+    Self::new(move |v| if v.is_blank() { Err(msg.clone()) } else { Ok(()) })
+  }
+}
+
+/// The state of a form field.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FieldStatus {
+  /// The value differs from the initial value, an empty text is the same as
+  /// an empty value.
+  pub dirty: bool,
+  /// The user has left the field, or the form was submitted.
+  pub touched: bool,
+  /// An asynchronous validator is still running.
+  pub validating: bool,
+  /// The error of the last validation.
+  pub error: Option<CowArc<str>>,
+}
+
+impl FieldStatus {
+  pub fn is_valid(&self) -> bool { self.error.is_none() && !self.validating }
+
+  /// The error that should be shown to the user, only after the field is
+  /// touched.
+  pub fn shown_error(&self) -> Option<&CowArc<str>> { self.error.as_ref().filter(|_| self.touched) }
+}
+
+/// The id of a field registered to a [`Form`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FieldId(usize);
+
+struct FieldEntry {
+  id: FieldId,
+  name: CowArc<str>,
+  initial: FieldValue,
+  value: FieldValue,
+  validators: Vec<Validator>,
+  status: FieldStatus,
+  /// Increase on every validation, to discard the outdated async results.
+  generation: usize,
+  host: TrackId,
+  wnd: WindowId,
+}
+
+/// The form model, see the [module-level documentation](self) for more.
+///
+/// Declare it as the ancestor of the [`FormField`]s, and read it to check the
+/// states of the form, or call [`Form::submit`] to collect its value.
+#[declare]
+#[derive(Default)]
+pub struct Form {
+  #[declare(skip)]
+  fields: Vec<FieldEntry>,
+  #[declare(skip)]
+  next_id: usize,
+}
+
+impl Form {
+  /// The value of the field named `name`.
+  pub fn value(&self, name: &str) -> Option<&FieldValue> {
+    self.field_by_name(name).map(|f| &f.value)
+  }
+
+  /// The state of the field named `name`.
+  pub fn status(&self, name: &str) -> Option<&FieldStatus> {
+    self.field_by_name(name).map(|f| &f.status)
+  }
+
+  /// Whether all the fields are valid, the form can be submitted only if it's
+  /// valid.
+  pub fn is_valid(&self) -> bool { self.fields.iter().all(|f| f.status.is_valid()) }
+
+  /// Whether any field differs from its initial value.
+  pub fn is_dirty(&self) -> bool { self.fields.iter().any(|f| f.status.dirty) }
+
+  /// Whether any field was touched.
+  pub fn is_touched(&self) -> bool { self.fields.iter().any(|f| f.status.touched) }
+
+  /// Whether any asynchronous validator is still running.
+  pub fn is_validating(&self) -> bool { self.fields.iter().any(|f| f.status.validating) }
+
+  /// The aggregated value of all the fields.
+  pub fn values(&self) -> FormValue {
+    FormValue(
+      self
+        .fields
+        .iter()
+        .map(|f| (f.name.to_string(), f.value.clone()))
+        .collect(),
+    )
+  }
+
+  /// Submit the form, return its value if all the fields are valid.
+  ///
+  /// Otherwise, all the fields are marked as touched to show their errors, and
+  /// the focus moves to the first invalid field.
+  pub fn submit(&mut self) -> Option<FormValue> {
+    self
+      .fields
+      .iter_mut()
+      .for_each(|f| f.status.touched = true);
+    if self.is_valid() {
+      return Some(self.values());
+    }
+
+    let invalid = self
+      .fields
+      .iter()
+      .find(|f| !f.status.is_valid())?;
+    if let Some(wnd) = AppCtx::get_window(invalid.wnd)
+      && let Some(host) = invalid.host.get()
+    {
+      wnd.request_focus(host, FocusReason::Other);
+    }
+    None
+  }
+
+  fn field_by_name(&self, name: &str) -> Option<&FieldEntry> {
+    self.fields.iter().find(|f| &*f.name == name)
+  }
+
+  fn field_status(&self, id: FieldId) -> FieldStatus {
+    self
+      .fields
+      .iter()
+      .find(|f| f.id == id)
+      .map(|f| f.status.clone())
+      .unwrap_or_default()
+  }
+
+  fn field_mut(&mut self, id: FieldId) -> Option<&mut FieldEntry> {
+    self.fields.iter_mut().find(|f| f.id == id)
+  }
+
+  fn register(
+    this: &impl StateWriter<Value = Self>, field: &FormField, host: TrackId, wnd: WindowId,
+  ) -> FieldId {
+    let mut form = this.write();
+    let id = FieldId(form.next_id);
+    form.next_id += 1;
+    form.fields.push(FieldEntry {
+      id,
+      name: field.name.clone(),
+      initial: field.initial_value.clone(),
+      value: field.initial_value.clone(),
+      validators: field.validators.clone(),
+      status: FieldStatus::default(),
+      generation: 0,
+      host,
+      wnd,
+    });
+    drop(form);
+    Self::validate(this, id);
+    id
+  }
+
+  fn unregister(&mut self, id: FieldId) { self.fields.retain(|f| f.id != id); }
+
+  fn change(this: &impl StateWriter<Value = Self>, id: FieldId, value: FieldValue) {
+    let mut form = this.write();
+    let Some(field) = form.field_mut(id) else { return };
+    field.status.dirty = !value.same_as(&field.initial);
+    field.value = value;
+    // The fields that depend on the other fields validate again.
+    let dependents = form
+      .fields
+      .iter()
+      .filter(|f| {
+        f.id != id
+          && f
+            .validators
+            .iter()
+            .any(|v| matches!(v, Validator::Form(_)))
+      })
+      .map(|f| f.id)
+      .collect::<Vec<_>>();
+    drop(form);
+    Self::validate(this, id);
+    dependents
+      .into_iter()
+      .for_each(|id| Self::validate(this, id));
+  }
+
+  fn touch(&mut self, id: FieldId) {
+    if let Some(field) = self.field_mut(id) {
+      field.status.touched = true;
+    }
+  }
+
+  fn validate(this: &impl StateWriter<Value = Self>, id: FieldId) {
+    // Take what the validators need out, they may read the form.
+    let mut form = this.write();
+    let values = form.values();
+    let Some(field) = form.field_mut(id) else { return };
+    field.generation += 1;
+    let (validators, value, generation) =
+      (field.validators.clone(), field.value.clone(), field.generation);
+    drop(form);
+
+    let mut error = None;
+    let mut pending = vec![];
+    for v in validators.iter() {
+      let res = match v {
+        Validator::Sync(f) => f(&value),
+        Validator::Form(f) => f(&value, &values),
+        Validator::Async(f) => {
+          pending.push(f(value.clone()));
+          Ok(())
+        }
+      };
+      if let Err(e) = res {
+        error = Some(e);
+        break;
+      }
+    }
+
+    let validating = error.is_none() && !pending.is_empty();
+    let mut form = this.write();
+    match form.field_mut(id) {
+      Some(field) if field.generation == generation => {
+        field.status.error = error;
+        field.status.validating = validating;
+      }
+      _ => {
+        form.forget_modifies();
+        return;
+      }
+    }
+    drop(form);
+
+    if validating {
+      let this = this.clone_writer();
+      AppCtx::spawn_local(async move {
+        let mut error = None;
+        for fut in pending {
+          if let Err(e) = fut.await {
+            error = Some(e);
+            break;
+          }
+        }
+        let mut form = this.write();
+        match form.field_mut(id) {
+          Some(field) if field.generation == generation => {
+            field.status.error = error;
+            field.status.validating = false;
+          }
+          _ => form.forget_modifies(),
+        }
+      });
+    }
+  }
+}
+
+impl<'c> ComposeChild<'c> for Form {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    providers! {
+      providers: [Provider::writer(this, None)],
+      @ { child }
+    }
+    .into_widget()
+  }
+}
+
+/// A field of the [`Form`] it is declared in, registers the control it wraps
+/// to the form by the `name`.
+///
+/// The field gets its value from the change events of the control, so the
+/// `initial_value` should be the same as the initial value of the control. The
+/// `name`, `initial_value` and `validators` are read once when the field is
+/// built.
+///
+/// It shows the error of the field, or the `supporting_text` if it's valid,
+/// below the control. The [`FieldStatus`] is provided to the control, so the
+/// theme can style it by the status.
+#[derive(Declare)]
+pub struct FormField {
+  /// The name of the field in the value of the form.
+  pub name: CowArc<str>,
+  #[declare(default)]
+  pub initial_value: FieldValue,
+  #[declare(default)]
+  pub validators: Vec<Validator>,
+  /// The text shown below the control while no error is shown, nothing is
+  /// shown if it's empty.
+  #[declare(default)]
+  pub supporting_text: CowArc<str>,
+}
+
+impl<'c> ComposeChild<'c> for FormField {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let form = Provider::writer_of::<Form>(BuildCtx::get())
+      .expect("`FormField` must be declared in a `Form`.");
+    let wnd = BuildCtx::get().window().id();
+    let mut host = FatObj::new(child);
+    let id = Form::register(&form, &this.read(), host.track_id(), wnd);
+
+    let status = Stateful::new(form.read().field_status(id));
+    let sync = watch!($read(form).field_status(id))
+      .distinct_until_changed()
+      .subscribe({
+        let status = status.clone_writer();
+        move |s| *status.write() = s
+      });
+
+    host
+      .on_custom({
+        let form = form.clone_writer();
+        move |e: &mut TextChangedEvent| Form::change(&form, id, e.data().text.clone().into())
+      })
+      .on_custom({
+        let form = form.clone_writer();
+        move |e: &mut CheckboxChanged| Form::change(&form, id, e.data().checked.into())
+      })
+      .on_custom({
+        let form = form.clone_writer();
+        move |e: &mut SwitchChanged| Form::change(&form, id, e.data().checked.into())
+      })
+      .on_custom({
+        let form = form.clone_writer();
+        move |e: &mut SliderChangedEvent| Form::change(&form, id, e.data().to.into())
+      })
+      .on_custom({
+        let form = form.clone_writer();
+        move |e: &mut NumberChangedEvent| Form::change(&form, id, e.data().to.into())
+      })
+      .on_focus_out({
+        let form = form.clone_writer();
+        move |_| form.write().touch(id)
+      })
+      .on_disposed(move |_| {
+        sync.unsubscribe();
+        form.write().unregister(id);
+      });
+
+    let message = pipe!((
+      $read(status).shown_error().cloned(),
+      $read(this).supporting_text.clone()
+    ))
+    .map(|(error, supporting)| {
+      let (text, class) = match error {
+        Some(error) => (error, FORM_FIELD_ERROR_TEXT),
+        None if !supporting.is_empty() => (supporting, FORM_FIELD_SUPPORTING_TEXT),
+        None => return None,
+      };
+      Some(fn_widget! { @Text { text: text, class: class } })
+    });
+
+    providers! {
+      providers: [Provider::watcher(status.clone_boxed_watcher())],
+      @Column {
+        // The focus scope lets the form request focus for the field, which
+        // moves the focus to the control.
+        @FocusScope { @ { host } }
+        @ { message }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn form_with_email(form: Stateful<Form>) -> Widget<'static> {
+    fn_widget! {
+      @(form.clone_writer()) {
+        @Column {
+          @FormField {
+            name: "email",
+            supporting_text: "Your email",
+            validators: vec![
+              Validator::required("required"),
+              Validator::new(|v| match v.as_text() {
+                Some(s) if !s.contains('@') => Err("invalid".into()),
+                _ => Ok(()),
+              }),
+            ],
+            @Input { auto_focus: true }
+          }
+          @FormField {
+            name: "agree",
+            initial_value: false,
+            @Checkbox {}
+          }
+        }
+      }
+    }
+    .into_widget()
+  }
+
+  #[test]
+  fn validate_and_submit() {
+    reset_test_env!();
+
+    let form = Stateful::new(Form::default());
+    let wnd =
+      TestWindow::new_with_size(form_with_email(form.clone_writer()), Size::new(300., 200.));
+    wnd.draw_frame();
+
+    assert!(!form.read().is_valid());
+    let status = form.read().status("email").cloned().unwrap();
+    assert_eq!(status.error.as_deref(), Some("required"));
+    // The error is not shown before the field is touched.
+    assert!(status.shown_error().is_none());
+
+    wnd.process_receive_chars("ribir".into());
+    wnd.draw_frame();
+    let form_ref = form.read();
+    assert_eq!(form_ref.value("email"), Some(&FieldValue::from("ribir")));
+    let status = form_ref.status("email").unwrap();
+    assert!(status.dirty);
+    assert_eq!(status.error.as_deref(), Some("invalid"));
+    drop(form_ref);
+
+    assert!(form.write().submit().is_none());
+    assert!(form.read().status("email").unwrap().touched);
+
+    wnd.process_receive_chars("@x.dev".into());
+    wnd.draw_frame();
+    let value = form.write().submit().unwrap();
+    assert_eq!(value.get("email"), Some(&FieldValue::from("ribir@x.dev")));
+    assert_eq!(value.get("agree"), Some(&FieldValue::Bool(false)));
+    assert!(!form.read().status("agree").unwrap().dirty);
+  }
+
+  #[test]
+  fn submit_focus_first_invalid() {
+    reset_test_env!();
+
+    let form = Stateful::new(Form::default());
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @(form.clone_writer()) {
+        @Column {
+          @FormField { name: "first", @Input { auto_focus: true } }
+          @FormField {
+            name: "second",
+            validators: vec![Validator::required("required")],
+            @Input {}
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+    let focused = wnd.focusing();
+
+    assert!(form.write().submit().is_none());
+    wnd.draw_frame();
+    assert!(wnd.focusing().is_some());
+    assert_ne!(wnd.focusing(), focused);
+
+    wnd.process_receive_chars("ok".into());
+    wnd.draw_frame();
+    assert_eq!(form.read().value("second"), Some(&FieldValue::from("ok")));
+    assert!(form.read().is_valid());
+  }
+
+  #[test]
+  fn bind_switch_and_slider() {
+    reset_test_env!();
+
+    let form = Stateful::new(Form::default());
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @(form.clone_writer()) {
+          @Column {
+            @FormField {
+              name: "notify",
+              initial_value: false,
+              @Switch { size: Size::new(40., 20.) }
+            }
+            @FormField {
+              name: "volume",
+              initial_value: 0f32,
+              @Slider { value: 0., max: 100., size: Size::new(100., 20.) }
+            }
+          }
+        }
+      },
+      Size::new(200., 100.),
+    );
+    wnd.draw_frame();
+    assert!(!form.read().is_dirty());
+
+    wnd.process_cursor_move(Point::new(20., 10.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+    assert_eq!(form.read().value("notify"), Some(&FieldValue::Bool(true)));
+    assert!(form.read().status("notify").unwrap().dirty);
+    assert!(!form.read().status("volume").unwrap().dirty);
+
+    wnd.process_cursor_move(Point::new(50., 30.));
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+    let value = form.write().submit().unwrap();
+    assert_eq!(value.get("notify"), Some(&FieldValue::Bool(true)));
+    assert_eq!(value.get("volume"), Some(&FieldValue::Number(50.)));
+  }
+
+  #[test]
+  fn clear_text_not_dirty() {
+    reset_test_env!();
+
+    let form = Stateful::new(Form::default());
+    let wnd =
+      TestWindow::new_with_size(form_with_email(form.clone_writer()), Size::new(300., 200.));
+    wnd.draw_frame();
+
+    wnd.process_receive_chars("a".into());
+    wnd.draw_frame();
+    assert!(form.read().status("email").unwrap().dirty);
+
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(KeyCode::Backspace),
+      VirtualKey::Named(NamedKey::Backspace),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+    assert_eq!(form.read().value("email"), Some(&FieldValue::from("")));
+    assert!(!form.read().status("email").unwrap().dirty);
+    assert!(!form.read().is_dirty());
+  }
+
+  #[test]
+  fn validator_reads_form() {
+    reset_test_env!();
+
+    let form = Stateful::new(Form::default());
+    // A validator reads the form state directly, it must not be borrowed.
+    let reader = form.clone_reader();
+    let wnd = TestWindow::from_widget(fn_widget! {
+      let reader = reader.clone_reader();
+      @(form.clone_writer()) {
+        @Column {
+          @FormField {
+            name: "password",
+            validators: vec![Validator::new(move |_| {
+              let _ = reader.read().is_dirty();
+              Ok(())
+            })],
+            @Input { auto_focus: true }
+          }
+          @FormField {
+            name: "confirm",
+            validators: vec![Validator::with_form(|v, form| {
+              if form.get("password").is_some_and(|p| p.same_as(v)) {
+                Ok(())
+              } else {
+                Err("mismatch".into())
+              }
+            })],
+            @Input {}
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+    assert!(form.read().is_valid());
+
+    wnd.process_receive_chars("secret".into());
+    wnd.draw_frame();
+    let status = form.read().status("confirm").cloned().unwrap();
+    assert_eq!(status.error.as_deref(), Some("mismatch"));
+    assert!(form.read().status("password").unwrap().is_valid());
+  }
+
+  fn wait_validation() {
+    AppCtx::run_until(AppCtx::timer(Duration::from_millis(20)));
+    AppCtx::run_until_stalled();
+  }
+
+  #[test]
+  fn async_validator() {
+    reset_test_env!();
+
+    let form = Stateful::new(Form::default());
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @(form.clone_writer()) {
+        @FormField {
+          name: "user",
+          validators: vec![Validator::new_async(|v: FieldValue| async move {
+            AppCtx::timer(Duration::from_millis(10)).await;
+            if v.as_text() == Some("taken") { Err("taken".into()) } else { Ok(()) }
+          })],
+          @Input { auto_focus: true }
+        }
+      }
+    });
+    wnd.draw_frame();
+    wait_validation();
+    assert!(form.read().is_valid());
+
+    wnd.process_receive_chars("taken".into());
+    wnd.draw_frame();
+    assert!(form.read().is_validating());
+    assert!(form.write().submit().is_none());
+
+    wait_validation();
+    assert!(!form.read().is_validating());
+    let form = form.read();
+    assert_eq!(form.status("user").unwrap().error.as_deref(), Some("taken"));
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn deserialize_form_value() {
+    #[derive(serde::Deserialize, PartialEq, Debug)]
+    struct SignUp {
+      email: String,
+      agree: bool,
+      age: f32,
+    }
+
+    let mut value = FormValue::default();
+    value.0.insert("email".into(), "a@b.c".into());
+    value.0.insert("agree".into(), true.into());
+    value.0.insert("age".into(), 18f32.into());
+    let sign_up: SignUp = value.deserialize_into().unwrap();
+    assert_eq!(sign_up, SignUp { email: "a@b.c".into(), agree: true, age: 18. });
+  }
+}
@@ -102,12 +102,18 @@ struct IconRender {
   scale: Cell<f32>,
 }
 
+/// The icon is a square of the line height of the text style, the child is
+/// scaled to fit it.
+fn icon_size(ctx: &MeasureCtx) -> f32 {
+  let text_style = Provider::of::<TextStyle>(ctx).unwrap();
+  text_style
+    .line_height
+    .resolve_for_font_size(text_style.font_size)
+}
+
 impl Render for IconRender {
   fn measure(&self, clamp: BoxClamp, ctx: &mut MeasureCtx) -> Size {
-    let text_style = Provider::of::<TextStyle>(ctx).unwrap();
-    let icon_size = text_style
-      .line_height
-      .resolve_for_font_size(text_style.font_size);
+    let icon_size = icon_size(ctx);
     let child_size = ctx
       .perform_single_child_layout(BoxClamp::default())
       .unwrap_or_default();
@@ -117,6 +123,14 @@ impl Render for IconRender {
     clamp.clamp(Size::splat(icon_size))
   }
 
+  fn min_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 { icon_size(ctx) }
+
+  fn max_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 { icon_size(ctx) }
+
+  fn min_intrinsic_height(&self, _width: f32, ctx: &mut MeasureCtx) -> f32 { icon_size(ctx) }
+
+  fn max_intrinsic_height(&self, _width: f32, ctx: &mut MeasureCtx) -> f32 { icon_size(ctx) }
+
   fn place_children(&self, size: Size, ctx: &mut PlaceCtx) {
     let child = ctx.assert_single_child();
     let child_size = ctx.widget_box_size(child).unwrap_or_default();
@@ -5,12 +5,16 @@ use ribir_core::prelude::*;
 use crate::prelude::*;
 
 mod edit_text;
+mod formatter;
+mod number_input;
 mod text_glyphs;
 
 mod text_editable;
 mod text_selectable;
 
 pub use edit_text::*;
+pub use formatter::*;
+pub use number_input::*;
 pub use text_editable::*;
 pub use text_glyphs::*;
 pub use text_selectable::*;
@@ -20,16 +24,27 @@ class_names!(
   INPUT,
   ///Class name for the text area widget
   TEXTAREA,
+  ///Class name for the icon that reveals or hides the text of a password input
+  INPUT_PASSWORD_TOGGLE,
 );
 
+/// The data of the [`TextChangedEvent`], bubbled by [`Input`] and [`TextArea`]
+/// when the user edits the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextChanged {
+  /// The text after the edit.
+  pub text: CowArc<str>,
+}
+
+pub type TextChangedEvent = CustomEvent<TextChanged>;
+
 /// The `Input` struct is a widget that represents a text input field
 /// that displays a single line of text. if you need multi line text, use
 /// `[TextArea]`
 ///
-/// The Input will emit the [TextChangedEvent] event when the text is changed,
-/// emit the [TextSelectChanged] event when the text selection is changed.
-/// The Input also implement the [EditableText] trait, which you can set
-/// the text and the caret selection.
+/// The Input will emit the [TextChangedEvent] event when the user changes the
+/// text. You can set the text and the caret selection by its methods, that
+/// will not emit the event.
 ///
 /// ## Example
 ///
@@ -47,26 +62,135 @@ class_names!(
 /// };
 /// App::run(w);
 /// ```
-#[declare]
+///
+/// ## Formatter and password
+///
+/// The `formatter` formats the text that the user inserts, see
+/// [`InputFormatter`]. The `password` input displays the text as bullets,
+/// disables copying it and provides an icon to reveal it.
+///
+/// ```rust no_run
+/// use ribir::prelude::*;
+/// let _w = fn_widget! {
+///   @Column {
+///     @Input { formatter: Formatter::new(MaskFormatter::new("(###) ###-####")) }
+///     @Input { password: true }
+///   }
+/// };
+/// ```
+#[declare(validate)]
+#[derive(Default)]
 pub struct Input {
+  /// The formatter of the text that the user inserts.
+  #[declare(default, setter = set_formatter)]
+  formatter: Option<Formatter>,
+  /// Whether the input is a password input.
+  #[declare(default, setter = set_password)]
+  password: bool,
+  #[declare(skip)]
+  revealed: bool,
   #[declare(skip)]
   basic: BasicEditor<InputText>,
 }
 
 impl Input {
+  fn declare_validate(mut self) -> Result<Self, std::convert::Infallible> {
+    self.basic.text_mut().formatter = self.formatter.clone();
+    self.sync_obscured();
+    Ok(self)
+  }
+
   /// set the text and the caret selection will be reset to the start.
+  ///
+  /// The text is not formatted by the formatter.
   pub fn set_text(&mut self, text: &str) {
     let v = text
       .chars()
       .filter(|c| *c != '\n' && *c != '\r')
       .collect::<String>();
-    *self.basic.text_mut() = InputText::new(v);
+    self.basic.text_mut().set_text(v.into());
     let selection = &mut self.basic.selection;
     selection.from = CaretPosition::default();
     selection.to = CaretPosition::default();
   }
 
-  pub fn text(&self) -> &CowArc<str> { &self.basic.text().0 }
+  pub fn text(&self) -> &CowArc<str> { self.basic.text().text() }
+
+  pub fn formatter(&self) -> Option<&Formatter> { self.formatter.as_ref() }
+
+  pub fn set_formatter(&mut self, formatter: Option<Formatter>) {
+    self.basic.text_mut().formatter = formatter.clone();
+    self.formatter = formatter;
+  }
+
+  pub fn is_password(&self) -> bool { self.password }
+
+  pub fn set_password(&mut self, password: bool) {
+    self.password = password;
+    self.sync_obscured();
+  }
+
+  /// Whether the text of the password is revealed.
+  pub fn is_revealed(&self) -> bool { self.revealed }
+
+  /// Reveal the text of the password, or hide it again.
+  pub fn set_revealed(&mut self, revealed: bool) {
+    self.revealed = revealed;
+    self.sync_obscured();
+  }
+
+  fn sync_obscured(&mut self) {
+    let obscured = self.password && !self.revealed;
+    if self.basic.text().is_obscured() != obscured {
+      self.basic.text_mut().set_obscured(obscured);
+      // The offsets of the displayed text are changed, so move the caret to the
+      // end.
+      let end = CaretPosition::new(self.basic.text().len());
+      let selection = &mut self.basic.selection;
+      selection.from = end;
+      selection.to = end;
+    }
+  }
+
+  fn a11y(&self) -> A11y {
+    if self.password {
+      A11y::new(Role::PasswordInput)
+    } else {
+      A11y::new(Role::TextInput).with_value(self.text().clone())
+    }
+  }
+
+  fn editor(this: impl StateWriter<Value = Self>, password: bool) -> Widget<'static> {
+    fn_widget! {
+      let editor = @FatObj {
+        scrollable: Scrollable::X,
+        @part_writer!(&mut this.basic)
+      };
+      if !password {
+        editor.into_widget()
+      } else {
+        @Row {
+          align_items: Align::Center,
+          @Expanded { @ { editor } }
+          @Icon {
+            class: INPUT_PASSWORD_TOGGLE,
+            cursor: CursorIcon::Pointer,
+            on_tap: move |_| {
+              let mut this = $write(this);
+              let revealed = !this.revealed;
+              this.set_revealed(revealed);
+            },
+            @pipe! {
+              let name = if $read(this).revealed { "visibility_off" } else { "visibility" };
+              svg_registry::get_or_default(name)
+            }
+          }
+        }
+        .into_widget()
+      }
+    }
+    .into_widget()
+  }
 
   /// set the caret selection, and the caret position will be set to the `to`
   /// cluster
@@ -114,46 +238,158 @@ impl TextArea {
   pub fn selection(&self) -> Range<usize> { self.basic.cluster_rg() }
 }
 
-#[derive(Clone, Eq, PartialEq, Default)]
-pub struct InputText(CowArc<str>);
+/// The character displayed in place of each character of a password.
+const BULLET: char = '•';
+
+/// The text of the [`Input`]. It's displayed as bullets when obscured, and the
+/// text inserted by the user is formatted by its formatter.
+///
+/// The offsets of the [`BaseText`] and [`EditText`] methods are the offsets of
+/// the displayed text.
+#[derive(Clone, Default)]
+pub struct InputText {
+  text: CowArc<str>,
+  obscured: Option<CowArc<str>>,
+  formatter: Option<Formatter>,
+}
+
 impl InputText {
-  pub fn new(v: impl Into<CowArc<str>>) -> Self { InputText(v.into()) }
-  pub fn text(&self) -> &CowArc<str> { &self.0 }
+  pub fn new(v: impl Into<CowArc<str>>) -> Self { Self { text: v.into(), ..Default::default() } }
+
+  pub fn text(&self) -> &CowArc<str> { &self.text }
+
+  pub fn is_obscured(&self) -> bool { self.obscured.is_some() }
+
+  fn visual(&self) -> &CowArc<str> { self.obscured.as_ref().unwrap_or(&self.text) }
+
+  fn set_text(&mut self, text: CowArc<str>) {
+    self.text = text;
+    self.update_obscured();
+  }
+
+  fn set_obscured(&mut self, obscured: bool) {
+    self.obscured = obscured.then(CowArc::default);
+    self.update_obscured();
+  }
+
+  fn update_obscured(&mut self) {
+    if let Some(obscured) = self.obscured.as_mut() {
+      *obscured = BULLET
+        .to_string()
+        .repeat(self.text.chars().count())
+        .into();
+    }
+  }
+
+  /// Map the offset of the displayed text to the offset of the text.
+  fn to_text_offset(&self, at: usize) -> usize {
+    if self.obscured.is_none() {
+      return at;
+    }
+    self
+      .text
+      .char_indices()
+      .nth(at / BULLET.len_utf8())
+      .map_or(self.text.len(), |(idx, _)| idx)
+  }
+
+  /// Map the offset of the text to the offset of the displayed text.
+  fn to_visual_offset(&self, at: usize) -> usize {
+    if self.obscured.is_none() {
+      return at;
+    }
+    self.text[..at].chars().count() * BULLET.len_utf8()
+  }
+
+  /// Reformat the whole text by the formatter after an edit, return the offset
+  /// of the caret in the reformatted text.
+  fn reformat(&mut self, caret: usize) -> usize {
+    let formatted = self
+      .formatter
+      .as_ref()
+      .and_then(|f| f.format_text(&self.text, caret));
+    match formatted {
+      Some((text, caret)) => {
+        self.text = text.into();
+        caret
+      }
+      None => caret,
+    }
+  }
+}
+
+impl PartialEq for InputText {
+  fn eq(&self, other: &Self) -> bool {
+    self.text == other.text && self.is_obscured() == other.is_obscured()
+  }
 }
 
+impl Eq for InputText {}
+
 impl BaseText for InputText {
-  fn len(&self) -> usize { self.0.len() }
-  fn substr(&self, rg: Range<usize>) -> Substr { self.0.substr(rg) }
+  fn len(&self) -> usize { self.visual().len() }
+  fn substr(&self, rg: Range<usize>) -> Substr { self.visual().substr(rg) }
   fn measure_bytes(&self, byte_from: usize, char_len: isize) -> usize {
-    self.0.measure_bytes(byte_from, char_len)
+    self.visual().measure_bytes(byte_from, char_len)
   }
   fn select_token(&self, byte_from: usize) -> Range<usize> {
-    BaseText::select_token(&self.0, byte_from)
+    BaseText::select_token(self.visual(), byte_from)
   }
+  fn can_copy(&self) -> bool { !self.is_obscured() }
 }
 
 impl VisualText for InputText {
   fn layout_glyphs(&self, clamp: BoxClamp, ctx: &MeasureCtx) -> ParagraphLayoutRef {
-    self.0.layout_glyphs(clamp, ctx)
+    self.visual().layout_glyphs(clamp, ctx)
   }
 
   fn paint(
     &self, painter: &mut Painter, style: PaintingStyle, glyphs: &ParagraphLayoutRef, rect: Rect,
   ) {
-    self.0.paint(painter, style, glyphs, rect);
+    self.visual().paint(painter, style, glyphs, rect);
   }
 }
 
 impl EditText for InputText {
   fn insert_str(&mut self, at: usize, v: &str) -> usize {
-    let new_v = v
+    let mut new_v = v
       .chars()
       .filter(|c| *c != '\n' && *c != '\r')
       .collect::<String>();
-    self.0.insert_str(at, new_v.as_str())
+    let text_at = self.to_text_offset(at);
+    if let Some(formatter) = &self.formatter {
+      new_v = formatter.format_insert(&self.text, text_at, &new_v);
+    }
+    let len = self.text.insert_str(text_at, &new_v);
+    let caret = self.reformat(text_at + len);
+    self.update_obscured();
+    self.to_visual_offset(caret).saturating_sub(at)
+  }
+
+  fn del_rg_str(&mut self, rg: Range<usize>) -> Range<usize> {
+    let len = self.len();
+    let rg = rg.start.min(len)..rg.end.min(len);
+    if rg.is_empty() {
+      return rg;
+    }
+    let text_rg = self.to_text_offset(rg.start)..self.to_text_offset(rg.end);
+    self.text.del_rg_str(text_rg.clone());
+    let caret = self.reformat(text_rg.start);
+    self.update_obscured();
+    // The caret is placed at the start of the returned range.
+    let caret = self.to_visual_offset(caret);
+    caret..rg.end.max(caret)
   }
 
-  fn del_rg_str(&mut self, rg: Range<usize>) -> Range<usize> { self.0.del_rg_str(rg) }
+  fn plain_text(&self) -> CowArc<str> { self.text.clone() }
+
+  fn ime_allowed(&self) -> bool {
+    !self.is_obscured()
+      && self
+        .formatter
+        .as_ref()
+        .is_none_or(|f| f.allow_ime())
+  }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -179,15 +415,18 @@ impl Default for CaretPosition {
 
 impl Compose for Input {
   fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
-    focus_scope! {
-      skip_host: true,
-      @TextClamp {
-        rows: Some(1.),
-        cols: Some(20.),
-        class: INPUT,
-        @FatObj {
-          scrollable: Scrollable::X,
-          @part_writer!(&mut this.basic)
+    fn_widget! {
+      let editor = pipe!($read(this).password)
+        .transform(|p| p.distinct_until_changed())
+        .map(move |password| fn_widget! { Input::editor($writer(this), password) });
+      @FocusScope {
+        skip_host: true,
+        @TextClamp {
+          rows: Some(1.),
+          cols: Some(20.),
+          class: INPUT,
+          a11y: pipe!($read(this).a11y()),
+          @ { editor }
         }
       }
     }
@@ -202,6 +441,7 @@ impl Compose for TextArea {
         rows: Some(2.),
         cols: Some(20.),
         class: TEXTAREA,
+        a11y: pipe!(A11y::new(Role::MultilineTextInput).with_value($read(this).text().clone())),
         @Scrollbar {
           text_overflow: TextOverflow::AutoWrap,
           @part_writer!(&mut this.basic)
@@ -215,6 +455,7 @@ impl Compose for TextArea {
 #[cfg(test)]
 mod tests {
   use ribir_core::{prelude::*, reset_test_env, test_helper::*};
+  use winit::event::{ElementState, Ime};
 
   use super::*;
 
@@ -267,4 +508,108 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*value.read(), "hello");
   }
+
+  fn input_window(input: &Stateful<Input>) -> TestWindow {
+    let input = input.clone_writer();
+    let wnd = TestWindow::new_with_size(
+      fn_widget! { @(input.clone_writer()) { auto_focus: true } },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+    wnd
+  }
+
+  #[test]
+  fn password_input() {
+    reset_test_env!();
+    let mut password = Input::default();
+    password.set_password(true);
+    let input = Stateful::new(password);
+    let wnd = input_window(&input);
+
+    wnd.process_receive_chars("secret".into());
+    wnd.draw_frame();
+    {
+      let input = input.read();
+      let text = input.basic.text();
+      assert_eq!(&**input.text(), "secret");
+      assert_eq!(text.substr(0..text.len()).to_string(), "••••••");
+      assert!(!text.can_copy());
+      assert!(!wnd.is_ime_allowed());
+    }
+
+    input.write().set_revealed(true);
+    wnd.draw_frame();
+    assert!(wnd.is_ime_allowed());
+    wnd.process_receive_chars("!".into());
+    wnd.draw_frame();
+    let input = input.read();
+    assert_eq!(&**input.text(), "secret!");
+    assert!(input.basic.text().can_copy());
+  }
+
+  #[test]
+  fn mask_input() {
+    reset_test_env!();
+    let mut phone = Input::default();
+    phone.set_formatter(Some(Formatter::new(MaskFormatter::new("(###) ###-####"))));
+    let input = Stateful::new(phone);
+    let wnd = input_window(&input);
+
+    wnd.process_receive_chars("555".into());
+    wnd.draw_frame();
+    assert_eq!(&**input.read().text(), "(555");
+
+    wnd.process_receive_chars("12a34567".into());
+    wnd.draw_frame();
+    assert_eq!(&**input.read().text(), "(555) 123-4567");
+
+    let backspace = || {
+      wnd.process_keyboard_event(
+        PhysicalKey::Code(KeyCode::Backspace),
+        VirtualKey::Named(NamedKey::Backspace),
+        false,
+        KeyLocation::Standard,
+        ElementState::Pressed,
+      );
+      wnd.draw_frame();
+    };
+    for _ in 0..4 {
+      backspace();
+    }
+    // The dangling `-` is removed with the last digit after it.
+    assert_eq!(&**input.read().text(), "(555) 123");
+
+    // Insert in the middle, the digits after it move to the next slots.
+    input.write().select(2, 2);
+    wnd.process_receive_chars("9".into());
+    wnd.draw_frame();
+    assert_eq!(&**input.read().text(), "(595) 512-3");
+    // Delete in the middle, the digits after it move back.
+    backspace();
+    assert_eq!(&**input.read().text(), "(555) 123");
+  }
+
+  #[test]
+  fn formatter_with_ime() {
+    reset_test_env!();
+    let mut digits = Input::default();
+    digits.set_formatter(Some(Formatter::new(NumericFormatter::DIGITS)));
+    let input = Stateful::new(digits);
+    let wnd = input_window(&input);
+    assert!(!wnd.is_ime_allowed());
+
+    wnd.process_ime(Ime::Preedit("１".into(), None));
+    wnd.draw_frame();
+    assert!(!wnd.is_pre_editing());
+    assert_eq!(&**input.read().text(), "");
+
+    wnd.process_ime(Ime::Commit("１２a".into()));
+    wnd.draw_frame();
+    assert_eq!(&**input.read().text(), "12");
+
+    input.write().set_formatter(None);
+    wnd.draw_frame();
+    assert!(wnd.is_ime_allowed());
+  }
 }
@@ -9,12 +9,21 @@ pub trait BaseText: Eq {
   fn substr(&self, rg: Range<usize>) -> Substr;
   fn len(&self) -> usize;
   fn is_empty(&self) -> bool { self.len() == 0 }
+  /// Whether the text can be copied to the clipboard, a password can't.
+  fn can_copy(&self) -> bool { true }
 }
 
 pub trait EditText: BaseText {
   fn insert_str(&mut self, at: usize, s: &str) -> usize;
 
   fn del_rg_str(&mut self, rg: Range<usize>) -> Range<usize>;
+
+  /// The text being edited, it may differ from the displayed text, such as
+  /// the text of a password.
+  fn plain_text(&self) -> CowArc<str> { self.substr(0..self.len()).to_string().into() }
+
+  /// Whether the IME is allowed to compose the text when editing.
+  fn ime_allowed(&self) -> bool { true }
 }
 
 impl BaseText for CowArc<str> {
@@ -91,4 +100,6 @@ impl EditText for CowArc<str> {
     }
     rg
   }
+
+  fn plain_text(&self) -> CowArc<str> { self.clone() }
 }
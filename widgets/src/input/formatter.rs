@@ -0,0 +1,329 @@
+use std::rc::Rc;
+
+use ribir_core::prelude::CowArc;
+
+/// A formatter intercepts the text that the user inserts into an [`Input`],
+/// whether it's typed, pasted or committed by the IME, and may reformat the
+/// whole text after each edit.
+///
+/// A closure `Fn(&str, usize, &str) -> String` is also a formatter.
+///
+/// [`Input`]: super::Input
+pub trait InputFormatter {
+  /// Return the text to insert at the byte offset `at` of `text`, in place of
+  /// the `insert` text.
+  fn format_insert(&self, text: &str, at: usize, insert: &str) -> String;
+
+  /// Return the reformatted `text` and the byte offset of the caret in it,
+  /// after the user inserted or deleted text and left the caret at `caret`.
+  /// `None` keeps the text as it is.
+  fn format_text(&self, _text: &str, _caret: usize) -> Option<(String, usize)> { None }
+
+  /// Whether the IME is allowed to compose the text, disallow it if the
+  /// formatter only accepts the characters that can be typed directly.
+  fn allow_ime(&self) -> bool { true }
+}
+
+impl<F: Fn(&str, usize, &str) -> String> InputFormatter for F {
+  fn format_insert(&self, text: &str, at: usize, insert: &str) -> String { self(text, at, insert) }
+}
+
+/// A shared [`InputFormatter`] that can be declared to the `formatter` of an
+/// [`Input`](super::Input).
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _code = input! {
+///   formatter: Formatter::new(UpperCase).then(MaxLength(6)),
+/// };
+/// ```
+#[derive(Clone)]
+pub struct Formatter(Rc<dyn InputFormatter>);
+
+impl Formatter {
+  pub fn new(formatter: impl InputFormatter + 'static) -> Self { Self(Rc::new(formatter)) }
+
+  /// Chain the `next` formatter, which formats the text that this formatter
+  /// returns.
+  pub fn then(self, next: impl InputFormatter + 'static) -> Self { Self::new(Chain(self, next)) }
+}
+
+impl InputFormatter for Formatter {
+  fn format_insert(&self, text: &str, at: usize, insert: &str) -> String {
+    self.0.format_insert(text, at, insert)
+  }
+
+  fn format_text(&self, text: &str, caret: usize) -> Option<(String, usize)> {
+    self.0.format_text(text, caret)
+  }
+
+  fn allow_ime(&self) -> bool { self.0.allow_ime() }
+}
+
+struct Chain<A, B>(A, B);
+
+impl<A: InputFormatter, B: InputFormatter> InputFormatter for Chain<A, B> {
+  fn format_insert(&self, text: &str, at: usize, insert: &str) -> String {
+    let insert = self.0.format_insert(text, at, insert);
+    self.1.format_insert(text, at, &insert)
+  }
+
+  fn format_text(&self, text: &str, caret: usize) -> Option<(String, usize)> {
+    match self.0.format_text(text, caret) {
+      Some((text, caret)) => self
+        .1
+        .format_text(&text, caret)
+        .or(Some((text, caret))),
+      None => self.1.format_text(text, caret),
+    }
+  }
+
+  fn allow_ime(&self) -> bool { self.0.allow_ime() && self.1.allow_ime() }
+}
+
+/// Accept only the characters of a number.
+///
+/// The full-width digits committed by the IME are converted to the ASCII
+/// digits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumericFormatter {
+  /// Accept a decimal point.
+  pub decimal: bool,
+  /// Accept a leading minus sign.
+  pub signed: bool,
+}
+
+impl NumericFormatter {
+  /// Accept only the digits.
+  pub const DIGITS: Self = Self { decimal: false, signed: false };
+  /// Accept the integers.
+  pub const INTEGER: Self = Self { decimal: false, signed: true };
+  /// Accept the decimal numbers.
+  pub const DECIMAL: Self = Self { decimal: true, signed: true };
+}
+
+impl InputFormatter for NumericFormatter {
+  fn format_insert(&self, text: &str, at: usize, insert: &str) -> String {
+    let mut has_point = text.contains('.');
+    let mut out = String::new();
+    for c in insert.chars().map(to_ascii_digit) {
+      let accept = match c {
+        '0'..='9' => true,
+        '-' => self.signed && at == 0 && out.is_empty() && !text.starts_with('-'),
+        '.' => self.decimal && !has_point,
+        _ => false,
+      };
+      if accept {
+        has_point |= c == '.';
+        out.push(c);
+      }
+    }
+    out
+  }
+
+  fn allow_ime(&self) -> bool { false }
+}
+
+fn to_ascii_digit(c: char) -> char {
+  match c {
+    '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+    c => c,
+  }
+}
+
+/// Limit the text to at most the given number of characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxLength(pub usize);
+
+impl InputFormatter for MaxLength {
+  fn format_insert(&self, text: &str, _: usize, insert: &str) -> String {
+    let remain = self.0.saturating_sub(text.chars().count());
+    insert.chars().take(remain).collect()
+  }
+}
+
+/// Convert the inserted text to uppercase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpperCase;
+
+impl InputFormatter for UpperCase {
+  fn format_insert(&self, _: &str, _: usize, insert: &str) -> String { insert.to_uppercase() }
+}
+
+/// Fill the inserted text into the slots of a mask, such as `(###) ###-####`
+/// for phone numbers or `####-##-##` for dates.
+///
+/// In the mask, `#` is a slot for a digit, `A` for a letter and `*` for a
+/// letter or digit. The other characters are literals, they're inserted
+/// before the next accepted character, so the user only types the slots.
+/// The characters beyond the mask are dropped.
+///
+/// The whole text is filled into the mask again after each edit, so the
+/// characters after an insertion or a deletion move to their slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskFormatter {
+  mask: CowArc<str>,
+}
+
+impl MaskFormatter {
+  pub fn new(mask: impl Into<CowArc<str>>) -> Self { Self { mask: mask.into() } }
+
+  pub fn mask(&self) -> &CowArc<str> { &self.mask }
+}
+
+fn is_slot(c: char) -> bool { matches!(c, '#' | 'A' | '*') }
+
+fn slot_accepts(slot: char, c: char) -> bool {
+  match slot {
+    '#' => c.is_ascii_digit(),
+    'A' => c.is_alphabetic(),
+    _ => c.is_alphanumeric(),
+  }
+}
+
+impl InputFormatter for MaskFormatter {
+  fn format_insert(&self, text: &str, at: usize, insert: &str) -> String {
+    let mut slots = self.mask.chars().skip(text[..at].chars().count());
+    let mut chars = insert.chars().peekable();
+    let mut out = String::new();
+    // The literals are only inserted with the character after them.
+    let mut literals = String::new();
+    while let Some(&c) = chars.peek() {
+      let Some(slot) = slots.next() else { break };
+      if !is_slot(slot) {
+        literals.push(slot);
+        if c == slot {
+          chars.next();
+          out.push_str(&literals);
+          literals.clear();
+        }
+        continue;
+      }
+      let Some(c) = chars.by_ref().find(|c| slot_accepts(slot, *c)) else { break };
+      out.push_str(&literals);
+      literals.clear();
+      out.push(c);
+    }
+    out
+  }
+
+  fn format_text(&self, text: &str, caret: usize) -> Option<(String, usize)> {
+    // Collect the characters in the slots and count the ones before the caret.
+    // A character matching a literal of the mask before the next slot is
+    // skipped, so a deleted literal doesn't shift the characters after it.
+    let mask = self.mask.chars().collect::<Vec<_>>();
+    let mut pos = 0;
+    let mut typed = vec![];
+    let mut before = 0;
+    for (idx, c) in text.char_indices() {
+      let literals = mask[pos..]
+        .iter()
+        .take_while(|m| !is_slot(**m))
+        .count();
+      if let Some(i) = mask[pos..pos + literals]
+        .iter()
+        .position(|m| *m == c)
+      {
+        pos += i + 1;
+        continue;
+      }
+      if !c.is_alphanumeric() {
+        continue;
+      }
+      pos = (pos + literals + 1).min(mask.len());
+      if idx < caret {
+        before += 1;
+      }
+      typed.push(c);
+    }
+
+    let mut typed = typed.into_iter().enumerate().peekable();
+    let mut out = String::new();
+    let mut new_caret = 0;
+    let mut literals = String::new();
+    for slot in self.mask.chars() {
+      if typed.peek().is_none() {
+        break;
+      }
+      if !is_slot(slot) {
+        literals.push(slot);
+        continue;
+      }
+      let Some((i, c)) = typed
+        .by_ref()
+        .find(|(_, c)| slot_accepts(slot, *c))
+      else {
+        break;
+      };
+      out.push_str(&literals);
+      literals.clear();
+      out.push(c);
+      if i < before {
+        new_caret = out.len();
+      }
+    }
+    Some((out, new_caret))
+  }
+
+  fn allow_ime(&self) -> bool { false }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn numeric() {
+    let f = NumericFormatter::DECIMAL;
+    assert_eq!(f.format_insert("", 0, "-12a.5.3"), "-12.53");
+    assert_eq!(f.format_insert("1.5", 3, "2.1"), "21");
+    assert_eq!(f.format_insert("15", 1, "-"), "");
+    assert_eq!(f.format_insert("", 0, "１２３"), "123");
+    assert_eq!(NumericFormatter::DIGITS.format_insert("", 0, "-1.2"), "12");
+  }
+
+  #[test]
+  fn max_length_and_uppercase() {
+    let f = Formatter::new(UpperCase).then(MaxLength(4));
+    assert_eq!(f.format_insert("AB", 2, "cdef"), "CD");
+    assert_eq!(f.format_insert("ABCD", 4, "e"), "");
+  }
+
+  #[test]
+  fn mask() {
+    let phone = MaskFormatter::new("(###) ###-####");
+    assert_eq!(phone.format_insert("", 0, "5551234567"), "(555) 123-4567");
+    assert_eq!(phone.format_insert("", 0, "555"), "(555");
+    assert_eq!(phone.format_insert("(555", 4, "1"), ") 1");
+    assert_eq!(phone.format_insert("(555", 4, ") 1"), ") 1");
+    assert_eq!(phone.format_insert("", 0, "x"), "");
+    assert_eq!(phone.format_insert("(555) 123-4567", 14, "8"), "");
+
+    let date = MaskFormatter::new("####-##-##");
+    assert_eq!(date.format_insert("", 0, "2024/01/31"), "2024-01-31");
+  }
+
+  #[test]
+  fn mask_realign() {
+    let phone = MaskFormatter::new("(###) ###-####");
+    let realign = |text: &str, caret| phone.format_text(text, caret).unwrap();
+    // Inserted `9` after `(5`.
+    assert_eq!(realign("(5955) 123", 3), ("(595) 512-3".into(), 3));
+    // Deleted the `5` before `)`.
+    assert_eq!(realign("(55) 123", 3), ("(551) 23".into(), 3));
+    // Deleted the space, the literals are restored.
+    assert_eq!(realign("(555)123", 5), ("(555) 123".into(), 4));
+    assert_eq!(realign("(", 0), (String::new(), 0));
+
+    let code = MaskFormatter::new("+1 (###)");
+    assert_eq!(code.format_text("+1 (15", 6), Some(("+1 (15".into(), 6)));
+    // Deleted the `+`, the literal `1` isn't taken as typed.
+    assert_eq!(code.format_text("1 (15", 5), Some(("+1 (15".into(), 6)));
+    // Deleted the `(`.
+    assert_eq!(code.format_text("+1 15", 5), Some(("+1 (15".into(), 6)));
+  }
+}
@@ -0,0 +1,257 @@
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy)]
+pub struct NumberChanged {
+  pub from: f32,
+  pub to: f32,
+}
+
+pub type NumberChangedEvent = CustomEvent<NumberChanged>;
+
+/// An input that edits a number.
+///
+/// The user can type the number, or step it by the `step` with the arrow up
+/// and down keys, or with the mouse wheel when it's focused. The value is
+/// clamped in the range of `min` and `max`, the typed text out of the range is
+/// restored to the value when the input loses focus.
+///
+/// The input emits the [`NumberChangedEvent`] when the user changes the value.
+///
+/// # Example
+///
+/// ```rust no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @NumberInput { value: 5., min: 0., max: 10., step: 0.5 }
+/// };
+/// ```
+#[declare(validate)]
+pub struct NumberInput {
+  #[declare(default, setter = set_value, event = NumberChanged.to)]
+  value: f32,
+  #[declare(default = f32::MIN, setter = set_min)]
+  min: f32,
+  #[declare(default = f32::MAX, setter = set_max)]
+  max: f32,
+  /// The amount that the arrow keys and the wheel change the value by.
+  #[declare(default = 1., setter = set_step)]
+  step: f32,
+}
+
+impl NumberInput {
+  fn declare_validate(mut self) -> Result<Self, std::convert::Infallible> {
+    if self.min > self.max {
+      std::mem::swap(&mut self.min, &mut self.max);
+    }
+    self.set_step(self.step);
+    self.set_value(self.value);
+    Ok(self)
+  }
+
+  pub fn value(&self) -> f32 { self.value }
+
+  pub fn min(&self) -> f32 { self.min }
+
+  pub fn max(&self) -> f32 { self.max }
+
+  pub fn step(&self) -> f32 { self.step }
+
+  pub fn set_value(&mut self, value: f32) { self.value = value.clamp(self.min, self.max); }
+
+  pub fn set_min(&mut self, min: f32) {
+    self.min = min.min(self.max);
+    self.set_value(self.value);
+  }
+
+  pub fn set_max(&mut self, max: f32) {
+    self.max = max.max(self.min);
+    self.set_value(self.value);
+  }
+
+  pub fn set_step(&mut self, step: f32) { self.step = if step > 0. { step } else { 1. }; }
+
+  /// Step the value by `steps` times of the step, the result is rounded to the
+  /// precision of the step, so it doesn't accumulate the float errors.
+  fn stepped(&self, steps: f32) -> f32 {
+    let precision = self
+      .step
+      .to_string()
+      .split_once('.')
+      .map_or(0, |(_, fract)| fract.len());
+    let scale = 10f32.powi(precision as i32);
+    let v = ((self.value + steps * self.step) * scale).round() / scale;
+    v.clamp(self.min, self.max)
+  }
+
+  fn request_change(&self, to: f32, e: &CommonEvent) {
+    let from = self.value;
+    if from != to {
+      e.window()
+        .bubble_custom_event(e.target(), NumberChanged { from, to });
+    }
+  }
+
+  fn is_in_range(&self, v: f32) -> bool { self.min <= v && v <= self.max }
+}
+
+impl Compose for NumberInput {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let mut input = @Input { formatter: Formatter::new(NumericFormatter::DECIMAL) };
+      $write(input).set_text(&$read(this).value.to_string());
+      let focused = input.is_focused();
+
+      // Sync the text when the value is changed but not by typing.
+      let u = watch!($read(this).value).subscribe(move |v| {
+        let mut input = $write(input);
+        if input.text().parse::<f32>().ok() != Some(v) {
+          let text = v.to_string();
+          input.set_text(&text);
+          input.select(text.len(), text.len());
+        }
+      });
+
+      @(input) {
+        on_custom: move |e: &mut TextChangedEvent| {
+          e.stop_propagation();
+          let this = $read(this);
+          if let Ok(v) = e.data().text.parse::<f32>()
+            && this.is_in_range(v) {
+            this.request_change(v, e);
+          }
+        },
+        on_key_down: move |e| {
+          let steps = match e.key() {
+            VirtualKey::Named(NamedKey::ArrowUp) => 1.,
+            VirtualKey::Named(NamedKey::ArrowDown) => -1.,
+            _ => return,
+          };
+          let this = $read(this);
+          this.request_change(this.stepped(steps), e);
+        },
+        on_wheel: move |e| {
+          if *$read(focused) && e.delta_y != 0. {
+            e.stop_propagation();
+            let this = $read(this);
+            this.request_change(this.stepped(e.delta_y.signum()), e);
+          }
+        },
+        on_focus_out: move |_| {
+          let text = $read(this).value.to_string();
+          let mut input = $write(input);
+          if **input.text() != *text {
+            input.set_text(&text);
+          }
+        },
+        on_disposed: move |_| u.unsubscribe(),
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  fn press(wnd: &TestWindow, code: KeyCode, key: NamedKey) {
+    wnd.process_keyboard_event(
+      PhysicalKey::Code(code),
+      VirtualKey::Named(key),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn step_and_clamp() {
+    reset_test_env!();
+
+    let (value, w_value) = split_value(0.);
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @NumberInput {
+        auto_focus: true,
+        value: 1.,
+        max: 1.5,
+        step: 0.1,
+        on_custom: move |e: &mut NumberChangedEvent| *$write(w_value) = e.data().to,
+      }
+    });
+    wnd.draw_frame();
+
+    press(&wnd, KeyCode::ArrowUp, NamedKey::ArrowUp);
+    assert_eq!(*value.read(), 1.1);
+    for _ in 0..10 {
+      press(&wnd, KeyCode::ArrowUp, NamedKey::ArrowUp);
+    }
+    assert_eq!(*value.read(), 1.5);
+    press(&wnd, KeyCode::ArrowDown, NamedKey::ArrowDown);
+    assert_eq!(*value.read(), 1.4);
+
+    wnd.process_receive_chars("a".into());
+    wnd.draw_frame();
+    assert_eq!(*value.read(), 1.4);
+  }
+
+  #[test]
+  fn wheel_steps_when_focused() {
+    reset_test_env!();
+
+    let (value, w_value) = split_value(0.);
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @NumberInput {
+        value: 1.,
+        step: 0.5,
+        on_custom: move |e: &mut NumberChangedEvent| *$write(w_value) = e.data().to,
+      }
+    });
+    wnd.draw_frame();
+    wnd.process_cursor_move(Point::new(5., 5.));
+
+    wnd.process_wheel(0., 1.);
+    wnd.draw_frame();
+    assert_eq!(*value.read(), 0.);
+
+    wnd.process_mouse_press(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.process_mouse_release(Box::new(DummyDeviceId), MouseButtons::PRIMARY);
+    wnd.draw_frame();
+    wnd.process_wheel(0., 1.);
+    wnd.draw_frame();
+    assert_eq!(*value.read(), 1.5);
+    // A wheel event steps once whatever its delta is.
+    wnd.process_wheel(0., -3.);
+    wnd.draw_frame();
+    assert_eq!(*value.read(), 1.);
+  }
+
+  #[test]
+  fn bind_form_field() {
+    reset_test_env!();
+
+    let form = Stateful::new(Form::default());
+    let wnd = TestWindow::from_widget(fn_widget! {
+      @(form.clone_writer()) {
+        @FormField {
+          name: "count",
+          initial_value: 1f32,
+          @NumberInput { auto_focus: true, value: 1. }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    press(&wnd, KeyCode::ArrowUp, NamedKey::ArrowUp);
+    let form = form.read();
+    // The value is the number, not the text of the input.
+    assert_eq!(form.value("count"), Some(&FieldValue::Number(2.)));
+    assert!(form.status("count").unwrap().dirty);
+  }
+}
@@ -30,27 +30,66 @@ impl<T: Default + VisualText + EditText + Clone + 'static> Compose for BasicEdit
         .map(move |v| v.then(|| fn_widget!{ Self::caret_widget($writer(this)) }));
 
       let mut caret = FatObj::new(caret);
-      @Stack {
-        fit: StackFit::Passthrough,
-        @(text) {
-          margin: pipe!(EdgeInsets::only_right(*$read(caret.layout_width()))),
-          on_focus_in: move |e| { e.window().set_ime_allowed(true); },
-          on_focus_out: move|e| { e.window().set_ime_allowed(false); },
-          on_chars: move |e| {
-            let mut this = $write(this);
-            if !this.chars_handle(e) {
-              this.forget_modifies();
-            }
-          },
-          on_key_down: move |k| {
-            let mut this = $write(this);
-            if !this.keys_handle(k) {
-              this.forget_modifies();
-            }
-          },
-          on_ime_pre_edit: move|e| { $write(this).process_pre_edit(e);},
+      // The password and the formatter may change whether the IME is allowed
+      // while the text is focused.
+      let focused = text.is_focused();
+      let wnd = BuildCtx::get().window();
+      let ime_sync = watch!($read(this).text().ime_allowed())
+        .distinct_until_changed()
+        .subscribe(move |allowed| {
+          if *$read(focused) {
+            wnd.set_ime_allowed(allowed);
+          }
+        });
+      let shortcuts = Shortcuts::new()
+        .with_binding("Mod+V", "edit.paste")
+        .with_binding("Mod+X", "edit.cut")
+        .with_command("edit.paste", move |e| {
+          let mut this = $write(this);
+          if this.paste() {
+            this.notify_text_changed(e);
+          } else {
+            this.forget_modifies();
+          }
+        })
+        .with_command("edit.cut", move |e| {
+          let mut this = $write(this);
+          if this.cut() {
+            this.notify_text_changed(e);
+          } else {
+            this.forget_modifies();
+          }
+        });
+      @(shortcuts) {
+        @Stack {
+          fit: StackFit::Passthrough,
+          @(text) {
+            margin: pipe!(EdgeInsets::only_right(*$read(caret.layout_width()))),
+            on_focus_in: move |e| {
+              e.window().set_ime_allowed($read(this).text().ime_allowed());
+            },
+            on_focus_out: move|e| { e.window().set_ime_allowed(false); },
+            on_disposed: move |_| ime_sync.unsubscribe(),
+            on_chars: move |e| {
+              let mut this = $write(this);
+              if this.chars_handle(e) {
+                this.notify_text_changed(e);
+              } else {
+                this.forget_modifies();
+              }
+            },
+            on_key_down: move |k| {
+              let mut this = $write(this);
+              if this.edit_with_key(k) {
+                this.notify_text_changed(k);
+              } else {
+                this.forget_modifies();
+              }
+            },
+            on_ime_pre_edit: move|e| { $write(this).process_pre_edit(e);},
+          }
+          @InParentLayout{ @ { caret } }
         }
-        @InParentLayout{ @ { caret } }
       }
     }
     .into_widget()
@@ -132,46 +171,27 @@ impl<T: EditText + 'static> BasicEditor<T> {
     false
   }
 
-  fn keys_handle(&mut self, event: &KeyboardEvent) -> bool {
-    let mut deal = false;
-    if event.with_command_key() {
-      deal = self.edit_with_command(event);
-    }
-    if !deal {
-      deal = self.edit_with_key(event);
+  fn paste(&mut self) -> bool {
+    let clipboard = AppCtx::clipboard();
+    let txt = clipboard.borrow_mut().read_text();
+    if let Ok(txt) = txt {
+      self.insert(&txt);
+      return true;
     }
-    deal
+    false
   }
 
-  fn edit_with_command(&mut self, event: &KeyboardEvent) -> bool {
-    if !event.with_command_key() {
+  fn cut(&mut self) -> bool {
+    let rg = self.cluster_rg();
+    if rg.is_empty() || !self.text().can_copy() {
       return false;
     }
-    // use the physical key to make sure the keyboard with different
-    // layout use the same key as shortcut.
-    match event.key_code() {
-      PhysicalKey::Code(KeyCode::KeyV) => {
-        let clipboard = AppCtx::clipboard();
-        let txt = clipboard.borrow_mut().read_text();
-        if let Ok(txt) = txt {
-          self.insert(&txt);
-          return true;
-        }
-      }
-      PhysicalKey::Code(KeyCode::KeyX) => {
-        let rg = self.cluster_rg();
-        if !rg.is_empty() {
-          let txt = self.substr(rg).to_string();
-          self.del_sel();
-          let clipboard = AppCtx::clipboard();
-          let _ = clipboard.borrow_mut().clear();
-          let _ = clipboard.borrow_mut().write_text(&txt);
-          return true;
-        }
-      }
-      _ => {}
-    };
-    false
+    let txt = self.substr(rg).to_string();
+    self.del_sel();
+    let clipboard = AppCtx::clipboard();
+    let _ = clipboard.borrow_mut().clear();
+    let _ = clipboard.borrow_mut().write_text(&txt);
+    true
   }
 
   fn edit_with_key(&mut self, key: &KeyboardEvent) -> bool {
@@ -196,6 +216,13 @@ impl<T: EditText + 'static> BasicEditor<T> {
     }
   }
 
+  /// Bubble a [`TextChanged`] event from the target of the user edit.
+  fn notify_text_changed(&self, e: &CommonEvent) {
+    let text = self.text().plain_text();
+    e.window()
+      .bubble_custom_event(e.target(), TextChanged { text });
+  }
+
   fn insert(&mut self, chars: &str) -> usize {
     let del_rg = self.del_sel();
     let len = self.text_mut().insert_str(del_rg.start, chars);
@@ -3,10 +3,7 @@ use std::{
   ops::Range,
 };
 
-use ribir_core::{
-  prelude::*,
-  text::{single_style_paragraph_style, single_style_span_style},
-};
+use ribir_core::{prelude::*, text::single_style_span_style};
 
 use super::{CaretPosition, edit_text::BaseText};
 
@@ -53,7 +50,7 @@ pub trait VisualText: BaseText {
 impl VisualText for CowArc<str> {
   fn layout_glyphs(&self, clamp: BoxClamp, ctx: &MeasureCtx) -> ParagraphLayoutRef {
     let style = Provider::of::<TextStyle>(ctx).unwrap();
-    let paragraph_style = single_style_paragraph_style(&style, TextAlign::Start);
+    let paragraph_style = provided_paragraph_style(&style, TextAlign::Start, ctx);
     let paragraph = AppCtx::text_services()
       .paragraph(AttributedText::styled(self.to_string(), single_style_span_style(&style, None)));
     paragraph.layout(&style, &paragraph_style, clamp)
@@ -94,6 +91,35 @@ impl<T: VisualText + 'static> Render for TextGlyphs<T> {
     size
   }
 
+  fn baseline(&self, _size: Size, _ctx: &mut PlaceCtx) -> Option<f32> {
+    self.glyphs()?.first_baseline()
+  }
+
+  fn min_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .text
+      .layout_glyphs(BoxClamp::default(), ctx)
+      .size()
+      .width
+  }
+
+  fn max_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self
+      .text
+      .layout_glyphs(BoxClamp::default(), ctx)
+      .size()
+      .width
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    let clamp = BoxClamp::max_size(Size::new(width, f32::INFINITY));
+    self.text.layout_glyphs(clamp, ctx).size().height
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.min_intrinsic_height(width, ctx)
+  }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let box_rect = Rect::from_size(ctx.box_size().unwrap());
     if ctx
@@ -258,7 +284,8 @@ mod tests {
     let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf";
     let _ = text_services.register_font_file(std::path::Path::new(&path));
 
-    let paragraph_style = ParagraphStyle { text_align: TextAlign::Start, wrap };
+    let paragraph_style =
+      ParagraphStyle { text_align: TextAlign::Start, wrap, ..Default::default() };
     let paragraph = text_services.paragraph(AttributedText::styled(
       text.to_owned(),
       SpanStyle {
@@ -273,6 +300,7 @@ mod tests {
         line_height: Some(LineHeight::Px(16.)),
         brush: None,
         decoration: None,
+        ..Default::default()
       },
     ));
     let text_style = TextStyle {
@@ -284,6 +312,7 @@ mod tests {
       letter_space: 0.,
       line_height: LineHeight::Px(16.),
       overflow: TextOverflow::AutoWrap,
+      ..<_>::default()
     };
     paragraph.layout(&text_style, &paragraph_style, BoxClamp::max_size(size))
   }
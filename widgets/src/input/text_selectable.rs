@@ -57,76 +57,87 @@ impl<T: VisualText + Clone + 'static> Compose for TextSelectable<T> {
   fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
     fn_widget! {
       let selection = part_writer!(&mut this.selection);
-
-      @PointerSelectRegion {
-        on_custom: {
-          move |e: &mut PointerSelectEvent| {
-            if let PointerSelectData::Move{ from, to } |
-              PointerSelectData::End { from, to } = e.data() {
-                let new_sel = $read(this).glyphs().map(|g| {
-                  Selection {
-                    from: g.caret_position_from_pos(*from),
-                    to: g.caret_position_from_pos(*to),
+      let shortcuts = Shortcuts::new()
+        .with_binding("Mod+C", "edit.copy")
+        .with_binding("Mod+A", "edit.select_all")
+        .with_command("edit.copy", move |_| $read(this).copy_selection())
+        .with_command("edit.select_all", move |_| {
+          let all = $read(this).select_all();
+          if let Some(all) = all {
+            *$write(selection) = all;
+          }
+        });
+
+      @(shortcuts) {
+        @PointerSelectRegion {
+          on_custom: {
+            move |e: &mut PointerSelectEvent| {
+              if let PointerSelectData::Move{ from, to } |
+                PointerSelectData::End { from, to } = e.data() {
+                  let new_sel = $read(this).glyphs().map(|g| {
+                    Selection {
+                      from: g.caret_position_from_pos(*from),
+                      to: g.caret_position_from_pos(*to),
+                    }
+                  });
+                  if let Some(new_sel) = new_sel {
+                    *$write(selection) = new_sel;
                   }
-                });
-                if let Some(new_sel) = new_sel {
-                  *$write(selection) = new_sel;
-                }
+              }
             }
-          }
-        },
-        on_key_down: move |e| {
-          let new_sel = $read(this).select_with_key(e);
-          if let Some(new_sel) = new_sel {
-            *$write(selection) = new_sel;
-          }
-        },
-        on_pointer_down: move |e| {
-          let caret = $read(this).glyphs().map(|g| g.caret_position_from_pos(e.position()));
-          if let Some(caret) = caret {
-            let mut selection = $write(selection);
-            if e.with_shift_key() {
-              selection.to = caret;
-            } else {
-              selection.from = caret;
-              selection.to = caret;
+          },
+          on_key_down: move |e| {
+            let new_sel = $read(this).select_with_key(e);
+            if let Some(new_sel) = new_sel {
+              *$write(selection) = new_sel;
             }
-          }
-        },
-        on_double_tap: move |e| {
-          let this = $read(this);
-          let caret = this
-            .glyphs()
-            .map(|glyphs| glyphs.caret_position_from_pos(e.position()));
-          if let Some(caret) = caret {
-            let rg = this.text().select_token(caret.cluster);
-            let mut selection = $write(selection);
-            selection.from = CaretPosition::new(rg.start);
-            selection.to = CaretPosition::new(rg.end);
-          }
-        },
-        @Stack {
-          @NoAffectedParentSize {
-            @Stack {
-              @pipe! {
-                let rcs = $read(this).glyphs()
-                  .map(|glyphs| glyphs.select_range(&$read(selection).cluster_rg()))
-                  .unwrap_or_default();
-                rcs.into_iter().map(move |rc| {
-                  @Container {
-                    class: TEXT_SELECTION,
-                    x: rc.origin.x,
-                    y: rc.origin.y,
-                    size: rc.size,
-                  }
-                })
+          },
+          on_pointer_down: move |e| {
+            let caret = $read(this).glyphs().map(|g| g.caret_position_from_pos(e.position()));
+            if let Some(caret) = caret {
+              let mut selection = $write(selection);
+              if e.with_shift_key() {
+                selection.to = caret;
+              } else {
+                selection.from = caret;
+                selection.to = caret;
+              }
+            }
+          },
+          on_double_tap: move |e| {
+            let this = $read(this);
+            let caret = this
+              .glyphs()
+              .map(|glyphs| glyphs.caret_position_from_pos(e.position()));
+            if let Some(caret) = caret {
+              let rg = this.text().select_token(caret.cluster);
+              let mut selection = $write(selection);
+              selection.from = CaretPosition::new(rg.start);
+              selection.to = CaretPosition::new(rg.end);
+            }
+          },
+          @Stack {
+            @NoAffectedParentSize {
+              @Stack {
+                @pipe! {
+                  let rcs = $read(this).glyphs()
+                    .map(|glyphs| glyphs.select_range(&$read(selection).cluster_rg()))
+                    .unwrap_or_default();
+                  rcs.into_iter().map(move |rc| {
+                    @Container {
+                      class: TEXT_SELECTION,
+                      x: rc.origin.x,
+                      y: rc.origin.y,
+                      size: rc.size,
+                    }
+                  })
+                }
               }
             }
+            @part_writer!(&mut this.text)
           }
-          @part_writer!(&mut this.text)
         }
       }
-
     }
     .into_widget()
   }
@@ -139,6 +150,21 @@ fn is_move_by_word(event: &KeyboardEvent) -> bool {
   return event.with_ctrl_key();
 }
 
+/// The named key in the motions of a horizontal line, the caret moves down a
+/// vertical line by `ArrowDown`, and crosses to the next line on the left by
+/// `ArrowLeft`.
+fn logical_key(key: &VirtualKey, writing_mode: WritingMode) -> Option<NamedKey> {
+  let VirtualKey::Named(key) = key else { return None };
+  let key = match (writing_mode, key) {
+    (WritingMode::VerticalRl, NamedKey::ArrowUp) => NamedKey::ArrowLeft,
+    (WritingMode::VerticalRl, NamedKey::ArrowDown) => NamedKey::ArrowRight,
+    (WritingMode::VerticalRl, NamedKey::ArrowLeft) => NamedKey::ArrowDown,
+    (WritingMode::VerticalRl, NamedKey::ArrowRight) => NamedKey::ArrowUp,
+    (_, key) => *key,
+  };
+  Some(key)
+}
+
 impl<T> TextSelectable<T> {
   pub fn cluster_rg(&self) -> Range<usize> { self.selection.cluster_rg() }
 }
@@ -153,14 +179,11 @@ impl Selection {
 
 impl<T: BaseText> TextSelectable<T> {
   fn select_with_key(&self, event: &KeyboardEvent) -> Option<Selection> {
-    if let Ok(selection) = self.deal_with_command(event) {
-      return selection;
-    }
     let glyphs = self.glyphs()?;
     let cur_sel = &self.selection;
     let text = &self.text;
-    let new_caret = match event.key() {
-      VirtualKey::Named(NamedKey::ArrowLeft) => {
+    let new_caret = match logical_key(event.key(), glyphs.writing_mode()) {
+      Some(NamedKey::ArrowLeft) => {
         if is_move_by_word(event) {
           let mut rg = text.text().select_token(cur_sel.to.cluster);
           if rg.start == cur_sel.to.cluster && cur_sel.to.cluster > 1 {
@@ -173,7 +196,7 @@ impl<T: BaseText> TextSelectable<T> {
           glyphs.prev(cur_sel.to)
         }
       }
-      VirtualKey::Named(NamedKey::ArrowRight) => {
+      Some(NamedKey::ArrowRight) => {
         if is_move_by_word(event) {
           let mut rg = text.text().select_token(cur_sel.to.cluster);
           if rg.end == cur_sel.to.cluster {
@@ -186,10 +209,10 @@ impl<T: BaseText> TextSelectable<T> {
           glyphs.next(cur_sel.to)
         }
       }
-      VirtualKey::Named(NamedKey::ArrowUp) => glyphs.up(cur_sel.to),
-      VirtualKey::Named(NamedKey::ArrowDown) => glyphs.down(cur_sel.to),
-      VirtualKey::Named(NamedKey::Home) => glyphs.line_begin(cur_sel.to),
-      VirtualKey::Named(NamedKey::End) => glyphs.line_end(cur_sel.to),
+      Some(NamedKey::ArrowUp) => glyphs.up(cur_sel.to),
+      Some(NamedKey::ArrowDown) => glyphs.down(cur_sel.to),
+      Some(NamedKey::Home) => glyphs.line_begin(cur_sel.to),
+      Some(NamedKey::End) => glyphs.line_end(cur_sel.to),
       _ => return None,
     };
 
@@ -197,34 +220,22 @@ impl<T: BaseText> TextSelectable<T> {
     Some(Selection { from, to: new_caret })
   }
 
-  fn deal_with_command(&self, event: &KeyboardEvent) -> Result<Option<Selection>, ()> {
-    if !event.with_command_key() {
-      return Err(());
+  fn copy_selection(&self) {
+    if !self.text.text().can_copy() {
+      return;
     }
-    let text = self.text.text();
-    match event.key_code() {
-      PhysicalKey::Code(KeyCode::KeyC) => {
-        let rg = self.cluster_rg();
-        let text = text.substr(rg);
-        if !text.is_empty() {
-          let clipboard = AppCtx::clipboard();
-          let _ = clipboard.borrow_mut().clear();
-          let _ = clipboard.borrow_mut().write_text(&text);
-        }
-        Ok(None)
-      }
-      PhysicalKey::Code(KeyCode::KeyA) => {
-        if text.len() > 0 {
-          let selection =
-            Selection { from: CaretPosition::new(0), to: CaretPosition::new(text.len()) };
-          Ok(Some(selection))
-        } else {
-          Ok(None)
-        }
-      }
-      _ => Err(()),
+    let text = self.text.text().substr(self.cluster_rg());
+    if !text.is_empty() {
+      let clipboard = AppCtx::clipboard();
+      let _ = clipboard.borrow_mut().clear();
+      let _ = clipboard.borrow_mut().write_text(&text);
     }
   }
+
+  fn select_all(&self) -> Option<Selection> {
+    let len = self.text.text().len();
+    (len > 0).then(|| Selection { from: CaretPosition::new(0), to: CaretPosition::new(len) })
+  }
 }
 
 impl Selection {
@@ -2,5 +2,9 @@ pub mod text_clamp;
 pub use text_clamp::*;
 pub mod no_affected_parent_size;
 pub use no_affected_parent_size::*;
+mod intrinsic_width;
+pub use intrinsic_width::*;
 mod linear;
 pub use linear::*;
+mod grid;
+pub use grid::*;
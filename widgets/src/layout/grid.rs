@@ -0,0 +1,766 @@
+use std::cell::RefCell;
+
+use ribir_core::prelude::{tracing::warn, *};
+
+use super::{Align, Direction, is_rtl_main_axis};
+
+/// A two-dimensional layout that places its children in the cells of rows and
+/// columns, like CSS Grid.
+///
+/// - `columns` and `rows` define the explicit tracks, a track can be a fixed
+///   size, a fraction of the free space, fit its content, or be sized between a
+///   minimum and a maximum, see [`GridTrack`].
+/// - A child can be wrapped by a [`GridItem`] to specify its row, column,
+///   spans, or a named area defined by `areas`.
+/// - Children without a position are placed automatically, row by row or column
+///   by column according to `auto_flow`, tracks are added as needed with the
+///   size of `auto_rows` or `auto_columns`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _dashboard = fn_widget! {
+///   @Grid {
+///     columns: vec![GridTrack::Fixed(200.), GridTrack::Fr(1.)],
+///     rows: vec![GridTrack::Auto, GridTrack::Fr(1.)],
+///     areas: GridAreas::new(["header header", "sidebar main"]),
+///     column_gap: 8.,
+///     row_gap: 8.,
+///     @GridItem { area: "header", @Text { text: "Dashboard" } }
+///     @GridItem { area: "sidebar", @Text { text: "Menu" } }
+///     @GridItem { area: "main", @Text { text: "Content" } }
+///   }
+/// };
+/// ```
+#[derive(Default, MultiChild, Declare)]
+pub struct Grid {
+  /// The explicit column tracks.
+  #[declare(default)]
+  pub columns: Vec<GridTrack>,
+  /// The explicit row tracks.
+  #[declare(default)]
+  pub rows: Vec<GridTrack>,
+  /// The size of the columns created by the placement beyond `columns`.
+  #[declare(default)]
+  pub auto_columns: GridTrack,
+  /// The size of the rows created by the placement beyond `rows`.
+  #[declare(default)]
+  pub auto_rows: GridTrack,
+  /// The named areas that the children can be placed into by
+  /// [`GridItem::area`].
+  #[declare(default)]
+  pub areas: GridAreas,
+  /// The gap between columns.
+  #[declare(default)]
+  pub column_gap: f32,
+  /// The gap between rows.
+  #[declare(default)]
+  pub row_gap: f32,
+  /// How the children without a position are placed.
+  #[declare(default)]
+  pub auto_flow: GridAutoFlow,
+  /// How the children are aligned horizontally in their cells.
+  #[declare(default = Align::Stretch)]
+  pub justify_items: Align,
+  /// How the children are aligned vertically in their cells.
+  #[declare(default = Align::Stretch)]
+  pub align_items: Align,
+  #[declare(skip)]
+  cells: RefCell<Vec<Rect>>,
+}
+
+/// The size of a row or a column of a [`Grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GridTrack {
+  /// A fixed size in pixels.
+  Fixed(f32),
+  /// A fraction of the free space, like `1fr` in CSS. If the grid has no
+  /// bounded size, the fractions are applied to the size of the contents.
+  Fr(f32),
+  /// Fits the largest child in the track.
+  #[default]
+  Auto,
+  /// At least `min` pixels, and grows up to the `TrackMax`.
+  MinMax(f32, TrackMax),
+}
+
+/// The maximum of a [`GridTrack::MinMax`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrackMax {
+  /// Grows to fit the contents but not larger than the pixels.
+  Fixed(f32),
+  /// Takes a fraction of the free space.
+  Fr(f32),
+  /// Grows to fit the contents.
+  Auto,
+}
+
+/// How the children without a position are placed in a [`Grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GridAutoFlow {
+  /// Fill the rows one by one, adding new rows as necessary.
+  #[default]
+  Row,
+  /// Fill the columns one by one, adding new columns as necessary.
+  Column,
+}
+
+/// The position of a child in a [`Grid`].
+///
+/// The `row` and `column` are zero-based, a child that has no `row` or no
+/// `column` is placed automatically in that axis. If `area` is specified, it
+/// overrides the other positions.
+#[derive(Debug, Clone, PartialEq)]
+#[declare(simple)]
+pub struct GridItem {
+  #[declare(custom, default)]
+  pub row: Option<usize>,
+  #[declare(custom, default)]
+  pub column: Option<usize>,
+  /// How many rows the child spans, at least 1.
+  #[declare(strict, default = 1)]
+  pub row_span: usize,
+  /// How many columns the child spans, at least 1.
+  #[declare(strict, default = 1)]
+  pub column_span: usize,
+  /// The name of an area defined by [`Grid::areas`].
+  #[declare(custom, default)]
+  pub area: Option<CowArc<str>>,
+}
+
+/// The rectangle of cells an item or a named area covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridArea {
+  pub row: usize,
+  pub column: usize,
+  pub row_span: usize,
+  pub column_span: usize,
+}
+
+/// Named areas of a [`Grid`] defined by a template, like
+/// `grid-template-areas` in CSS.
+///
+/// Every string of the template is a row, and every whitespace separated name
+/// in a row is a cell, `.` means an unnamed cell. An area covers the
+/// rectangle of all the cells with its name.
+///
+/// ```
+/// use ribir_widgets::prelude::*;
+///
+/// let areas = GridAreas::new(["header header", "sidebar main"]);
+/// let header = areas.area("header").unwrap();
+/// assert_eq!((header.row, header.column, header.column_span), (0, 0, 2));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GridAreas {
+  rows: usize,
+  columns: usize,
+  areas: Vec<(CowArc<str>, GridArea)>,
+}
+
+impl GridAreas {
+  pub fn new<'a>(template: impl IntoIterator<Item = &'a str>) -> Self {
+    let mut this = GridAreas::default();
+    for (row, line) in template.into_iter().enumerate() {
+      this.rows = row + 1;
+      for (column, name) in line.split_whitespace().enumerate() {
+        this.columns = this.columns.max(column + 1);
+        if name == "." {
+          continue;
+        }
+        if let Some((_, area)) = this.areas.iter_mut().find(|(n, _)| &**n == name) {
+          let row_end = (area.row + area.row_span).max(row + 1);
+          let column_end = (area.column + area.column_span).max(column + 1);
+          area.row = area.row.min(row);
+          area.column = area.column.min(column);
+          area.row_span = row_end - area.row;
+          area.column_span = column_end - area.column;
+        } else {
+          let area = GridArea { row, column, row_span: 1, column_span: 1 };
+          this.areas.push((name.to_string().into(), area));
+        }
+      }
+    }
+    this
+  }
+
+  /// The cells covered by the area `name`.
+  pub fn area(&self, name: &str) -> Option<GridArea> {
+    self
+      .areas
+      .iter()
+      .find(|(n, _)| &**n == name)
+      .map(|(_, area)| *area)
+  }
+
+  /// The number of rows of the template.
+  pub fn rows(&self) -> usize { self.rows }
+
+  /// The number of columns of the template.
+  pub fn columns(&self) -> usize { self.columns }
+}
+
+impl GridTrack {
+  /// `count` tracks of the same size.
+  pub fn repeat(count: usize, track: GridTrack) -> Vec<GridTrack> { vec![track; count] }
+}
+
+impl GridItemDeclarer {
+  pub fn with_row(&mut self, row: usize) -> &mut Self {
+    self.row = Some(Some(row));
+    self
+  }
+
+  pub fn with_column(&mut self, column: usize) -> &mut Self {
+    self.column = Some(Some(column));
+    self
+  }
+
+  pub fn with_area(&mut self, area: impl Into<CowArc<str>>) -> &mut Self {
+    self.area = Some(Some(area.into()));
+    self
+  }
+}
+
+impl Default for GridItem {
+  fn default() -> Self { Self { row: None, column: None, row_span: 1, column_span: 1, area: None } }
+}
+
+impl<'c> ComposeChild<'c> for GridItem {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, mut child: Self::Child) -> Widget<'c> {
+    let data: Box<dyn Query> = match this.try_into_value() {
+      Ok(this) => Box::new(Queryable(this)),
+      Err(this) => {
+        child = child.dirty_on(this.raw_modifies(), DirtyPhase::Layout);
+        Box::new(this)
+      }
+    };
+
+    child.attach_data(data)
+  }
+}
+
+impl Render for Grid {
+  fn measure(&self, clamp: BoxClamp, ctx: &mut MeasureCtx) -> Size {
+    let (children, cells, row_cnt, column_cnt) = self.children_cells(ctx);
+
+    // Resolve the columns by the widths of the children.
+    let columns = track_defs(&self.columns, self.auto_columns, column_cnt);
+    let widths = children
+      .iter()
+      .zip(&cells)
+      .map(|(c, cell)| {
+        let span = &columns[cell.column..cell.column + cell.column_span];
+        let width = if span
+          .iter()
+          .any(|t| t.fits_content(clamp.max.width))
+        {
+          let loose =
+            BoxClamp { min: Size::zero(), max: Size::new(clamp.max.width, f32::INFINITY) };
+          ctx.layout_child(*c, loose).width
+        } else {
+          0.
+        };
+        (cell.column, cell.column_span, width)
+      })
+      .collect::<Vec<_>>();
+    let columns = resolve_tracks(&columns, &widths, clamp.max.width, self.column_gap);
+    let column_offsets = track_offsets(&columns, self.column_gap);
+
+    // Resolve the rows by the heights of the children in their columns.
+    let rows = track_defs(&self.rows, self.auto_rows, row_cnt);
+    let mut sizes = Vec::with_capacity(children.len());
+    let heights = children
+      .iter()
+      .zip(&cells)
+      .map(|(c, cell)| {
+        let width = span_size(&columns, cell.column, cell.column_span, self.column_gap);
+        let mut child_clamp = BoxClamp { min: Size::zero(), max: Size::new(width, f32::INFINITY) };
+        if self.justify_items == Align::Stretch {
+          child_clamp.min.width = width;
+        }
+        let size = ctx.layout_child(*c, child_clamp);
+        sizes.push((child_clamp, size));
+        (cell.row, cell.row_span, size.height)
+      })
+      .collect::<Vec<_>>();
+    let rows = resolve_tracks(&rows, &heights, clamp.max.height, self.row_gap);
+    let row_offsets = track_offsets(&rows, self.row_gap);
+
+    let mut rects = self.cells.borrow_mut();
+    rects.clear();
+    for ((c, cell), (mut child_clamp, mut size)) in children.iter().zip(&cells).zip(sizes) {
+      let width = span_size(&columns, cell.column, cell.column_span, self.column_gap);
+      let height = span_size(&rows, cell.row, cell.row_span, self.row_gap);
+      if self.align_items == Align::Stretch && size.height != height {
+        child_clamp.min.height = height;
+        child_clamp.max.height = height;
+        size = ctx.layout_child(*c, child_clamp);
+      }
+      let x = column_offsets[cell.column] + self.justify_items.align_value(size.width, width);
+      let y = row_offsets[cell.row] + self.align_items.align_value(size.height, height);
+      rects.push(Rect::new(Point::new(x, y), size));
+    }
+
+    let width = span_size(&columns, 0, columns.len(), self.column_gap);
+    let height = span_size(&rows, 0, rows.len(), self.row_gap);
+    clamp.clamp(Size::new(width, height))
+  }
+
+  fn min_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_size(IntrinsicSize::MinWidth, ctx)
+  }
+
+  fn max_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_size(IntrinsicSize::MaxWidth, ctx)
+  }
+
+  fn min_intrinsic_height(&self, _width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_size(IntrinsicSize::MinHeight, ctx)
+  }
+
+  fn max_intrinsic_height(&self, _width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_size(IntrinsicSize::MaxHeight, ctx)
+  }
+
+  fn place_children(&self, size: Size, ctx: &mut PlaceCtx) {
+    let rtl = is_rtl_main_axis(Direction::Horizontal, ctx);
+    let cells = self.cells.borrow();
+    let (ctx, children) = ctx.split_children();
+    for (c, rect) in children.zip(cells.iter()) {
+      let x = if rtl { size.width - rect.max_x() } else { rect.min_x() };
+      ctx.update_position(c, Point::new(x, rect.min_y()));
+    }
+  }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+
+  #[cfg(feature = "debug")]
+  fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("grid") }
+}
+
+impl Grid {
+  /// Collect the children and place them in the cells, return the children,
+  /// their cells, and the number of rows and columns.
+  fn children_cells(&self, ctx: &mut MeasureCtx) -> (Vec<WidgetId>, Vec<GridArea>, usize, usize) {
+    let (ctx, children) = ctx.split_children();
+    let children = children.collect::<Vec<_>>();
+    let items = children
+      .iter()
+      .map(|c| {
+        ctx
+          .query_of_widget::<GridItem>(*c)
+          .map(|item| (*item).clone())
+          .unwrap_or_default()
+      })
+      .collect::<Vec<_>>();
+    let (cells, row_cnt, column_cnt) = self.place_items(&items);
+    (children, cells, row_cnt, column_cnt)
+  }
+
+  /// The size of the axis whose tracks are sized by the intrinsic sizes of the
+  /// children, as if the space is unlimited.
+  fn intrinsic_size(&self, kind: IntrinsicSize, ctx: &mut MeasureCtx) -> f32 {
+    let (children, cells, row_cnt, column_cnt) = self.children_cells(ctx);
+    let (tracks, gap) = if kind.is_width() {
+      (track_defs(&self.columns, self.auto_columns, column_cnt), self.column_gap)
+    } else {
+      (track_defs(&self.rows, self.auto_rows, row_cnt), self.row_gap)
+    };
+    let contents = children
+      .iter()
+      .zip(&cells)
+      .map(|(c, cell)| {
+        let size = ctx.intrinsic_size(*c, kind, f32::INFINITY);
+        if kind.is_width() {
+          (cell.column, cell.column_span, size)
+        } else {
+          (cell.row, cell.row_span, size)
+        }
+      })
+      .collect::<Vec<_>>();
+    let sizes = resolve_tracks(&tracks, &contents, f32::INFINITY, gap);
+    span_size(&sizes, 0, sizes.len(), gap)
+  }
+
+  /// Place the items in the cells, return the cells of the items and the
+  /// number of rows and columns.
+  fn place_items(&self, items: &[GridItem]) -> (Vec<GridArea>, usize, usize) {
+    let is_row_flow = self.auto_flow == GridAutoFlow::Row;
+    // Work in the (major, minor) axes of the flow, the rows are the major axis
+    // in the row flow.
+    let to_flow = |row, column| if is_row_flow { (row, column) } else { (column, row) };
+    let specs = items
+      .iter()
+      .map(|item| {
+        let area = item.area.as_ref().and_then(|name| {
+          let area = self.areas.area(name);
+          if area.is_none() {
+            warn!("The grid area `{name}` is not defined.");
+          }
+          area
+        });
+        let (row, column, row_span, column_span) = match area {
+          Some(a) => (Some(a.row), Some(a.column), a.row_span, a.column_span),
+          None => (item.row, item.column, item.row_span.max(1), item.column_span.max(1)),
+        };
+        let (major, minor) = to_flow(row, column);
+        let (major_span, minor_span) = to_flow(row_span, column_span);
+        (major, minor, major_span, minor_span)
+      })
+      .collect::<Vec<_>>();
+
+    let (explicit_major, explicit_minor) =
+      to_flow(self.rows.len().max(self.areas.rows()), self.columns.len().max(self.areas.columns()));
+    let minor_cnt = specs
+      .iter()
+      .filter_map(|(_, minor, _, span)| minor.map(|m| m + span))
+      .fold(explicit_minor.max(1), usize::max);
+
+    let mut occupied = Occupied { minor_cnt, rows: vec![] };
+    let mut cells = vec![None; specs.len()];
+    let mut place = |idx: usize, major: usize, minor: usize, occupied: &mut Occupied| {
+      let (_, _, major_span, minor_span) = specs[idx];
+      let minor_span = minor_span.min(minor_cnt);
+      occupied.mark(major, minor, major_span, minor_span);
+      cells[idx] = Some((major, minor, major_span, minor_span));
+    };
+
+    // The items with a fixed position first, then the items locked in the
+    // major axis, and then the others in order.
+    for (idx, (major, minor, ..)) in specs.iter().enumerate() {
+      if let (Some(major), Some(minor)) = (major, minor) {
+        place(idx, *major, *minor, &mut occupied);
+      }
+    }
+    for (idx, (major, minor, _, minor_span)) in specs.iter().enumerate() {
+      if let (Some(major), None) = (major, minor) {
+        let span = (*minor_span).min(minor_cnt);
+        let minor = (0..=minor_cnt - span)
+          .find(|m| occupied.fits(*major, *m, specs[idx].2, span))
+          .unwrap_or(0);
+        place(idx, *major, minor, &mut occupied);
+      }
+    }
+    let mut cursor = (0, 0);
+    for (idx, (major, minor, major_span, minor_span)) in specs.iter().enumerate() {
+      if major.is_some() {
+        continue;
+      }
+      let span = (*minor_span).min(minor_cnt);
+      let (major, minor) = match minor {
+        Some(minor) => {
+          let mut major = if *minor < cursor.1 { cursor.0 + 1 } else { cursor.0 };
+          while !occupied.fits(major, *minor, *major_span, span) {
+            major += 1;
+          }
+          (major, *minor)
+        }
+        None => {
+          let (mut major, mut minor) = cursor;
+          while minor + span > minor_cnt || !occupied.fits(major, minor, *major_span, span) {
+            minor += 1;
+            if minor + span > minor_cnt {
+              major += 1;
+              minor = 0;
+            }
+          }
+          (major, minor)
+        }
+      };
+      cursor = (major, minor + span);
+      place(idx, major, minor, &mut occupied);
+    }
+
+    let major_cnt = cells
+      .iter()
+      .flatten()
+      .map(|(major, _, span, _)| major + span)
+      .fold(explicit_major, usize::max);
+    let cells = cells
+      .into_iter()
+      .map(|cell| {
+        let (major, minor, major_span, minor_span) = cell.unwrap();
+        let (row, column) = to_flow(major, minor);
+        let (row_span, column_span) = to_flow(major_span, minor_span);
+        GridArea { row, column, row_span, column_span }
+      })
+      .collect();
+    let (row_cnt, column_cnt) = to_flow(major_cnt, minor_cnt);
+    (cells, row_cnt, column_cnt)
+  }
+}
+
+/// The cells used by the placed items, indexed by the major axis.
+struct Occupied {
+  minor_cnt: usize,
+  rows: Vec<Vec<bool>>,
+}
+
+impl Occupied {
+  fn fits(&self, major: usize, minor: usize, major_span: usize, minor_span: usize) -> bool {
+    minor + minor_span <= self.minor_cnt
+      && (major..major + major_span).all(|r| {
+        self
+          .rows
+          .get(r)
+          .is_none_or(|row| row[minor..minor + minor_span].iter().all(|c| !c))
+      })
+  }
+
+  fn mark(&mut self, major: usize, minor: usize, major_span: usize, minor_span: usize) {
+    if self.rows.len() < major + major_span {
+      self
+        .rows
+        .resize(major + major_span, vec![false; self.minor_cnt]);
+    }
+    for row in &mut self.rows[major..major + major_span] {
+      let end = (minor + minor_span).min(self.minor_cnt);
+      row[minor..end].fill(true);
+    }
+  }
+}
+
+impl GridTrack {
+  /// Whether the size of the track depends on its contents.
+  fn fits_content(&self, available: f32) -> bool {
+    match self {
+      GridTrack::Fixed(_) => false,
+      GridTrack::Auto => true,
+      GridTrack::Fr(_) | GridTrack::MinMax(_, TrackMax::Fr(_)) => !available.is_finite(),
+      GridTrack::MinMax(..) => true,
+    }
+  }
+
+  fn min(&self) -> f32 {
+    match self {
+      GridTrack::Fixed(v) => *v,
+      GridTrack::MinMax(min, _) => *min,
+      GridTrack::Fr(_) | GridTrack::Auto => 0.,
+    }
+  }
+
+  fn limit(&self) -> f32 {
+    match self {
+      GridTrack::Fixed(v) => *v,
+      GridTrack::MinMax(min, TrackMax::Fixed(max)) => min.max(*max),
+      _ => f32::INFINITY,
+    }
+  }
+
+  fn fr(&self) -> Option<f32> {
+    match self {
+      GridTrack::Fr(f) | GridTrack::MinMax(_, TrackMax::Fr(f)) => Some(f.max(0.)),
+      _ => None,
+    }
+  }
+}
+
+fn track_defs(explicit: &[GridTrack], auto: GridTrack, cnt: usize) -> Vec<GridTrack> {
+  let mut tracks = explicit.to_vec();
+  tracks.resize(cnt.max(explicit.len()), auto);
+  tracks
+}
+
+/// Resolve the sizes of the tracks, the `items` are the start track, the span
+/// and the content size of every item.
+fn resolve_tracks(
+  tracks: &[GridTrack], items: &[(usize, usize, f32)], available: f32, gap: f32,
+) -> Vec<f32> {
+  let mut sizes = tracks
+    .iter()
+    .map(GridTrack::min)
+    .collect::<Vec<_>>();
+  let fits_content = |i: usize| tracks[i].fits_content(available);
+
+  // The items span a single track first, then the spanning items grow the
+  // content-sized tracks they span equally.
+  for &(start, _, size) in items.iter().filter(|(_, span, _)| *span == 1) {
+    if fits_content(start) {
+      sizes[start] = sizes[start].max(size.min(tracks[start].limit()));
+    }
+  }
+  let mut spanning = items
+    .iter()
+    .filter(|(_, span, _)| *span > 1)
+    .collect::<Vec<_>>();
+  spanning.sort_by_key(|(_, span, _)| *span);
+  for &&(start, span, size) in spanning.iter() {
+    let rg = start..start + span;
+    let growable = rg
+      .clone()
+      .filter(|i| fits_content(*i))
+      .collect::<Vec<_>>();
+    let current = span_size(&sizes, start, span, gap);
+    if size > current && !growable.is_empty() {
+      let each = (size - current) / growable.len() as f32;
+      for i in growable {
+        sizes[i] = (sizes[i] + each).min(tracks[i].limit().max(sizes[i]));
+      }
+    }
+  }
+
+  let fr_tracks = (0..tracks.len())
+    .filter(|i| tracks[*i].fr().is_some())
+    .collect::<Vec<_>>();
+  if fr_tracks.is_empty() {
+    return sizes;
+  }
+  if available.is_finite() {
+    let fixed = (0..tracks.len())
+      .filter(|i| tracks[*i].fr().is_none())
+      .map(|i| sizes[i])
+      .sum::<f32>();
+    let gaps = gap * tracks.len().saturating_sub(1) as f32;
+    let mut free = available - fixed - gaps;
+    let mut flexible = fr_tracks;
+    // Tracks whose share is smaller than their minimum keep the minimum, and
+    // the rest share the remaining space.
+    loop {
+      let total = flexible
+        .iter()
+        .map(|i| tracks[*i].fr().unwrap())
+        .sum::<f32>();
+      let unit = if total > 0. { free.max(0.) / total } else { 0. };
+      let (frozen, rest): (Vec<_>, Vec<_>) = flexible
+        .into_iter()
+        .partition(|i| tracks[*i].fr().unwrap() * unit < tracks[*i].min());
+      if frozen.is_empty() {
+        for i in rest {
+          sizes[i] = tracks[i].fr().unwrap() * unit;
+        }
+        break;
+      }
+      for i in frozen {
+        sizes[i] = tracks[i].min();
+        free -= sizes[i];
+      }
+      flexible = rest;
+    }
+  } else {
+    // Without a bounded size, keep the fractions between the content sizes.
+    let unit = fr_tracks
+      .iter()
+      .filter(|i| tracks[**i].fr().unwrap() > 0.)
+      .map(|i| sizes[*i] / tracks[*i].fr().unwrap())
+      .fold(0., f32::max);
+    for i in fr_tracks {
+      sizes[i] = sizes[i].max(tracks[i].fr().unwrap() * unit);
+    }
+  }
+  sizes
+}
+
+fn track_offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+  let mut offset = 0.;
+  sizes
+    .iter()
+    .map(|size| {
+      let start = offset;
+      offset += size + gap;
+      start
+    })
+    .collect()
+}
+
+fn span_size(sizes: &[f32], start: usize, span: usize, gap: f32) -> f32 {
+  let sizes = &sizes[start..start + span];
+  sizes.iter().sum::<f32>() + gap * sizes.len().saturating_sub(1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use ribir_dev_helper::*;
+
+  use super::*;
+  use crate::prelude::*;
+
+  widget_layout_test!(
+    tracks,
+    WidgetTester::new(fn_widget! {
+      @Grid {
+        columns: vec![GridTrack::Fixed(100.), GridTrack::Fr(1.), GridTrack::Fr(2.)],
+        rows: vec![GridTrack::Auto, GridTrack::Fixed(50.)],
+        column_gap: 10.,
+        row_gap: 5.,
+        @Container { size: Size::new(20., 30.) }
+        @Container { size: Size::new(20., 20.) }
+        @Container { size: Size::new(20., 20.) }
+        @Container { size: Size::new(20., 20.) }
+      }
+    })
+    .with_wnd_size(Size::new(420., 500.)),
+    LayoutCase::default().with_size(Size::new(420., 85.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_types::rect(0., 0., 100., 30.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_types::rect(110., 0., 100., 30.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_types::rect(220., 0., 200., 30.)),
+    LayoutCase::new(&[0, 3]).with_rect(ribir_types::rect(0., 35., 100., 50.))
+  );
+
+  widget_layout_test!(
+    areas_and_spans,
+    WidgetTester::new(fn_widget! {
+      let size = Size::new(10., 10.);
+      @Grid {
+        columns: GridTrack::repeat(2, GridTrack::Fixed(50.)),
+        rows: GridTrack::repeat(2, GridTrack::Fixed(20.)),
+        areas: GridAreas::new(["head head", "side main"]),
+        @GridItem { area: "main", @Container { size } }
+        @GridItem { area: "head", @Container { size } }
+        @Container { size }
+        @Container { size }
+        @GridItem { row: 3, column: 0, column_span: 2, @Container { size } }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::default().with_size(Size::new(100., 60.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_types::rect(50., 20., 50., 20.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_types::rect(0., 0., 100., 20.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_types::rect(0., 20., 50., 20.)),
+    LayoutCase::new(&[0, 3]).with_rect(ribir_types::rect(0., 40., 50., 10.)),
+    LayoutCase::new(&[0, 4]).with_rect(ribir_types::rect(0., 50., 100., 10.))
+  );
+
+  widget_layout_test!(
+    column_flow_and_align,
+    WidgetTester::new(fn_widget! {
+      @Grid {
+        rows: GridTrack::repeat(2, GridTrack::Fixed(40.)),
+        auto_columns: GridTrack::Auto,
+        auto_flow: GridAutoFlow::Column,
+        justify_items: Align::Center,
+        align_items: Align::End,
+        @Container { size: Size::new(30., 10.) }
+        @Container { size: Size::new(10., 10.) }
+        @Container { size: Size::new(20., 10.) }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::default().with_size(Size::new(50., 80.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_types::rect(0., 30., 30., 10.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_types::rect(10., 70., 10., 10.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_types::rect(30., 30., 20., 10.))
+  );
+
+  #[test]
+  fn min_max_tracks() {
+    let tracks = [GridTrack::MinMax(100., TrackMax::Fr(1.)), GridTrack::Fr(1.)];
+    assert_eq!(resolve_tracks(&tracks, &[], 150., 0.), vec![100., 50.]);
+    assert_eq!(resolve_tracks(&tracks, &[], 300., 0.), vec![150., 150.]);
+
+    let tracks = [GridTrack::MinMax(20., TrackMax::Fixed(60.)), GridTrack::Auto];
+    let items = [(0, 1, 100.), (1, 1, 30.)];
+    assert_eq!(resolve_tracks(&tracks, &items, f32::INFINITY, 0.), vec![60., 30.]);
+
+    // A spanning item grows the content-sized tracks equally.
+    let tracks = [GridTrack::Fixed(10.), GridTrack::Auto, GridTrack::Auto];
+    let items = [(0, 3, 70.)];
+    assert_eq!(resolve_tracks(&tracks, &items, f32::INFINITY, 10.), vec![10., 20., 20.]);
+  }
+}
@@ -0,0 +1,111 @@
+use ribir_core::prelude::*;
+
+/// A widget that sizes its child to the max intrinsic width of the child.
+///
+/// It's useful when a child should be as wide as its content rather than the
+/// space its parent gives, such as sizing a column of buttons to the widest
+/// one:
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _actions = fn_widget! {
+///   @IntrinsicWidth {
+///     @Column {
+///       align_items: Align::Stretch,
+///       @FilledButton { @{ "Open" } }
+///       @FilledButton { @{ "Save as..." } }
+///     }
+///   }
+/// };
+/// ```
+///
+/// The width is queried from the intrinsic sizes of the subtree, which is
+/// cheaper than laying it out twice but still walks the whole subtree, so
+/// avoid it for a deep one.
+#[derive(SingleChild, Declare)]
+pub struct IntrinsicWidth {}
+
+impl Render for IntrinsicWidth {
+  fn measure(&self, clamp: BoxClamp, ctx: &mut MeasureCtx) -> Size {
+    let Some(child) = ctx.single_child() else { return clamp.min };
+
+    let width = ctx
+      .intrinsic_size(child, IntrinsicSize::MaxWidth, clamp.max.height)
+      .max(clamp.min.width)
+      .min(clamp.max.width);
+    ctx.layout_child(child, clamp.with_fixed_width(width))
+  }
+
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    ctx.children_intrinsic_size(IntrinsicSize::MaxWidth, height)
+  }
+
+  fn baseline(&self, _size: Size, ctx: &mut PlaceCtx) -> Option<f32> {
+    let child = ctx.single_child()?;
+    ctx.child_baseline(child)
+  }
+
+  #[cfg(feature = "debug")]
+  fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+    std::borrow::Cow::Borrowed("intrinsicWidth")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::test_helper::*;
+  use ribir_dev_helper::*;
+
+  use super::*;
+  use crate::prelude::*;
+
+  widget_layout_test!(
+    widest_child,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicWidth {
+        @Column {
+          align_items: Align::Stretch,
+          @Container { size: Size::new(30., 10.) }
+          @Container { size: Size::new(50., 10.) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(200., 200.)),
+    LayoutCase::default().with_size(Size::new(50., 20.)),
+    LayoutCase::new(&[0, 0, 0]).with_size(Size::new(50., 10.)),
+    LayoutCase::new(&[0, 0, 1]).with_size(Size::new(50., 10.))
+  );
+
+  widget_layout_test!(
+    flex_with_gap,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicWidth {
+        @Flex {
+          item_gap: 4.,
+          padding: EdgeInsets::horizontal(3.),
+          @Container { size: Size::new(30., 10.) }
+          @Container { size: Size::new(50., 10.) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(200., 200.)),
+    LayoutCase::default().with_size(Size::new(90., 10.))
+  );
+
+  widget_layout_test!(
+    leaf_content,
+    WidgetTester::new(fn_widget! {
+      @IntrinsicWidth {
+        text_line_height: 24.,
+        @Column {
+          align_items: Align::Stretch,
+          @ { white_image(20, 10) }
+          @Icon { @ { svg_registry::get_or_default("delete") } }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(200., 200.)),
+    LayoutCase::default().with_size(Size::new(24., 34.))
+  );
+}
@@ -45,6 +45,43 @@ pub enum JustifyContent {
   SpaceEvenly,
 }
 
+/// Returns `true` if the horizontal main axis should be laid out from right to
+/// left, which follows the `TextDirection` provided by the ancestors.
+pub(crate) fn is_rtl_main_axis(dir: Direction, ctx: &impl AsRef<ProviderCtx>) -> bool {
+  dir.is_horizontal()
+    && Provider::of::<TextDirection>(ctx).is_some_and(|direction| direction.is_rtl())
+}
+
+/// Returns the baseline of the first child that has one, in the coordinate of
+/// the layout widget.
+pub(crate) fn first_child_baseline(ctx: &mut PlaceCtx) -> Option<f32> {
+  let (ctx, mut children) = ctx.split_children();
+  children.find_map(|c| ctx.child_baseline(c))
+}
+
+/// The intrinsic size of the children arranged in a line along `dir`, it's the
+/// sum of the children in the main axis and the largest one in the cross axis,
+/// where every child takes its max intrinsic size in the main axis.
+pub(crate) fn linear_intrinsic_size(
+  dir: Direction, kind: IntrinsicSize, extent: f32, ctx: &mut MeasureCtx,
+) -> f32 {
+  let (ctx, children) = ctx.split_children();
+  if kind.is_width() == dir.is_horizontal() {
+    children
+      .map(|c| ctx.intrinsic_size(c, kind, extent))
+      .sum()
+  } else {
+    let main_kind =
+      if dir.is_horizontal() { IntrinsicSize::MaxWidth } else { IntrinsicSize::MaxHeight };
+    children
+      .map(|c| {
+        let main = ctx.intrinsic_size(c, main_kind, f32::INFINITY);
+        ctx.intrinsic_size(c, kind, main)
+      })
+      .fold(0., f32::max)
+  }
+}
+
 impl Direction {
   /// Returns `true` if the direction is horizontal.
   pub const fn is_horizontal(&self) -> bool { matches!(self, Direction::Horizontal) }
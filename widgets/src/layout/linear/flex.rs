@@ -1,6 +1,9 @@
 use ribir_core::prelude::{tracing::warn, *};
 
-use super::{Direction, Expanded, JustifyContent};
+use super::{
+  Direction, Expanded, JustifyContent, first_child_baseline, is_rtl_main_axis,
+  linear_intrinsic_size,
+};
 
 /// Enum describing how a widget is aligned inside its box.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -16,6 +19,10 @@ pub enum Align {
   /// Require the children to fill the whole box of one axis. This causes the
   /// constraints passed to the children to be tight.
   Stretch,
+  /// The children are aligned by the baseline of their first line of text,
+  /// and the ones without a baseline are aligned by their bottom edge. Only
+  /// works in a horizontal layout, it's the same as `Start` in a vertical one.
+  Baseline,
 }
 
 impl Align {
@@ -26,11 +33,16 @@ impl Align {
       Align::Start => 0.,
       Align::Center => (parent_size - child_size) / 2.,
       Align::End => parent_size - child_size,
-      Align::Stretch => 0.,
+      Align::Stretch | Align::Baseline => 0.,
     }
   }
 }
 
+/// The height above the baseline of an item aligned by baseline.
+pub(crate) fn baseline_ascent(height: f32, baseline: Option<f32>) -> f32 {
+  baseline.unwrap_or(height)
+}
+
 /// The `Flex` is a layout container that arranges its children in a
 /// one-dimensional manner. It distributes space among the children and provides
 /// alignment options in two axes.
@@ -129,6 +141,24 @@ impl Render for Flex {
     layouter.layout_children(size, ctx)
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_size(IntrinsicSize::MinWidth, height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_size(IntrinsicSize::MaxWidth, height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_size(IntrinsicSize::MinHeight, width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_size(IntrinsicSize::MaxHeight, width, ctx)
+  }
+
+  fn baseline(&self, _size: Size, ctx: &mut PlaceCtx) -> Option<f32> { first_child_baseline(ctx) }
+
   #[inline]
   fn paint(&self, _: &mut PaintingCtx) {}
 
@@ -136,6 +166,26 @@ impl Render for Flex {
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("flex") }
 }
 
+impl Flex {
+  /// The intrinsic size of the items in a single line, a wrapping flex can be
+  /// as narrow as its largest item in the main axis.
+  fn intrinsic_size(&self, kind: IntrinsicSize, extent: f32, ctx: &mut MeasureCtx) -> f32 {
+    let is_main = kind.is_width() == self.direction.is_horizontal();
+    let is_min = matches!(kind, IntrinsicSize::MinWidth | IntrinsicSize::MinHeight);
+    if is_main && is_min && self.wrap {
+      return ctx.children_intrinsic_size(kind, extent);
+    }
+
+    let size = linear_intrinsic_size(self.direction, kind, extent, ctx);
+    let cnt = ctx.children().count();
+    if is_main && cnt > 1 && !self.justify_content.is_spacing_distributed() {
+      size + self.item_gap * (cnt - 1) as f32
+    } else {
+      size
+    }
+  }
+}
+
 struct FlexLayouter {
   reverse: bool,
   dir: Direction,
@@ -219,10 +269,10 @@ impl FlexLayouter {
         .map(|e| *e)
         .filter(|e| (e.flex.is_normal() && e.flex > 0.) || e.flex == 0.);
 
-      let size = if expanded.is_some_and(|e| e.defer_alloc) {
-        Size::zero()
+      let (size, baseline) = if expanded.is_some_and(|e| e.defer_alloc) {
+        (Size::zero(), None)
       } else {
-        ctx.layout_child(c, clamp)
+        (ctx.layout_child(c, clamp), ctx.widget_baseline(c))
       };
       let main = dir.main_of(size);
       if wrap && !line.is_empty() && line.main + main > max_main {
@@ -244,6 +294,7 @@ impl FlexLayouter {
         main_pos: 0.,
         cross_pos: 0.,
         size,
+        baseline,
         defer_layout: expanded.is_some_and(|e| e.defer_alloc),
       };
       self.current_line.items_info.push(info);
@@ -275,6 +326,7 @@ impl FlexLayouter {
 
       // Use cached size instead of re-measuring
       let size = ctx.widget_box_size(c).unwrap_or(Size::zero());
+      let baseline = ctx.widget_baseline(c);
       let main = dir.main_of(size);
       if wrap && !line.is_empty() && line.main + main > max_main {
         self.place_line();
@@ -295,6 +347,7 @@ impl FlexLayouter {
         main_pos: 0.,
         cross_pos: 0.,
         size,
+        baseline,
         defer_layout: expanded.is_some_and(|e| e.defer_alloc),
       };
       self.current_line.items_info.push(info);
@@ -331,6 +384,7 @@ impl FlexLayouter {
         };
 
         info.size = ctx.layout_child(child, item_clamp);
+        info.baseline = ctx.widget_baseline(child);
         line.main += dir.main_of(info.size) - item_main;
       }
     });
@@ -352,7 +406,7 @@ impl FlexLayouter {
       self
         .lines
         .iter()
-        .map(|l| l.max_cross(self.dir))
+        .map(|l| l.max_cross(self.dir, self.is_baseline_aligned()))
         .sum::<f32>()
         + self.cross_axis_gap * (self.lines.len() - 1) as f32
     };
@@ -363,6 +417,7 @@ impl FlexLayouter {
   fn update_children_position(&mut self, container: f32, cross_offset: f32, ctx: &mut PlaceCtx) {
     let Self { reverse, dir, align_items, justify_content, cross_axis_gap, main_axis_gap, .. } =
       *self;
+    let baseline_aligned = self.is_baseline_aligned();
     let mut cross = cross_offset - cross_axis_gap;
     self.for_each_line(|line| {
       let (mut main, mut step) =
@@ -371,10 +426,15 @@ impl FlexLayouter {
         step += main_axis_gap;
       }
 
-      let line_cross = line.max_cross(dir);
+      let line_cross = line.max_cross(dir, baseline_aligned);
+      let (line_ascent, _) = line.baseline_extent();
       line.for_each_item(reverse, |item| {
         let (item_main, item_cross) = dir.main_cross_of(item.size);
-        let item_cross_offset = align_items.align_value(item_cross, line_cross);
+        let item_cross_offset = if baseline_aligned {
+          line_ascent - baseline_ascent(item_cross, item.baseline)
+        } else {
+          align_items.align_value(item_cross, line_cross)
+        };
 
         item.cross_pos = cross + item_cross_offset + cross_axis_gap;
         item.main_pos = main;
@@ -383,12 +443,15 @@ impl FlexLayouter {
       cross += line_cross + cross_axis_gap;
     });
 
+    let rtl = is_rtl_main_axis(dir, ctx);
     let (ctx, mut children) = ctx.split_children();
 
     self.lines.iter_mut().for_each(|line| {
       line.items_info.iter_mut().for_each(|info| {
         let child = children.next().unwrap();
-        let pos = dir.to_point(info.main_pos, info.cross_pos);
+        let main_pos =
+          if rtl { container - info.main_pos - dir.main_of(info.size) } else { info.main_pos };
+        let pos = dir.to_point(main_pos, info.cross_pos);
         ctx.update_position(child, pos);
       })
     });
@@ -404,6 +467,10 @@ impl FlexLayouter {
 
   fn main_size(&self) -> f32 { self.lines.iter().fold(0., |m, l| m.max(l.main)) }
 
+  fn is_baseline_aligned(&self) -> bool {
+    self.align_items == Align::Baseline && self.dir.is_horizontal()
+  }
+
   fn for_each_line(&mut self, mut f: impl FnMut(&mut MainLineInfo)) {
     if self.reverse {
       self.lines.iter_mut().rev().for_each(&mut f);
@@ -425,6 +492,7 @@ struct FlexLayoutInfo {
   main_pos: f32,
   cross_pos: f32,
   size: Size,
+  baseline: Option<f32>,
   flex: Option<f32>,
   defer_layout: bool,
 }
@@ -477,11 +545,29 @@ impl MainLineInfo {
     }
   }
 
-  fn max_cross(&self, dir: Direction) -> f32 {
+  fn max_cross(&self, dir: Direction, baseline_aligned: bool) -> f32 {
+    if baseline_aligned {
+      let (ascent, descent) = self.baseline_extent();
+      ascent + descent
+    } else {
+      self
+        .items_info
+        .iter()
+        .fold(0., |acc, info| acc.max(dir.cross_of(info.size)))
+    }
+  }
+
+  /// The max ascent and descent of the items aligned by baseline in a
+  /// horizontal line.
+  fn baseline_extent(&self) -> (f32, f32) {
     self
       .items_info
       .iter()
-      .fold(0., |acc, info| acc.max(dir.cross_of(info.size)))
+      .fold((0f32, 0f32), |(ascent, descent), info| {
+        let height = info.size.height;
+        let item_ascent = baseline_ascent(height, info.baseline);
+        (ascent.max(item_ascent), descent.max(height - item_ascent))
+      })
   }
 
   fn for_each_item(&mut self, reverse: bool, mut f: impl FnMut(&mut FlexLayoutInfo)) {
@@ -541,6 +627,24 @@ mod tests {
     LayoutCase::new(&[0, 2]).with_rect(ribir_types::rect(0., 20., 200., 20.))
   );
 
+  widget_layout_test!(
+    rtl_row_wrap,
+    WidgetTester::new(fn_widget! {
+      @Providers {
+        providers: [Provider::new(TextDirection::Rtl)],
+        @Flex {
+          wrap: true,
+          @{ (0..3).map(|_| @Container { size: Size::new(200., 20.) }) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(500., 500.)),
+    LayoutCase::default().with_size(Size::new(400., 40.)),
+    LayoutCase::new(&[0, 0]).with_rect(ribir_types::rect(200., 0., 200., 20.)),
+    LayoutCase::new(&[0, 1]).with_rect(ribir_types::rect(0., 0., 200., 20.)),
+    LayoutCase::new(&[0, 2]).with_rect(ribir_types::rect(200., 20., 200., 20.))
+  );
+
   widget_layout_test!(
     reverse_row_wrap,
     WidgetTester::new(fn_widget! {
@@ -1,6 +1,9 @@
 use ribir_core::prelude::*;
 
-use super::{Align, Direction, JustifyContent};
+use super::{
+  Align, Direction, JustifyContent, baseline_ascent, first_child_baseline, is_rtl_main_axis,
+  linear_intrinsic_size,
+};
 
 /// A horizontal layout container that arranges children sequentially in a row.
 ///
@@ -22,7 +25,8 @@ pub struct Row {
   /// Vertical alignment of children within the container's height.
   ///
   /// When set to [`Align::Stretch`], children will expand to match the
-  /// container's height (if constrained).
+  /// container's height (if constrained). When set to [`Align::Baseline`],
+  /// children line up the baselines of their first line of text.
   #[declare(default)]
   pub align_items: Align,
 
@@ -77,6 +81,24 @@ impl Render for Row {
     )
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    linear_intrinsic_size(Direction::Horizontal, IntrinsicSize::MinWidth, height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    linear_intrinsic_size(Direction::Horizontal, IntrinsicSize::MaxWidth, height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    linear_intrinsic_size(Direction::Horizontal, IntrinsicSize::MinHeight, width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    linear_intrinsic_size(Direction::Horizontal, IntrinsicSize::MaxHeight, width, ctx)
+  }
+
+  fn baseline(&self, _size: Size, ctx: &mut PlaceCtx) -> Option<f32> { first_child_baseline(ctx) }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("row") }
 }
@@ -96,6 +118,24 @@ impl Render for Column {
     )
   }
 
+  fn min_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    linear_intrinsic_size(Direction::Vertical, IntrinsicSize::MinWidth, height, ctx)
+  }
+
+  fn max_intrinsic_width(&self, height: f32, ctx: &mut MeasureCtx) -> f32 {
+    linear_intrinsic_size(Direction::Vertical, IntrinsicSize::MaxWidth, height, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    linear_intrinsic_size(Direction::Vertical, IntrinsicSize::MinHeight, width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    linear_intrinsic_size(Direction::Vertical, IntrinsicSize::MaxHeight, width, ctx)
+  }
+
+  fn baseline(&self, _size: Size, ctx: &mut PlaceCtx) -> Option<f32> { first_child_baseline(ctx) }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("column") }
 }
@@ -122,12 +162,22 @@ fn perform_linear_measure(
     dir.with_cross_max(clamp.loose(), cross_max)
   };
 
+  let baseline_aligned = align_items == Align::Baseline && dir.is_horizontal();
   let (ctx, children) = ctx.split_children();
   let (mut main, mut cross) = (0., 0f32);
+  let (mut ascent, mut descent) = (0f32, 0f32);
   for child in children {
     let child_size = ctx.layout_child(child, child_clamp);
     main += dir.main_of(child_size);
     cross = cross.max(dir.cross_of(child_size));
+    if baseline_aligned {
+      let child_ascent = baseline_ascent(child_size.height, ctx.widget_baseline(child));
+      ascent = ascent.max(child_ascent);
+      descent = descent.max(child_size.height - child_ascent);
+    }
+  }
+  if baseline_aligned {
+    cross = ascent + descent;
   }
 
   let main_container = dir.container_main(&clamp, main);
@@ -153,23 +203,35 @@ fn perform_linear_layout_positions(
   let main_container = dir.main_of(size);
   let cross_container = dir.cross_of(size);
 
-  // Calculate total main size from already-measured children
+  // Calculate total main size and the max ascent from already-measured children
+  let baseline_aligned = align_items == Align::Baseline && dir.is_horizontal();
   let (ctx, children) = ctx.split_children();
-  let total_main: f32 = children
-    .map(|c| {
-      let size = ctx.widget_box_size(c).unwrap();
-      dir.main_of(size)
-    })
-    .sum();
+  let (mut total_main, mut max_ascent) = (0., 0f32);
+  for c in children {
+    let size = ctx.widget_box_size(c).unwrap();
+    total_main += dir.main_of(size);
+    if baseline_aligned {
+      max_ascent = max_ascent.max(baseline_ascent(size.height, ctx.widget_baseline(c)));
+    }
+  }
   let (mut main_pos, step) =
     justify_content.item_offset_and_step(main_container - total_main, child_cnt);
 
+  let rtl = is_rtl_main_axis(dir, ctx);
   let (ctx, children) = ctx.split_children();
   for child in children {
     let child_size = ctx.widget_box_size(child).unwrap();
-    let cross_pos = align_items.align_value(dir.cross_of(child_size), cross_container);
+    let cross_pos = if baseline_aligned {
+      max_ascent - baseline_ascent(child_size.height, ctx.widget_baseline(child))
+    } else {
+      align_items.align_value(dir.cross_of(child_size), cross_container)
+    };
 
-    let pos = dir.to_point(main_pos, cross_pos);
+    let pos = if rtl {
+      dir.to_point(main_container - main_pos - dir.main_of(child_size), cross_pos)
+    } else {
+      dir.to_point(main_pos, cross_pos)
+    };
     ctx.update_position(child, pos);
     main_pos += dir.main_of(child_size) + step;
   }
@@ -177,11 +239,11 @@ fn perform_linear_layout_positions(
 
 #[cfg(test)]
 mod tests {
-  use ribir_core::test_helper::*;
+  use ribir_core::{reset_test_env, test_helper::*};
   use ribir_dev_helper::*;
 
   use super::*;
-  use crate::prelude::*;
+  use crate::prelude::{Input, *};
 
   widget_layout_test!(
     row_stretch_height,
@@ -194,4 +256,81 @@ mod tests {
     .with_wnd_size(Size::new(500., 200.)),
     LayoutCase::new(&[0, 0]).with_size(Size::new(100., 200.))
   );
+
+  widget_layout_test!(
+    rtl_row_starts_from_right,
+    WidgetTester::new(fn_widget! {
+      @Providers {
+        providers: [Provider::new(TextDirection::Rtl)],
+        @Row {
+          @Container { size: Size::new(100., 50.) }
+          @Container { size: Size::new(50., 50.) }
+        }
+      }
+    })
+    .with_wnd_size(Size::new(500., 200.)),
+    LayoutCase::new(&[0, 0]).with_x(400.),
+    LayoutCase::new(&[0, 1]).with_x(350.)
+  );
+
+  #[test]
+  fn row_align_baseline() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Row {
+          align_items: Align::Baseline,
+          @Text { text: "Small", font_size: 12., padding: EdgeInsets::only_top(6.) }
+          @Text { text: "Large", font_size: 24., margin: EdgeInsets::only_top(10.) }
+          @Container { size: Size::new(10., 10.) }
+        }
+      },
+      Size::new(500., 200.),
+    );
+    wnd.draw_frame();
+
+    let baseline_of = |path: &[usize]| {
+      let info = wnd.layout_info_by_path(path).unwrap();
+      info.pos.y + info.baseline.unwrap()
+    };
+    let small = baseline_of(&[0, 0]);
+    let large = baseline_of(&[0, 1]);
+    assert!((small - large).abs() < 0.01);
+
+    // A child without baseline sits on the baseline by its bottom edge.
+    let container = wnd.layout_info_by_path(&[0, 2]).unwrap();
+    assert!((container.pos.y + 10. - large).abs() < 0.01);
+
+    // The row reports the baseline of its first child.
+    let row = wnd.layout_info_by_path(&[0]).unwrap();
+    assert!((row.baseline.unwrap() - small).abs() < 0.01);
+  }
+
+  #[test]
+  fn row_align_text_and_input_baseline() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Row {
+          align_items: Align::Baseline,
+          @Text { text: "Name:", font_size: 24. }
+          @Input {}
+        }
+      },
+      Size::new(500., 200.),
+    );
+    wnd.draw_frame();
+
+    let baseline_of = |path: &[usize]| {
+      let info = wnd.layout_info_by_path(path).unwrap();
+      info.pos.y + info.baseline.unwrap()
+    };
+    // The input reports the baseline of its text through the stacks wrapping
+    // it, and lines up with the label.
+    let label = baseline_of(&[0, 0]);
+    let input = baseline_of(&[0, 1]);
+    assert!((label - input).abs() < 0.01);
+  }
 }
@@ -1,5 +1,6 @@
 use ribir_core::prelude::*;
 
+use super::first_child_baseline;
 use crate::prelude::{NoAffectedParentSize, no_affected_parent_size};
 /// A widget that overlaps its children, allowing for flexible layout
 /// management.
@@ -98,6 +99,8 @@ impl Render for Stack {
     stack_size
   }
 
+  fn baseline(&self, _size: Size, ctx: &mut PlaceCtx) -> Option<f32> { first_child_baseline(ctx) }
+
   #[cfg(feature = "debug")]
   fn debug_name(&self) -> std::borrow::Cow<'static, str> { std::borrow::Cow::Borrowed("stack") }
 }
@@ -22,12 +22,15 @@ extern crate bitflags;
 use ribir_core::prelude::Provider;
 
 pub mod animation;
+pub mod async_image;
 pub mod avatar;
 pub mod badge;
 pub mod buttons;
 pub mod checkbox;
 pub mod common_widget;
 pub mod divider;
+pub mod fit_image;
+pub mod form;
 pub mod grid_view;
 pub mod icon;
 pub mod input;
@@ -36,6 +39,7 @@ pub mod layout;
 pub mod list;
 pub mod menu;
 pub mod navigation_rail;
+pub mod nine_patch;
 pub mod overlay;
 pub mod path;
 pub mod progress;
@@ -62,10 +66,11 @@ pub use ribir_core as core;
 
 pub mod prelude {
   pub use super::{
-    animation::*, avatar::*, badge::*, buttons::*, checkbox::*, common_widget::*, divider::*,
-    grid_view::*, icon::*, input::*, label::*, layout::*, list::*, menu::*, navigation_rail::*,
-    overlay::*, path::*, progress::*, radio::*, rich_text::*, router::*, scrollbar::*,
-    select_region::*, slider::*, switch::*, tabs::*, tooltip::*, transform_box::*,
+    animation::*, async_image::*, avatar::*, badge::*, buttons::*, checkbox::*, common_widget::*,
+    divider::*, fit_image::*, form::*, grid_view::*, icon::*, input::*, label::*, layout::*,
+    list::*, menu::*, navigation_rail::*, nine_patch::*, overlay::*, path::*, progress::*,
+    radio::*, rich_text::*, router::*, scrollbar::*, select_region::*, slider::*, switch::*,
+    tabs::*, tooltip::*, transform_box::*,
   };
   pub use crate::{cases, transitions};
 }
@@ -381,8 +381,10 @@ impl<'c> ComposeChild<'c> for List {
     List::collect_items(&this, &child);
     let select_mode = this.read().select_mode;
 
+    let role = if select_mode == ListSelectMode::None { Role::List } else { Role::ListBox };
     self::column! {
       class: LIST,
+      a11y: A11y::new(role),
       align_items: Align::Stretch,
       on_disposed: move |_| $write(this).clear(),
       on_key_down: move |e| {
@@ -713,10 +715,16 @@ impl List {
 
     let mode = self.select_mode;
     if mode == ListSelectMode::None {
+      list_item.with_a11y(A11y::new(Role::ListItem));
       list_item.into_widget()
     } else {
       rdl! {
         @(list_item) {
+          a11y: pipe! {
+            A11y::new(Role::ListItem)
+              .with_selected($read(item).is_selected())
+              .with_action(Action::Click)
+          },
           on_tap: move |_| ListItem::select_action($write(item), mode),
           on_key_down: move |e| {
             if matches!(e.key(), VirtualKey::Named(NamedKey::Enter)
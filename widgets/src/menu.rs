@@ -88,12 +88,16 @@ pub struct MenuControl(Rc<RefCell<MenuData>>);
 struct MenuItemData {
   wid: TrackId,
   label: CowArc<str>,
+  command: Option<CowArc<str>>,
 }
 
 struct MenuData {
   id: Option<TrackId>,
   handle: Option<Overlay>,
   item_trigger: Option<ParentMenuInfo>,
+  /// The widget that has the focus when the menu is shown, the commands of the
+  /// items are resolved from it.
+  invoker: Option<WidgetId>,
   selected: Option<usize>,
   items: Vec<MenuItemData>,
   gen_widget: GenWidget,
@@ -106,6 +110,7 @@ impl MenuControl {
       gen_widget: gen_widget.r_into(),
       handle: None,
       item_trigger: None,
+      invoker: None,
       selected: None,
       items: vec![],
       id: None,
@@ -255,9 +260,40 @@ impl MenuControl {
     true
   }
 
+  /// Enter the menu item at `idx` and execute its [`MenuCommand`], the menu is
+  /// completed after the command is executed.
+  ///
+  /// Return:
+  /// return true if activate successfully, false otherwise.
+  pub fn activate(&self, idx: usize, wnd: &Rc<Window>) -> bool {
+    if !self.enter(idx, wnd) {
+      return false;
+    }
+
+    let this = self.0.borrow();
+    let item = &this.items[idx];
+    let (wid, label, invoker) = (item.wid.get(), item.label.clone(), this.invoker);
+    let Some(command) = item.command.clone() else { return true };
+    drop(this);
+
+    wnd.execute_command_from(invoker, &command);
+    if let Some(wid) = wid {
+      wnd.bubble_custom_event(
+        wid,
+        MenuEventData::Complete { idx, label, menu: self.clone(), data: None },
+      );
+    }
+    true
+  }
+
   fn selected(&self) -> Option<usize> { self.0.borrow().selected }
 
   fn inner_show(&self, gen_widget: GenWidget, parent: Option<ParentMenuInfo>, wnd: &Rc<Window>) {
+    let invoker = match parent.as_ref() {
+      Some(parent) => parent.menu.0.borrow().invoker,
+      None => wnd.focusing(),
+    };
+    self.0.borrow_mut().invoker = invoker;
     let handle = self.clone();
     let fn_gen = GenWidget::from_fn_widget(fn_widget! {
       let mut w = FatObj::new(gen_widget.clone());
@@ -298,12 +334,12 @@ impl MenuControl {
     this.handle = Some(handle);
   }
 
-  fn new_item(&self, wid: TrackId, key: CowArc<str>) -> usize {
+  fn new_item(&self, wid: TrackId, key: CowArc<str>, command: Option<CowArc<str>>) -> usize {
     self
       .0
       .borrow_mut()
       .items
-      .push(MenuItemData { wid, label: key });
+      .push(MenuItemData { wid, label: key, command });
     self.0.borrow().items.len() - 1
   }
 
@@ -373,6 +409,17 @@ impl MenuHintText {
   pub fn new<K: ?Sized>(child: impl RInto<TextValue, K>) -> Self { MenuHintText(child.r_into()) }
 }
 
+/// The command of a menu item, executed when the item is activated.
+///
+/// The command is resolved from the widget that has the focus when the menu is
+/// shown, like the key bindings of the [`Shortcuts`]. The item shows the key
+/// binding of the command as its trailing hint text if no [`MenuHintText`] is
+/// given.
+pub struct MenuCommand(CowArc<str>);
+impl MenuCommand {
+  pub fn new(command: impl Into<CowArc<str>>) -> Self { MenuCommand(command.into()) }
+}
+
 #[derive(Template)]
 pub struct MenuItem<'w> {
   /// the label string of this menu item, if the custom widget is not specified,
@@ -382,6 +429,8 @@ pub struct MenuItem<'w> {
   custom: Option<Widget<'w>>,
   /// trailing hint text
   trailing_text: Option<MenuHintText>,
+  /// the command whose key binding is shown as the trailing hint text
+  command: Option<MenuCommand>,
   /// leading icon
   leading: Option<Leading<Widget<'w>>>,
   /// trailing icon
@@ -392,16 +441,26 @@ pub struct MenuItem<'w> {
 
 impl<'w> MenuItem<'w> {
   fn into_widget(self) -> Widget<'w> {
-    let MenuItem { label, custom, leading, trailing, trailing_text: trailing_hint_text, sub_menu } =
-      self;
+    let MenuItem {
+      label,
+      custom,
+      leading,
+      trailing,
+      trailing_text: trailing_hint_text,
+      command,
+      sub_menu,
+    } = self;
     fn_widget! {
+      let trailing_hint_text = trailing_hint_text
+        .map(|w| w.0)
+        .or_else(|| command.and_then(|cmd| command_hint(&cmd.0)));
       let leading = leading.map(|w| {
         let mut w = FatObj::new(w.unwrap());
         @(w) { class: MENU_ITEM_LEADING }
       });
       let trailing_text = trailing_hint_text.map(
-        |w| @Text{
-          text: w.0,
+        |text| @Text{
+          text,
           class: MENU_ITEM_HINT_TEXT
         }
       );
@@ -423,6 +482,7 @@ impl<'w> MenuItem<'w> {
       let class = Stateful::new(MENU_ITEM);
       @Row{
         class: pipe!(*$read(class)),
+        a11y: A11y::new(Role::MenuItem).with_label(label.clone()).with_action(Action::Click),
         align_items: Align::Center,
         on_disposed: {
           let sub_menu = sub_menu.clone();
@@ -471,6 +531,16 @@ impl<'w> MenuItem<'w> {
   }
 }
 
+fn command_hint(command: &str) -> Option<TextValue> {
+  let ctx = BuildCtx::get();
+  let invoker = Provider::of::<MenuControl>(ctx)?
+    .0
+    .borrow()
+    .invoker;
+  let binding = ctx.window().command_binding(invoker, command)?;
+  Some(TextValue::Value(binding.to_string().into()))
+}
+
 #[derive(Clone)]
 struct ParentMenuInfo {
   idx: usize,
@@ -504,11 +574,13 @@ impl MenuDivider {
   }
 }
 
-fn wrap_menu_item<'w>(w: Widget<'w>, key: CowArc<str>, menu: &MenuControl) -> Widget<'w> {
+fn wrap_menu_item<'w>(
+  w: Widget<'w>, key: CowArc<str>, command: Option<CowArc<str>>, menu: &MenuControl,
+) -> Widget<'w> {
   let menu = menu.clone();
   fn_widget! {
     let mut w = FatObj::new(w);
-    let idx = menu.new_item(w.track_id(), key);
+    let idx = menu.new_item(w.track_id(), key, command);
     @(w) {
       on_pointer_move: {
         let menu = menu.clone();
@@ -519,7 +591,7 @@ fn wrap_menu_item<'w>(w: Widget<'w>, key: CowArc<str>, menu: &MenuControl) -> Wi
       on_tap: {
         let menu = menu.clone();
         move |e| {
-          menu.enter(idx, &e.window());
+          menu.activate(idx, &e.window());
         }
       },
     }
@@ -533,6 +605,7 @@ impl<'w> ComposeChild<'w> for Menu {
     fn_widget! {
       @Column {
         class: MENU,
+        a11y: A11y::new(Role::Menu),
         clip_boundary: true,
         on_disposed: move |e| {
           let menu = Provider::of::<MenuControl>(e).unwrap();
@@ -555,7 +628,7 @@ impl<'w> ComposeChild<'w> for Menu {
             }
             VirtualKey::Named(NamedKey::Enter) => {
               if let Some(idx) = menu.selected() {
-                menu.enter(idx, &e.window());
+                menu.activate(idx, &e.window());
               }
             }
             _ => {}
@@ -566,7 +639,8 @@ impl<'w> ComposeChild<'w> for Menu {
           child.into_iter().map(move |w| match w {
             MenuChild::Item(w) => {
               let key = w.label.clone();
-              wrap_menu_item(w.into_widget(), key, &menu)
+              let command = w.command.as_ref().map(|cmd| cmd.0.clone());
+              wrap_menu_item(w.into_widget(), key, command, &menu)
             },
             MenuChild::Divider(w) => w.into_divider_widget(),
           })
@@ -708,4 +782,46 @@ mod tests {
     assert!(!menu.is_show());
     assert_eq!(*r.read(), "close from sub item");
   }
+
+  #[test]
+  fn activate_menu_command() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let menu = MenuControl::new(menu! {
+      @MenuItem {
+        @ { "Save" }
+        @ { MenuCommand::new("save") }
+      }
+      @MenuItem { @ { "Close" } }
+    });
+
+    let wnd: TestWindow = TestWindow::from_widget(fn_widget! {
+      let outer = Shortcuts::new()
+        .with_command("save", move |_| $write(w_log).push("outer save"));
+      let inner = Shortcuts::new()
+        .with_command("save", move |_| $write(w_log).push("inner save"));
+      @(outer) {
+        @(inner) {
+          @MockBox { size: Size::new(100., 100.), auto_focus: true }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let raw_wnd = wnd.0.clone();
+    menu.show(&raw_wnd);
+    wnd.draw_frame();
+
+    // An item without command is just entered.
+    assert!(menu.activate(1, &raw_wnd));
+    assert!(menu.is_show());
+    assert!(log.read().is_empty());
+
+    // The command is resolved from the focused widget when the menu is shown.
+    assert!(menu.activate(0, &raw_wnd));
+    wnd.draw_frame();
+    assert_eq!(&*log.read(), &["inner save"]);
+    assert!(!menu.is_show());
+  }
 }
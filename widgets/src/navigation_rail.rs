@@ -422,7 +422,7 @@ impl NavigationRail {
   fn content_align() -> PipeValue<JustifyContent> {
     Variant::<RailContentAlign>::new_or_default(BuildCtx::get())
       .map(|a| match a.0 {
-        Align::Start => JustifyContent::Start,
+        Align::Start | Align::Baseline => JustifyContent::Start,
         Align::Center => JustifyContent::Center,
         Align::End => JustifyContent::End,
         Align::Stretch => JustifyContent::SpaceBetween,
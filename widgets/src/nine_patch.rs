@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+
+use ribir_core::prelude::*;
+
+/// A widget paints a nine-slice image, usually to skin a panel.
+///
+/// The `insets` split the image into nine slices in image pixels, the corners
+/// keep their size, and the edges and the center are stretched or repeated by
+/// the `mode` to fill the box. Only the first frame of an animated image is
+/// painted.
+///
+/// # Example
+///
+/// ```rust ignore
+/// use ribir::prelude::*;
+///
+/// fn_widget! {
+///   @NinePatch {
+///     size: Size::new(200., 120.),
+///     image: asset!("./panel.png", "image"),
+///     insets: EdgeInsets::all(12.),
+///     mode: NinePatchMode::Repeat,
+///   }
+/// };
+/// ```
+#[declare]
+pub struct NinePatch {
+  pub image: Image,
+  /// The distances from the image edges to the center slice, in image pixels.
+  pub insets: EdgeInsets,
+  #[declare(default)]
+  pub mode: NinePatchMode,
+  #[declare(skip)]
+  slices: RefCell<NineSlices>,
+}
+
+impl NinePatch {
+  fn center_rect(&self) -> Rect {
+    let EdgeInsets { left, right, top, bottom } = self.insets;
+    let width = self.image.width() as f32 - left - right;
+    let height = self.image.height() as f32 - top - bottom;
+    Rect::new(Point::new(left, top), Size::new(width.max(0.), height.max(0.)))
+  }
+}
+
+impl Render for NinePatch {
+  fn size_affected_by_child(&self) -> bool { false }
+
+  fn measure(&self, clamp: BoxClamp, _: &mut MeasureCtx) -> Size {
+    clamp.clamp(Size::new(self.image.width() as f32, self.image.height() as f32))
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let size = ctx.box_size().unwrap();
+    let Some(frame) = self.image.frame(0) else { return };
+    let center = self.center_rect();
+    ctx.painter().draw_img_nine(
+      frame.image,
+      &center,
+      &Rect::from_size(size),
+      self.mode,
+      &mut self.slices.borrow_mut(),
+    );
+  }
+
+  #[cfg(feature = "debug")]
+  fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+    std::borrow::Cow::Borrowed("nine_patch")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::test_helper::*;
+  use ribir_dev_helper::*;
+
+  use super::*;
+
+  #[test]
+  fn center_rect() {
+    let patch = NinePatch {
+      image: white_image(30, 20),
+      insets: EdgeInsets::new(5., 8., 6., 4.),
+      mode: NinePatchMode::Stretch,
+      slices: <_>::default(),
+    };
+    assert_eq!(patch.center_rect(), Rect::new(Point::new(4., 5.), Size::new(18., 9.)));
+  }
+
+  widget_layout_test!(
+    nine_patch_fill_box,
+    WidgetTester::new(fn_widget! {
+      @NinePatch {
+        size: Size::new(200., 120.),
+        image: white_image(30, 30),
+        insets: EdgeInsets::all(10.),
+      }
+    }),
+    LayoutCase::default().with_size(Size::new(200., 120.))
+  );
+}
@@ -5,7 +5,9 @@ use std::{
 
 use ribir_core::{
   prelude::*,
-  text::{CaretAffinity, LineHeight, TextHitResult, TextRange, single_style_paragraph_style},
+  text::{
+    BaselineShift, CaretAffinity, FontFeature, FontVariation, LineHeight, TextHitResult, TextRange,
+  },
 };
 use rxrust::subscription::BoxedSubscription;
 use smallvec::SmallVec;
@@ -39,9 +41,14 @@ pub struct Span {
   pub font: SpanStyleValue<FontFace>,
   pub font_size: SpanStyleValue<f32>,
   pub letter_spacing: SpanStyleValue<f32>,
+  pub word_spacing: SpanStyleValue<f32>,
   pub text_line_height: SpanStyleValue<LineHeight>,
+  pub font_features: SpanStyleValue<Box<[FontFeature]>>,
+  pub font_variations: SpanStyleValue<Box<[FontVariation]>>,
+  pub baseline_shift: SpanStyleValue<BaselineShift>,
   pub text_decoration: SpanStyleValue<TextDecorationStyle>,
   pub foreground: SpanStyleValue<Brush>,
+  pub background: SpanStyleValue<TextBackground>,
 }
 
 #[derive(Default)]
@@ -51,9 +58,14 @@ pub struct SpanDeclarer {
   font: SpanStyleValue<FontFace>,
   font_size: SpanStyleValue<f32>,
   letter_spacing: SpanStyleValue<f32>,
+  word_spacing: SpanStyleValue<f32>,
   text_line_height: SpanStyleValue<LineHeight>,
+  font_features: SpanStyleValue<Box<[FontFeature]>>,
+  font_variations: SpanStyleValue<Box<[FontVariation]>>,
+  baseline_shift: SpanStyleValue<BaselineShift>,
   text_decoration: SpanStyleValue<TextDecorationStyle>,
   foreground: SpanStyleValue<Brush>,
+  background: SpanStyleValue<TextBackground>,
 }
 
 impl Declare for Span {
@@ -77,9 +89,14 @@ impl ObjDeclarer for SpanDeclarer {
       font: self.font,
       font_size: self.font_size,
       letter_spacing: self.letter_spacing,
+      word_spacing: self.word_spacing,
       text_line_height: self.text_line_height,
+      font_features: self.font_features,
+      font_variations: self.font_variations,
+      baseline_shift: self.baseline_shift,
       text_decoration: self.text_decoration,
       foreground: self.foreground,
+      background: self.background,
     }
   }
 }
@@ -121,6 +138,12 @@ impl SpanDeclarer {
     self
   }
 
+  #[inline]
+  pub fn with_word_spacing<K: ?Sized>(&mut self, v: impl RInto<PipeValue<f32>, K>) -> &mut Self {
+    self.word_spacing = Some(v.r_into());
+    self
+  }
+
   #[inline]
   pub fn with_text_line_height<K: ?Sized>(
     &mut self, v: impl RInto<PipeValue<LineHeight>, K>,
@@ -129,6 +152,30 @@ impl SpanDeclarer {
     self
   }
 
+  #[inline]
+  pub fn with_font_features<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<Box<[FontFeature]>>, K>,
+  ) -> &mut Self {
+    self.font_features = Some(v.r_into());
+    self
+  }
+
+  #[inline]
+  pub fn with_font_variations<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<Box<[FontVariation]>>, K>,
+  ) -> &mut Self {
+    self.font_variations = Some(v.r_into());
+    self
+  }
+
+  #[inline]
+  pub fn with_baseline_shift<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<BaselineShift>, K>,
+  ) -> &mut Self {
+    self.baseline_shift = Some(v.r_into());
+    self
+  }
+
   #[inline]
   pub fn with_text_decoration<K: ?Sized>(
     &mut self, v: impl RInto<PipeValue<TextDecorationStyle>, K>,
@@ -142,6 +189,15 @@ impl SpanDeclarer {
     self.foreground = Some(v.r_into());
     self
   }
+
+  /// Paints a background behind the span, following its line boxes.
+  #[inline]
+  pub fn with_background<K: ?Sized>(
+    &mut self, v: impl RInto<PipeValue<TextBackground>, K>,
+  ) -> &mut Self {
+    self.background = Some(v.r_into());
+    self
+  }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -151,9 +207,14 @@ struct SpanSnapshot {
   pub font: Option<FontFace>,
   pub font_size: Option<f32>,
   pub letter_spacing: Option<f32>,
+  pub word_spacing: Option<f32>,
   pub text_line_height: Option<LineHeight>,
+  pub font_features: Option<Box<[FontFeature]>>,
+  pub font_variations: Option<Box<[FontVariation]>>,
+  pub baseline_shift: Option<BaselineShift>,
   pub text_decoration: Option<TextDecorationStyle>,
   pub foreground: Option<Brush>,
+  pub background: Option<TextBackground>,
 }
 
 impl Span {
@@ -171,17 +232,27 @@ impl Span {
       font,
       font_size,
       letter_spacing,
+      word_spacing,
       text_line_height,
+      font_features,
+      font_variations,
+      baseline_shift,
       text_decoration,
       foreground,
+      background,
     } = self;
     let (text, text_stream) = text.unzip();
     let (font, font_stream) = unzip_optional_pipe(font);
     let (font_size, font_size_stream) = unzip_optional_pipe(font_size);
     let (letter_spacing, letter_spacing_stream) = unzip_optional_pipe(letter_spacing);
+    let (word_spacing, word_spacing_stream) = unzip_optional_pipe(word_spacing);
     let (text_line_height, text_line_height_stream) = unzip_optional_pipe(text_line_height);
+    let (font_features, font_features_stream) = unzip_optional_pipe(font_features);
+    let (font_variations, font_variations_stream) = unzip_optional_pipe(font_variations);
+    let (baseline_shift, baseline_shift_stream) = unzip_optional_pipe(baseline_shift);
     let (text_decoration, text_decoration_stream) = unzip_optional_pipe(text_decoration);
     let (foreground, foreground_stream) = unzip_optional_pipe(foreground);
+    let (background, background_stream) = unzip_optional_pipe(background);
 
     let index = append_fragment(
       this,
@@ -191,9 +262,14 @@ impl Span {
         font,
         font_size,
         letter_spacing,
+        word_spacing,
         text_line_height,
+        font_features,
+        font_variations,
+        baseline_shift,
         text_decoration,
         foreground,
+        background,
       }),
     );
 
@@ -207,6 +283,13 @@ impl Span {
       letter_spacing_stream,
       set_span_letter_spacing,
     );
+    push_fragment_subscription(
+      subscriptions,
+      this,
+      index,
+      word_spacing_stream,
+      set_span_word_spacing,
+    );
     push_fragment_subscription(
       subscriptions,
       this,
@@ -214,6 +297,27 @@ impl Span {
       text_line_height_stream,
       set_span_text_line_height,
     );
+    push_fragment_subscription(
+      subscriptions,
+      this,
+      index,
+      font_features_stream,
+      set_span_font_features,
+    );
+    push_fragment_subscription(
+      subscriptions,
+      this,
+      index,
+      font_variations_stream,
+      set_span_font_variations,
+    );
+    push_fragment_subscription(
+      subscriptions,
+      this,
+      index,
+      baseline_shift_stream,
+      set_span_baseline_shift,
+    );
     push_fragment_subscription(
       subscriptions,
       this,
@@ -222,6 +326,7 @@ impl Span {
       set_span_text_decoration,
     );
     push_fragment_subscription(subscriptions, this, index, foreground_stream, set_span_foreground);
+    push_fragment_subscription(subscriptions, this, index, background_stream, set_span_background);
   }
 }
 
@@ -240,8 +345,13 @@ impl SpanSnapshot {
     self.font.is_some()
       || self.font_size.is_some()
       || self.letter_spacing.is_some()
+      || self.word_spacing.is_some()
       || self.text_line_height.is_some()
+      || self.font_features.is_some()
+      || self.font_variations.is_some()
+      || self.baseline_shift.is_some()
       || self.foreground.is_some()
+      || self.background.is_some()
       || self
         .decoration_style(inherited_decoration)
         .is_some()
@@ -285,8 +395,13 @@ impl SpanSnapshot {
       font: self.font.clone().map(|face| FontRequest { face }),
       font_size: self.font_size,
       letter_spacing: self.letter_spacing,
+      word_spacing: self.word_spacing,
       line_height: self.text_line_height,
+      font_features: self.font_features.clone(),
+      font_variations: self.font_variations.clone(),
+      baseline_shift: self.baseline_shift,
       brush: self.foreground.clone(),
+      background: self.background.clone(),
       decoration: self.decoration_style(inherited_decoration),
     }
   }
@@ -383,6 +498,36 @@ fn set_span_letter_spacing(fragment: &mut RichTextFragment, letter_spacing: f32)
   }
 }
 
+fn set_span_word_spacing(fragment: &mut RichTextFragment, word_spacing: f32) {
+  match fragment {
+    RichTextFragment::Span(span) => span.word_spacing = Some(word_spacing),
+    RichTextFragment::Text(_) => unreachable!("expected a span fragment"),
+  }
+}
+
+fn set_span_font_features(fragment: &mut RichTextFragment, font_features: Box<[FontFeature]>) {
+  match fragment {
+    RichTextFragment::Span(span) => span.font_features = Some(font_features),
+    RichTextFragment::Text(_) => unreachable!("expected a span fragment"),
+  }
+}
+
+fn set_span_font_variations(
+  fragment: &mut RichTextFragment, font_variations: Box<[FontVariation]>,
+) {
+  match fragment {
+    RichTextFragment::Span(span) => span.font_variations = Some(font_variations),
+    RichTextFragment::Text(_) => unreachable!("expected a span fragment"),
+  }
+}
+
+fn set_span_baseline_shift(fragment: &mut RichTextFragment, baseline_shift: BaselineShift) {
+  match fragment {
+    RichTextFragment::Span(span) => span.baseline_shift = Some(baseline_shift),
+    RichTextFragment::Text(_) => unreachable!("expected a span fragment"),
+  }
+}
+
 fn set_span_text_line_height(fragment: &mut RichTextFragment, text_line_height: LineHeight) {
   match fragment {
     RichTextFragment::Span(span) => span.text_line_height = Some(text_line_height),
@@ -404,6 +549,13 @@ fn set_span_foreground(fragment: &mut RichTextFragment, foreground: Brush) {
   }
 }
 
+fn set_span_background(fragment: &mut RichTextFragment, background: TextBackground) {
+  match fragment {
+    RichTextFragment::Span(span) => span.background = Some(background),
+    RichTextFragment::Text(_) => unreachable!("expected a span fragment"),
+  }
+}
+
 fn push_child_fragment(
   child: RichTextChild, this: &impl StateWriter<Value = RichText>,
   subscriptions: &mut RichTextSubscriptions,
@@ -586,11 +738,32 @@ pub struct RichText {
 }
 
 fn rich_text_layout(
-  text: AttributedText, text_style: &TextStyle, text_align: TextAlign, clamp: BoxClamp,
+  text: AttributedText, text_style: &TextStyle, paragraph_style: &ParagraphStyle, clamp: BoxClamp,
 ) -> ParagraphLayoutRef {
-  let paragraph_style = single_style_paragraph_style(text_style, text_align);
   let paragraph = AppCtx::text_services().paragraph(text);
-  paragraph.layout(text_style, &paragraph_style, clamp)
+  paragraph.layout(text_style, paragraph_style, clamp)
+}
+
+impl RichText {
+  fn intrinsic_width(&self, min: bool, ctx: &MeasureCtx) -> f32 {
+    let style = Provider::of::<TextStyle>(ctx).unwrap();
+    let paragraph_style = provided_paragraph_style(&style, TextAlign::Start, ctx);
+    let paragraph = AppCtx::text_services().paragraph(self.combined_text(None));
+    if min && style.overflow == TextOverflow::AutoWrap {
+      paragraph.min_intrinsic_width(&style, &paragraph_style)
+    } else {
+      paragraph.max_intrinsic_width(&style, &paragraph_style)
+    }
+  }
+
+  fn intrinsic_height(&self, width: f32, ctx: &MeasureCtx) -> f32 {
+    let style = Provider::of::<TextStyle>(ctx).unwrap();
+    let clamp = BoxClamp::max_size(Size::new(width, f32::INFINITY));
+    let paragraph_style = provided_paragraph_style(&style, TextAlign::Start, ctx);
+    rich_text_layout(self.combined_text(None), &style, &paragraph_style, clamp)
+      .size()
+      .height
+  }
 }
 
 impl Render for RichText {
@@ -603,7 +776,8 @@ impl Render for RichText {
       .map(|align| *align)
       .unwrap_or_default();
     let text = self.combined_text(text_decoration.as_ref());
-    let layout = rich_text_layout(text, &style, text_align, clamp);
+    let paragraph_style = provided_paragraph_style(&style, text_align, ctx);
+    let layout = rich_text_layout(text, &style, &paragraph_style, clamp);
     let size = layout.size();
     *self.layout.borrow_mut() = Some(layout);
     size
@@ -612,11 +786,32 @@ impl Render for RichText {
   #[inline]
   fn size_affected_by_child(&self) -> bool { false }
 
+  fn baseline(&self, _size: Size, _ctx: &mut PlaceCtx) -> Option<f32> {
+    self.layout.borrow().as_ref()?.first_baseline()
+  }
+
+  fn min_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_width(true, ctx)
+  }
+
+  fn max_intrinsic_width(&self, _height: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_width(false, ctx)
+  }
+
+  fn min_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_height(width, ctx)
+  }
+
+  fn max_intrinsic_height(&self, width: f32, ctx: &mut MeasureCtx) -> f32 {
+    self.intrinsic_height(width, ctx)
+  }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let style = Provider::of::<PaintingStyle>(ctx).map(|p| p.clone());
     let Some(layout) = self.layout.borrow().clone() else {
       return;
     };
+    TextHighlightsWidget::paint_of(&**layout, ctx);
     let brush = ctx.painter().fill_brush().clone();
     if !brush.is_visible() {
       return;
@@ -706,6 +901,7 @@ mod tests {
       letter_space: 0.,
       line_height: LineHeight::Px(16.),
       overflow: TextOverflow::Overflow,
+      ..<_>::default()
     }
   }
 
@@ -1231,6 +1427,7 @@ mod tests {
             letter_space: 0.,
             line_height: LineHeight::Px(32.),
             overflow: TextOverflow::AutoWrap,
+            ..<_>::default()
           },
           foreground: Color::WHITE,
           @Span { text: "text:" }
@@ -1252,6 +1449,7 @@ mod tests {
             letter_space: 0.,
             line_height: LineHeight::Px(32.),
             overflow: TextOverflow::AutoWrap,
+            ..<_>::default()
           },
           foreground: Color::WHITE,
           @Span { text: "text:" }
@@ -81,6 +81,36 @@ trait SliderCore {
   fn convert_ratio(&self, ratio: f32) -> f32 {
     self.min() + ratio.clamp(0., 1.) * (self.max() - self.min())
   }
+
+  /// The value changed by one increment or decrement of the assistive
+  /// technologies.
+  fn step(&self) -> f32 {
+    let (min, max) = (self.min(), self.max());
+    match self.divisions() {
+      Some(divisions) => (max - min) / divisions as f32,
+      None => (max - min) / 100.,
+    }
+  }
+
+  fn a11y_range(&self, value: f32) -> A11yRange {
+    A11yRange {
+      value: value as f64,
+      min: self.min() as f64,
+      max: self.max() as f64,
+      step: Some(self.step() as f64),
+    }
+  }
+
+  /// The value requested by an accessibility action, or `None` if the action
+  /// is not for the slider.
+  fn a11y_action_value(&self, value: f32, e: &A11yActionEvent) -> Option<f32> {
+    match (e.action, &e.data) {
+      (Action::Increment, _) => Some(value + self.step()),
+      (Action::Decrement, _) => Some(value - self.step()),
+      (Action::SetValue, Some(ActionData::NumericValue(v))) => Some(*v as f32),
+      _ => None,
+    }
+  }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -182,6 +212,20 @@ impl Slider {
 
   fn ratio(&self) -> f32 { self.calc_ratio(self.value) }
 
+  fn a11y(&self) -> A11y {
+    A11y::new(Role::Slider)
+      .with_range(self.a11y_range(self.value))
+      .with_action(Action::Increment)
+      .with_action(Action::Decrement)
+      .with_action(Action::SetValue)
+  }
+
+  fn on_a11y_action(&self, e: &mut CustomEvent<A11yActionEvent>) {
+    if let Some(v) = self.a11y_action_value(self.value, e.data()) {
+      self.request_value_change(self.calc_ratio(v), e);
+    }
+  }
+
   pub fn set_value(&mut self, val: f32) { self.value = self.snap_v(val); }
 
   pub fn set_max(&mut self, max: f32) {
@@ -251,6 +295,8 @@ impl Compose for Slider {
         },
         @Stack {
           class: SLIDER_CONTAINER,
+          a11y: pipe!($read(this).a11y()),
+          on_custom: move |e: &mut CustomEvent<A11yActionEvent>| $read(this).on_a11y_action(e),
           @(track) {
             y: AnchorY::center(),
             @Expanded {
@@ -323,6 +369,10 @@ impl RangeSlider {
           let this = $read(this);
           slider_tooltip(this.min, this.max, this.start)
         },
+        a11y: pipe!($read(this).a11y(RangeSliderPart::Start)),
+        on_custom: move |e: &mut CustomEvent<A11yActionEvent>| {
+          $read(this).on_a11y_action(RangeSliderPart::Start, e)
+        },
       };
       let start_thumb_width = start_thumb.layout_width();
       let mut end_thumb = @Void {
@@ -331,6 +381,10 @@ impl RangeSlider {
           let this = $read(this);
           slider_tooltip(this.min, this.max, this.end)
         },
+        a11y: pipe!($read(this).a11y(RangeSliderPart::End)),
+        on_custom: move |e: &mut CustomEvent<A11yActionEvent>| {
+          $read(this).on_a11y_action(RangeSliderPart::End, e)
+        },
       };
       let end_thumb_width = end_thumb.layout_width();
 
@@ -439,6 +493,30 @@ impl RangeSlider {
   fn start_ratio(&self) -> f32 { self.calc_ratio(self.start) }
 
   fn end_ratio(&self) -> f32 { self.calc_ratio(self.end) }
+
+  /// Every thumb of the range slider is a slider for the assistive
+  /// technologies.
+  fn a11y(&self, part: RangeSliderPart) -> A11y {
+    let value = match part {
+      RangeSliderPart::Start => self.start,
+      RangeSliderPart::End => self.end,
+    };
+    A11y::new(Role::Slider)
+      .with_range(self.a11y_range(value))
+      .with_action(Action::Increment)
+      .with_action(Action::Decrement)
+      .with_action(Action::SetValue)
+  }
+
+  fn on_a11y_action(&self, part: RangeSliderPart, e: &mut CustomEvent<A11yActionEvent>) {
+    let value = match part {
+      RangeSliderPart::Start => self.start,
+      RangeSliderPart::End => self.end,
+    };
+    if let Some(v) = self.a11y_action_value(value, e.data()) {
+      self.request_value_change(self.calc_ratio(v), part, e);
+    }
+  }
 }
 
 impl SliderCore for RangeSlider {
@@ -199,7 +199,7 @@ impl<'c> ComposeChild<'c> for Tabs {
       let (headers, panes): (Vec<_>, Vec<_>) = child
         .into_iter()
         .enumerate()
-        .map(|(idx, tab)| tab.into_header_and_pane(idx))
+        .map(|(idx, tab)| tab.into_header_and_pane(idx, this.clone_boxed_watcher()))
         .unzip();
 
       @Flex {
@@ -215,6 +215,7 @@ impl<'c> ComposeChild<'c> for Tabs {
             align_items: Align::Center,
             direction: position.map(TabPos::headers_dir),
             class: TAB_HEADERS_CONTAINER,
+            a11y: A11y::new(Role::TabList),
             @ { headers }
           }
         }
@@ -229,13 +230,15 @@ impl<'c> ComposeChild<'c> for Tabs {
 }
 
 impl<'w> Tab<'w> {
-  fn into_header_and_pane(mut self, idx: usize) -> (Widget<'w>, GenWidget) {
+  fn into_header_and_pane(
+    mut self, idx: usize, tabs: Box<dyn StateWatcher<Value = Tabs>>,
+  ) -> (Widget<'w>, GenWidget) {
     let pane = self.take_pane();
-    let header = self.tab_header(idx);
+    let header = self.tab_header(idx, tabs);
     (header, pane)
   }
 
-  fn tab_header(self, idx: usize) -> Widget<'w> {
+  fn tab_header(self, idx: usize, tabs: Box<dyn StateWatcher<Value = Tabs>>) -> Widget<'w> {
     let tab_info = self.info(idx);
     fn_widget! {
       let ctx = BuildCtx::get();
@@ -247,6 +250,11 @@ impl<'w> Tab<'w> {
 
       let header = @Class {
         class: TAB_HEADER,
+        a11y: pipe! {
+          A11y::new(Role::Tab)
+            .with_selected($read(tabs).active == idx)
+            .with_action(Action::Click)
+        },
         on_tap: move |e| {
           let prev = Provider::of::<Tabs>(e).unwrap().active;
           if prev != idx {
@@ -275,6 +283,7 @@ impl<'w> Tab<'w> {
 
     GenWidget::from_fn_widget(fat_obj! {
       class: TAB_PANE,
+      a11y: A11y::new(Role::TabPanel),
       @ {
         pane.as_ref()
           .map_or_else(|| Void::default().into_widget(), GenWidget::gen_widget)